@@ -1,5 +1,8 @@
 use crate::*;
 
+/// Admins compiled into the runtimes before spec_version 101. The storage migration writes them
+/// to the swap pallet's `Admins` when none are set, so this must list the admins of the chain
+/// being upgraded.
 pub fn admins() -> Vec<AccountId> {
     vec![]
 }
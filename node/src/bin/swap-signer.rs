@@ -0,0 +1,100 @@
+//! Reference signing server for `node-swap --keystore-uri`, serving a local keystore with the
+//! protocol of `node_swap::remote_keystore`.
+//!
+//! ```text
+//! node-swap key insert --keystore-path /secure/keystore --key-type swap --scheme Sr25519 --suri "<admin secret phrase>"
+//! swap-signer --listen unix:///run/swap-signer.sock --keystore-path /secure/keystore
+//! node-swap --validator --keystore-uri unix:///run/swap-signer.sock ...
+//! ```
+//!
+//! The socket is only accessible to the user running the signer, the node must run as the same
+//! user.
+
+use node_swap::remote_keystore::{serve, Endpoint};
+use sc_keystore::LocalKeystore;
+use sp_core::crypto::SecretString;
+use std::{
+	env, fs, io,
+	os::unix::{fs::{DirBuilderExt, PermissionsExt}, net::UnixListener},
+	path::{Path, PathBuf},
+	process,
+	sync::Arc,
+	thread,
+};
+use structopt::StructOpt;
+
+#[derive(Debug, StructOpt)]
+#[structopt(name = "swap-signer", about = "Serve a keystore to nodes started with --keystore-uri.")]
+struct Opt {
+	/// Socket to listen on, `unix://<path>`.
+	#[structopt(long)]
+	listen: Endpoint,
+
+	/// Keystore directory, as filled by `node-swap key insert`. Keys are only kept in memory if not set.
+	#[structopt(long, parse(from_os_str))]
+	keystore_path: Option<PathBuf>,
+
+	/// File with the password of the keystore. The `SWAP_SIGNER_PASSWORD` environment variable
+	/// is used if not set.
+	#[structopt(long, parse(from_os_str))]
+	password_filename: Option<PathBuf>,
+}
+
+const PASSWORD_ENV: &str = "SWAP_SIGNER_PASSWORD";
+
+fn password(path: &Option<PathBuf>) -> Result<Option<SecretString>, String> {
+	match path {
+		Some(path) => fs::read_to_string(path)
+			.map(|password| Some(SecretString::new(password)))
+			.map_err(|e| format!("Cannot read {}: {}", path.display(), e)),
+		None => Ok(env::var(PASSWORD_ENV).ok().map(SecretString::new)),
+	}
+}
+
+/// Binds the socket in a private directory next to `path` and moves it to `path` once its
+/// permissions are 0600, so that it is never reachable by other users.
+fn bind(path: &Path) -> io::Result<UnixListener> {
+	if path.exists() {
+		return Err(io::Error::new(io::ErrorKind::AlreadyExists, "the socket already exists"));
+	}
+	let parent = path.parent().filter(|parent| !parent.as_os_str().is_empty()).unwrap_or(Path::new("."));
+	let dir = parent.join(format!(".swap-signer-{}", process::id()));
+	fs::DirBuilder::new().mode(0o700).create(&dir)?;
+
+	let tmp_path = dir.join("socket");
+	let result = UnixListener::bind(&tmp_path).and_then(|listener| {
+		fs::set_permissions(&tmp_path, fs::Permissions::from_mode(0o600))?;
+		fs::rename(&tmp_path, path)?;
+		Ok(listener)
+	});
+	let _ = fs::remove_file(&tmp_path);
+	fs::remove_dir(&dir)?;
+	result
+}
+
+fn main() -> Result<(), String> {
+	let opt = Opt::from_args();
+
+	let keystore = Arc::new(match opt.keystore_path {
+		Some(path) => LocalKeystore::open(path, password(&opt.password_filename)?)
+			.map_err(|e| format!("Cannot open keystore: {}", e))?,
+		None => LocalKeystore::in_memory(),
+	});
+
+	let Endpoint(path) = opt.listen;
+	let listener = bind(&path).map_err(|e| format!("Cannot listen on {}: {}", path.display(), e))?;
+	for stream in listener.incoming() {
+		match stream {
+			Ok(stream) => {
+				let keystore = keystore.clone();
+				thread::spawn(move || {
+					if let Err(e) = serve(&*keystore, stream) {
+						eprintln!("Connection closed: {}", e);
+					}
+				});
+			}
+			Err(e) => eprintln!("Cannot accept connection: {}", e),
+		}
+	}
+	Ok(())
+}
@@ -1,16 +1,14 @@
-use sp_core::{Pair, Public, sr25519};
+use sp_core::{Pair, Public, U256, bytes::from_hex, crypto::Ss58Codec, ed25519, sr25519};
 use node_swap_runtime::{
 	AccountId, AuraConfig, BalancesConfig, GenesisConfig, GrandpaConfig,
-	SudoConfig, SystemConfig, WASM_BINARY, Signature
+	SudoConfig, SwapModuleConfig, SystemConfig, WASM_BINARY, Signature, pallet_swap,
 };
 use sp_consensus_aura::sr25519::AuthorityId as AuraId;
 use sp_finality_grandpa::AuthorityId as GrandpaId;
 use sp_runtime::traits::{Verify, IdentifyAccount};
 use sc_service::ChainType;
-
-#[path = "../../generated_config/account_config.rs"]
-mod account_config;
-use account_config::*;
+use serde::Deserialize;
+use std::{collections::HashSet, path::Path};
 
 // The URL for the telemetry server.
 // const STAGING_TELEMETRY_URL: &str = "wss://telemetry.polkadot.io/submit/";
@@ -44,18 +42,6 @@ pub fn authority_keys_from_seed(s: &str) -> (AuraId, GrandpaId) {
 
 pub fn development_config() -> Result<ChainSpec, String> {
 	let wasm_binary = WASM_BINARY.ok_or_else(|| "Development wasm binary not available".to_string())?;
-	let account_info = get_account_info();
-
-	// Generate initial PoA authorities
-	let mut init_auths = Vec::new();
-	for seed in account_info.authorities_seeds.iter() {
-	    init_auths.push(authority_keys_from_seed(seed));
-	}
-	// Generate pre-funded accounts
-	let mut pre_funded_accounts = Vec::new();
-	for seed in account_info.pre_funded_seeds.iter() {
-	    pre_funded_accounts.push(get_account_id_from_seed::<sr25519::Public>(seed));
-	}
 
 	Ok(ChainSpec::from_genesis(
 		// Name
@@ -66,11 +52,25 @@ pub fn development_config() -> Result<ChainSpec, String> {
 		move || testnet_genesis(
 			wasm_binary,
 			// Initial PoA authorities
-			init_auths.clone(),
+			vec![
+				authority_keys_from_seed("Alice"),
+			],
 			// Sudo account
-			get_account_id_from_seed::<sr25519::Public>(account_info.sudo_account_seed),
+			get_account_id_from_seed::<sr25519::Public>("Alice"),
 			// Pre-funded accounts
-			pre_funded_accounts.clone(),
+			vec![
+				get_account_id_from_seed::<sr25519::Public>("Alice"),
+				get_account_id_from_seed::<sr25519::Public>("Bob"),
+				get_account_id_from_seed::<sr25519::Public>("Alice//stash"),
+				get_account_id_from_seed::<sr25519::Public>("Bob//stash"),
+			],
+			// Swap admins
+			vec![
+				get_account_id_from_seed::<sr25519::Public>("Alice"),
+			],
+			vec![],
+			vec![],
+			vec![],
 			true,
 		),
 		// Bootnodes
@@ -119,6 +119,339 @@ pub fn local_testnet_config() -> Result<ChainSpec, String> {
 				get_account_id_from_seed::<sr25519::Public>("Eve//stash"),
 				get_account_id_from_seed::<sr25519::Public>("Ferdie//stash"),
 			],
+			// Swap admins
+			vec![
+				get_account_id_from_seed::<sr25519::Public>("Alice"),
+				get_account_id_from_seed::<sr25519::Public>("Bob"),
+			],
+			vec![],
+			vec![],
+			vec![],
+			true,
+		),
+		// Bootnodes
+		vec![],
+		// Telemetry
+		None,
+		// Protocol ID
+		None,
+		// Properties
+		None,
+		// Extensions
+		None,
+	))
+}
+
+/// Chain described by a TOML file. Keys and accounts are SS58 addresses or secret URIs, e.g.
+///
+/// ```toml
+/// name = "Delphinus Testnet"
+/// id = "delphinus_testnet"
+/// chain_type = "Live"
+/// authorities = [{ aura = "5Grw...", grandpa = "5FA9..." }, "//Bob"]
+/// prefunded = ["5Grw...", "//Bob"]
+/// sudo = "5Grw..."
+///
+/// [swap]
+/// admins = ["5Grw..."]
+/// tokens = [{ index = 0, l1_address = "0x6b17...", decimals = 18, symbol = "DAI" }]
+/// pools = [{ token0 = 0, token1 = 2 }]
+/// balances = [{ account = "5Grw...", key = "0x...", amounts = [{ token = 0, amount = "1000" }] }]
+/// ```
+///
+/// A secret URI as authority derives both its Aura and GRANDPA keys.
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct ChainConfig {
+	name: String,
+	id: String,
+	#[serde(default)]
+	chain_type: ChainConfigType,
+	authorities: Vec<AuthorityConfig>,
+	#[serde(default)]
+	prefunded: Vec<String>,
+	sudo: String,
+	#[serde(default)]
+	swap: SwapChainConfig,
+}
+
+#[derive(Debug, Deserialize)]
+enum ChainConfigType {
+	Development,
+	Local,
+	Live,
+}
+
+impl Default for ChainConfigType {
+	fn default() -> Self {
+		ChainConfigType::Live
+	}
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(untagged)]
+enum AuthorityConfig {
+	Suri(String),
+	Keys { aura: String, grandpa: String },
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct SwapChainConfig {
+	#[serde(default)]
+	admins: Vec<String>,
+	#[serde(default)]
+	tokens: Vec<TokenConfig>,
+	#[serde(default)]
+	pools: Vec<PoolConfig>,
+	#[serde(default)]
+	balances: Vec<BalanceConfig>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct TokenConfig {
+	index: pallet_swap::TokenIndex,
+	l1_address: String,
+	decimals: u8,
+	symbol: String,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct PoolConfig {
+	token0: pallet_swap::TokenIndex,
+	token1: pallet_swap::TokenIndex,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct BalanceConfig {
+	account: String,
+	key: String,
+	#[serde(default)]
+	amounts: Vec<AmountConfig>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct AmountConfig {
+	token: pallet_swap::TokenIndex,
+	/* Decimal string, amounts can exceed the TOML integer range */
+	amount: String,
+}
+
+/// Public key from an SS58 address or a secret URI.
+fn parse_public<TPublic: Public + Ss58Codec>(field: &str, value: &str) -> Result<TPublic, String> {
+	if let Ok(public) = TPublic::from_ss58check(value) {
+		return Ok(public);
+	}
+	TPublic::Pair::from_string(value, None)
+		.map(|pair| pair.public())
+		.map_err(|_| format!("{}: {} is neither an SS58 address nor a secret URI", field, value))
+}
+
+fn parse_account(field: &str, value: &str) -> Result<AccountId, String> {
+	if let Ok(account) = AccountId::from_ss58check(value) {
+		return Ok(account);
+	}
+	parse_public::<sr25519::Public>(field, value).map(|public| AccountPublic::from(public).into_account())
+}
+
+fn parse_authority(field: &str, authority: &AuthorityConfig) -> Result<(AuraId, GrandpaId), String> {
+	let (aura, grandpa) = match authority {
+		AuthorityConfig::Suri(suri) => (suri, suri),
+		AuthorityConfig::Keys { aura, grandpa } => (aura, grandpa),
+	};
+	Ok((
+		parse_public::<sr25519::Public>(&format!("{}.aura", field), aura)?.into(),
+		parse_public::<ed25519::Public>(&format!("{}.grandpa", field), grandpa)?.into(),
+	))
+}
+
+fn parse_token(field: &str, token: &TokenConfig) -> Result<(pallet_swap::TokenIndex, pallet_swap::L1Account, u8, Vec<u8>), String> {
+	if token.index >= pallet_swap::MAX_TOKEN_COUNT {
+		return Err(format!("{}.index: {} is not below {}", field, token.index, pallet_swap::MAX_TOKEN_COUNT));
+	}
+	let l1_address = from_hex(&token.l1_address)
+		.ok()
+		.filter(|bytes| bytes.len() <= 32)
+		.ok_or_else(|| format!("{}.l1_address: {} is not a hex string of at most 32 bytes", field, token.l1_address))?;
+	if token.symbol.len() > pallet_swap::MAX_TOKEN_SYMBOL_LENGTH {
+		return Err(format!("{}.symbol: {} is longer than {} bytes", field, token.symbol, pallet_swap::MAX_TOKEN_SYMBOL_LENGTH));
+	}
+	Ok((token.index, U256::from_big_endian(&l1_address), token.decimals, token.symbol.as_bytes().to_vec()))
+}
+
+fn parse_registered_token(
+	field: &str,
+	tokens: &HashSet<pallet_swap::TokenIndex>,
+	token: pallet_swap::TokenIndex,
+) -> Result<pallet_swap::TokenIndex, String> {
+	if !tokens.contains(&token) {
+		return Err(format!("{}: token {} is not in swap.tokens", field, token));
+	}
+	Ok(token)
+}
+
+fn parse_pool(
+	field: &str,
+	tokens: &HashSet<pallet_swap::TokenIndex>,
+	pool: &PoolConfig,
+) -> Result<(pallet_swap::TokenIndex, pallet_swap::TokenIndex), String> {
+	let token0 = parse_registered_token(&format!("{}.token0", field), tokens, pool.token0)?;
+	let token1 = parse_registered_token(&format!("{}.token1", field), tokens, pool.token1)?;
+	if token0 == token1 {
+		return Err(format!("{}: both tokens are {}", field, token0));
+	}
+	Ok((token0, token1))
+}
+
+fn parse_balance(
+	field: &str,
+	tokens: &HashSet<pallet_swap::TokenIndex>,
+	balance: &BalanceConfig,
+) -> Result<(AccountId, [u8; 32], Vec<(pallet_swap::TokenIndex, pallet_swap::Amount)>), String> {
+	let account = parse_account(&format!("{}.account", field), &balance.account)?;
+	let key = from_hex(&balance.key)
+		.ok()
+		.filter(|bytes| bytes.len() == 32)
+		.map(|bytes| {
+			let mut key = [0u8; 32];
+			key.copy_from_slice(&bytes);
+			key
+		})
+		.filter(pallet_swap::key_check)
+		.ok_or_else(|| format!("{}.key: {} is not an encoded BabyJubjub public key", field, balance.key))?;
+	let amounts = balance.amounts.iter().enumerate()
+		.map(|(i, amount)| -> Result<_, String> {
+			let field = format!("{}.amounts[{}]", field, i);
+			let token = parse_registered_token(&format!("{}.token", field), tokens, amount.token)?;
+			let value = U256::from_dec_str(&amount.amount)
+				.ok()
+				.and_then(|value| pallet_swap::CircuitRange::valid_on_circuit(&value))
+				.ok_or_else(|| format!("{}.amount: {} is not a decimal below 2^250", field, amount.amount))?;
+			Ok((token, value))
+		})
+		.collect::<Result<Vec<_>, _>>()?;
+	if amounts.iter().map(|amount| amount.0).collect::<HashSet<_>>().len() != amounts.len() {
+		return Err(format!("{}.amounts: duplicated token", field));
+	}
+	Ok((account, key, amounts))
+}
+
+/// Genesis values of a `ChainConfig` once checked.
+struct ChainGenesis {
+	name: String,
+	id: String,
+	chain_type: ChainType,
+	authorities: Vec<(AuraId, GrandpaId)>,
+	prefunded: Vec<AccountId>,
+	sudo: AccountId,
+	admins: Vec<AccountId>,
+	tokens: Vec<(pallet_swap::TokenIndex, pallet_swap::L1Account, u8, Vec<u8>)>,
+	pools: Vec<(pallet_swap::TokenIndex, pallet_swap::TokenIndex)>,
+	balances: Vec<(AccountId, [u8; 32], Vec<(pallet_swap::TokenIndex, pallet_swap::Amount)>)>,
+}
+
+/// Parses and checks the TOML chain config in `content`, see `ChainConfig`.
+fn parse_chain_config(content: &str) -> Result<ChainGenesis, String> {
+	let config: ChainConfig = toml::from_str(content).map_err(|e| e.to_string())?;
+
+	if config.authorities.is_empty() {
+		return Err("authorities: at least one authority is needed".into());
+	}
+	let authorities = config.authorities.iter().enumerate()
+		.map(|(i, authority)| parse_authority(&format!("authorities[{}]", i), authority))
+		.collect::<Result<Vec<_>, _>>()?;
+	let prefunded = config.prefunded.iter().enumerate()
+		.map(|(i, account)| parse_account(&format!("prefunded[{}]", i), account))
+		.collect::<Result<Vec<_>, _>>()?;
+	let sudo = parse_account("sudo", &config.sudo)?;
+
+	let admins = config.swap.admins.iter().enumerate()
+		.map(|(i, account)| parse_account(&format!("swap.admins[{}]", i), account))
+		.collect::<Result<Vec<_>, _>>()?;
+	if admins.len() > pallet_swap::MAX_ADMIN_COUNT {
+		return Err(format!("swap.admins: at most {} admins are supported", pallet_swap::MAX_ADMIN_COUNT));
+	}
+	if admins.iter().collect::<HashSet<_>>().len() != admins.len() {
+		return Err("swap.admins: duplicated admin".into());
+	}
+
+	let tokens = config.swap.tokens.iter().enumerate()
+		.map(|(i, token)| parse_token(&format!("swap.tokens[{}]", i), token))
+		.collect::<Result<Vec<_>, _>>()?;
+	if tokens.iter().map(|token| token.0).collect::<HashSet<_>>().len() != tokens.len() {
+		return Err("swap.tokens: duplicated token index".into());
+	}
+	let token_indexes = tokens.iter().map(|token| token.0).collect::<HashSet<_>>();
+
+	let pools = config.swap.pools.iter().enumerate()
+		.map(|(i, pool)| parse_pool(&format!("swap.pools[{}]", i), &token_indexes, pool))
+		.collect::<Result<Vec<_>, _>>()?;
+	if pools.len() > pallet_swap::MAX_POOL_COUNT as usize {
+		return Err(format!("swap.pools: at most {} pools are supported", pallet_swap::MAX_POOL_COUNT));
+	}
+	let pairs = pools.iter().map(|(token0, token1)| (token0.min(token1), token0.max(token1))).collect::<HashSet<_>>();
+	if pairs.len() != pools.len() {
+		return Err("swap.pools: duplicated token pair".into());
+	}
+
+	let balances = config.swap.balances.iter().enumerate()
+		.map(|(i, balance)| parse_balance(&format!("swap.balances[{}]", i), &token_indexes, balance))
+		.collect::<Result<Vec<_>, _>>()?;
+	if balances.len() > pallet_swap::MAX_ACCOUNT_COUNT as usize {
+		return Err(format!("swap.balances: at most {} accounts are supported", pallet_swap::MAX_ACCOUNT_COUNT));
+	}
+	if balances.iter().map(|balance| &balance.0).collect::<HashSet<_>>().len() != balances.len() {
+		return Err("swap.balances: duplicated account".into());
+	}
+
+	let chain_type = match config.chain_type {
+		ChainConfigType::Development => ChainType::Development,
+		ChainConfigType::Local => ChainType::Local,
+		ChainConfigType::Live => ChainType::Live,
+	};
+
+	Ok(ChainGenesis {
+		name: config.name,
+		id: config.id,
+		chain_type,
+		authorities,
+		prefunded,
+		sudo,
+		admins,
+		tokens,
+		pools,
+		balances,
+	})
+}
+
+/// Builds the chain spec described by the TOML file at `path`, see `ChainConfig`.
+pub fn config_file_config(path: &Path) -> Result<ChainSpec, String> {
+	let wasm_binary = WASM_BINARY.ok_or_else(|| "Wasm binary not available".to_string())?;
+	let content = std::fs::read_to_string(path)
+		.map_err(|e| format!("Cannot read {}: {}", path.display(), e))?;
+	let ChainGenesis { name, id, chain_type, authorities, prefunded, sudo, admins, tokens, pools, balances } =
+		parse_chain_config(&content)
+			.map_err(|e| format!("Invalid chain config {}: {}", path.display(), e))?;
+
+	Ok(ChainSpec::from_genesis(
+		// Name
+		&name,
+		// ID
+		&id,
+		chain_type,
+		move || testnet_genesis(
+			wasm_binary,
+			authorities.clone(),
+			sudo.clone(),
+			prefunded.clone(),
+			admins.clone(),
+			tokens.clone(),
+			pools.clone(),
+			balances.clone(),
 			true,
 		),
 		// Bootnodes
@@ -140,6 +473,10 @@ fn testnet_genesis(
 	initial_authorities: Vec<(AuraId, GrandpaId)>,
 	root_key: AccountId,
 	endowed_accounts: Vec<AccountId>,
+	swap_admins: Vec<AccountId>,
+	swap_tokens: Vec<(pallet_swap::TokenIndex, pallet_swap::L1Account, u8, Vec<u8>)>,
+	swap_pools: Vec<(pallet_swap::TokenIndex, pallet_swap::TokenIndex)>,
+	swap_balances: Vec<(AccountId, [u8; 32], Vec<(pallet_swap::TokenIndex, pallet_swap::Amount)>)>,
 	_enable_println: bool,
 ) -> GenesisConfig {
 	GenesisConfig {
@@ -162,5 +499,188 @@ fn testnet_genesis(
 			// Assign network admin rights.
 			key: root_key,
 		}),
+		pallet_swap: Some(SwapModuleConfig {
+			admins: swap_admins,
+			// More tokens can be registered by the swap admins.
+			tokens: swap_tokens,
+			pools: swap_pools,
+			balances: swap_balances,
+		}),
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	const CONFIG: &str = r#"
+		name = "Delphinus Testnet"
+		id = "delphinus_testnet"
+		authorities = ["//Alice"]
+		prefunded = ["//Alice", "//Bob"]
+		sudo = "//Alice"
+	"#;
+
+	fn error(swap: &str) -> String {
+		parse_chain_config(&format!("{}{}", CONFIG, swap)).err().unwrap()
+	}
+
+	#[test]
+	fn parse_chain_config_works() {
+		let alice = get_account_id_from_seed::<sr25519::Public>("Alice");
+		let config = format!("{}{}", CONFIG, r#"
+			[swap]
+			admins = ["//Alice", "5FHneW46xGXgs5mUiveU4sbTyGBzmstUspZC92UhjJM694ty"]
+			tokens = [{ index = 1, l1_address = "0x6b17", decimals = 18, symbol = "DAI" }]
+		"#);
+		let genesis = parse_chain_config(&config).unwrap();
+
+		assert_eq!(genesis.chain_type, ChainType::Live);
+		assert_eq!(genesis.authorities, vec![authority_keys_from_seed("Alice")]);
+		assert_eq!(genesis.prefunded, vec![alice.clone(), get_account_id_from_seed::<sr25519::Public>("Bob")]);
+		assert_eq!(genesis.sudo, alice);
+		assert_eq!(genesis.admins, vec![alice, get_account_id_from_seed::<sr25519::Public>("Bob")]);
+		assert_eq!(genesis.tokens, vec![(1, U256::from(0x6b17), 18u8, b"DAI".to_vec())]);
+	}
+
+	fn key() -> String {
+		sp_core::bytes::to_hex(&pallet_swap_sdk::public_key(&[2u8; 32]), false)
+	}
+
+	fn swap_with_tokens(sections: &str) -> String {
+		format!(r#"
+			[swap]
+			tokens = [
+				{{ index = 0, l1_address = "0x01", decimals = 18, symbol = "DAI" }},
+				{{ index = 2, l1_address = "0x02", decimals = 6, symbol = "USDC" }},
+			]
+			{}"#, sections)
+	}
+
+	#[test]
+	fn parse_chain_config_pools_and_balances() {
+		let config = format!("{}{}", CONFIG, swap_with_tokens(&format!(r#"
+			pools = [{{ token0 = 2, token1 = 0 }}]
+			balances = [{{ account = "//Bob", key = "{}", amounts = [
+				{{ token = 0, amount = "1000000000000000000000" }},
+				{{ token = 2, amount = "5" }},
+			] }}]"#, key())));
+		let genesis = parse_chain_config(&config).unwrap();
+
+		assert_eq!(genesis.pools, vec![(2, 0)]);
+		assert_eq!(genesis.balances, vec![(
+			get_account_id_from_seed::<sr25519::Public>("Bob"),
+			pallet_swap_sdk::public_key(&[2u8; 32]),
+			vec![(0, U256::exp10(21)), (2, U256::from(5))],
+		)]);
+	}
+
+	#[test]
+	fn parse_chain_config_invalid_pool() {
+		assert_eq!(
+			error(&swap_with_tokens("pools = [{ token0 = 0, token1 = 1 }]")),
+			"swap.pools[0].token1: token 1 is not in swap.tokens"
+		);
+		assert_eq!(
+			error(&swap_with_tokens("pools = [{ token0 = 2, token1 = 2 }]")),
+			"swap.pools[0]: both tokens are 2"
+		);
+		assert_eq!(
+			error(&swap_with_tokens("pools = [{ token0 = 0, token1 = 2 }, { token0 = 2, token1 = 0 }]")),
+			"swap.pools: duplicated token pair"
+		);
+	}
+
+	#[test]
+	fn parse_chain_config_invalid_balance() {
+		assert_eq!(
+			error(&swap_with_tokens(&format!(
+				r#"balances = [{{ account = "//Bob", key = "{}", amounts = [{{ token = 3, amount = "1" }}] }}]"#, key()
+			))),
+			"swap.balances[0].amounts[0].token: token 3 is not in swap.tokens"
+		);
+		assert_eq!(
+			error(&swap_with_tokens(&format!(
+				r#"balances = [{{ account = "//Bob", key = "{}", amounts = [{{ token = 0, amount = "0x10" }}] }}]"#, key()
+			))),
+			"swap.balances[0].amounts[0].amount: 0x10 is not a decimal below 2^250"
+		);
+		assert_eq!(
+			error(&swap_with_tokens(&format!(
+				r#"balances = [{{ account = "//Bob", key = "{}", amounts = [{{ token = 0, amount = "{}" }}] }}]"#,
+				key(), U256::from(1u64) << 250
+			))),
+			format!("swap.balances[0].amounts[0].amount: {} is not a decimal below 2^250", U256::from(1u64) << 250)
+		);
+		assert_eq!(
+			error(&swap_with_tokens(r#"balances = [{ account = "//Bob", key = "0x01" }]"#)),
+			"swap.balances[0].key: 0x01 is not an encoded BabyJubjub public key"
+		);
+		assert_eq!(
+			error(&swap_with_tokens(&format!(
+				r#"balances = [{{ account = "//Bob", key = "{0}" }}, {{ account = "//Bob", key = "{0}" }}]"#, key()
+			))),
+			"swap.balances: duplicated account"
+		);
+	}
+
+	#[test]
+	fn parse_chain_config_unknown_field() {
+		assert!(error("bootnodes = []").contains("unknown field `bootnodes`"));
+		assert!(error("[swap]\nnfts = []").contains("unknown field `nfts`"));
+	}
+
+	#[test]
+	fn parse_chain_config_invalid_account() {
+		assert_eq!(
+			parse_chain_config(&CONFIG.replace(r#"sudo = "//Alice""#, r#"sudo = "5Invalid""#)).err(),
+			Some("sudo: 5Invalid is neither an SS58 address nor a secret URI".to_string())
+		);
+		assert_eq!(
+			error(r#"[swap]
+			admins = ["//Alice", "not a secret uri"]"#),
+			"swap.admins[1]: not a secret uri is neither an SS58 address nor a secret URI"
+		);
+	}
+
+	#[test]
+	fn parse_chain_config_duplicated_admin() {
+		assert_eq!(
+			error(r#"[swap]
+			admins = ["//Alice", "//Alice"]"#),
+			"swap.admins: duplicated admin"
+		);
+	}
+
+	#[test]
+	fn parse_chain_config_duplicated_token() {
+		assert_eq!(
+			error(r#"[swap]
+			tokens = [
+				{ index = 1, l1_address = "0x01", decimals = 18, symbol = "DAI" },
+				{ index = 1, l1_address = "0x02", decimals = 6, symbol = "USDT" },
+			]"#),
+			"swap.tokens: duplicated token index"
+		);
+	}
+
+	#[test]
+	fn parse_chain_config_too_many_admins() {
+		let admins = (0..=pallet_swap::MAX_ADMIN_COUNT)
+			.map(|i| format!(r#""//Admin{}""#, i))
+			.collect::<Vec<_>>()
+			.join(", ");
+		assert_eq!(
+			error(&format!("[swap]\nadmins = [{}]", admins)),
+			format!("swap.admins: at most {} admins are supported", pallet_swap::MAX_ADMIN_COUNT)
+		);
+	}
+
+	#[test]
+	fn parse_chain_config_no_authority() {
+		assert_eq!(
+			parse_chain_config(&CONFIG.replace(r#"authorities = ["//Alice"]"#, "authorities = []")).err(),
+			Some("authorities: at least one authority is needed".to_string())
+		);
 	}
 }
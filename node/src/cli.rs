@@ -35,6 +35,18 @@ pub enum Subcommand {
 	/// Revert the chain to a previous state.
 	Revert(sc_cli::RevertCmd),
 
+	/// L2 key management and command signing.
+	Swap(crate::swap::SwapSubcommand),
+
+	/// Dump the L2 state of the swap pallet at a block as JSON.
+	SwapInspect(crate::inspect::InspectCmd),
+
+	/// Rebuild the L2 state from the swap events and check it against storage.
+	SwapReplay(crate::replay::ReplayCmd),
+
+	/// Export a range of swap requests as a batch for the prover.
+	SwapExport(crate::export::ExportCmd),
+
 	/// The custom benchmark subcommmand benchmarking runtime pallets.
 	#[structopt(name = "benchmark", about = "Benchmark runtime pallets.")]
 	Benchmark(frame_benchmarking_cli::BenchmarkCmd),
@@ -50,6 +50,9 @@ impl SubstrateCli for Cli {
 		Ok(match id {
 			"dev" => Box::new(chain_spec::development_config()?),
 			"" | "local" => Box::new(chain_spec::local_testnet_config()?),
+			path if path.ends_with(".toml") => Box::new(chain_spec::config_file_config(
+				std::path::Path::new(path),
+			)?),
 			path => Box::new(chain_spec::ChainSpec::from_json_file(
 				std::path::PathBuf::from(path),
 			)?),
@@ -115,6 +118,28 @@ pub fn run() -> sc_cli::Result<()> {
 				Ok((cmd.run(client, backend), task_manager))
 			})
 		},
+		Some(Subcommand::Swap(cmd)) => cmd.run(),
+		Some(Subcommand::SwapInspect(cmd)) => {
+			let runner = cli.create_runner(cmd)?;
+			runner.sync_run(|config| {
+				let PartialComponents { client, ..} = service::new_partial(&config)?;
+				cmd.run(client)
+			})
+		},
+		Some(Subcommand::SwapReplay(cmd)) => {
+			let runner = cli.create_runner(cmd)?;
+			runner.sync_run(|config| {
+				let PartialComponents { client, ..} = service::new_partial(&config)?;
+				cmd.run(client)
+			})
+		},
+		Some(Subcommand::SwapExport(cmd)) => {
+			let runner = cli.create_runner(cmd)?;
+			runner.sync_run(|config| {
+				let PartialComponents { client, ..} = service::new_partial(&config)?;
+				cmd.run(client)
+			})
+		},
 		Some(Subcommand::Benchmark(cmd)) => {
 			if cfg!(feature = "runtime-benchmarks") {
 				let runner = cli.create_runner(cmd)?;
@@ -0,0 +1,363 @@
+//! Exports a range of swap requests as a batch for the prover.
+//!
+//! The ops in storage do not carry the amounts computed on chain, such as the swap result
+//! or the share change, so every op is taken from the event of the block that submitted it.
+//!
+//! Every op is a fixed record, the same in JSON and in the binary format:
+//!
+//! | op           | indexes                                | fields                              |
+//! |--------------|----------------------------------------|-------------------------------------|
+//! | SetKey       | account, reserve                       | key x, key y                        |
+//! | Deposit      | account, token, admin                  | amount, reserve                     |
+//! | Withdraw     | account, token                         | amount, l1account                   |
+//! | Swap         | account, pool, reverse                 | amount, result amount               |
+//! | PoolSupply   | account, pool                          | amount0, amount1, share change      |
+//! | PoolRetrieve | account, pool                          | amount0, amount1, share change      |
+//! | AddPool      | token0, token1, pool, admin            | reserve, reserve                    |
+//! | DepositNFT   | account, nft, admin                    |                                     |
+//! | WithdrawNFT  | account, nft                           | l1account                           |
+//! | TransferNFT  | account, nft, recipient                |                                     |
+//! | BidNFT       | account, nft                           | amount                              |
+//! | FinalizeNFT  | account, nft                           |                                     |
+//! | Reject       |                                        | rejected req id                     |
+//!
+//! Unused slots are zero. The binary format is big-endian: a header of `BATCH_MAGIC`,
+//! `BATCH_VERSION`, the first req id (32 bytes) and the op count (4 bytes), then per op
+//! the req id (32), op code (1), status (1), signature rx, ry, s (3 * 32), nonce (8),
+//! indexes (4 * 4) and fields (3 * 32).
+//!
+//! Requests submitted before the upgrade that added `ReqRecordMap`, or pruned since, have no
+//! record. Their event is searched block by block from the block of the previous request, and
+//! their status is read from the request maps.
+
+use crate::{inspect::block_hash, replay::swap_events};
+use codec::Decode;
+use node_swap_runtime::{pallet_swap, BlockNumber, Runtime};
+use pallet_swap::{NonceId, ReqId, ReqRecord, ReqStatus, Signature};
+use pallet_swap_sdk::{
+	OP_ADDPOOL, OP_BID_NFT, OP_DEPOSIT, OP_DEPOSIT_NFT, OP_FINALIZE_NFT, OP_REJECT, OP_RETRIEVE, OP_SETKEY,
+	OP_SUPPLY, OP_SWAP, OP_TRANSFER_NFT, OP_WITHDRAW, OP_WITHDRAW_NFT,
+};
+use sc_cli::{BlockNumberOrHash, CliConfiguration, DatabaseParams, PruningParams, SharedParams};
+use sc_client_api::{Backend, StorageProvider};
+use serde_json::json;
+use sp_blockchain::HeaderBackend;
+use sp_core::{storage::StorageKey, U256};
+use sp_runtime::{generic::BlockId, traits::{Block as BlockT, NumberFor, UniqueSaturatedInto}};
+use std::{fmt::Debug, path::PathBuf, str::FromStr, sync::Arc};
+use structopt::StructOpt;
+
+pub const BATCH_MAGIC: [u8; 4] = *b"ZKCB";
+/// Bumped on any change of the record layout.
+pub const BATCH_VERSION: u8 = 1u8;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum BatchFormat {
+	Json,
+	Binary,
+}
+
+impl FromStr for BatchFormat {
+	type Err = String;
+
+	fn from_str(s: &str) -> Result<Self, Self::Err> {
+		match s {
+			"json" => Ok(BatchFormat::Json),
+			"binary" => Ok(BatchFormat::Binary),
+			_ => Err(format!("Unknown batch format {}, expected json or binary", s)),
+		}
+	}
+}
+
+fn parse_req_id(s: &str) -> Result<ReqId, String> {
+	U256::from_dec_str(s).map_err(|e| format!("Invalid req id {}: {:?}", s, e))
+}
+
+#[derive(Debug, StructOpt)]
+pub struct ExportCmd {
+	/// First req id of the batch.
+	#[structopt(long, parse(try_from_str = parse_req_id))]
+	pub start: ReqId,
+
+	/// Number of requests in the batch.
+	#[structopt(long)]
+	pub count: u32,
+
+	/// `json` or `binary`.
+	#[structopt(long, default_value = "json")]
+	pub format: BatchFormat,
+
+	/// File to write the batch to. JSON batches are printed when omitted.
+	#[structopt(long, parse(from_os_str), required_if("format", "binary"))]
+	pub output: Option<PathBuf>,
+
+	/// Block hash or number to read the requests at. Defaults to the best block.
+	#[structopt(long, value_name = "HASH or NUMBER")]
+	pub block: Option<BlockNumberOrHash>,
+
+	#[allow(missing_docs)]
+	#[structopt(flatten)]
+	pub shared_params: SharedParams,
+
+	#[allow(missing_docs)]
+	#[structopt(flatten)]
+	pub pruning_params: PruningParams,
+
+	#[allow(missing_docs)]
+	#[structopt(flatten)]
+	pub database_params: DatabaseParams,
+}
+
+/// One request of a batch, see the module documentation for the meaning of the slots.
+pub struct BatchOp {
+	pub req_id: ReqId,
+	pub op: u8,
+	pub name: &'static str,
+	pub status: ReqStatus,
+	pub signature: Signature,
+	pub nonce: NonceId,
+	pub indexes: [u32; 4],
+	pub fields: [U256; 3],
+}
+
+impl BatchOp {
+	/// Builds the record of a request from its event, `None` for events that are not requests.
+	fn from_event(event: pallet_swap::Event<Runtime>, status: ReqStatus) -> Option<Self> {
+		use pallet_swap::Event as SwapEvent;
+
+		let zero = U256::zero();
+		let (req_id, op, name, signature, nonce, indexes, fields) = match event {
+			SwapEvent::SetKey(req_id, rx, ry, s, nonce, account_index, reserve, x, y) =>
+				(req_id, OP_SETKEY, "SetKey", (rx, ry, s), nonce, [account_index, reserve, 0, 0], [x, y, zero]),
+			SwapEvent::Deposit(req_id, rx, ry, s, nonce, account_index, token_index, amount, reserve, admin) =>
+				(req_id, OP_DEPOSIT, "Deposit", (rx, ry, s), nonce, [account_index, token_index, admin, 0], [amount, reserve, zero]),
+			SwapEvent::Withdraw(req_id, rx, ry, s, nonce, account_index, token_index, amount, l1account) =>
+				(req_id, OP_WITHDRAW, "Withdraw", (rx, ry, s), nonce, [account_index, token_index, 0, 0], [amount, l1account, zero]),
+			SwapEvent::Swap(req_id, rx, ry, s, nonce, account_index, pool_index, reverse, amount, result_amount) =>
+				(req_id, OP_SWAP, "Swap", (rx, ry, s), nonce, [account_index, pool_index, reverse as u32, 0], [amount, result_amount, zero]),
+			SwapEvent::PoolSupply(req_id, rx, ry, s, nonce, account_index, pool_index, amount0, amount1, share_change) =>
+				(req_id, OP_SUPPLY, "PoolSupply", (rx, ry, s), nonce, [account_index, pool_index, 0, 0], [amount0, amount1, share_change]),
+			SwapEvent::PoolRetrieve(req_id, rx, ry, s, nonce, account_index, pool_index, amount0, amount1, share_change) =>
+				(req_id, OP_RETRIEVE, "PoolRetrieve", (rx, ry, s), nonce, [account_index, pool_index, 0, 0], [amount0, amount1, share_change]),
+			SwapEvent::AddPool(req_id, rx, ry, s, nonce, token0, token1, reserve0, reserve1, pool_index, admin) =>
+				(req_id, OP_ADDPOOL, "AddPool", (rx, ry, s), nonce, [token0, token1, pool_index, admin], [reserve0, reserve1, zero]),
+			SwapEvent::DepositNFT(req_id, rx, ry, s, nonce, account_index, nft_id, admin) =>
+				(req_id, OP_DEPOSIT_NFT, "DepositNFT", (rx, ry, s), nonce, [account_index, nft_id, admin, 0], [zero, zero, zero]),
+			SwapEvent::WithdrawNFT(req_id, rx, ry, s, nonce, account_index, nft_id, l1account) =>
+				(req_id, OP_WITHDRAW_NFT, "WithdrawNFT", (rx, ry, s), nonce, [account_index, nft_id, 0, 0], [l1account, zero, zero]),
+			SwapEvent::TransferNFT(req_id, rx, ry, s, nonce, from, to, nft_id) =>
+				(req_id, OP_TRANSFER_NFT, "TransferNFT", (rx, ry, s), nonce, [from, nft_id, to, 0], [zero, zero, zero]),
+			SwapEvent::BidNFT(req_id, rx, ry, s, nonce, account_index, nft_id, amount) =>
+				(req_id, OP_BID_NFT, "BidNFT", (rx, ry, s), nonce, [account_index, nft_id, 0, 0], [amount, zero, zero]),
+			SwapEvent::FinalizeNFT(req_id, rx, ry, s, nonce, account_index, nft_id) =>
+				(req_id, OP_FINALIZE_NFT, "FinalizeNFT", (rx, ry, s), nonce, [account_index, nft_id, 0, 0], [zero, zero, zero]),
+			SwapEvent::Reject(req_id, rejected_req_id) =>
+				(req_id, OP_REJECT, "Reject", (zero, zero, zero), 0, [0, 0, 0, 0], [rejected_req_id, zero, zero]),
+			_ => return None,
+		};
+
+		Some(BatchOp { req_id, op, name, status, signature, nonce, indexes, fields })
+	}
+
+	fn to_json(&self) -> serde_json::Value {
+		json!({
+			"req_id": self.req_id,
+			"op": self.op,
+			"name": self.name,
+			"status": self.status,
+			"signature": [self.signature.0, self.signature.1, self.signature.2],
+			"nonce": self.nonce,
+			"indexes": self.indexes,
+			"fields": self.fields,
+		})
+	}
+
+	fn write_bytes(&self, out: &mut Vec<u8>) {
+		let mut word = [0u8; 32];
+		let mut push_u256 = |out: &mut Vec<u8>, value: &U256| {
+			value.to_big_endian(&mut word);
+			out.extend_from_slice(&word);
+		};
+
+		push_u256(out, &self.req_id);
+		out.push(self.op);
+		out.push(self.status as u8);
+		push_u256(out, &self.signature.0);
+		push_u256(out, &self.signature.1);
+		push_u256(out, &self.signature.2);
+		out.extend_from_slice(&self.nonce.to_be_bytes());
+		for index in self.indexes.iter() {
+			out.extend_from_slice(&index.to_be_bytes());
+		}
+		for field in self.fields.iter() {
+			push_u256(out, field);
+		}
+	}
+}
+
+fn contains<B, BA, C>(client: &C, id: &BlockId<B>, key: Vec<u8>) -> sc_cli::Result<bool>
+where
+	B: BlockT,
+	BA: Backend<B>,
+	C: StorageProvider<B, BA>,
+{
+	let value = client.storage(id, &StorageKey(key))
+		.map_err(|e| format!("Failed to read storage: {}", e))?;
+	Ok(value.is_some())
+}
+
+/// Status of a request without a `ReqRecord`, from the map it is in.
+fn status_without_record<B, BA, C>(client: &C, id: &BlockId<B>, req_id: &ReqId) -> sc_cli::Result<ReqStatus>
+where
+	B: BlockT,
+	BA: Backend<B>,
+	C: StorageProvider<B, BA>,
+{
+	if contains(client, id, pallet_swap::PendingReqMap::<Runtime>::hashed_key_for(req_id))? {
+		return Ok(ReqStatus::Pending);
+	}
+	if contains(client, id, pallet_swap::RejectedReqMap::<Runtime>::hashed_key_for(req_id))? {
+		return Ok(ReqStatus::Rejected);
+	}
+	if contains(client, id, pallet_swap::CompleteReqMap::<Runtime>::hashed_key_for(req_id))?
+		|| contains(client, id, pallet_swap::PrunedReqMap::<Runtime>::hashed_key_for(req_id))?
+	{
+		return Ok(ReqStatus::Complete);
+	}
+	Err(format!("Request {} not found", req_id).into())
+}
+
+/// Swap events of the last block read.
+#[derive(Default)]
+struct EventCache {
+	cached: Option<(BlockNumber, Vec<pallet_swap::Event<Runtime>>)>,
+}
+
+impl EventCache {
+	/// The op of `req_id` if it was submitted in block `number`.
+	fn find<B, BA, C>(&mut self, client: &C, number: BlockNumber, req_id: &ReqId, status: ReqStatus) -> sc_cli::Result<Option<BatchOp>>
+	where
+		B: BlockT,
+		BA: Backend<B>,
+		C: StorageProvider<B, BA> + HeaderBackend<B>,
+	{
+		if self.cached.as_ref().map(|(cached, _)| *cached) != Some(number) {
+			let events = swap_events(client, NumberFor::<B>::from(number))?;
+			self.cached = Some((number, events));
+		}
+		let events = &self.cached.as_ref().expect("cached above; qed").1;
+
+		Ok(events.iter()
+			.cloned()
+			.filter_map(|event| BatchOp::from_event(event, status))
+			.find(|op| op.req_id == *req_id))
+	}
+}
+
+/// Requests `start .. start + count` as of block `id`. Fails if any of them is missing.
+pub fn batch<B, BA, C>(client: &C, id: &BlockId<B>, start: ReqId, count: u32) -> sc_cli::Result<Vec<BatchOp>>
+where
+	B: BlockT,
+	BA: Backend<B>,
+	C: StorageProvider<B, BA> + HeaderBackend<B>,
+{
+	let last: BlockNumber = client.block_number_from_id(id)
+		.map_err(|e| format!("Failed to read block: {}", e))?
+		.ok_or_else(|| format!("Block {} not found", id))?
+		.unique_saturated_into();
+
+	let mut ops = Vec::new();
+	let mut events = EventCache::default();
+	/* Requests are submitted in order, so the next one is at or after this block */
+	let mut block: BlockNumber = 1;
+
+	for i in 0..count {
+		let req_id = start.checked_add(U256::from(i)).ok_or_else(|| "Req id overflow".to_string())?;
+		let key = StorageKey(pallet_swap::ReqRecordMap::<Runtime>::hashed_key_for(&req_id));
+		let record = client.storage(id, &key)
+			.map_err(|e| format!("Failed to read storage: {}", e))?
+			.map(|record| ReqRecord::<BlockNumber>::decode(&mut &record.0[..]))
+			.transpose()
+			.map_err(|e| format!("Failed to decode request {}: {}", req_id, e))?;
+
+		let op = match record {
+			Some(record) => {
+				block = record.submitted_at;
+				events.find(client, block, &req_id, record.status)?
+					.ok_or_else(|| format!("Event of request {} not found in block {}", req_id, block))?
+			},
+			None => {
+				let status = status_without_record(client, id, &req_id)?;
+				loop {
+					if block > last {
+						return Err(format!("Event of request {} not found", req_id).into());
+					}
+					if let Some(op) = events.find(client, block, &req_id, status)? {
+						break op;
+					}
+					block += 1;
+				}
+			},
+		};
+		ops.push(op);
+	}
+
+	Ok(ops)
+}
+
+impl ExportCmd {
+	pub fn run<B, BA, C>(&self, client: Arc<C>) -> sc_cli::Result<()>
+	where
+		B: BlockT,
+		BA: Backend<B>,
+		C: StorageProvider<B, BA> + HeaderBackend<B>,
+		<<B::Header as sp_runtime::traits::Header>::Number as FromStr>::Err: Debug,
+	{
+		let hash = block_hash(&*client, &self.block)?;
+		let ops = batch(&*client, &BlockId::Hash(hash), self.start, self.count)?;
+
+		let bytes = match self.format {
+			BatchFormat::Json => {
+				let output = json!({
+					"version": BATCH_VERSION,
+					"start": self.start,
+					"count": self.count,
+					"ops": ops.iter().map(BatchOp::to_json).collect::<Vec<_>>(),
+				});
+				serde_json::to_string_pretty(&output).map_err(|e| e.to_string())?.into_bytes()
+			},
+			BatchFormat::Binary => {
+				let mut bytes = BATCH_MAGIC.to_vec();
+				bytes.push(BATCH_VERSION);
+				let mut start = [0u8; 32];
+				self.start.to_big_endian(&mut start);
+				bytes.extend_from_slice(&start);
+				bytes.extend_from_slice(&self.count.to_be_bytes());
+				for op in ops.iter() {
+					op.write_bytes(&mut bytes);
+				}
+				bytes
+			},
+		};
+
+		match &self.output {
+			Some(path) => std::fs::write(path, bytes)?,
+			None => println!("{}", String::from_utf8_lossy(&bytes)),
+		}
+		Ok(())
+	}
+}
+
+impl CliConfiguration for ExportCmd {
+	fn shared_params(&self) -> &SharedParams {
+		&self.shared_params
+	}
+
+	fn pruning_params(&self) -> Option<&PruningParams> {
+		Some(&self.pruning_params)
+	}
+
+	fn database_params(&self) -> Option<&DatabaseParams> {
+		Some(&self.database_params)
+	}
+}
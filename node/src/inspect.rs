@@ -0,0 +1,188 @@
+//! Dumps the L2 state kept by the swap pallet from the local database.
+
+use codec::Decode;
+use frame_support::storage::StoragePrefixedMap;
+use node_swap_runtime::{pallet_swap, Runtime};
+use pallet_swap::{AccountIndex, Amount, NFTId, PoolIndex, TokenIndex};
+use sc_cli::{BlockNumberOrHash, CliConfiguration, DatabaseParams, PruningParams, SharedParams};
+use sc_client_api::{Backend, StorageProvider};
+use serde_json::{json, Value};
+use sp_blockchain::HeaderBackend;
+use sp_core::storage::StorageKey;
+use sp_runtime::{generic::BlockId, traits::Block as BlockT};
+use std::{fmt::Debug, str::FromStr, sync::Arc};
+use structopt::StructOpt;
+
+/// Length of the pallet and storage item prefix of a map key.
+const PREFIX_LENGTH: usize = 32usize;
+/// Length of the hash in front of a `Blake2_128Concat` key.
+const BLAKE2_128_LENGTH: usize = 16usize;
+
+#[derive(Debug, StructOpt)]
+pub struct InspectCmd {
+	/// Block hash or number to read the state at. Defaults to the best block.
+	#[structopt(long, value_name = "HASH or NUMBER")]
+	pub block: Option<BlockNumberOrHash>,
+
+	/// Only dump the balances, shares and NFTs of this account index.
+	#[structopt(long, conflicts_with_all = &["pool", "nft"])]
+	pub account_index: Option<AccountIndex>,
+
+	/// Only dump this pool and its shares.
+	#[structopt(long, conflicts_with = "nft")]
+	pub pool: Option<PoolIndex>,
+
+	/// Only dump this NFT.
+	#[structopt(long)]
+	pub nft: Option<NFTId>,
+
+	#[allow(missing_docs)]
+	#[structopt(flatten)]
+	pub shared_params: SharedParams,
+
+	#[allow(missing_docs)]
+	#[structopt(flatten)]
+	pub pruning_params: PruningParams,
+
+	#[allow(missing_docs)]
+	#[structopt(flatten)]
+	pub database_params: DatabaseParams,
+}
+
+/// Hash of `block`, or of the best block if it is not given.
+pub fn block_hash<B, C>(client: &C, block: &Option<BlockNumberOrHash>) -> sc_cli::Result<B::Hash>
+where
+	B: BlockT,
+	C: HeaderBackend<B>,
+	<<B::Header as sp_runtime::traits::Header>::Number as FromStr>::Err: Debug,
+{
+	let id = match block {
+		Some(block) => block.parse::<B>()?,
+		None => BlockId::Hash(client.info().best_hash),
+	};
+	let hash = client.block_hash_from_id(&id)
+		.map_err(|e| format!("Failed to read block: {}", e))?
+		.ok_or_else(|| format!("Block {} not found", id))?;
+	Ok(hash)
+}
+
+/// Every entry of a `Blake2_128Concat` map of the swap pallet.
+pub fn map_entries<B, BA, C, K, V>(
+	client: &C,
+	id: &BlockId<B>,
+	prefix: [u8; 32],
+) -> sc_cli::Result<Vec<(K, V)>>
+where
+	B: BlockT,
+	BA: Backend<B>,
+	C: StorageProvider<B, BA>,
+	K: Decode,
+	V: Decode,
+{
+	let pairs = client.storage_pairs(id, &StorageKey(prefix.to_vec()))
+		.map_err(|e| format!("Failed to read storage: {}", e))?;
+
+	pairs.into_iter()
+		.map(|(key, value)| {
+			let k = K::decode(&mut &key.0[PREFIX_LENGTH + BLAKE2_128_LENGTH..])
+				.map_err(|e| format!("Failed to decode key {:?}: {}", key, e))?;
+			let v = V::decode(&mut &value.0[..])
+				.map_err(|e| format!("Failed to decode value of {:?}: {}", key, e))?;
+			Ok((k, v))
+		})
+		.collect()
+}
+
+impl InspectCmd {
+	pub fn run<B, BA, C>(&self, client: Arc<C>) -> sc_cli::Result<()>
+	where
+		B: BlockT,
+		BA: Backend<B>,
+		C: StorageProvider<B, BA> + HeaderBackend<B>,
+		<<B::Header as sp_runtime::traits::Header>::Number as FromStr>::Err: Debug,
+	{
+		let hash = block_hash(&*client, &self.block)?;
+		let id = BlockId::Hash(hash);
+
+		let balances: Vec<((AccountIndex, TokenIndex), Amount)> =
+			map_entries(&*client, &id, pallet_swap::BalanceMap::<Runtime>::final_prefix())?;
+		let shares: Vec<((AccountIndex, PoolIndex), Amount)> =
+			map_entries(&*client, &id, pallet_swap::ShareMap::<Runtime>::final_prefix())?;
+		let pools: Vec<(PoolIndex, (TokenIndex, TokenIndex, Amount, Amount, Amount))> =
+			map_entries(&*client, &id, pallet_swap::PoolMap::<Runtime>::final_prefix())?;
+		let nfts: Vec<(NFTId, (AccountIndex, Amount, Option<AccountIndex>))> =
+			map_entries(&*client, &id, pallet_swap::NFTMap::<Runtime>::final_prefix())?;
+
+		let (account, pool, nft) = (self.account_index, self.pool, self.nft);
+		let full = account.is_none() && pool.is_none() && nft.is_none();
+
+		let balances: Vec<Value> = balances.into_iter()
+			.filter(|((account_index, _), _)| full || account == Some(*account_index))
+			.map(|((account_index, token_index), amount)| json!({
+				"account_index": account_index,
+				"token_index": token_index,
+				"amount": amount,
+			}))
+			.collect();
+
+		let shares: Vec<Value> = shares.into_iter()
+			.filter(|((account_index, pool_index), _)| {
+				full || account == Some(*account_index) || pool == Some(*pool_index)
+			})
+			.map(|((account_index, pool_index), amount)| json!({
+				"account_index": account_index,
+				"pool_index": pool_index,
+				"share": amount,
+			}))
+			.collect();
+
+		let pools: Vec<Value> = pools.into_iter()
+			.filter(|(pool_index, _)| full || pool == Some(*pool_index))
+			.map(|(pool_index, (token_index_0, token_index_1, amount_0, amount_1, total_share))| json!({
+				"pool_index": pool_index,
+				"token_index_0": token_index_0,
+				"token_index_1": token_index_1,
+				"amount_0": amount_0,
+				"amount_1": amount_1,
+				"total_share": total_share,
+			}))
+			.collect();
+
+		let nfts: Vec<Value> = nfts.into_iter()
+			.filter(|(nft_id, (owner, _, bidder))| {
+				full || nft == Some(*nft_id) || account == Some(*owner) || (account.is_some() && account == *bidder)
+			})
+			.map(|(nft_id, (owner, amount, bidder))| json!({
+				"nft_id": nft_id,
+				"owner": owner,
+				"amount": amount,
+				"bidder": bidder,
+			}))
+			.collect();
+
+		let output = json!({
+			"block": format!("{:?}", hash),
+			"balances": balances,
+			"shares": shares,
+			"pools": pools,
+			"nfts": nfts,
+		});
+		println!("{}", serde_json::to_string_pretty(&output).map_err(|e| e.to_string())?);
+
+		Ok(())
+	}
+}
+
+impl CliConfiguration for InspectCmd {
+	fn shared_params(&self) -> &SharedParams {
+		&self.shared_params
+	}
+
+	fn pruning_params(&self) -> Option<&PruningParams> {
+		Some(&self.pruning_params)
+	}
+
+	fn database_params(&self) -> Option<&DatabaseParams> {
+		Some(&self.database_params)
+	}
+}
@@ -6,7 +6,13 @@ mod chain_spec;
 mod service;
 mod cli;
 mod command;
+mod export;
+mod inspect;
+mod replay;
 mod rpc;
+mod swap;
+
+use node_swap::remote_keystore;
 
 fn main() -> sc_cli::Result<()> {
 	command::run()
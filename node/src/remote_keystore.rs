@@ -0,0 +1,547 @@
+//! Remote keystore for `--keystore-uri`, so validator and swap admin keys can be kept on a
+//! separate signing server instead of the node's keystore directory.
+//!
+//! The node sends one JSON request per line and the server answers each with one JSON line, over
+//! a Unix socket (`unix:///run/swap-signer.sock`). The socket has no other authentication, only
+//! the signer's user can connect to it. Byte strings are `0x` prefixed hex, key types and crypto
+//! types are their four characters:
+//!
+//! ```text
+//! {"method": "sign_with", "params": {"key_type": "swap", "crypto": "sr25", "public": "0x..", "msg": "0x.."}}
+//! {"result": "0x.."}
+//! {"error": {"kind": "pair_not_found", "message": "..."}}
+//! ```
+//!
+//! `serve` answers the requests with a local keystore, `swap-signer` runs it on a socket. VRF signing is not supported, it is
+//! only used by BABE.
+
+use futures::{channel::oneshot, executor::ThreadPool};
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+use serde_json::Value;
+use sp_core::{
+	bytes::from_hex,
+	crypto::{CryptoTypeId, CryptoTypePublicPair, KeyTypeId},
+	ecdsa, ed25519,
+	hexdisplay::HexDisplay,
+	sr25519,
+};
+use sp_keystore::{vrf::{VRFSignature, VRFTranscriptData}, CryptoStore, Error, SyncCryptoStore};
+use std::{
+	convert::TryFrom,
+	io::{self, BufRead, BufReader, Read, Write},
+	os::unix::net::UnixStream,
+	path::PathBuf,
+	str::FromStr,
+	time::Duration,
+};
+
+const TIMEOUT: Duration = Duration::from_secs(10);
+const LOG_TARGET: &str = "remote-keystore";
+/* Threads the `CryptoStore` calls wait on, further calls queue until one is free */
+const WORKERS: usize = 4;
+const VRF_UNSUPPORTED: &str = "VRF signing is not supported by the remote keystore";
+
+/// Socket path of a signing server.
+#[derive(Debug, Clone)]
+pub struct Endpoint(pub PathBuf);
+
+impl FromStr for Endpoint {
+	type Err = String;
+
+	fn from_str(uri: &str) -> Result<Self, Self::Err> {
+		match uri.strip_prefix("unix://") {
+			Some(path) => Ok(Endpoint(PathBuf::from(path))),
+			None => Err(format!("Unsupported keystore uri {}, expected unix://<path>", uri)),
+		}
+	}
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct Key {
+	pub crypto: String,
+	pub public: String,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(tag = "method", content = "params", rename_all = "snake_case")]
+pub enum Request {
+	/// All the keys of a key type, as a list of `Key`.
+	Keys { key_type: String },
+	/// Generates a key, from the seed if given, and returns its public key.
+	GenerateNew { key_type: String, crypto: String, seed: Option<String> },
+	InsertUnknown { key_type: String, suri: String, public: String },
+	/// Whether all the `(public, key_type)` keys are there.
+	HasKeys { keys: Vec<(String, String)> },
+	/// Returns the signature of `msg`.
+	SignWith { key_type: String, crypto: String, public: String, msg: String },
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Response {
+	Result(Value),
+	Error(RemoteError),
+}
+
+/// `sp_keystore::Error` on the wire.
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(tag = "kind", content = "message", rename_all = "snake_case")]
+pub enum RemoteError {
+	KeyNotSupported(String),
+	PairNotFound(String),
+	ValidationError(String),
+	Unavailable,
+	Other(String),
+}
+
+impl From<RemoteError> for Error {
+	fn from(error: RemoteError) -> Self {
+		match error {
+			RemoteError::KeyNotSupported(key_type) => match key_type_id(&key_type) {
+				Ok(id) => Error::KeyNotSupported(id),
+				Err(e) => e,
+			},
+			RemoteError::PairNotFound(message) => Error::PairNotFound(message),
+			RemoteError::ValidationError(message) => Error::ValidationError(message),
+			RemoteError::Unavailable => Error::Unavailable,
+			RemoteError::Other(message) => Error::Other(message),
+		}
+	}
+}
+
+pub fn hex(bytes: &[u8]) -> String {
+	format!("0x{}", HexDisplay::from(&bytes))
+}
+
+pub fn decode_hex(value: &str) -> Result<Vec<u8>, Error> {
+	from_hex(value).map_err(|e| Error::ValidationError(format!("Invalid hex {}: {:?}", value, e)))
+}
+
+fn four_cc(value: &str) -> Result<[u8; 4], Error> {
+	<[u8; 4]>::try_from(value.as_bytes())
+		.map_err(|_| Error::ValidationError(format!("Invalid id {}, expected 4 characters", value)))
+}
+
+pub fn key_type_id(value: &str) -> Result<KeyTypeId, Error> {
+	four_cc(value).map(KeyTypeId)
+}
+
+pub fn key_type_name(id: KeyTypeId) -> String {
+	String::from_utf8_lossy(&id.0).into_owned()
+}
+
+pub fn crypto_type_id(value: &str) -> Result<CryptoTypeId, Error> {
+	four_cc(value).map(CryptoTypeId)
+}
+
+pub fn crypto_type_name(id: CryptoTypeId) -> String {
+	String::from_utf8_lossy(&id.0).into_owned()
+}
+
+fn remote_error(error: Error) -> RemoteError {
+	match error {
+		Error::KeyNotSupported(id) => RemoteError::KeyNotSupported(key_type_name(id)),
+		Error::PairNotFound(message) => RemoteError::PairNotFound(message),
+		Error::ValidationError(message) => RemoteError::ValidationError(message),
+		Error::Unavailable => RemoteError::Unavailable,
+		Error::Other(message) => RemoteError::Other(message),
+	}
+}
+
+fn generate_new(keystore: &dyn SyncCryptoStore, key_type: &str, crypto: &str, seed: Option<&str>) -> Result<Vec<u8>, Error> {
+	let id = key_type_id(key_type)?;
+	let crypto = crypto_type_id(crypto)?;
+	if crypto == sr25519::CRYPTO_ID {
+		Ok(SyncCryptoStore::sr25519_generate_new(keystore, id, seed)?.0.to_vec())
+	} else if crypto == ed25519::CRYPTO_ID {
+		Ok(SyncCryptoStore::ed25519_generate_new(keystore, id, seed)?.0.to_vec())
+	} else if crypto == ecdsa::CRYPTO_ID {
+		Ok(SyncCryptoStore::ecdsa_generate_new(keystore, id, seed)?.0.to_vec())
+	} else {
+		Err(Error::KeyNotSupported(id))
+	}
+}
+
+fn handle(keystore: &dyn SyncCryptoStore, request: Request) -> Result<Value, Error> {
+	let result = match request {
+		Request::Keys { key_type } => {
+			let keys = SyncCryptoStore::keys(keystore, key_type_id(&key_type)?)?
+				.into_iter()
+				.map(|key| Key { crypto: crypto_type_name(key.0), public: hex(&key.1) })
+				.collect::<Vec<_>>();
+			serde_json::to_value(keys).map_err(|e| Error::Other(e.to_string()))?
+		}
+		Request::GenerateNew { key_type, crypto, seed } => {
+			Value::String(hex(&generate_new(keystore, &key_type, &crypto, seed.as_deref())?))
+		}
+		Request::InsertUnknown { key_type, suri, public } => {
+			SyncCryptoStore::insert_unknown(keystore, key_type_id(&key_type)?, &suri, &decode_hex(&public)?)
+				.map_err(|_| Error::Other(format!("Cannot insert key {}", public)))?;
+			Value::Null
+		}
+		Request::HasKeys { keys } => {
+			let keys = keys.iter()
+				.map(|(public, key_type)| Ok((decode_hex(public)?, key_type_id(key_type)?)))
+				.collect::<Result<Vec<_>, Error>>()?;
+			Value::Bool(SyncCryptoStore::has_keys(keystore, &keys))
+		}
+		Request::SignWith { key_type, crypto, public, msg } => {
+			let key = CryptoTypePublicPair(crypto_type_id(&crypto)?, decode_hex(&public)?);
+			let signature = SyncCryptoStore::sign_with(keystore, key_type_id(&key_type)?, &key, &decode_hex(&msg)?)?;
+			Value::String(hex(&signature))
+		}
+	};
+	Ok(result)
+}
+
+/// Answers the requests of a connection until the node closes it.
+pub fn serve<S: Read + Write>(keystore: &dyn SyncCryptoStore, stream: S) -> io::Result<()> {
+	let mut stream = BufReader::new(stream);
+	let mut line = String::new();
+	while stream.read_line(&mut line)? > 0 {
+		let response = match serde_json::from_str::<Request>(&line) {
+			Ok(request) => match handle(keystore, request) {
+				Ok(result) => Response::Result(result),
+				Err(error) => Response::Error(remote_error(error)),
+			},
+			Err(e) => Response::Error(RemoteError::ValidationError(format!("Invalid request: {}", e))),
+		};
+		let mut response = serde_json::to_vec(&response)?;
+		response.push(b'\n');
+		stream.get_mut().write_all(&response)?;
+		stream.get_mut().flush()?;
+		line.clear();
+	}
+	Ok(())
+}
+
+/// `CryptoStore` and `SyncCryptoStore` of a signing server. Each call opens a new connection and
+/// waits until the server answers or `TIMEOUT` passes. The `CryptoStore` calls wait on a pool of
+/// `WORKERS` threads, so they do not block the executor.
+#[derive(Clone)]
+pub struct RemoteKeystore {
+	endpoint: Endpoint,
+	workers: ThreadPool,
+}
+
+impl RemoteKeystore {
+	/// Connects to the server at `uri` once, so that a wrong uri fails at startup.
+	pub fn open(uri: &str) -> Result<Self, String> {
+		let workers = ThreadPool::builder()
+			.pool_size(WORKERS)
+			.name_prefix("remote-keystore-")
+			.create()
+			.map_err(|e| format!("Cannot start the keystore workers: {}", e))?;
+		let keystore = RemoteKeystore { endpoint: uri.parse()?, workers };
+		keystore.connect().map_err(|e| format!("Cannot connect: {}", e))?;
+		Ok(keystore)
+	}
+
+	fn connect(&self) -> io::Result<UnixStream> {
+		let stream = UnixStream::connect(&self.endpoint.0)?;
+		stream.set_read_timeout(Some(TIMEOUT))?;
+		stream.set_write_timeout(Some(TIMEOUT))?;
+		Ok(stream)
+	}
+
+	fn exchange(&self, request: &Request) -> io::Result<Response> {
+		let mut stream = self.connect()?;
+		let mut line = serde_json::to_vec(request)?;
+		line.push(b'\n');
+		stream.write_all(&line)?;
+		stream.flush()?;
+
+		let mut response = String::new();
+		BufReader::new(stream).read_line(&mut response)?;
+		Ok(serde_json::from_str(&response)?)
+	}
+
+	fn call<R: DeserializeOwned>(&self, request: Request) -> Result<R, Error> {
+		match self.exchange(&request).map_err(|_| Error::Unavailable)? {
+			Response::Result(result) => serde_json::from_value(result)
+				.map_err(|e| Error::Other(format!("Invalid response: {}", e))),
+			Response::Error(error) => Err(error.into()),
+		}
+	}
+
+	/// Runs `f` on a worker thread and waits for it without blocking the executor.
+	async fn spawn_blocking<R, F>(&self, f: F) -> Result<R, Error>
+		where R: Send + 'static, F: FnOnce(&RemoteKeystore) -> Result<R, Error> + Send + 'static
+	{
+		let keystore = self.clone();
+		let (tx, rx) = oneshot::channel();
+		self.workers.spawn_ok(async move {
+			let _ = tx.send(f(&keystore));
+		});
+		rx.await.unwrap_or_else(|_| {
+			log::error!(target: LOG_TARGET, "Remote keystore call panicked");
+			Err(Error::Unavailable)
+		})
+	}
+
+	fn public_keys<P>(&self, id: KeyTypeId, crypto: CryptoTypeId) -> Vec<P>
+		where P: for<'a> TryFrom<&'a [u8]>
+	{
+		let keys = match SyncCryptoStore::keys(self, id) {
+			Ok(keys) => keys,
+			Err(e) => {
+				log::error!(target: LOG_TARGET, "Cannot get the {} keys: {}", key_type_name(id), e);
+				return Vec::new();
+			}
+		};
+		keys.into_iter()
+			.filter(|key| key.0 == crypto)
+			.filter_map(|key| P::try_from(&key.1[..]).ok())
+			.collect()
+	}
+
+	fn generate_new<P>(&self, id: KeyTypeId, crypto: CryptoTypeId, seed: Option<&str>) -> Result<P, Error>
+		where P: for<'a> TryFrom<&'a [u8]>
+	{
+		let public: String = self.call(Request::GenerateNew {
+			key_type: key_type_name(id),
+			crypto: crypto_type_name(crypto),
+			seed: seed.map(String::from),
+		})?;
+		P::try_from(&decode_hex(&public)?[..])
+			.map_err(|_| Error::ValidationError(format!("Invalid public key {}", public)))
+	}
+}
+
+impl SyncCryptoStore for RemoteKeystore {
+	fn sr25519_public_keys(&self, id: KeyTypeId) -> Vec<sr25519::Public> {
+		self.public_keys(id, sr25519::CRYPTO_ID)
+	}
+
+	fn sr25519_generate_new(&self, id: KeyTypeId, seed: Option<&str>) -> Result<sr25519::Public, Error> {
+		self.generate_new(id, sr25519::CRYPTO_ID, seed)
+	}
+
+	fn ed25519_public_keys(&self, id: KeyTypeId) -> Vec<ed25519::Public> {
+		self.public_keys(id, ed25519::CRYPTO_ID)
+	}
+
+	fn ed25519_generate_new(&self, id: KeyTypeId, seed: Option<&str>) -> Result<ed25519::Public, Error> {
+		self.generate_new(id, ed25519::CRYPTO_ID, seed)
+	}
+
+	fn ecdsa_public_keys(&self, id: KeyTypeId) -> Vec<ecdsa::Public> {
+		self.public_keys(id, ecdsa::CRYPTO_ID)
+	}
+
+	fn ecdsa_generate_new(&self, id: KeyTypeId, seed: Option<&str>) -> Result<ecdsa::Public, Error> {
+		self.generate_new(id, ecdsa::CRYPTO_ID, seed)
+	}
+
+	fn insert_unknown(&self, key_type: KeyTypeId, suri: &str, public: &[u8]) -> Result<(), ()> {
+		self.call(Request::InsertUnknown {
+			key_type: key_type_name(key_type),
+			suri: suri.to_string(),
+			public: hex(public),
+		}).map_err(|e| log::error!(target: LOG_TARGET, "Cannot insert key {}: {}", hex(public), e))
+	}
+
+	fn supported_keys(
+		&self,
+		id: KeyTypeId,
+		keys: Vec<CryptoTypePublicPair>,
+	) -> Result<Vec<CryptoTypePublicPair>, Error> {
+		let all_keys = SyncCryptoStore::keys(self, id)?;
+		Ok(keys.into_iter().filter(|key| all_keys.contains(key)).collect())
+	}
+
+	fn keys(&self, id: KeyTypeId) -> Result<Vec<CryptoTypePublicPair>, Error> {
+		let keys: Vec<Key> = self.call(Request::Keys { key_type: key_type_name(id) })?;
+		keys.iter()
+			.map(|key| Ok(CryptoTypePublicPair(crypto_type_id(&key.crypto)?, decode_hex(&key.public)?)))
+			.collect()
+	}
+
+	fn has_keys(&self, public_keys: &[(Vec<u8>, KeyTypeId)]) -> bool {
+		let keys = public_keys.iter().map(|(public, id)| (hex(public), key_type_name(*id))).collect();
+		self.call(Request::HasKeys { keys }).unwrap_or_else(|e| {
+			log::error!(target: LOG_TARGET, "Cannot check the keys: {}", e);
+			false
+		})
+	}
+
+	fn sign_with(&self, id: KeyTypeId, key: &CryptoTypePublicPair, msg: &[u8]) -> Result<Vec<u8>, Error> {
+		let signature: String = self.call(Request::SignWith {
+			key_type: key_type_name(id),
+			crypto: crypto_type_name(key.0),
+			public: hex(&key.1),
+			msg: hex(msg),
+		})?;
+		decode_hex(&signature)
+	}
+
+	fn sr25519_vrf_sign(
+		&self,
+		_key_type: KeyTypeId,
+		_public: &sr25519::Public,
+		_transcript_data: VRFTranscriptData,
+	) -> Result<VRFSignature, Error> {
+		Err(Error::Other(VRF_UNSUPPORTED.into()))
+	}
+}
+
+#[async_trait::async_trait]
+impl CryptoStore for RemoteKeystore {
+	async fn sr25519_public_keys(&self, id: KeyTypeId) -> Vec<sr25519::Public> {
+		self.spawn_blocking(move |keystore| Ok(SyncCryptoStore::sr25519_public_keys(keystore, id)))
+			.await
+			.unwrap_or_default()
+	}
+
+	async fn sr25519_generate_new(&self, id: KeyTypeId, seed: Option<&str>) -> Result<sr25519::Public, Error> {
+		let seed = seed.map(String::from);
+		self.spawn_blocking(move |keystore| SyncCryptoStore::sr25519_generate_new(keystore, id, seed.as_deref()))
+			.await
+	}
+
+	async fn ed25519_public_keys(&self, id: KeyTypeId) -> Vec<ed25519::Public> {
+		self.spawn_blocking(move |keystore| Ok(SyncCryptoStore::ed25519_public_keys(keystore, id)))
+			.await
+			.unwrap_or_default()
+	}
+
+	async fn ed25519_generate_new(&self, id: KeyTypeId, seed: Option<&str>) -> Result<ed25519::Public, Error> {
+		let seed = seed.map(String::from);
+		self.spawn_blocking(move |keystore| SyncCryptoStore::ed25519_generate_new(keystore, id, seed.as_deref()))
+			.await
+	}
+
+	async fn ecdsa_public_keys(&self, id: KeyTypeId) -> Vec<ecdsa::Public> {
+		self.spawn_blocking(move |keystore| Ok(SyncCryptoStore::ecdsa_public_keys(keystore, id)))
+			.await
+			.unwrap_or_default()
+	}
+
+	async fn ecdsa_generate_new(&self, id: KeyTypeId, seed: Option<&str>) -> Result<ecdsa::Public, Error> {
+		let seed = seed.map(String::from);
+		self.spawn_blocking(move |keystore| SyncCryptoStore::ecdsa_generate_new(keystore, id, seed.as_deref()))
+			.await
+	}
+
+	async fn insert_unknown(&self, key_type: KeyTypeId, suri: &str, public: &[u8]) -> Result<(), ()> {
+		let (suri, public) = (suri.to_string(), public.to_vec());
+		self.spawn_blocking(move |keystore| Ok(SyncCryptoStore::insert_unknown(keystore, key_type, &suri, &public)))
+			.await
+			.unwrap_or(Err(()))
+	}
+
+	async fn supported_keys(
+		&self,
+		id: KeyTypeId,
+		keys: Vec<CryptoTypePublicPair>,
+	) -> Result<Vec<CryptoTypePublicPair>, Error> {
+		self.spawn_blocking(move |keystore| SyncCryptoStore::supported_keys(keystore, id, keys)).await
+	}
+
+	async fn keys(&self, id: KeyTypeId) -> Result<Vec<CryptoTypePublicPair>, Error> {
+		self.spawn_blocking(move |keystore| SyncCryptoStore::keys(keystore, id)).await
+	}
+
+	async fn has_keys(&self, public_keys: &[(Vec<u8>, KeyTypeId)]) -> bool {
+		let public_keys = public_keys.to_vec();
+		self.spawn_blocking(move |keystore| Ok(SyncCryptoStore::has_keys(keystore, &public_keys)))
+			.await
+			.unwrap_or(false)
+	}
+
+	async fn sign_with(
+		&self,
+		id: KeyTypeId,
+		key: &CryptoTypePublicPair,
+		msg: &[u8],
+	) -> Result<Vec<u8>, Error> {
+		let (key, msg) = (key.clone(), msg.to_vec());
+		self.spawn_blocking(move |keystore| SyncCryptoStore::sign_with(keystore, id, &key, &msg)).await
+	}
+
+	async fn sr25519_vrf_sign(
+		&self,
+		_key_type: KeyTypeId,
+		_public: &sr25519::Public,
+		_transcript_data: VRFTranscriptData,
+	) -> Result<VRFSignature, Error> {
+		Err(Error::Other(VRF_UNSUPPORTED.into()))
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use sc_keystore::LocalKeystore;
+	use sp_core::Pair;
+	use std::{os::unix::net::UnixListener, sync::Arc, thread};
+
+	const SWAP: KeyTypeId = KeyTypeId(*b"swap");
+
+	/// Serves `keystore` on a temporary socket and connects to it.
+	fn remote(keystore: Arc<LocalKeystore>) -> RemoteKeystore {
+		let path = std::env::temp_dir().join(format!("swap-signer-test-{}.sock", std::process::id()));
+		let _ = std::fs::remove_file(&path);
+		let listener = UnixListener::bind(&path).unwrap();
+		thread::spawn(move || {
+			for stream in listener.incoming() {
+				let keystore = keystore.clone();
+				thread::spawn(move || serve(&*keystore, stream.unwrap()));
+			}
+		});
+		RemoteKeystore::open(&format!("unix://{}", path.display())).unwrap()
+	}
+
+	#[test]
+	fn remote_keystore_round_trip() {
+		let local = Arc::new(LocalKeystore::in_memory());
+		let public = SyncCryptoStore::sr25519_generate_new(&*local, SWAP, None).unwrap();
+		let key = CryptoTypePublicPair(sr25519::CRYPTO_ID, public.to_vec());
+		let remote = remote(local.clone());
+
+		assert_eq!(SyncCryptoStore::keys(&remote, SWAP).unwrap(), SyncCryptoStore::keys(&*local, SWAP).unwrap());
+		assert_eq!(SyncCryptoStore::sr25519_public_keys(&remote, SWAP), vec![public]);
+
+		assert!(SyncCryptoStore::has_keys(&remote, &[(public.to_vec(), SWAP)]));
+		assert!(!SyncCryptoStore::has_keys(&remote, &[(vec![0u8; 32], SWAP)]));
+
+		let signature = SyncCryptoStore::sign_with(&remote, SWAP, &key, b"batch").unwrap();
+		assert!(sr25519::Pair::verify(&sr25519::Signature::from_slice(&signature), b"batch", &public));
+
+		//The async calls answer the same
+		let signature = futures::executor::block_on(CryptoStore::sign_with(&remote, SWAP, &key, b"batch")).unwrap();
+		assert!(sr25519::Pair::verify(&sr25519::Signature::from_slice(&signature), b"batch", &public));
+		assert_eq!(futures::executor::block_on(CryptoStore::sr25519_public_keys(&remote, SWAP)), vec![public]);
+
+		//More calls than workers queue until one is free
+		let calls = (0..4 * WORKERS).map(|_| CryptoStore::sign_with(&remote, SWAP, &key, b"batch"));
+		assert!(futures::executor::block_on(futures::future::join_all(calls)).iter().all(Result::is_ok));
+		assert!(matches!(
+			futures::executor::block_on(CryptoStore::sr25519_vrf_sign(&remote, SWAP, &public, VRFTranscriptData {
+				label: b"swap",
+				items: vec![],
+			})),
+			Err(Error::Other(_))
+		));
+
+		//Server errors map back to the keystore errors
+		let unknown = CryptoTypePublicPair(sr25519::CRYPTO_ID, vec![0u8; 32]);
+		assert!(matches!(
+			SyncCryptoStore::sign_with(&remote, SWAP, &unknown, b"batch"),
+			Err(Error::PairNotFound(_))
+		));
+		let unsupported = CryptoTypePublicPair(CryptoTypeId(*b"none"), public.to_vec());
+		assert!(matches!(
+			SyncCryptoStore::sign_with(&remote, SWAP, &unsupported, b"batch"),
+			Err(Error::KeyNotSupported(id)) if id == SWAP
+		));
+
+		//No server
+		let offline = RemoteKeystore {
+			endpoint: Endpoint(std::env::temp_dir().join("swap-signer-test-none.sock")),
+			..remote.clone()
+		};
+		assert!(matches!(SyncCryptoStore::keys(&offline, SWAP), Err(Error::Unavailable)));
+		assert!(!SyncCryptoStore::has_keys(&offline, &[(public.to_vec(), SWAP)]));
+	}
+}
@@ -0,0 +1,303 @@
+//! Rebuilds the L2 state of the swap pallet from its events and checks it against storage.
+
+use crate::inspect::{block_hash, map_entries};
+use codec::Decode;
+use frame_support::storage::StoragePrefixedMap;
+use frame_system::EventRecord;
+use node_swap_runtime::{pallet_swap, Event, Runtime};
+use pallet_swap::{AccountIndex, Amount, NFTId, PoolIndex, ReqId, TokenIndex, NFT_TOKEN_INDEX};
+use sc_cli::{BlockNumberOrHash, CliConfiguration, DatabaseParams, PruningParams, SharedParams};
+use sc_client_api::{Backend, StorageProvider};
+use serde_json::{json, Value};
+use sp_blockchain::HeaderBackend;
+use sp_core::{hashing::twox_128, storage::StorageKey, U256};
+use sp_runtime::{generic::BlockId, traits::{Block as BlockT, NumberFor, One}};
+use std::{collections::BTreeMap, fmt::Debug, str::FromStr, sync::Arc};
+use structopt::StructOpt;
+
+type Pool = (TokenIndex, TokenIndex, Amount, Amount, Amount);
+type NFT = (AccountIndex, Amount, Option<AccountIndex>);
+
+#[derive(Debug, StructOpt)]
+pub struct ReplayCmd {
+	/// Block hash or number to replay up to. Defaults to the best block.
+	#[structopt(long, value_name = "HASH or NUMBER")]
+	pub block: Option<BlockNumberOrHash>,
+
+	#[allow(missing_docs)]
+	#[structopt(flatten)]
+	pub shared_params: SharedParams,
+
+	#[allow(missing_docs)]
+	#[structopt(flatten)]
+	pub pruning_params: PruningParams,
+
+	#[allow(missing_docs)]
+	#[structopt(flatten)]
+	pub database_params: DatabaseParams,
+}
+
+/// Requests that `Abort` can roll back.
+enum Rejectable {
+	Withdraw(AccountIndex, TokenIndex, Amount),
+	WithdrawNFT(AccountIndex, NFTId),
+}
+
+/// In-memory L2 state, updated the way the pallet updates storage.
+#[derive(Default)]
+pub struct L2State {
+	pub balances: BTreeMap<(AccountIndex, TokenIndex), Amount>,
+	pub shares: BTreeMap<(AccountIndex, PoolIndex), Amount>,
+	pub pools: BTreeMap<PoolIndex, Pool>,
+	pub nfts: BTreeMap<NFTId, NFT>,
+	rejectable: BTreeMap<ReqId, Rejectable>,
+}
+
+fn add(value: Amount, change: Amount) -> Result<Amount, String> {
+	value.checked_add(change).ok_or_else(|| "Amount overflow".to_string())
+}
+
+fn sub(value: Amount, change: Amount) -> Result<Amount, String> {
+	value.checked_sub(change).ok_or_else(|| "Amount underflow".to_string())
+}
+
+/// `amount0 * liq1 / liq0`, rounded up on supply as in `calculate_amount1_to_pool`.
+fn amount1_to_pool(amount0: Amount, liq0: Amount, liq1: Amount, is_supply: bool) -> Result<Amount, String> {
+	if liq0.is_zero() {
+		return Err("Empty pool".to_string());
+	}
+	let dividend = amount0.checked_mul(liq1).ok_or_else(|| "Amount overflow".to_string())?;
+	let (quotient, rem) = dividend.div_mod(liq0);
+	if is_supply && !rem.is_zero() {
+		add(quotient, U256::one())
+	} else {
+		Ok(quotient)
+	}
+}
+
+impl L2State {
+	fn balance_change(&mut self, account_index: AccountIndex, token_index: TokenIndex, amount: Amount, is_add: bool) -> Result<(), String> {
+		let balance = self.balances.entry((account_index, token_index)).or_default();
+		*balance = if is_add { add(*balance, amount)? } else { sub(*balance, amount)? };
+		Ok(())
+	}
+
+	fn share_change(&mut self, account_index: AccountIndex, pool_index: PoolIndex, amount: Amount, is_add: bool) -> Result<(), String> {
+		let share = self.shares.entry((account_index, pool_index)).or_default();
+		*share = if is_add { add(*share, amount)? } else { sub(*share, amount)? };
+		Ok(())
+	}
+
+	fn pool(&self, pool_index: PoolIndex) -> Result<Pool, String> {
+		self.pools.get(&pool_index).cloned().ok_or_else(|| format!("Unknown pool {}", pool_index))
+	}
+
+	fn nft(&self, nft_id: NFTId) -> NFT {
+		self.nfts.get(&nft_id).cloned().unwrap_or_default()
+	}
+
+	/// Applies one event of the swap pallet.
+	pub fn apply(&mut self, event: pallet_swap::Event<Runtime>) -> Result<(), String> {
+		use pallet_swap::Event as SwapEvent;
+
+		match event {
+			SwapEvent::Deposit(_, _, _, _, _, account_index, token_index, amount, _, _) => {
+				self.balance_change(account_index, token_index, amount, true)?;
+			},
+			SwapEvent::Withdraw(req_id, _, _, _, _, account_index, token_index, amount, _) => {
+				self.balance_change(account_index, token_index, amount, false)?;
+				self.rejectable.insert(req_id, Rejectable::Withdraw(account_index, token_index, amount));
+			},
+			SwapEvent::Swap(_, _, _, _, _, account_index, pool_index, reverse, amount, result_amount) => {
+				let (token0, token1, amount0, amount1, total_share) = self.pool(pool_index)?;
+				let pool = if reverse == 0 {
+					self.balance_change(account_index, token0, amount, false)?;
+					self.balance_change(account_index, token1, result_amount, true)?;
+					(token0, token1, add(amount0, amount)?, sub(amount1, result_amount)?, total_share)
+				} else {
+					self.balance_change(account_index, token1, amount, false)?;
+					self.balance_change(account_index, token0, result_amount, true)?;
+					(token0, token1, sub(amount0, result_amount)?, add(amount1, amount)?, total_share)
+				};
+				self.pools.insert(pool_index, pool);
+			},
+			SwapEvent::PoolSupply(_, _, _, _, _, account_index, pool_index, amount0, amount1, share_change) => {
+				let (token0, token1, liq0, liq1, total_share) = self.pool(pool_index)?;
+				let amount1 = if liq0.is_zero() { amount1 } else { amount1_to_pool(amount0, liq0, liq1, true)? };
+				self.balance_change(account_index, token0, amount0, false)?;
+				self.balance_change(account_index, token1, amount1, false)?;
+				self.share_change(account_index, pool_index, share_change, true)?;
+				self.pools.insert(pool_index, (token0, token1, add(liq0, amount0)?, add(liq1, amount1)?, add(total_share, share_change)?));
+			},
+			SwapEvent::PoolRetrieve(_, _, _, _, _, account_index, pool_index, amount0, _, share_change) => {
+				let (token0, token1, liq0, liq1, total_share) = self.pool(pool_index)?;
+				let amount1 = amount1_to_pool(amount0, liq0, liq1, false)?;
+				self.balance_change(account_index, token0, amount0, true)?;
+				self.balance_change(account_index, token1, amount1, true)?;
+				self.share_change(account_index, pool_index, share_change, false)?;
+				self.pools.insert(pool_index, (token0, token1, sub(liq0, amount0)?, sub(liq1, amount1)?, sub(total_share, share_change)?));
+			},
+			SwapEvent::AddPool(_, _, _, _, _, token0, token1, _, _, pool_index, _) => {
+				self.pools.insert(pool_index, (token0, token1, U256::zero(), U256::zero(), U256::zero()));
+			},
+			SwapEvent::DepositNFT(_, _, _, _, _, account_index, nft_id, _) => {
+				self.nfts.insert(nft_id, (account_index, U256::zero(), None));
+			},
+			SwapEvent::WithdrawNFT(req_id, _, _, _, _, account_index, nft_id, _) => {
+				let (_, amount, bidder) = self.nft(nft_id);
+				if let Some(bidder) = bidder {
+					self.balance_change(bidder, NFT_TOKEN_INDEX, amount, true)?;
+				}
+				self.nfts.insert(nft_id, (0, U256::zero(), None));
+				self.rejectable.insert(req_id, Rejectable::WithdrawNFT(account_index, nft_id));
+			},
+			SwapEvent::TransferNFT(_, _, _, _, _, _, recipient, nft_id) => {
+				let (_, amount, bidder) = self.nft(nft_id);
+				self.nfts.insert(nft_id, (recipient, amount, bidder));
+			},
+			SwapEvent::BidNFT(_, _, _, _, _, account_index, nft_id, amount) => {
+				let (owner, last_amount, last_bidder) = self.nft(nft_id);
+				if let Some(last_bidder) = last_bidder {
+					self.balance_change(last_bidder, NFT_TOKEN_INDEX, last_amount, true)?;
+				}
+				self.balance_change(account_index, NFT_TOKEN_INDEX, amount, false)?;
+				self.nfts.insert(nft_id, (owner, amount, Some(account_index)));
+			},
+			SwapEvent::FinalizeNFT(_, _, _, _, _, account_index, nft_id) => {
+				let (_, amount, bidder) = self.nft(nft_id);
+				let bidder = bidder.ok_or_else(|| format!("NFT {} has no bid", nft_id))?;
+				self.balance_change(account_index, NFT_TOKEN_INDEX, amount, true)?;
+				self.nfts.insert(nft_id, (bidder, U256::zero(), None));
+			},
+			SwapEvent::Abort(req_id) => {
+				match self.rejectable.remove(&req_id) {
+					Some(Rejectable::Withdraw(account_index, token_index, amount)) => {
+						self.balance_change(account_index, token_index, amount, true)?;
+					},
+					Some(Rejectable::WithdrawNFT(account_index, nft_id)) => {
+						self.nfts.insert(nft_id, (account_index, U256::zero(), None));
+					},
+					None => return Err(format!("Abort of unknown request {}", req_id)),
+				}
+			},
+			_ => {},
+		}
+
+		Ok(())
+	}
+}
+
+/// Entries of `replayed` and `on_chain` that differ, ignoring entries holding the default value.
+fn diff<K, V>(map: &str, replayed: &BTreeMap<K, V>, on_chain: Vec<(K, V)>) -> Vec<Value>
+where
+	K: Ord + Clone + serde::Serialize,
+	V: PartialEq + Default + serde::Serialize,
+{
+	let on_chain: BTreeMap<K, V> = on_chain.into_iter().collect();
+	let empty = V::default();
+	let mut keys: Vec<&K> = replayed.keys().chain(on_chain.keys()).collect();
+	keys.sort();
+	keys.dedup();
+
+	keys.into_iter()
+		.filter_map(|key| {
+			let left = replayed.get(key).unwrap_or(&empty);
+			let right = on_chain.get(key).unwrap_or(&empty);
+			if left == right {
+				None
+			} else {
+				Some(json!({ "map": map, "key": key, "replayed": left, "on_chain": right }))
+			}
+		})
+		.collect()
+}
+
+/// Events of the swap pallet deposited in block `number`, in order.
+pub fn swap_events<B, BA, C>(client: &C, number: NumberFor<B>) -> sc_cli::Result<Vec<pallet_swap::Event<Runtime>>>
+where
+	B: BlockT,
+	BA: Backend<B>,
+	C: StorageProvider<B, BA> + HeaderBackend<B>,
+{
+	let mut events_key = twox_128(b"System").to_vec();
+	events_key.extend_from_slice(&twox_128(b"Events"));
+
+	let hash = client.hash(number)
+		.map_err(|e| format!("Failed to read block {}: {}", number, e))?
+		.ok_or_else(|| format!("Block {} not found", number))?;
+	let events = client.storage(&BlockId::Hash(hash), &StorageKey(events_key))
+		.map_err(|e| format!("Failed to read the events of block {}, is the node an archive node? {}", number, e))?;
+
+	let events = match events {
+		Some(events) => Vec::<EventRecord<Event, B::Hash>>::decode(&mut &events.0[..])
+			.map_err(|e| format!("Failed to decode the events of block {}: {}", number, e))?,
+		None => Vec::new(),
+	};
+
+	Ok(events.into_iter()
+		.filter_map(|record| match record.event {
+			Event::pallet_swap(event) => Some(event),
+			_ => None,
+		})
+		.collect())
+}
+
+impl ReplayCmd {
+	pub fn run<B, BA, C>(&self, client: Arc<C>) -> sc_cli::Result<()>
+	where
+		B: BlockT,
+		BA: Backend<B>,
+		C: StorageProvider<B, BA> + HeaderBackend<B>,
+		<<B::Header as sp_runtime::traits::Header>::Number as FromStr>::Err: Debug,
+	{
+		let head = block_hash(&*client, &self.block)?;
+		let head_number = client.number(head)
+			.map_err(|e| format!("Failed to read block: {}", e))?
+			.ok_or_else(|| format!("Block {:?} not found", head))?;
+
+		let mut state = L2State::default();
+		let mut number = One::one();
+		while number <= head_number {
+			for event in swap_events(&*client, number)? {
+				state.apply(event).map_err(|e| format!("Failed to replay block {}: {}", number, e))?;
+			}
+			number = number + One::one();
+		}
+
+		let id = BlockId::Hash(head);
+		let mut divergences = Vec::new();
+		divergences.extend(diff("BalanceMap", &state.balances,
+			map_entries(&*client, &id, pallet_swap::BalanceMap::<Runtime>::final_prefix())?));
+		divergences.extend(diff("ShareMap", &state.shares,
+			map_entries(&*client, &id, pallet_swap::ShareMap::<Runtime>::final_prefix())?));
+		divergences.extend(diff("PoolMap", &state.pools,
+			map_entries(&*client, &id, pallet_swap::PoolMap::<Runtime>::final_prefix())?));
+		divergences.extend(diff("NFTMap", &state.nfts,
+			map_entries(&*client, &id, pallet_swap::NFTMap::<Runtime>::final_prefix())?));
+
+		let output = json!({
+			"block": format!("{:?}", head),
+			"divergences": divergences,
+		});
+		println!("{}", serde_json::to_string_pretty(&output).map_err(|e| e.to_string())?);
+
+		if !divergences.is_empty() {
+			return Err(format!("Replayed state diverges from storage in {} entries", divergences.len()).into());
+		}
+		Ok(())
+	}
+}
+
+impl CliConfiguration for ReplayCmd {
+	fn shared_params(&self) -> &SharedParams {
+		&self.shared_params
+	}
+
+	fn pruning_params(&self) -> Option<&PruningParams> {
+		Some(&self.pruning_params)
+	}
+
+	fn database_params(&self) -> Option<&DatabaseParams> {
+		Some(&self.database_params)
+	}
+}
@@ -0,0 +1,175 @@
+//! Offline L2 key management and command signing for the swap pallet.
+
+use node_swap_runtime::pallet_swap::{l2_secret_key_from_signature, L2_SECRET_KEY_MESSAGE};
+use pallet_swap_sdk::{Command, EncodedPublicKey, EncodedSignature, SecretKey};
+use rand::{rngs::OsRng, RngCore};
+use sp_core::{bytes::from_hex, ed25519, hexdisplay::HexDisplay, Pair};
+use std::path::PathBuf;
+use structopt::StructOpt;
+
+#[derive(Debug, StructOpt)]
+pub enum SwapSubcommand {
+	/// Generate a BabyJubjub keypair.
+	Keygen,
+
+	/// Print the compressed public key of a secret key, as taken by `set_key`.
+	Pubkey(PubkeyCmd),
+
+	/// Print the compressed public key derived from an ed25519 key of the `zkl2` key type, which an
+	/// admin node submits L1 deposits with.
+	L2Pubkey(L2PubkeyCmd),
+
+	/// Sign an L2 command given as JSON.
+	Sign(SignCmd),
+
+	/// Verify the signature of an L2 command given as JSON.
+	Verify(VerifyCmd),
+}
+
+#[derive(Debug, StructOpt)]
+pub struct SecretKeyArgs {
+	/// Read the hex encoded 32 bytes secret key from a file instead of stdin.
+	#[structopt(long, parse(from_os_str))]
+	secret_key_file: Option<PathBuf>,
+}
+
+#[derive(Debug, StructOpt)]
+pub struct PubkeyCmd {
+	#[structopt(flatten)]
+	secret_key: SecretKeyArgs,
+}
+
+#[derive(Debug, StructOpt)]
+pub struct L2PubkeyCmd {
+	/// Read the secret URI of the ed25519 key from a file instead of stdin.
+	#[structopt(long, parse(from_os_str))]
+	suri_file: Option<PathBuf>,
+}
+
+#[derive(Debug, StructOpt)]
+pub struct CommandArgs {
+	/// The command as JSON, e.g. `{"op": "FinalizeNFT", "nonce": 1, "account_index": 0, "nft_id": 2}`.
+	/// 256-bit fields are hex strings.
+	#[structopt(long, conflicts_with = "command-file", required_unless = "command-file")]
+	command: Option<String>,
+
+	/// Read the command JSON from a file.
+	#[structopt(long, parse(from_os_str))]
+	command_file: Option<PathBuf>,
+}
+
+#[derive(Debug, StructOpt)]
+pub struct SignCmd {
+	#[structopt(flatten)]
+	secret_key: SecretKeyArgs,
+
+	#[structopt(flatten)]
+	command: CommandArgs,
+}
+
+#[derive(Debug, StructOpt)]
+pub struct VerifyCmd {
+	/// Hex encoded 32 bytes compressed public key.
+	#[structopt(long)]
+	public_key: String,
+
+	/// Hex encoded 64 bytes signature.
+	#[structopt(long)]
+	signature: String,
+
+	#[structopt(flatten)]
+	command: CommandArgs,
+}
+
+fn hex(bytes: &[u8]) -> String {
+	format!("0x{}", HexDisplay::from(&bytes))
+}
+
+fn parse_bytes(name: &str, value: &str, out: &mut [u8]) -> sc_cli::Result<()> {
+	let bytes = from_hex(value).map_err(|e| format!("Invalid {}: {:?}", name, e))?;
+	if bytes.len() != out.len() {
+		return Err(format!("Invalid {}: expected {} bytes, got {}", name, out.len(), bytes.len()).into());
+	}
+	out.copy_from_slice(&bytes);
+	Ok(())
+}
+
+fn read_secret(path: &Option<PathBuf>) -> sc_cli::Result<String> {
+	let value = match path {
+		Some(path) => std::fs::read_to_string(path)?,
+		None => {
+			let mut value = String::new();
+			std::io::stdin().read_line(&mut value)?;
+			value
+		},
+	};
+	Ok(value)
+}
+
+impl SecretKeyArgs {
+	fn secret_key(&self) -> sc_cli::Result<SecretKey> {
+		let value = read_secret(&self.secret_key_file)?;
+		let mut secret_key: SecretKey = [0u8; 32];
+		parse_bytes("secret key", value.trim(), &mut secret_key)?;
+		Ok(secret_key)
+	}
+}
+
+impl CommandArgs {
+	fn command(&self) -> sc_cli::Result<Command> {
+		let json = match (&self.command, &self.command_file) {
+			(Some(json), _) => json.clone(),
+			(None, Some(path)) => std::fs::read_to_string(path)?,
+			(None, None) => return Err("Either --command or --command-file is required".into()),
+		};
+		serde_json::from_str(&json).map_err(|e| format!("Invalid command: {}", e).into())
+	}
+}
+
+impl SwapSubcommand {
+	pub fn run(&self) -> sc_cli::Result<()> {
+		match self {
+			SwapSubcommand::Keygen => {
+				let mut secret_key: SecretKey = [0u8; 32];
+				OsRng.fill_bytes(&mut secret_key);
+				let output = serde_json::json!({
+					"secret_key": hex(&secret_key),
+					"public_key": hex(&pallet_swap_sdk::public_key(&secret_key)),
+				});
+				println!("{}", output);
+			},
+			SwapSubcommand::Pubkey(cmd) => {
+				let secret_key = cmd.secret_key.secret_key()?;
+				println!("{}", hex(&pallet_swap_sdk::public_key(&secret_key)));
+			},
+			SwapSubcommand::L2Pubkey(cmd) => {
+				let suri = read_secret(&cmd.suri_file)?;
+				let pair = ed25519::Pair::from_string(suri.trim(), None)
+					.map_err(|e| format!("Invalid secret URI: {:?}", e))?;
+				let secret_key = l2_secret_key_from_signature(&pair.sign(L2_SECRET_KEY_MESSAGE).0);
+				println!("{}", hex(&pallet_swap_sdk::public_key(&secret_key)));
+			},
+			SwapSubcommand::Sign(cmd) => {
+				let secret_key = cmd.secret_key.secret_key()?;
+				let command = cmd.command.command()?;
+				let output = serde_json::json!({
+					"command": hex(&command.encode()),
+					"signature": hex(&pallet_swap_sdk::sign(&secret_key, &command)),
+				});
+				println!("{}", output);
+			},
+			SwapSubcommand::Verify(cmd) => {
+				let mut public_key: EncodedPublicKey = [0u8; 32];
+				parse_bytes("public key", &cmd.public_key, &mut public_key)?;
+				let mut signature: EncodedSignature = [0u8; 64];
+				parse_bytes("signature", &cmd.signature, &mut signature)?;
+				let command = cmd.command.command()?;
+				if !pallet_swap_sdk::verify(&command, &signature, &public_key) {
+					return Err("Signature is invalid".into());
+				}
+				println!("Signature is valid");
+			},
+		}
+		Ok(())
+	}
+}
@@ -0,0 +1,214 @@
+#![cfg_attr(not(feature = "std"), no_std)]
+
+//! Byte layout of the L2 commands signed with BabyJubjub and checked by the swap pallet.
+//!
+//! Every command is `COMMAND_SIZE` bytes, all integers big-endian:
+//!
+//! ```text
+//!   [0]       op
+//!   [1..9]    nonce
+//!   [9..13]   account index (token index 0 for AddPool)
+//!   [13..17]  token index / pool index / nft id (token index 1 for AddPool)
+//!   [17..49]  first 256-bit field
+//!   [49..81]  second 256-bit field
+//! ```
+//!
+//! Fields an op does not use are reserved and must be zero.
+
+use sp_core::U256;
+#[cfg(feature = "std")]
+use serde::{Deserialize, Serialize};
+
+pub const COMMAND_SIZE: usize = 81usize;
+
+pub const OP_DEPOSIT: u8 = 0u8;
+pub const OP_WITHDRAW: u8 = 1u8;
+pub const OP_SWAP: u8 = 2u8;
+pub const OP_RETRIEVE: u8 = 3u8;
+pub const OP_SUPPLY: u8 = 4u8;
+pub const OP_ADDPOOL: u8 = 5u8;
+pub const OP_SETKEY: u8 = 6u8;
+pub const OP_DEPOSIT_NFT: u8 = 7u8;
+pub const OP_WITHDRAW_NFT: u8 = 8u8;
+pub const OP_TRANSFER_NFT: u8 = 9u8;
+pub const OP_BID_NFT: u8 = 10u8;
+pub const OP_FINALIZE_NFT: u8 = 11u8;
+/// Refund of a rejected withdrawal, queued by the pallet and never signed.
+pub const OP_REJECT: u8 = 12u8;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum CommandError {
+    /// The input is not `COMMAND_SIZE` bytes.
+    InvalidLength,
+    /// The op byte is unknown or belongs to an op without a signed command.
+    InvalidOp,
+    /// A field is out of range or a reserved byte is not zero.
+    InvalidField,
+}
+
+/// A signed L2 command, as packed by clients and checked by the pallet.
+/// In JSON the variant name is carried in an `op` field, e.g. `{"op": "FinalizeNFT", "nonce": 1, ...}`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "std", serde(tag = "op"))]
+pub enum Command {
+    /// The l1 tx hash exceeds field limits, so it is not signed.
+    Deposit { nonce: u64, account_index: u32, token_index: u32, amount: U256 },
+    Withdraw { nonce: u64, account_index: u32, token_index: u32, amount: U256, l1account: U256 },
+    /// `reverse` is signed as a 256-bit field.
+    Swap { nonce: u64, account_index: u32, pool_index: u32, reverse: u8, amount: U256 },
+    PoolRetrieve { nonce: u64, account_index: u32, pool_index: u32, amount0: U256, amount1: U256 },
+    PoolSupply { nonce: u64, account_index: u32, pool_index: u32, amount0: U256, amount1: U256 },
+    AddPool { nonce: u64, token_index_0: u32, token_index_1: u32 },
+    DepositNFT { nonce: u64, account_index: u32, nft_id: u32, l1_tx_hash: U256 },
+    WithdrawNFT { nonce: u64, account_index: u32, nft_id: u32, l1account: U256 },
+    /// `recipient` is signed right-aligned in the first 256-bit field.
+    TransferNFT { nonce: u64, account_index: u32, nft_id: u32, recipient: u32 },
+    BidNFT { nonce: u64, account_index: u32, nft_id: u32, amount: U256 },
+    FinalizeNFT { nonce: u64, account_index: u32, nft_id: u32 },
+}
+
+impl Command {
+    pub fn op(&self) -> u8 {
+        match self {
+            Command::Deposit { .. } => OP_DEPOSIT,
+            Command::Withdraw { .. } => OP_WITHDRAW,
+            Command::Swap { .. } => OP_SWAP,
+            Command::PoolRetrieve { .. } => OP_RETRIEVE,
+            Command::PoolSupply { .. } => OP_SUPPLY,
+            Command::AddPool { .. } => OP_ADDPOOL,
+            Command::DepositNFT { .. } => OP_DEPOSIT_NFT,
+            Command::WithdrawNFT { .. } => OP_WITHDRAW_NFT,
+            Command::TransferNFT { .. } => OP_TRANSFER_NFT,
+            Command::BidNFT { .. } => OP_BID_NFT,
+            Command::FinalizeNFT { .. } => OP_FINALIZE_NFT,
+        }
+    }
+
+    pub fn nonce(&self) -> u64 {
+        match *self {
+            Command::Deposit { nonce, .. }
+            | Command::Withdraw { nonce, .. }
+            | Command::Swap { nonce, .. }
+            | Command::PoolRetrieve { nonce, .. }
+            | Command::PoolSupply { nonce, .. }
+            | Command::AddPool { nonce, .. }
+            | Command::DepositNFT { nonce, .. }
+            | Command::WithdrawNFT { nonce, .. }
+            | Command::TransferNFT { nonce, .. }
+            | Command::BidNFT { nonce, .. }
+            | Command::FinalizeNFT { nonce, .. } => nonce,
+        }
+    }
+
+    /// The bytes that are signed for this command.
+    pub fn encode(&self) -> [u8; COMMAND_SIZE] {
+        let (index_0, index_1, field_0, field_1) = match *self {
+            Command::Deposit { account_index, token_index, amount, .. } =>
+                (account_index, token_index, amount, U256::zero()),
+            Command::Withdraw { account_index, token_index, amount, l1account, .. } =>
+                (account_index, token_index, amount, l1account),
+            Command::Swap { account_index, pool_index, reverse, amount, .. } =>
+                (account_index, pool_index, U256::from(reverse), amount),
+            Command::PoolRetrieve { account_index, pool_index, amount0, amount1, .. } =>
+                (account_index, pool_index, amount0, amount1),
+            Command::PoolSupply { account_index, pool_index, amount0, amount1, .. } =>
+                (account_index, pool_index, amount0, amount1),
+            Command::AddPool { token_index_0, token_index_1, .. } =>
+                (token_index_0, token_index_1, U256::zero(), U256::zero()),
+            Command::DepositNFT { account_index, nft_id, l1_tx_hash, .. } =>
+                (account_index, nft_id, l1_tx_hash, U256::zero()),
+            Command::WithdrawNFT { account_index, nft_id, l1account, .. } =>
+                (account_index, nft_id, l1account, U256::zero()),
+            Command::TransferNFT { account_index, nft_id, recipient, .. } =>
+                (account_index, nft_id, U256::from(recipient), U256::zero()),
+            Command::BidNFT { account_index, nft_id, amount, .. } =>
+                (account_index, nft_id, amount, U256::zero()),
+            Command::FinalizeNFT { account_index, nft_id, .. } =>
+                (account_index, nft_id, U256::zero(), U256::zero()),
+        };
+
+        let mut command = [0u8; COMMAND_SIZE];
+        command[0] = self.op();
+        command[1..9].copy_from_slice(&self.nonce().to_be_bytes());
+        command[9..13].copy_from_slice(&index_0.to_be_bytes());
+        command[13..17].copy_from_slice(&index_1.to_be_bytes());
+        field_0.to_big_endian(&mut command[17..49]);
+        field_1.to_big_endian(&mut command[49..81]);
+        command
+    }
+
+    /// Parses the bytes of a command. Only the exact encoding of a command is accepted,
+    /// so `decode` and `encode` round-trip.
+    pub fn decode(bytes: &[u8]) -> Result<Self, CommandError> {
+        if bytes.len() != COMMAND_SIZE {
+            return Err(CommandError::InvalidLength);
+        }
+
+        let mut nonce = [0u8; 8];
+        nonce.copy_from_slice(&bytes[1..9]);
+        let nonce = u64::from_be_bytes(nonce);
+        let mut index_0 = [0u8; 4];
+        index_0.copy_from_slice(&bytes[9..13]);
+        let index_0 = u32::from_be_bytes(index_0);
+        let mut index_1 = [0u8; 4];
+        index_1.copy_from_slice(&bytes[13..17]);
+        let index_1 = u32::from_be_bytes(index_1);
+        let field_0 = U256::from_big_endian(&bytes[17..49]);
+        let field_1 = U256::from_big_endian(&bytes[49..81]);
+
+        let command = match bytes[0] {
+            OP_DEPOSIT => Command::Deposit {
+                nonce, account_index: index_0, token_index: index_1, amount: field_0,
+            },
+            OP_WITHDRAW => Command::Withdraw {
+                nonce, account_index: index_0, token_index: index_1, amount: field_0, l1account: field_1,
+            },
+            OP_SWAP => {
+                if field_0 > U256::from(u8::MAX) {
+                    return Err(CommandError::InvalidField);
+                }
+                Command::Swap {
+                    nonce, account_index: index_0, pool_index: index_1, reverse: field_0.low_u32() as u8, amount: field_1,
+                }
+            },
+            OP_RETRIEVE => Command::PoolRetrieve {
+                nonce, account_index: index_0, pool_index: index_1, amount0: field_0, amount1: field_1,
+            },
+            OP_SUPPLY => Command::PoolSupply {
+                nonce, account_index: index_0, pool_index: index_1, amount0: field_0, amount1: field_1,
+            },
+            OP_ADDPOOL => Command::AddPool {
+                nonce, token_index_0: index_0, token_index_1: index_1,
+            },
+            OP_DEPOSIT_NFT => Command::DepositNFT {
+                nonce, account_index: index_0, nft_id: index_1, l1_tx_hash: field_0,
+            },
+            OP_WITHDRAW_NFT => Command::WithdrawNFT {
+                nonce, account_index: index_0, nft_id: index_1, l1account: field_0,
+            },
+            OP_TRANSFER_NFT => {
+                if field_0 > U256::from(u32::MAX) {
+                    return Err(CommandError::InvalidField);
+                }
+                Command::TransferNFT {
+                    nonce, account_index: index_0, nft_id: index_1, recipient: field_0.low_u32(),
+                }
+            },
+            OP_BID_NFT => Command::BidNFT {
+                nonce, account_index: index_0, nft_id: index_1, amount: field_0,
+            },
+            OP_FINALIZE_NFT => Command::FinalizeNFT {
+                nonce, account_index: index_0, nft_id: index_1,
+            },
+            _ => return Err(CommandError::InvalidOp),
+        };
+
+        /* Reserved bytes must be zero */
+        if command.encode()[..] != bytes[..] {
+            return Err(CommandError::InvalidField);
+        }
+
+        Ok(command)
+    }
+}
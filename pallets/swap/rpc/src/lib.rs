@@ -0,0 +1,95 @@
+//! RPC interface for the swap pallet.
+
+use std::sync::Arc;
+
+use jsonrpc_core::{Error as RpcError, ErrorCode, Result};
+use jsonrpc_derive::rpc;
+use pallet_swap::{DepositStatus, L1TxHash, MerkleLeaf, MerkleProof, ReqId};
+use sp_api::ProvideRuntimeApi;
+use sp_blockchain::HeaderBackend;
+use sp_core::H256;
+use sp_runtime::{generic::BlockId, traits::{Block as BlockT, NumberFor}};
+
+pub use pallet_swap_runtime_api::SwapApi as SwapRuntimeApi;
+
+/// Error code for failures of the runtime API call.
+const RUNTIME_ERROR: i64 = 1;
+
+#[rpc]
+pub trait SwapApi<BlockHash> {
+    /// Status of the deposit made by the L1 transaction `l1_tx_hash`.
+    #[rpc(name = "swap_depositStatus")]
+    fn deposit_status(&self, l1_tx_hash: L1TxHash, at: Option<BlockHash>) -> Result<DepositStatus>;
+
+    /// Merkle root of the L2 state after the request `req_id`, or the latest root.
+    #[rpc(name = "swap_merkleRoot")]
+    fn merkle_root(&self, req_id: Option<ReqId>, at: Option<BlockHash>) -> Result<Option<H256>>;
+
+    /// Merkle proof of `leaf` against the latest root.
+    #[rpc(name = "swap_merkleProof")]
+    fn merkle_proof(&self, leaf: MerkleLeaf, at: Option<BlockHash>) -> Result<MerkleProof>;
+}
+
+/// Implements the swap RPC methods on top of the runtime API.
+pub struct Swap<C, Block> {
+    client: Arc<C>,
+    _marker: std::marker::PhantomData<Block>,
+}
+
+impl<C, Block> Swap<C, Block> {
+    /// Creates a new instance of the swap RPC handler.
+    pub fn new(client: Arc<C>) -> Self {
+        Self { client, _marker: Default::default() }
+    }
+}
+
+fn runtime_error<E: std::fmt::Debug>(message: &str, e: E) -> RpcError {
+    RpcError {
+        code: ErrorCode::ServerError(RUNTIME_ERROR),
+        message: message.into(),
+        data: Some(format!("{:?}", e).into()),
+    }
+}
+
+impl<C, Block> SwapApi<<Block as BlockT>::Hash> for Swap<C, Block>
+where
+    Block: BlockT,
+    C: 'static + ProvideRuntimeApi<Block> + HeaderBackend<Block>,
+    C::Api: SwapRuntimeApi<Block, NumberFor<Block>>,
+{
+    fn deposit_status(
+        &self,
+        l1_tx_hash: L1TxHash,
+        at: Option<<Block as BlockT>::Hash>,
+    ) -> Result<DepositStatus> {
+        let api = self.client.runtime_api();
+        let at = BlockId::hash(at.unwrap_or_else(|| self.client.info().best_hash));
+
+        api.deposit_status(&at, l1_tx_hash)
+            .map_err(|e| runtime_error("Unable to query deposit status.", e))
+    }
+
+    fn merkle_root(
+        &self,
+        req_id: Option<ReqId>,
+        at: Option<<Block as BlockT>::Hash>,
+    ) -> Result<Option<H256>> {
+        let api = self.client.runtime_api();
+        let at = BlockId::hash(at.unwrap_or_else(|| self.client.info().best_hash));
+
+        api.merkle_root(&at, req_id)
+            .map_err(|e| runtime_error("Unable to query merkle root.", e))
+    }
+
+    fn merkle_proof(
+        &self,
+        leaf: MerkleLeaf,
+        at: Option<<Block as BlockT>::Hash>,
+    ) -> Result<MerkleProof> {
+        let api = self.client.runtime_api();
+        let at = BlockId::hash(at.unwrap_or_else(|| self.client.info().best_hash));
+
+        api.merkle_proof(&at, leaf)
+            .map_err(|e| runtime_error("Unable to query merkle proof.", e))
+    }
+}
@@ -0,0 +1,39 @@
+//! Runtime API definition for the swap pallet.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+use codec::Codec;
+use sp_core::{H256, U256};
+use sp_std::vec::Vec;
+use pallet_swap::{AccountIndex, Amount, DepositStatus, L1TxHash, MerkleLeaf, MerkleProof, Ops, PoolIndex, ReqId, ReqRecord, SwapDirection};
+
+sp_api::decl_runtime_apis! {
+    pub trait SwapApi<BlockNumber> where
+        BlockNumber: Codec,
+    {
+        /// Time-weighted average price of `pool_index` between blocks `from` and `to`,
+        /// scaled by 10 ^ 18. `None` if the range is not covered by the price oracle.
+        fn twap(pool_index: PoolIndex, direction: SwapDirection, from: BlockNumber, to: BlockNumber) -> Option<U256>;
+
+        /// `(block, amount0, amount1, total_share)` of `pool_index` for every block in `from..=to`
+        /// that changed the pool, limited to the snapshot window.
+        fn pool_history(pool_index: PoolIndex, from: BlockNumber, to: BlockNumber) -> Vec<(BlockNumber, Amount, Amount, Amount)>;
+
+        /// Status of the deposit made by the L1 transaction `l1_tx_hash`.
+        fn deposit_status(l1_tx_hash: L1TxHash) -> DepositStatus;
+
+        /// Lifecycle records of up to `count` requests starting at `start`, with their ops.
+        fn requests(start: ReqId, count: u32) -> Vec<(ReqId, ReqRecord<BlockNumber>, Option<Ops>)>;
+
+        /// Lifecycle records of up to `count` requests submitted by `account_index`,
+        /// skipping its first `start` requests.
+        fn account_requests(account_index: AccountIndex, start: u32, count: u32) -> Vec<(ReqId, ReqRecord<BlockNumber>, Option<Ops>)>;
+
+        /// Merkle root of the L2 state after the op of `req_id` is applied,
+        /// or the current root if `req_id` is `None`.
+        fn merkle_root(req_id: Option<ReqId>) -> Option<H256>;
+
+        /// Merkle proof of the current value of `leaf`.
+        fn merkle_proof(leaf: MerkleLeaf) -> MerkleProof;
+    }
+}
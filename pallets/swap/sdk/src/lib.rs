@@ -0,0 +1,166 @@
+//! Builds, signs and wraps the L2 commands of the swap pallet into calls ready to submit.
+
+use delphinus_crypto::{BabyJubjub, BabyJubjubField, BabyJubjubPoint, Curve, Encode, EDDSA};
+use pallet_swap::{AccountIndex, Amount, Call, Config, L1Account, L1TxHash, NFTId, NonceId, PoolIndex, Reverse, TokenIndex};
+
+pub use pallet_swap_command::{
+    Command, CommandError, COMMAND_SIZE, OP_ADDPOOL, OP_BID_NFT, OP_DEPOSIT, OP_DEPOSIT_NFT, OP_FINALIZE_NFT,
+    OP_REJECT, OP_RETRIEVE, OP_SETKEY, OP_SUPPLY, OP_SWAP, OP_TRANSFER_NFT, OP_WITHDRAW, OP_WITHDRAW_NFT,
+};
+
+#[cfg(test)]
+mod tests;
+
+pub type SecretKey = [u8; 32];
+/// Compressed BabyJubjub point, as taken by `set_key`.
+pub type EncodedPublicKey = [u8; 32];
+/// `r` then `s`, as taken by every signed call.
+pub type EncodedSignature = [u8; 64];
+
+/// A signed command and the call that carries it.
+pub struct SignedCall<T: Config> {
+    pub command: Command,
+    pub signature: EncodedSignature,
+    pub call: Call<T>,
+}
+
+/// Signs commands with the BabyJubjub key registered for `account_index`.
+pub struct Signer {
+    secret_key: SecretKey,
+    account_index: AccountIndex,
+}
+
+impl Signer {
+    pub fn new(secret_key: SecretKey, account_index: AccountIndex) -> Self {
+        Signer { secret_key, account_index }
+    }
+
+    pub fn account_index(&self) -> AccountIndex {
+        self.account_index
+    }
+
+    pub fn public_key(&self) -> EncodedPublicKey {
+        public_key(&self.secret_key)
+    }
+
+    pub fn sign(&self, command: &Command) -> EncodedSignature {
+        sign(&self.secret_key, command)
+    }
+
+    /// Registers the key of this signer. The account index is assigned on chain.
+    pub fn set_key<T: Config>(&self) -> Call<T> {
+        Call::set_key(self.public_key())
+    }
+
+    pub fn add_pool<T: Config>(&self, token_index_0: TokenIndex, token_index_1: TokenIndex, nonce: NonceId) -> SignedCall<T> {
+        let command = Command::AddPool { nonce, token_index_0, token_index_1 };
+        let signature = self.sign(&command);
+        SignedCall { command, signature, call: Call::add_pool(signature, token_index_0, token_index_1, nonce) }
+    }
+
+    /// Deposit into `account_index`, signed by an admin.
+    pub fn deposit<T: Config>(
+        &self,
+        account_index: AccountIndex,
+        token_index: TokenIndex,
+        amount: Amount,
+        l1_tx_hash: L1TxHash,
+        nonce: NonceId,
+    ) -> SignedCall<T> {
+        let command = Command::Deposit { nonce, account_index, token_index, amount };
+        let signature = self.sign(&command);
+        SignedCall {
+            command,
+            signature,
+            call: Call::deposit(signature, account_index, token_index, amount, l1_tx_hash, nonce),
+        }
+    }
+
+    pub fn withdraw<T: Config>(&self, token_index: TokenIndex, amount: Amount, l1account: L1Account, nonce: NonceId) -> SignedCall<T> {
+        let command = Command::Withdraw { nonce, account_index: self.account_index, token_index, amount, l1account };
+        let signature = self.sign(&command);
+        SignedCall { command, signature, call: Call::withdraw(signature, token_index, amount, l1account, nonce) }
+    }
+
+    pub fn swap<T: Config>(&self, pool_index: PoolIndex, reverse: Reverse, amount: Amount, nonce: NonceId) -> SignedCall<T> {
+        let command = Command::Swap { nonce, account_index: self.account_index, pool_index, reverse, amount };
+        let signature = self.sign(&command);
+        SignedCall { command, signature, call: Call::swap(signature, pool_index, reverse, amount, nonce) }
+    }
+
+    pub fn pool_supply<T: Config>(&self, pool_index: PoolIndex, amount0: Amount, amount1: Amount, nonce: NonceId) -> SignedCall<T> {
+        let command = Command::PoolSupply { nonce, account_index: self.account_index, pool_index, amount0, amount1 };
+        let signature = self.sign(&command);
+        SignedCall { command, signature, call: Call::pool_supply(signature, pool_index, amount0, amount1, nonce) }
+    }
+
+    pub fn pool_retrieve<T: Config>(&self, pool_index: PoolIndex, amount0: Amount, amount1: Amount, nonce: NonceId) -> SignedCall<T> {
+        let command = Command::PoolRetrieve { nonce, account_index: self.account_index, pool_index, amount0, amount1 };
+        let signature = self.sign(&command);
+        SignedCall { command, signature, call: Call::pool_retrieve(signature, pool_index, amount0, amount1, nonce) }
+    }
+
+    /// NFT deposit into `account_index`, signed by an admin.
+    pub fn deposit_nft<T: Config>(
+        &self,
+        account_index: AccountIndex,
+        nft_id: NFTId,
+        l1_tx_hash: L1TxHash,
+        nonce: NonceId,
+    ) -> SignedCall<T> {
+        let command = Command::DepositNFT { nonce, account_index, nft_id, l1_tx_hash };
+        let signature = self.sign(&command);
+        SignedCall { command, signature, call: Call::deposit_nft(signature, account_index, nft_id, l1_tx_hash, nonce) }
+    }
+
+    pub fn withdraw_nft<T: Config>(&self, nft_id: NFTId, l1account: L1Account, nonce: NonceId) -> SignedCall<T> {
+        let command = Command::WithdrawNFT { nonce, account_index: self.account_index, nft_id, l1account };
+        let signature = self.sign(&command);
+        SignedCall { command, signature, call: Call::withdraw_nft(signature, nft_id, l1account, nonce) }
+    }
+
+    pub fn transfer_nft<T: Config>(&self, nft_id: NFTId, recipient: AccountIndex, nonce: NonceId) -> SignedCall<T> {
+        let command = Command::TransferNFT { nonce, account_index: self.account_index, nft_id, recipient };
+        let signature = self.sign(&command);
+        SignedCall { command, signature, call: Call::transfer_nft(signature, nft_id, recipient, nonce) }
+    }
+
+    pub fn bid_nft<T: Config>(&self, nft_id: NFTId, amount: Amount, nonce: NonceId) -> SignedCall<T> {
+        let command = Command::BidNFT { nonce, account_index: self.account_index, nft_id, amount };
+        let signature = self.sign(&command);
+        SignedCall { command, signature, call: Call::bid_nft(signature, nft_id, amount, nonce) }
+    }
+
+    pub fn finalize_nft<T: Config>(&self, nft_id: NFTId, nonce: NonceId) -> SignedCall<T> {
+        let command = Command::FinalizeNFT { nonce, account_index: self.account_index, nft_id };
+        let signature = self.sign(&command);
+        SignedCall { command, signature, call: Call::finalize_nft(signature, nft_id, nonce) }
+    }
+}
+
+pub fn public_key(secret_key: &SecretKey) -> EncodedPublicKey {
+    BabyJubjub::pubkey_from_secretkey(secret_key).encode()
+}
+
+pub fn sign(secret_key: &SecretKey, command: &Command) -> EncodedSignature {
+    let sign = BabyJubjub::sign(&command.encode(), secret_key);
+    let mut signature = [0u8; 64];
+    signature[..32].copy_from_slice(&sign.r.encode());
+    signature[32..].copy_from_slice(&sign.s.encode());
+    signature
+}
+
+/// Checks `signature` of `command` against `public_key`, as the pallet does.
+pub fn verify(command: &Command, signature: &EncodedSignature, public_key: &EncodedPublicKey) -> bool {
+    let r = match BabyJubjubPoint::decode(&signature[..32]) {
+        Ok(r) => r,
+        Err(_) => return false,
+    };
+    let s = BabyJubjubField::decode(&signature[32..]);
+    let key = match BabyJubjubPoint::decode(public_key) {
+        Ok(key) => key,
+        Err(_) => return false,
+    };
+
+    BabyJubjub::verify(&command.encode(), delphinus_crypto::Sign { r, s }, key)
+}
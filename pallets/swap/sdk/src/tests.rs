@@ -0,0 +1,16 @@
+use super::*;
+
+#[test]
+fn sign_then_verify() {
+    let signer = Signer::new([2u8; 32], 0u32);
+    let command = Command::Swap { nonce: 1, account_index: 0, pool_index: 0, reverse: 0, amount: 10u64.into() };
+    let signature = signer.sign(&command);
+
+    assert!(verify(&command, &signature, &signer.public_key()));
+
+    let other = Signer::new([3u8; 32], 1u32);
+    assert!(!verify(&command, &signature, &other.public_key()));
+
+    let command = Command::Swap { nonce: 2, account_index: 0, pool_index: 0, reverse: 0, amount: 10u64.into() };
+    assert!(!verify(&command, &signature, &signer.public_key()));
+}
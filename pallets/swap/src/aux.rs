@@ -1,10 +1,69 @@
 use super::*;
 
 pub fn is_admin<T: Config>(who: &T::AccountId) -> Result<(), Error<T>> {
-    let _ = T::AckAdmins::get().iter().position(|x| x== who).ok_or(Error::<T>::NoAccess)?;
+    let _ = Admins::<T>::get().iter().position(|x| x== who).ok_or(Error::<T>::NoAccess)?;
     return Ok(());
 }
 
+pub fn admins_check<T: Config>(admins: &[T::AccountId]) -> Result<(), Error<T>> {
+    if admins.len() > MAX_ADMIN_COUNT {
+        return Err(Error::<T>::InvalidAdmins);
+    }
+
+    for (i, admin) in admins.iter().enumerate() {
+        if admins[..i].contains(admin) {
+            return Err(Error::<T>::InvalidAdmins);
+        }
+    }
+    return Ok(());
+}
+
+pub fn ensure_migrated<T: Config>() -> Result<(), Error<T>> {
+    if StorageVersion::<T>::get() != Releases::LATEST {
+        return Err(Error::<T>::Migrating);
+    }
+    return Ok(());
+}
+
+pub fn ensure_not_paused<T: Config>(op: u8, pool_index: Option<&PoolIndex>) -> Result<(), Error<T>> {
+    ensure_migrated::<T>()?;
+
+    if ExitMode::<T>::get() {
+        return Err(Error::<T>::ExitMode);
+    }
+
+    if PalletPaused::<T>::get() || PausedOpMap::<T>::get(op) {
+        return Err(Error::<T>::Paused);
+    }
+
+    if let Some(pool_index) = pool_index {
+        if PausedPoolMap::<T>::get(pool_index) {
+            return Err(Error::<T>::Paused);
+        }
+    }
+    return Ok(());
+}
+
+/// Whether the oldest request not yet acked has waited `ExitModeDelay` blocks
+/// since it was submitted and since the last ack. Requests without a record were
+/// submitted before the storage migration and count from `UpgradedAt`.
+pub fn exit_mode_check<T: Config>(now: T::BlockNumber) -> bool {
+    if ExitMode::<T>::get() {
+        return false;
+    }
+
+    let req_id = CompleteReqIndex::<T>::get() + U256::from(1u32);
+    if req_id > ReqIndex::<T>::get() {
+        return false;
+    }
+
+    let since = match ReqRecordMap::<T>::get(&req_id) {
+        Some(record) => record.submitted_at.max(LastAckBlock::<T>::get()),
+        None => UpgradedAt::<T>::get().max(LastAckBlock::<T>::get()),
+    };
+    return now >= since.saturating_add(T::ExitModeDelay::get());
+}
+
 pub fn nonce_check<T: Config>(account: &T::AccountId, nonce: NonceId) -> Result<NonceId, Error<T>> {
     if nonce != NonceMap::<T>::get(account) {
         return Err(Error::<T>::NonceInconsistent);
@@ -32,22 +91,106 @@ pub fn create_account_index<T: Config>(account: &T::AccountId) -> Result<Account
         return Err(Error::<T>::AccountExists);
     }
 
-    let index = AccountIndexCount::get();
+    let index = AccountIndexCount::<T>::get();
     if index >= MAX_ACCOUNT_COUNT {
         return Err(Error::<T>::AccountIndexOverflow);
     }
-    AccountIndexCount::set(index + 1);
+    AccountIndexCount::<T>::set(index + 1);
     AccountIndexMap::<T>::insert(account, index);
     return Ok(index);
 }
 
+/* ---- Token ---- */
+pub fn token_check<T: Config>(token_index: &TokenIndex) -> Result<(), Error<T>> {
+    if !TokenMap::<T>::contains_key(token_index) {
+        return Err(Error::<T>::TokenNotExists);
+    }
+    return Ok(());
+}
+
+// Emits TokenLimitExceeded with the amount the op would have reached and the limit, and returns
+// the error of the limit.
+fn token_limit_exceeded<T: Config>(
+    account_index: &AccountIndex,
+    token_index: &TokenIndex,
+    kind: TokenLimitKind,
+    reached: Amount,
+    limit: Amount
+) -> Error<T> {
+    Pallet::<T>::deposit_event(Event::<T>::TokenLimitExceeded(*account_index, *token_index, kind, reached, limit));
+    return match kind {
+        TokenLimitKind::Deposit => Error::<T>::DepositLimitExceeded,
+        TokenLimitKind::Withdraw => Error::<T>::WithdrawLimitExceeded,
+        TokenLimitKind::PeriodWithdraw => Error::<T>::PeriodWithdrawLimitExceeded,
+    };
+}
+
+pub fn deposit_limit_check<T: Config>(
+    account_index: &AccountIndex,
+    token_index: &TokenIndex,
+    amount: Amount
+) -> Result<(), Error<T>> {
+    let max_deposit = TokenLimitMap::<T>::get(token_index).and_then(|limit| limit.max_deposit);
+    if let Some(max_deposit) = max_deposit {
+        if amount > max_deposit {
+            return Err(token_limit_exceeded::<T>(account_index, token_index, TokenLimitKind::Deposit, amount, max_deposit));
+        }
+    }
+    return Ok(());
+}
+
+// Returns the new (period start, withdrawn amount) of the token if it has a rolling cap.
+pub fn withdraw_limit_check<T: Config>(
+    account_index: &AccountIndex,
+    token_index: &TokenIndex,
+    amount: Amount
+) -> Result<Option<(T::BlockNumber, Amount)>, Error<T>> {
+    let limit = match TokenLimitMap::<T>::get(token_index) {
+        Some(limit) => limit,
+        None => return Ok(None),
+    };
+
+    if let Some(max_withdraw) = limit.max_withdraw {
+        if amount > max_withdraw {
+            return Err(token_limit_exceeded::<T>(account_index, token_index, TokenLimitKind::Withdraw, amount, max_withdraw));
+        }
+    }
+
+    let max_period_withdraw = match limit.max_period_withdraw {
+        Some(max_period_withdraw) => max_period_withdraw,
+        None => return Ok(None),
+    };
+
+    let now = <frame_system::Module<T>>::block_number();
+    let (period_start, withdrawn) = WithdrawPeriodMap::<T>::get(token_index);
+    let (period_start, withdrawn) = if now >= period_start + limit.period {
+        (now, U256::from(0))
+    } else {
+        (period_start, withdrawn)
+    };
+
+    let withdrawn = withdrawn.saturating_add(amount);
+    if withdrawn > max_period_withdraw {
+        return Err(token_limit_exceeded::<T>(account_index, token_index, TokenLimitKind::PeriodWithdraw, withdrawn, max_period_withdraw));
+    }
+    return Ok(Some((period_start, withdrawn)));
+}
+
+// Takes a rejected withdrawal back out of the rolling cap if it was counted in the current period.
+pub fn withdraw_period_refund<T: Config>(token_index: &TokenIndex, amount: Amount, submitted_at: T::BlockNumber) -> () {
+    let (period_start, withdrawn) = WithdrawPeriodMap::<T>::get(token_index);
+    if submitted_at >= period_start {
+        WithdrawPeriodMap::<T>::insert(token_index, (period_start, withdrawn.saturating_sub(amount)));
+    }
+}
+
 /* ---- Pool Index ---- */
 pub fn get_pool_index<T: Config>(
     token_src_index: &TokenIndex,
     token_dst_index: &TokenIndex,
 ) -> Result<TokenIndex, Error<T>> {
     let pool_index =
-        PoolIndexMap::get((token_src_index, token_dst_index)).ok_or(Error::<T>::PoolNotExists)?;
+        PoolIndexMap::<T>::get((token_src_index, token_dst_index)).ok_or(Error::<T>::PoolNotExists)?;
     return Ok(pool_index);
 }
 
@@ -59,13 +202,13 @@ pub fn create_pool_index<T: Config>(
         return Err(Error::<T>::PoolExists);
     }
 
-    let index = PoolIndexCount::get();
+    let index = PoolIndexCount::<T>::get();
     if index >= MAX_POOL_COUNT {
         return Err(Error::<T>::PoolIndexOverflow);
     }
-    PoolIndexCount::set(index + 1);
-    PoolIndexMap::insert((token_src_index, token_dst_index), index);
-    PoolMap::insert(
+    PoolIndexCount::<T>::set(index + 1);
+    PoolIndexMap::<T>::insert((token_src_index, token_dst_index), index);
+    PoolMap::<T>::insert(
         index,
         (
             token_src_index.clone(),
@@ -75,6 +218,7 @@ pub fn create_pool_index<T: Config>(
             U256::from(0)
         ),
     );
+    merkle_leaf_update::<T>(MerkleLeaf::Pool(index));
     return Ok(index);
 }
 
@@ -85,7 +229,7 @@ pub fn balance_add<T: Config>(
     token_index: &TokenIndex,
     amount: Amount,
 ) -> Result<Amount, Error<T>> {
-    let new_amount = BalanceMap::get((&account_index, token_index))
+    let new_amount = BalanceMap::<T>::get((&account_index, token_index))
         .checked_add_on_circuit(amount)
         .ok_or(Error::<T>::BalanceOverflow)?;
     return Ok(new_amount);
@@ -96,14 +240,15 @@ pub fn balance_sub<T: Config>(
     token_index: &TokenIndex,
     amount: Amount,
 ) -> Result<Amount, Error<T>> {
-    let new_amount = BalanceMap::get((&account_index, token_index))
+    let new_amount = BalanceMap::<T>::get((&account_index, token_index))
         .checked_sub(amount)
         .ok_or(Error::<T>::BalanceNotEnough)?;
     return Ok(new_amount);
 }
 
-pub fn balance_set(account_index: &AccountIndex, token_index: &TokenIndex, amount: Amount) -> () {
-    BalanceMap::insert((&account_index, token_index), amount);
+pub fn balance_set<T: Config>(account_index: &AccountIndex, token_index: &TokenIndex, amount: Amount) -> () {
+    BalanceMap::<T>::insert((&account_index, token_index), amount);
+    merkle_leaf_update::<T>(MerkleLeaf::Balance(*account_index, *token_index));
 }
 
 /* ---- Pool ---- */
@@ -115,7 +260,7 @@ pub fn pool_change<T: Config>(
     change_1: Amount,
 ) -> Result<(Amount, Amount), Error<T>> {
     let (token_index_0, token_index_1, amount_0, amount_1, total_share) =
-        PoolMap::get(pool_index).ok_or(Error::<T>::PoolNotExists)?;
+        PoolMap::<T>::get(pool_index).ok_or(Error::<T>::PoolNotExists)?;
     let new_amount_0 = if is_add_0 {
         amount_0
             .checked_add_on_circuit(change_0)
@@ -134,10 +279,13 @@ pub fn pool_change<T: Config>(
             .checked_sub(change_1)
             .ok_or(Error::<T>::PoolBalanceNotEnough)?
     };
-    PoolMap::insert(
+    price_observe::<T>(pool_index, new_amount_0, new_amount_1);
+    pool_snapshot::<T>(pool_index, new_amount_0, new_amount_1, total_share);
+    PoolMap::<T>::insert(
         pool_index,
         (token_index_0, token_index_1, new_amount_0, new_amount_1, total_share)
     );
+    merkle_leaf_update::<T>(MerkleLeaf::Pool(*pool_index));
     return Ok((new_amount_0, new_amount_1));
 }
 
@@ -150,7 +298,7 @@ pub fn pool_change_with_share<T: Config>(
     amount: Amount
 ) -> Result<(Amount, Amount, Amount), Error<T>> {
     let (token_index_0, token_index_1, amount_0, amount_1, total_share) =
-        PoolMap::get(pool_index).ok_or(Error::<T>::PoolNotExists)?;
+        PoolMap::<T>::get(pool_index).ok_or(Error::<T>::PoolNotExists)?;
     let new_amount_0 = if is_add_0 {
         amount_0
             .checked_add_on_circuit(change_0)
@@ -178,20 +326,107 @@ pub fn pool_change_with_share<T: Config>(
             .checked_sub(amount)
             .ok_or(Error::<T>::ShareNotEnough)?
     };
-    PoolMap::insert(
+    price_observe::<T>(pool_index, new_amount_0, new_amount_1);
+    pool_snapshot::<T>(pool_index, new_amount_0, new_amount_1, total_share_new);
+    PoolMap::<T>::insert(
         pool_index,
         (token_index_0, token_index_1, new_amount_0, new_amount_1, total_share_new)
     );
+    merkle_leaf_update::<T>(MerkleLeaf::Pool(*pool_index));
     return Ok((new_amount_0, new_amount_1, total_share_new));
 }
 
+pub fn pool_snapshot<T: Config>(
+    pool_index: &PoolIndex,
+    amount_0: Amount,
+    amount_1: Amount,
+    total_share: Amount
+) -> () {
+    let now = <frame_system::Module<T>>::block_number();
+    PoolSnapshotMap::<T>::insert(now, pool_index, (amount_0, amount_1, total_share));
+}
+
+/* ---- Price Oracle ---- */
+pub fn pool_price(reserve_in: Amount, reserve_out: Amount) -> U256 {
+    if reserve_in == U256::from(0) {
+        return U256::from(0);
+    }
+    reserve_out.saturating_mul(U256::exp10(PRICE_ORDER_OF_MAGNITUDE)) / reserve_in
+}
+
+fn price_cumulative_advance<T: Config>(
+    observation: &PriceObservation<T::BlockNumber>,
+    block_number: T::BlockNumber
+) -> (U256, U256) {
+    let elapsed = U256::from((block_number - observation.block_number).saturated_into::<u64>());
+    let price0 = pool_price(observation.reserve0, observation.reserve1);
+    let price1 = pool_price(observation.reserve1, observation.reserve0);
+    (
+        observation.price0_cumulative.overflowing_add(price0.overflowing_mul(elapsed).0).0,
+        observation.price1_cumulative.overflowing_add(price1.overflowing_mul(elapsed).0).0
+    )
+}
+
+// Must be called with the new reserves on every pool change, before PoolMap is updated.
+pub fn price_observe<T: Config>(
+    pool_index: &PoolIndex,
+    reserve0: Amount,
+    reserve1: Amount
+) -> () {
+    let now = <frame_system::Module<T>>::block_number();
+    let count = PriceObservationCount::<T>::get(pool_index);
+    let last = if count == 0 {
+        None
+    } else {
+        PriceObservationMap::<T>::get(pool_index, ((count - 1) % MAX_PRICE_OBSERVATIONS as u64) as u32)
+    };
+
+    let (count, price0_cumulative, price1_cumulative) = match last {
+        // several changes in one block only update the reserves of the block
+        Some(last) if last.block_number == now => (count - 1, last.price0_cumulative, last.price1_cumulative),
+        Some(last) => {
+            let (price0_cumulative, price1_cumulative) = price_cumulative_advance::<T>(&last, now);
+            (count, price0_cumulative, price1_cumulative)
+        },
+        None => (count, U256::from(0), U256::from(0)),
+    };
+
+    PriceObservationMap::<T>::insert(
+        pool_index,
+        (count % MAX_PRICE_OBSERVATIONS as u64) as u32,
+        PriceObservation {
+            block_number: now,
+            price0_cumulative,
+            price1_cumulative,
+            reserve0,
+            reserve1,
+        }
+    );
+    PriceObservationCount::<T>::insert(pool_index, count + 1);
+}
+
+pub fn price_cumulative_at<T: Config>(
+    pool_index: &PoolIndex,
+    block_number: T::BlockNumber
+) -> Option<(U256, U256)> {
+    let count = PriceObservationCount::<T>::get(pool_index);
+    let oldest = count.saturating_sub(MAX_PRICE_OBSERVATIONS as u64);
+    for i in (oldest..count).rev() {
+        let observation = PriceObservationMap::<T>::get(pool_index, (i % MAX_PRICE_OBSERVATIONS as u64) as u32)?;
+        if observation.block_number <= block_number {
+            return Some(price_cumulative_advance::<T>(&observation, block_number));
+        }
+    }
+    None
+}
+
 /* ---- Share ---- */
 pub fn share_add<T: Config>(
     account_index: &AccountIndex,
     pool_index: &PoolIndex,
     amount: Amount,
 ) -> Result<Amount, Error<T>> {
-    let new_amount = ShareMap::get((account_index, pool_index))
+    let new_amount = ShareMap::<T>::get((account_index, pool_index))
         .checked_add_on_circuit(amount)
         .ok_or(Error::<T>::ShareOverflow)?;
     return Ok(new_amount);
@@ -202,23 +437,28 @@ pub fn share_sub<T: Config>(
     pool_index: &PoolIndex,
     amount: Amount,
 ) -> Result<Amount, Error<T>> {
-    let new_amount = ShareMap::get((&account_index, pool_index))
+    let new_amount = ShareMap::<T>::get((&account_index, pool_index))
         .checked_sub(amount)
         .ok_or(Error::<T>::ShareNotEnough)?;
     return Ok(new_amount);
 }
 
+pub fn share_set<T: Config>(account_index: &AccountIndex, pool_index: &PoolIndex, amount: Amount) -> () {
+    ShareMap::<T>::insert((account_index, pool_index), amount);
+    merkle_leaf_update::<T>(MerkleLeaf::Share(*account_index, *pool_index));
+}
+
 pub fn get_share_change<T: Config>(
     pool_index: &PoolIndex,
     amount: Amount,
     is_supply: bool
 ) -> Result<Amount, Error<T>>{
-    let (_, _, liq0, _, total_share) = PoolMap::get(pool_index).ok_or(Error::<T>::PoolNotExists)?;
+    let (_, _, liq0, _, total_share) = PoolMap::<T>::get(pool_index).ok_or(Error::<T>::PoolNotExists)?;
 
     valid_pool_amount(amount).ok_or(Error::<T>::InvalidAmount)?;
 
     let share_change = if is_supply {
-        if is_pool_empty(pool_index) {
+        if is_pool_empty::<T>(pool_index) {
             let initial_amount = amount.checked_mul_on_circuit(U256::exp10(ORDER_OF_MAGNITUDE)).ok_or(Error::<T>::InternalCalcOverflow)?;
             initial_amount
         } else {
@@ -263,7 +503,7 @@ pub fn calculate_amount1_to_pool<T: Config>(
     amount0: Amount,
     is_supply: bool
 ) -> Result<Amount, Error<T>> {
-    let (_, _, liq0, liq1, _) = PoolMap::get(pool_index).ok_or(Error::<T>::PoolNotExists)?;
+    let (_, _, liq0, liq1, _) = PoolMap::<T>::get(pool_index).ok_or(Error::<T>::PoolNotExists)?;
 
     let dividend = amount0.checked_mul_on_circuit(liq1).ok_or(Error::<T>::InternalCalcOverflow)?;
     let quotient = dividend.checked_div_on_circuit(liq0).ok_or(Error::<T>::InternalCalcOverflow)?;
@@ -281,10 +521,10 @@ pub fn calculate_amount1_to_pool<T: Config>(
     return Ok(amount1_to_pool);
 }
 
-pub fn is_pool_empty (
+pub fn is_pool_empty<T: Config>(
     pool_index: &PoolIndex
 ) -> bool {
-    let (_, _, liq0, _, _) = PoolMap::get(pool_index).unwrap();
+    let (_, _, liq0, _, _) = PoolMap::<T>::get(pool_index).unwrap();
     liq0 == U256::from(0)
 }
 
@@ -334,7 +574,7 @@ pub fn valid_input_y_amount(
 
 /* --- NFT --- */
 
-trait NFTData<T: Config> {
+pub trait NFTData<T: Config> {
     fn checked_empty(&self) -> Result<(), Error<T>>;
     fn checked_owner(&self, account_index: &AccountIndex) -> Result<(), Error<T>>;
 }
@@ -361,10 +601,11 @@ pub fn nft_add<T: Config>(
     account_index: &AccountIndex,
     nft_id: &NFTId,
 ) -> Result<(), Error<T>> {
-    let nft = NFTMap::get(&nft_id);
+    let nft = NFTMap::<T>::get(&nft_id);
     nft.checked_empty()?;
     let bidder: Option<AccountIndex> = None;
-    NFTMap::insert(nft_id, (account_index, U256::from(0), bidder));
+    NFTMap::<T>::insert(nft_id, (account_index, U256::from(0), bidder));
+    merkle_leaf_update::<T>(MerkleLeaf::NFT(*nft_id));
     return Ok(());
 }
 
@@ -372,14 +613,15 @@ pub fn nft_withdraw<T: Config>(
     account_index: &AccountIndex,
     nft_id: &NFTId,
 ) -> Result<(), Error<T>> {
-    let nft = NFTMap::get(&nft_id);
+    let nft = NFTMap::<T>::get(&nft_id);
     nft.checked_owner(account_index)?;
     if nft.2 != None {
         let new_balance_amount = balance_add::<T>(&nft.2.unwrap(), &NFT_TOKEN_INDEX, nft.1)?;
-        balance_set(&nft.2.unwrap(), &NFT_TOKEN_INDEX, new_balance_amount);
+        balance_set::<T>(&nft.2.unwrap(), &NFT_TOKEN_INDEX, new_balance_amount);
     }
     let bidder: Option<AccountIndex> = None;
-    NFTMap::insert(nft_id, (0, U256::from(0), bidder));
+    NFTMap::<T>::insert(nft_id, (0, U256::from(0), bidder));
+    merkle_leaf_update::<T>(MerkleLeaf::NFT(*nft_id));
     return Ok(());
 }
 
@@ -388,10 +630,11 @@ pub fn nft_transfer<T: Config>(
     to_index: &AccountIndex,
     nft_id: &NFTId,
 ) -> Result<(), Error<T>> {
-    let nft = NFTMap::get(&nft_id);
+    let nft = NFTMap::<T>::get(&nft_id);
     nft.checked_owner(from_index)?;
 
-    NFTMap::insert(nft_id, (to_index, nft.1, nft.2));
+    NFTMap::<T>::insert(nft_id, (to_index, nft.1, nft.2));
+    merkle_leaf_update::<T>(MerkleLeaf::NFT(*nft_id));
     return Ok(());
 }
 
@@ -400,22 +643,23 @@ pub fn nft_bid<T: Config>(
     amount: Amount,
     nft_id: &NFTId,
 ) -> Result<(), Error<T>> {
-    let nft = NFTMap::get(&nft_id);
+    let nft = NFTMap::<T>::get(&nft_id);
     if nft.0 == 0u32 {
         return Err(Error::<T>::InvalidNFTIndex);
     }
     if nft.2 != None {
         let new_balance_amount0 = balance_add::<T>(&nft.2.unwrap(), &NFT_TOKEN_INDEX, nft.1)?;
         let new_balance_amount1 = balance_sub::<T>(bidder, &NFT_TOKEN_INDEX, amount)?;
-        balance_set(&nft.2.unwrap(), &NFT_TOKEN_INDEX, new_balance_amount0);
-        balance_set(bidder, &NFT_TOKEN_INDEX, new_balance_amount1);
+        balance_set::<T>(&nft.2.unwrap(), &NFT_TOKEN_INDEX, new_balance_amount0);
+        balance_set::<T>(bidder, &NFT_TOKEN_INDEX, new_balance_amount1);
     } else {
         let new_balance_amount1 = balance_sub::<T>(bidder, &NFT_TOKEN_INDEX, amount)?;
-        balance_set(bidder, &NFT_TOKEN_INDEX, new_balance_amount1);
+        balance_set::<T>(bidder, &NFT_TOKEN_INDEX, new_balance_amount1);
 
     }
     let bidder: Option<&AccountIndex> = Some(bidder);
-    NFTMap::insert(nft_id, (nft.0, amount, bidder));
+    NFTMap::<T>::insert(nft_id, (nft.0, amount, bidder));
+    merkle_leaf_update::<T>(MerkleLeaf::NFT(*nft_id));
     return Ok(());
 }
 
@@ -423,20 +667,21 @@ pub fn nft_finalize<T: Config>(
     account_index: &AccountIndex,
     nft_id: &NFTId,
 ) -> Result<(), Error<T>> {
-    let nft = NFTMap::get(&nft_id);
+    let nft = NFTMap::<T>::get(&nft_id);
     if nft.2 == None {
         return Err(Error::<T>::InvalidNFTIndex);
     }
     nft.checked_owner(account_index)?;
     let new_balance_amount = balance_add::<T>(account_index, &NFT_TOKEN_INDEX, nft.1)?;
-    balance_set(account_index, &NFT_TOKEN_INDEX, new_balance_amount);
+    balance_set::<T>(account_index, &NFT_TOKEN_INDEX, new_balance_amount);
     let bidder: Option<AccountIndex> = None;
-    NFTMap::insert(nft_id, (nft.2.unwrap(), U256::from(0u8), bidder));
+    NFTMap::<T>::insert(nft_id, (nft.2.unwrap(), U256::from(0u8), bidder));
+    merkle_leaf_update::<T>(MerkleLeaf::NFT(*nft_id));
     return Ok(());
 }
 
 pub fn validation_account_index<T: Config>(account_index: AccountIndex) -> Result<(), Error<T>> {
-    if account_index >= AccountIndexCount::get() || account_index == 0u32 {
+    if account_index >= AccountIndexCount::<T>::get() || account_index == 0u32 {
         return Err(Error::<T>::InvalidAccount);
     }
     return Ok(());
@@ -449,8 +694,90 @@ pub fn validation_nft_index<T: Config>(nft_id: NFTId) -> Result<(), Error<T>> {
     return Ok(());
 }
 
+/* ---- Request ---- */
+pub fn req_submit<T: Config>(
+    req_id: &ReqId,
+    op_code: u8,
+    op: Ops,
+    account_index: AccountIndex
+) -> () {
+    PendingReqMap::<T>::insert(req_id, op);
+    ReqIndex::<T>::put(req_id);
+
+    ReqRecordMap::<T>::insert(req_id, ReqRecord {
+        op: op_code,
+        account_index,
+        submitted_at: <frame_system::Module<T>>::block_number(),
+        acks: 0u8,
+        completed_at: None,
+        status: ReqStatus::Pending,
+    });
+    let count = AccountReqCount::<T>::get(account_index);
+    AccountReqMap::<T>::insert(account_index, count, req_id);
+    AccountReqCount::<T>::insert(account_index, count + 1);
+}
+
+pub fn req_complete<T: Config>(req_id: &ReqId) -> () {
+    if let Some(l1txhash) = DepositMap::<T>::get(req_id) {
+        L1TxMap::<T>::insert(l1txhash, L1TxStatus::Done);
+    }
+
+    if let Some(req) = PendingReqMap::<T>::take(req_id) {
+        CompleteReqMap::<T>::insert(req_id, req);
+    }
+
+    ReqRecordMap::<T>::mutate(req_id, |record| {
+        if let Some(record) = record {
+            record.completed_at = Some(<frame_system::Module<T>>::block_number());
+            record.status = ReqStatus::Complete;
+        }
+    });
+}
+
+/// Removes completed and rejected requests after `PrunedReqIndex` and up to `up_to` from state,
+/// keeping the hash of their op. Visits at most `max` requests and stops at the first pending
+/// request, or at the first one still inside the retention window when `now` is given. Requests
+/// without a record are retained from `UpgradedAt`. Returns the number of requests pruned.
+pub fn complete_reqs_prune<T: Config>(up_to: ReqId, max: u32, now: Option<T::BlockNumber>) -> u32 {
+    let mut req_id = PrunedReqIndex::<T>::get();
+    let mut pruned = 0u32;
+
+    while req_id < up_to && pruned < max {
+        let next = req_id + U256::from(1u32);
+
+        let op = match CompleteReqMap::<T>::get(&next) {
+            Some(op) => op,
+            None => match RejectedReqMap::<T>::get(&next) {
+                Some((op, _)) => op,
+                None => break,
+            },
+        };
+
+        if let Some(now) = now {
+            let completed_at = ReqRecordMap::<T>::get(&next)
+                .and_then(|record| record.completed_at)
+                .unwrap_or_else(UpgradedAt::<T>::get);
+            if completed_at.saturating_add(T::CompleteReqRetention::get()) > now {
+                break;
+            }
+        }
+
+        PrunedReqMap::<T>::insert(&next, T::Hashing::hash_of(&op));
+        CompleteReqMap::<T>::remove(&next);
+        RejectedReqMap::<T>::remove(&next);
+        ReqRecordMap::<T>::remove(&next);
+        AckMap::<T>::remove(&next);
+        pruned += 1;
+
+        req_id = next;
+    }
+
+    PrunedReqIndex::<T>::put(req_id);
+    return pruned;
+}
+
 pub fn req_id_get<T: Config>() -> Result<ReqId, Error<T>> {
-    let req_id = ReqIndex::get()
+    let req_id = ReqIndex::<T>::get()
         .checked_add_on_circuit(U256::from(1))
         .ok_or(Error::<T>::ReqIdOverflow)?;
     return Ok(req_id);
@@ -523,6 +850,11 @@ pub fn u256_from_bigint(x: &BigInt) -> U256 {
     U256::from_little_endian(&buf)
 }
 
+/// Whether `key` is an encoded BabyJubjub point, as accepted by `set_key`.
+pub fn key_check(key: &[u8; 32]) -> bool {
+    BabyJubjubPoint::decode(key).is_ok()
+}
+
 fn _check_sign<T: Config>(data: &[u8], sign: Signature, key: PublicKey) -> Result<(), Error<T>> {
     let s = delphinus_crypto::Sign::<BabyJubjubField> {
         r: BabyJubjubPoint {
@@ -548,7 +880,7 @@ pub fn check_sign<T: Config>(
     command: &[u8],
     sign: &[u8],
 ) -> Result<Signature, Error<T>> {
-    let key = KeyMap::get(account_index).ok_or(Error::<T>::InvalidAccount)?;
+    let key = KeyMap::<T>::get(account_index).ok_or(Error::<T>::InvalidAccount)?;
     let _r = BabyJubjubPoint::decode(&sign[..32]).map_err(|_| Error::<T>::InvalidSignature)?;
     let _s = BabyJubjubField::decode(&sign[32..]);
     let rx = u256_from_bigint(&_r.x.v);
@@ -0,0 +1,350 @@
+use super::*;
+use frame_support::{debug, sp_io};
+use frame_support::sp_runtime::offchain::{
+    http,
+    storage::StorageValueRef,
+    storage_lock::{StorageLock, Time},
+    Duration,
+    StorageKind,
+};
+use frame_support::sp_std::str;
+use lite_json::json::JsonValue;
+
+/// Offchain storage key of the URL, as raw UTF-8, of the L1 JSON-RPC endpoint read by `HttpL1Source`.
+pub const L1_RPC_URL_STORAGE: &[u8] = b"pallet_swap::l1_rpc_url";
+/// Offchain storage key of the raw 20 bytes address of the L1 bridge contract.
+pub const L1_BRIDGE_STORAGE: &[u8] = b"pallet_swap::l1_bridge";
+/// Offchain storage key of the SCALE encoded `u64` depth an L1 block must reach before its
+/// deposits are read, 12 if unset.
+pub const L1_CONFIRMATIONS_STORAGE: &[u8] = b"pallet_swap::l1_confirmations";
+/// Offchain storage key of the SCALE encoded `u64` L1 block the first scan starts at, the latest
+/// confirmed block if unset.
+pub const L1_START_BLOCK_STORAGE: &[u8] = b"pallet_swap::l1_start_block";
+/// Offchain storage key of the SCALE encoded `(u64, U256)` number and hash of the last scanned L1 block.
+pub const L1_CURSOR_STORAGE: &[u8] = b"pallet_swap::l1_cursor";
+/// Offchain storage key of the SCALE encoded `Vec<(u64, L1Deposit)>` deposits read from L1 and not
+/// forgotten yet, with the L1 block they were read from.
+pub const L1_PENDING_STORAGE: &[u8] = b"pallet_swap::l1_pending";
+/// Offchain storage key of the lock taken by `HttpL1Source` while it updates the cursor and the
+/// pending deposits, so that the workers of overlapping blocks do not overwrite each other.
+pub const L1_LOCK_STORAGE: &[u8] = b"pallet_swap::l1_lock";
+/// Offchain storage key of the SCALE encoded `Vec<L1Deposit>` read by `LocalStorageL1Source`.
+pub const L1_DEPOSITS_STORAGE: &[u8] = b"pallet_swap::l1_deposits";
+
+const DEFAULT_CONFIRMATIONS: u64 = 12u64;
+/// Most L1 blocks scanned by one `eth_getLogs`.
+const MAX_LOG_RANGE: u64 = 1_000u64;
+const HTTP_TIMEOUT_MS: u64 = 3_000u64;
+/* Longer than the HTTP requests of a scan */
+const LOCK_EXPIRATION_MS: u64 = 30_000u64;
+
+/* Signatures of the events of the bridge contract */
+const DEPOSIT_EVENT: &[u8] = b"Deposit(uint32,uint32,uint256)";
+const DEPOSIT_NFT_EVENT: &[u8] = b"DepositNFT(uint32,uint32)";
+
+/// Reads the deposits of the bridge contract at `L1_BRIDGE_STORAGE` from the Ethereum JSON-RPC
+/// endpoint at `L1_RPC_URL_STORAGE`. The bridge emits, without indexed parameters and at most one
+/// per L1 transaction,
+///
+/// ```text
+/// event Deposit(uint32 account_index, uint32 token_index, uint256 amount);
+/// event DepositNFT(uint32 account_index, uint32 nft_id);
+/// ```
+///
+/// Blocks are scanned with `eth_getLogs` once `L1_CONFIRMATIONS_STORAGE` blocks deep, at most
+/// `MAX_LOG_RANGE` of them per call, and the deposits found are kept until forgotten. If the last
+/// scanned block is no longer on the L1 chain, the scan moves back by the confirmation depth and
+/// drops the deposits read after that block. Logs that are not one of the two events are skipped.
+/// If another worker holds `L1_LOCK_STORAGE`, the deposits are not read in this block.
+/// Nothing is requested until the URL and the bridge address are set, e.g. with the
+/// `offchain_localStorageSet` RPC.
+pub struct HttpL1Source;
+
+impl L1EventSource for HttpL1Source {
+    fn deposits() -> Result<Vec<L1Deposit>, ()> {
+        let url = match sp_io::offchain::local_storage_get(StorageKind::PERSISTENT, L1_RPC_URL_STORAGE) {
+            Some(url) => url,
+            None => return Ok(Vec::new()),
+        };
+        let url = str::from_utf8(&url).map_err(|_| ())?;
+        let bridge = match sp_io::offchain::local_storage_get(StorageKind::PERSISTENT, L1_BRIDGE_STORAGE) {
+            Some(bridge) if bridge.len() == 20 => bridge,
+            Some(_) => return Err(()),
+            None => return Ok(Vec::new()),
+        };
+
+        let mut lock = StorageLock::<Time>::with_deadline(L1_LOCK_STORAGE, Duration::from_millis(LOCK_EXPIRATION_MS));
+        let _guard = lock.try_lock().map_err(|_| ())?;
+
+        let pending_storage = StorageValueRef::persistent(L1_PENDING_STORAGE);
+        let mut pending = match pending_storage.get::<Vec<(u64, L1Deposit)>>() {
+            Some(Some(pending)) => pending,
+            Some(None) => return Err(()),
+            None => Vec::new(),
+        };
+
+        /* The deposits are stored before the cursor moves past them */
+        if let Some(cursor) = scan(url, &bridge, &mut pending)? {
+            pending_storage.set(&pending);
+            StorageValueRef::persistent(L1_CURSOR_STORAGE).set(&cursor);
+        }
+
+        Ok(pending.into_iter().map(|(_, deposit)| deposit).collect())
+    }
+
+    fn forget(l1_tx_hash: &L1TxHash) {
+        /* Forgotten in a later block if the lock is held */
+        let mut lock = StorageLock::<Time>::with_deadline(L1_LOCK_STORAGE, Duration::from_millis(LOCK_EXPIRATION_MS));
+        let _guard = match lock.try_lock() {
+            Ok(guard) => guard,
+            Err(_) => return,
+        };
+
+        let pending_storage = StorageValueRef::persistent(L1_PENDING_STORAGE);
+        if let Some(Some(mut pending)) = pending_storage.get::<Vec<(u64, L1Deposit)>>() {
+            pending.retain(|(_, deposit)| deposit.l1_tx_hash() != *l1_tx_hash);
+            pending_storage.set(&pending);
+        }
+    }
+}
+
+/* Scans the next confirmed blocks into `pending`, returning the new cursor if it moved */
+fn scan(url: &str, bridge: &[u8], pending: &mut Vec<(u64, L1Deposit)>) -> Result<Option<(u64, U256)>, ()> {
+    let confirmations = match StorageValueRef::persistent(L1_CONFIRMATIONS_STORAGE).get::<u64>() {
+        Some(Some(confirmations)) => confirmations,
+        Some(None) => return Err(()),
+        None => DEFAULT_CONFIRMATIONS,
+    };
+    let head = parse_u64(&rpc(url, b"eth_blockNumber", b"[]")?).ok_or(())?;
+    let confirmed = match head.checked_sub(confirmations) {
+        Some(confirmed) => confirmed,
+        None => return Ok(None),
+    };
+
+    let from = match StorageValueRef::persistent(L1_CURSOR_STORAGE).get::<(u64, U256)>() {
+        Some(Some((block, hash))) => {
+            if block_hash(url, block)? != hash {
+                let block = block.saturating_sub(confirmations);
+                pending.retain(|(deposit_block, _)| *deposit_block <= block);
+                return Ok(Some((block, block_hash(url, block)?)));
+            }
+            block + 1
+        },
+        Some(None) => return Err(()),
+        None => match StorageValueRef::persistent(L1_START_BLOCK_STORAGE).get::<u64>() {
+            Some(Some(block)) => block,
+            Some(None) => return Err(()),
+            None => confirmed,
+        },
+    };
+    if from > confirmed {
+        return Ok(None);
+    }
+    let to = confirmed.min(from + MAX_LOG_RANGE - 1);
+
+    let mut params = b"[{\"fromBlock\":".to_vec();
+    params.extend(quantity(from));
+    params.extend_from_slice(b",\"toBlock\":");
+    params.extend(quantity(to));
+    params.extend_from_slice(b",\"address\":");
+    params.extend(data(bridge));
+    params.extend_from_slice(b",\"topics\":[[");
+    params.extend(data(&sp_io::hashing::keccak_256(DEPOSIT_EVENT)));
+    params.push(b',');
+    params.extend(data(&sp_io::hashing::keccak_256(DEPOSIT_NFT_EVENT)));
+    params.extend_from_slice(b"]]}]");
+
+    let logs = match rpc(url, b"eth_getLogs", &params)? {
+        JsonValue::Array(logs) => logs,
+        _ => return Err(()),
+    };
+    for log in logs.iter() {
+        if let Some(JsonValue::Boolean(true)) = field_of(log, "removed") {
+            continue;
+        }
+        let (block, deposit) = match parse_log(log) {
+            Some(log) => log,
+            None => {
+                debug::warn!("Skipping malformed L1 bridge log {:?}", log);
+                continue;
+            },
+        };
+        if !pending.iter().any(|(_, known)| known.l1_tx_hash() == deposit.l1_tx_hash()) {
+            pending.push((block, deposit));
+        }
+    }
+
+    Ok(Some((to, block_hash(url, to)?)))
+}
+
+/* Result of the JSON-RPC call of `method` with the JSON array `params` */
+fn rpc(url: &str, method: &[u8], params: &[u8]) -> Result<JsonValue, ()> {
+    let mut body = b"{\"jsonrpc\":\"2.0\",\"id\":1,\"method\":\"".to_vec();
+    body.extend_from_slice(method);
+    body.extend_from_slice(b"\",\"params\":");
+    body.extend_from_slice(params);
+    body.push(b'}');
+    let mut chunks = Vec::new();
+    chunks.push(body);
+
+    let deadline = sp_io::offchain::timestamp().add(Duration::from_millis(HTTP_TIMEOUT_MS));
+    let pending = http::Request::post(url, chunks)
+        .add_header("Content-Type", "application/json")
+        .deadline(deadline)
+        .send()
+        .map_err(|_| ())?;
+    let response = pending.try_wait(deadline).map_err(|_| ())?.map_err(|_| ())?;
+    if response.code != 200 {
+        return Err(());
+    }
+
+    let body = response.body().collect::<Vec<u8>>();
+    match lite_json::parse_json(str::from_utf8(&body).map_err(|_| ())?).map_err(|_| ())? {
+        JsonValue::Object(object) => object.into_iter()
+            .find(|(key, _)| key.iter().copied().eq("result".chars()))
+            .map(|(_, value)| value)
+            .ok_or(()),
+        _ => Err(()),
+    }
+}
+
+fn block_hash(url: &str, block: u64) -> Result<U256, ()> {
+    let mut params = b"[".to_vec();
+    params.extend(quantity(block));
+    params.extend_from_slice(b",false]");
+    let block = rpc(url, b"eth_getBlockByNumber", &params)?;
+    field_of(&block, "hash").and_then(parse_u256).ok_or(())
+}
+
+fn hex_extend(out: &mut Vec<u8>, bytes: &[u8]) {
+    const DIGITS: &[u8] = b"0123456789abcdef";
+    for byte in bytes {
+        out.push(DIGITS[(byte >> 4) as usize]);
+        out.push(DIGITS[(byte & 0xf) as usize]);
+    }
+}
+
+/* JSON string of a quantity, "0x" prefixed hex without leading zeros */
+fn quantity(value: u64) -> Vec<u8> {
+    let mut digits = Vec::new();
+    hex_extend(&mut digits, &value.to_be_bytes());
+    let start = digits.iter().position(|digit| *digit != b'0').unwrap_or(digits.len() - 1);
+
+    let mut out = b"\"0x".to_vec();
+    out.extend_from_slice(&digits[start..]);
+    out.push(b'"');
+    out
+}
+
+/* JSON string of bytes, "0x" prefixed hex */
+fn data(bytes: &[u8]) -> Vec<u8> {
+    let mut out = b"\"0x".to_vec();
+    hex_extend(&mut out, bytes);
+    out.push(b'"');
+    out
+}
+
+/// Reads the deposits from offchain storage at `L1_DEPOSITS_STORAGE`, for tests and dev chains
+/// without an L1 node.
+pub struct LocalStorageL1Source;
+
+impl L1EventSource for LocalStorageL1Source {
+    fn deposits() -> Result<Vec<L1Deposit>, ()> {
+        match StorageValueRef::persistent(L1_DEPOSITS_STORAGE).get::<Vec<L1Deposit>>() {
+            Some(Some(deposits)) => Ok(deposits),
+            Some(None) => Err(()),
+            None => Ok(Vec::new()),
+        }
+    }
+}
+
+fn field<'a>(object: &'a [(Vec<char>, JsonValue)], name: &str) -> Option<&'a JsonValue> {
+    object.iter()
+        .find(|(key, _)| key.iter().copied().eq(name.chars()))
+        .map(|(_, value)| value)
+}
+
+fn field_of<'a>(value: &'a JsonValue, name: &str) -> Option<&'a JsonValue> {
+    match value {
+        JsonValue::Object(object) => field(object, name),
+        _ => None,
+    }
+}
+
+/* "0x" prefixed hex string */
+fn parse_u256(value: &JsonValue) -> Option<U256> {
+    let chars = match value {
+        JsonValue::String(chars) => chars,
+        _ => return None,
+    };
+    if chars.len() < 3 || chars.len() > 66 || chars[0] != '0' || chars[1] != 'x' {
+        return None;
+    }
+
+    let mut result = U256::from(0);
+    for c in chars[2..].iter() {
+        let digit = c.to_digit(16)?;
+        result = (result << 4) | U256::from(digit);
+    }
+    return Some(result);
+}
+
+fn parse_u64(value: &JsonValue) -> Option<u64> {
+    let value = parse_u256(value)?;
+    if value > U256::from(u64::MAX) {
+        return None;
+    }
+    return Some(value.low_u64());
+}
+
+/* "0x" prefixed hex string of whole bytes */
+fn parse_bytes(value: &JsonValue) -> Option<Vec<u8>> {
+    let chars = match value {
+        JsonValue::String(chars) => chars,
+        _ => return None,
+    };
+    if chars.len() < 2 || chars.len() % 2 != 0 || chars[0] != '0' || chars[1] != 'x' {
+        return None;
+    }
+
+    chars[2..].chunks(2)
+        .map(|pair| Some((pair[0].to_digit(16)? * 16 + pair[1].to_digit(16)?) as u8))
+        .collect()
+}
+
+/* ABI encoded uint32 */
+fn word_u32(word: &[u8]) -> Option<u32> {
+    if word[..28].iter().any(|byte| *byte != 0) {
+        return None;
+    }
+    return Some(u32::from_be_bytes([word[28], word[29], word[30], word[31]]));
+}
+
+/* L1 block and deposit of an `eth_getLogs` entry */
+fn parse_log(log: &JsonValue) -> Option<(u64, L1Deposit)> {
+    let block = parse_u64(field_of(log, "blockNumber")?)?;
+    let l1_tx_hash = parse_u256(field_of(log, "transactionHash")?)?;
+    let topic = match field_of(log, "topics")? {
+        JsonValue::Array(topics) => parse_bytes(topics.first()?)?,
+        _ => return None,
+    };
+    let data = parse_bytes(field_of(log, "data")?)?;
+    let words = data.chunks(32).collect::<Vec<_>>();
+
+    if topic[..] == sp_io::hashing::keccak_256(DEPOSIT_EVENT)[..] && data.len() == 96 {
+        return Some((block, L1Deposit::Token {
+            l1_tx_hash,
+            account_index: word_u32(words[0])?,
+            token_index: word_u32(words[1])?,
+            amount: U256::from_big_endian(words[2]),
+        }));
+    }
+    if topic[..] == sp_io::hashing::keccak_256(DEPOSIT_NFT_EVENT)[..] && data.len() == 64 {
+        return Some((block, L1Deposit::NFT {
+            l1_tx_hash,
+            account_index: word_u32(words[0])?,
+            nft_id: word_u32(words[1])?,
+        }));
+    }
+    return None;
+}
@@ -4,19 +4,34 @@ use codec::{Decode, Encode};
 use delphinus_crypto::Encode as BabyJubJubEncode;
 use delphinus_crypto::{BabyJubjub, BabyJubjubField, BabyJubjubPoint, Curve, PrimeField, EDDSA};
 use frame_support::traits::{Currency, ReservableCurrency};
-use frame_support::{decl_event, decl_module, decl_storage, dispatch, traits::Get};
+use frame_support::traits::Get;
+use frame_support::sp_runtime::{SaturatedConversion, traits::{Hash, One, Saturating, Zero}};
+use frame_support::weights::Weight;
+use frame_support::sp_std::convert::TryFrom;
 use frame_system::ensure_signed;
 use frame_support::traits::Vec;
 use num_bigint::{BigInt, Sign};
-use sp_core::U256;
+use pallet_swap_command::*;
+use sp_core::{H256, U256};
 
 mod aux;
-mod errors;
+mod l1_source;
+mod merkle;
+mod migrations;
+mod offchain;
 mod types;
+mod weights;
 
 use aux::*;
-use errors::*;
-use types::*;
+use merkle::*;
+pub use aux::{key_check, CircuitRange};
+pub use l1_source::{
+    HttpL1Source, LocalStorageL1Source, L1_BRIDGE_STORAGE, L1_CONFIRMATIONS_STORAGE, L1_CURSOR_STORAGE,
+    L1_DEPOSITS_STORAGE, L1_LOCK_STORAGE, L1_PENDING_STORAGE, L1_RPC_URL_STORAGE, L1_START_BLOCK_STORAGE
+};
+pub use offchain::{crypto, l2_secret_key_from_signature, KEY_TYPE, L2_KEY_TYPE, L2_SECRET_KEY_MESSAGE};
+pub use pallet::*;
+pub use types::*;
 
 #[cfg(test)]
 mod mock;
@@ -24,10 +39,51 @@ mod mock;
 #[cfg(test)]
 mod tests;
 
-pub trait Config: frame_system::Config {
-    type Currency: Currency<Self::AccountId> + ReservableCurrency<Self::AccountId>;
-    type Event: From<Event<Self>> + Into<<Self as frame_system::Config>::Event>;
-    type AckAdmins: Get<Vec<<Self as frame_system::Config>::AccountId>>;
+/// Verifies a rollup proof that applying `ops` to the L2 state committed by `old_root`
+/// results in the state committed by `new_root`.
+pub trait BatchProofVerifier {
+    fn verify(old_root: &H256, new_root: &H256, ops: &[Ops], proof: &[u8]) -> bool;
+    /// Benchmarked cost of `verify` for `ops` ops and a proof of `proof_len` bytes.
+    fn verify_weight(ops: u32, proof_len: u32) -> Weight;
+}
+
+/// Rejects every proof, leaving `ack` as the only way to complete requests.
+impl BatchProofVerifier for () {
+    fn verify(_old_root: &H256, _new_root: &H256, _ops: &[Ops], _proof: &[u8]) -> bool {
+        false
+    }
+
+    fn verify_weight(_ops: u32, _proof_len: u32) -> Weight {
+        0
+    }
+}
+
+/// Checked by the off-chain worker of an admin node before it acks the requests after `req_id_start`.
+pub trait AckValidator {
+    fn validate(req_id_start: &ReqId, ops: &[(ReqId, Ops)]) -> bool;
+}
+
+/// Acks every batch.
+impl AckValidator for () {
+    fn validate(_req_id_start: &ReqId, _ops: &[(ReqId, Ops)]) -> bool {
+        true
+    }
+}
+
+/// Source of the deposits made on L1, polled by the off-chain worker of admin nodes.
+pub trait L1EventSource {
+    /// Deposits not forgotten yet, including the ones already submitted to L2.
+    fn deposits() -> Result<Vec<L1Deposit>, ()>;
+
+    /// Called for the deposits found on L2, which need not be returned again.
+    fn forget(_l1_tx_hash: &L1TxHash) {}
+}
+
+/// No L1 source, deposits are only submitted by hand.
+impl L1EventSource for () {
+    fn deposits() -> Result<Vec<L1Deposit>, ()> {
+        Ok(Vec::new())
+    }
 }
 
 type BalanceOf<T> =
@@ -35,226 +91,147 @@ type BalanceOf<T> =
 
 // Initial 1 / sharePrice is 10 ^ 15
 const ORDER_OF_MAGNITUDE: usize = 15usize;
-const PENDING: u8 = 1u8;
-const DONE: u8 = 2u8;
+// Oracle prices are scaled by 10 ^ 18
+const PRICE_ORDER_OF_MAGNITUDE: usize = 18usize;
 
-const MAX_ACCOUNT_COUNT: u32 = 1u32 << 20;
+pub const MAX_ACCOUNT_COUNT: u32 = 1u32 << 20;
 const MAX_NFTINDEX_COUNT: u32 = 1u32 << 20;
-const MAX_TOKEN_COUNT: u32 = 1u32 << 10;
-const MAX_POOL_COUNT: u32 = 1u32 << 10;
-const NFT_TOKEN_INDEX: u32 = 1u32;
-
-const OP_DEPOSIT: u8 = 0u8;
-const OP_WITHDRAW: u8 = 1u8;
-const OP_SWAP: u8 = 2u8;
-const OP_RETRIEVE: u8 = 3u8;
-const OP_SUPPLY: u8 = 4u8;
-const OP_ADDPOOL: u8 = 5u8;
-const OP_SETKEY: u8 = 6u8;
-const OP_DEPOSIT_NFT: u8 = 7u8;
-const OP_WITHDRAW_NFT: u8 = 8u8;
-const OP_TRANSFER_NFT: u8 = 9u8;
-const OP_BID_NFT: u8 = 10u8;
-const OP_FINALIZE_NFT: u8 = 11u8;
-
-decl_event!(
-    pub enum Event<T>
-    where
-        Balance = BalanceOf<T>,
-        BlockNumber = <T as frame_system::Config>::BlockNumber,
-        AccountId = <T as frame_system::Config>::AccountId,
-    {
-        SetKey(
-            ReqId,
-            SignatureRX,
-            SignatureRY,
-            SignatureS,
-            NonceId,
-            AccountIndex,
-            ReserveU32,
-            PublicKeyX,
-            PublicKeyY,
-        ),
-        Deposit(
-            ReqId,
-            SignatureRX,
-            SignatureRY,
-            SignatureS,
-            NonceId,
-            AccountIndex,
-            TokenIndex,
-            Amount,
-            ReserveU256,
-            AccountIndex
-        ),
-        Withdraw(
-            ReqId,
-            SignatureRX,
-            SignatureRY,
-            SignatureS,
-            NonceId,
-            AccountIndex,
-            TokenIndex,
-            Amount,
-            L1Account,
-        ),
-        Swap(
-            ReqId,
-            SignatureRX,
-            SignatureRY,
-            SignatureS,
-            NonceId,
-            AccountIndex,
-            PoolIndex,
-            Reverse,
-            Amount,
-            Amount,
-        ),
-        PoolSupply(
-            ReqId,
-            SignatureRX,
-            SignatureRY,
-            SignatureS,
-            NonceId,
-            AccountIndex,
-            PoolIndex,
-            Amount,
-            Amount,
-            Amount
-        ),
-        PoolRetrieve(
-            ReqId,
-            SignatureRX,
-            SignatureRY,
-            SignatureS,
-            NonceId,
-            AccountIndex,
-            PoolIndex,
-            Amount,
-            Amount,
-            Amount
-        ),
-        AddPool(
-            ReqId,
-            SignatureRX,
-            SignatureRY,
-            SignatureS,
-            NonceId,
-            TokenIndex,
-            TokenIndex,
-            ReserveU256,
-            ReserveU256,
-            PoolIndex,
-            AccountIndex
-        ),
-        DepositNFT(
-            ReqId,
-            SignatureRX,
-            SignatureRY,
-            SignatureS,
-            NonceId,
-            AccountIndex,
-            NFTId,
-            AccountIndex
-        ),
-        WithdrawNFT(
-            ReqId,
-            SignatureRX,
-            SignatureRY,
-            SignatureS,
-            NonceId,
-            AccountIndex,
-            NFTId,
-            L1Account,
-        ),
-        TransferNFT(
-            ReqId,
-            SignatureRX,
-            SignatureRY,
-            SignatureS,
-            NonceId,
-            AccountIndex, // From
-            AccountIndex, // To
-            NFTId,
-        ),
-        BidNFT(
-            ReqId,
-            SignatureRX,
-            SignatureRY,
-            SignatureS,
-            NonceId,
-            AccountIndex,
-            NFTId,
-            Amount
-        ),
-        FinalizeNFT(
-            ReqId,
-            SignatureRX,
-            SignatureRY,
-            SignatureS,
-            NonceId,
-            AccountIndex,
-            NFTId
-        ),
-        Ack(ReqId, u8),
-        Abort(ReqId),
-        RewardFunds(AccountId, Balance, BlockNumber),
+pub const MAX_TOKEN_COUNT: u32 = 1u32 << 10;
+pub const MAX_POOL_COUNT: u32 = 1u32 << 10;
+pub const NFT_TOKEN_INDEX: u32 = 1u32;
+const MAX_PRICE_OBSERVATIONS: u32 = 1u32 << 8;
+pub const MAX_TOKEN_SYMBOL_LENGTH: usize = 16usize;
+const MAX_REQ_PAGE_SIZE: u32 = 100u32;
+const MERKLE_DEPTH: u8 = 32u8;
+const MAX_BATCH_PROOF_SIZE: u32 = 100u32;
+const ACK_BATCH_SIZE: u32 = 10u32;
+/* AckMap keeps one bit per admin in a u8, with all bits set once a request is complete */
+pub const MAX_ADMIN_COUNT: usize = 7usize;
+/* Storage prefix of the pallet, the runtime must report it as the pallet name in `PalletInfo` */
+pub const STORAGE_PREFIX: &str = "Swap";
+
+#[frame_support::pallet]
+pub mod pallet {
+    use super::*;
+    use frame_support::pallet_prelude::*;
+    use frame_system::pallet_prelude::*;
+    use frame_system::offchain::{AppCrypto, CreateSignedTransaction};
+
+    #[pallet::config]
+    pub trait Config: CreateSignedTransaction<Call<Self>> + frame_system::Config {
+        type Currency: Currency<Self::AccountId> + ReservableCurrency<Self::AccountId>;
+        type Event: From<Event<Self>> + IsType<<Self as frame_system::Config>::Event>;
+        /// Number of blocks for which pool reserve snapshots are kept.
+        type SnapshotWindow: Get<Self::BlockNumber>;
+        /// Number of blocks a completed request is kept in state before it is pruned.
+        type CompleteReqRetention: Get<Self::BlockNumber>;
+        /// Maximum number of completed requests pruned per block or per call.
+        type MaxPrunePerBlock: Get<u32>;
+        type BatchProofVerifier: BatchProofVerifier;
+        /// Maximum length in bytes of a batch proof.
+        type MaxProofLen: Get<u32>;
+        /// Number of blocks without ack while requests are pending after which the pallet enters exit mode.
+        type ExitModeDelay: Get<Self::BlockNumber>;
+        /// Keys of the local keystore the off-chain worker acks with, when they belong to an admin.
+        type AuthorityId: AppCrypto<Self::Public, Self::Signature>;
+        type AckValidator: AckValidator;
+        type L1EventSource: L1EventSource;
+        /// Maximum number of storage keys a storage migration writes per block.
+        type MaxMigrationKeysPerBlock: Get<u32>;
+        /// Admins of a chain upgraded from a runtime with compiled-in admins, written to `Admins`
+        /// by the migration to `Releases::V3_0_0` when none are set.
+        type LegacyAdmins: Get<Vec<Self::AccountId>>;
     }
-);
 
-decl_storage! {
-    trait Store for Module<T: Config> as SimpleMap {
-        pub AccountIndexCount: AccountIndex;
-        pub AccountIndexMap get(fn account_index_map): map hasher(blake2_128_concat) T::AccountId => Option<AccountIndex>;
+    #[pallet::pallet]
+    #[pallet::generate_store(pub(super) trait Store)]
+    pub struct Pallet<T>(_);
 
-        pub PoolIndexCount: PoolIndex;
-        pub PoolIndexMap get(fn pool_index_map): map hasher(blake2_128_concat) (TokenIndex, TokenIndex) => Option<PoolIndex>;
+    #[pallet::hooks]
+    impl<T: Config> Hooks<BlockNumberFor<T>> for Pallet<T> {
+        fn on_runtime_upgrade() -> Weight {
+            #[cfg(feature = "try-runtime")]
+            let pre_upgrade = migrations::pre_upgrade::<T>().expect("swap pre_upgrade check failed");
 
-        pub NFTIDCount: NFTId;
+            let mut weight = migrations::migrate::<T>(T::MaxMigrationKeysPerBlock::get());
 
-        pub BalanceMap get(fn balance_map): map hasher(blake2_128_concat) (AccountIndex, TokenIndex) => Amount;
-        pub ShareMap get(fn share_map): map hasher(blake2_128_concat) (AccountIndex, PoolIndex) => Amount;
-        pub PoolMap get(fn pool_map): map hasher(blake2_128_concat) PoolIndex => Option<(TokenIndex, TokenIndex, Amount, Amount, Amount)>;
+            #[cfg(feature = "try-runtime")]
+            migrations::post_upgrade::<T>(pre_upgrade).expect("swap post_upgrade check failed");
 
-        /* Owner * bid * CurrentWinner */
-        pub NFTMap get(fn nft_map): map hasher(blake2_128_concat) NFTId => (AccountIndex, Amount, Option<AccountIndex>);
+            /* on_initialize of this block skips its migration step */
+            if StorageVersion::<T>::get() != Releases::LATEST {
+                MigrationStepAt::<T>::put(<frame_system::Module<T>>::block_number());
+                weight = weight.saturating_add(T::DbWeight::get().reads_writes(1, 1));
+            }
 
-        pub PendingReqMap get(fn pending_req_map): map hasher(blake2_128_concat) ReqId => Option<Ops>;
-        pub CompleteReqMap get(fn complete_req_map): map hasher(blake2_128_concat) ReqId => Option<Ops>;
-        pub AckMap get(fn ack_map): map hasher(blake2_128_concat) ReqId => u8;
-        pub ReqIndex get(fn req_index): ReqId;
-        pub CompleteReqIndex get(fn complete_req_index): ReqId;
-        pub NonceMap get(fn nonce_map): map hasher(blake2_128_concat) T::AccountId => NonceId;
-        pub KeyMap get(fn key_map): map hasher(blake2_128_concat) AccountIndex => Option<PublicKey>;
+            weight
+        }
 
-        pub DepositMap get(fn deposit_map): map hasher(blake2_128_concat) ReqId => Option<L1TxHash>;
-        pub L1TxMap get(fn l1txhash_map): map hasher(blake2_128_concat) L1TxHash => u8;
-    }
-}
+        fn on_initialize(n: BlockNumberFor<T>) -> Weight {
+            let mut weight = T::DbWeight::get().reads(1);
 
-decl_module! {
-    pub struct Module<T: Config> for enum Call where origin: T::Origin {
-        type Error = Error<T>;
-        fn deposit_event() = default;
+            let window = T::SnapshotWindow::get();
+            if n > window {
+                /* At most one snapshot per pool was taken at the expired block */
+                let pools = PoolIndexCount::<T>::get() as Weight;
+                PoolSnapshotMap::<T>::remove_prefix(n - window);
+                weight = weight.saturating_add(T::DbWeight::get().reads_writes(pools + 1, pools));
+            }
+
+            if StorageVersion::<T>::get() != Releases::LATEST {
+                weight = weight.saturating_add(T::DbWeight::get().reads_writes(1, 1));
+                if MigrationStepAt::<T>::take() != Some(n) {
+                    weight = weight.saturating_add(migrations::migrate::<T>(T::MaxMigrationKeysPerBlock::get()));
+                }
+                return weight;
+            }
+
+            weight = weight.saturating_add(T::DbWeight::get().reads_writes(5, 2));
+
+            if exit_mode_check::<T>(n) {
+                ExitMode::<T>::put(true);
+                Self::deposit_event(Event::<T>::ExitModeEntered(n));
+                weight = weight.saturating_add(T::DbWeight::get().writes(2));
+            }
+
+            let pruned = complete_reqs_prune::<T>(ReqIndex::<T>::get(), T::MaxPrunePerBlock::get(), Some(n));
+            weight = weight.saturating_add(T::DbWeight::get().reads_writes(3 * pruned as Weight + 3, 5 * pruned as Weight + 1));
+            if pruned > 0 {
+                Self::deposit_event(Event::<T>::ReqsPruned(PrunedReqIndex::<T>::get(), pruned));
+                weight = weight.saturating_add(T::DbWeight::get().writes(1));
+            }
+
+            weight
+        }
+
+        fn offchain_worker(n: BlockNumberFor<T>) {
+            offchain::offchain_run::<T>(n);
+        }
+    }
 
+    #[pallet::call]
+    impl<T: Config> Pallet<T> {
         /// Awards the specified amount of funds to the specified account
-        #[weight = 0]
-        pub fn charge(origin,
+        #[pallet::weight(weights::charge::<T>())]
+        pub fn charge(origin: OriginFor<T>,
             account: T::AccountId,
             reward: BalanceOf<T>,
             l1_tx_hash: L1TxHash,
-        ) {
+        ) -> DispatchResultWithPostInfo {
             let who = ensure_signed(origin)?;
+            ensure_migrated::<T>()?;
+
             let _r = T::Currency::deposit_creating(&account, reward);
             let now = <frame_system::Module<T>>::block_number();
-            Self::deposit_event(RawEvent::RewardFunds(who, reward, now));
-            L1TxMap::insert(&l1_tx_hash, DONE);
-            return Ok(());
+            Self::deposit_event(Event::<T>::RewardFunds(who, reward, now));
+            L1TxMap::<T>::insert(&l1_tx_hash, L1TxStatus::Done);
+            return Ok(().into());
         }
 
-        #[weight = 0]
-        pub fn set_key(origin, key: [u8; 32]) -> dispatch::DispatchResult {
+        #[pallet::weight(weights::set_key::<T>())]
+        pub fn set_key(origin: OriginFor<T>, key: [u8; 32]) -> DispatchResultWithPostInfo {
             let who = ensure_signed(origin)?;
+            ensure_not_paused::<T>(OP_SETKEY, None)?;
 
             let key = BabyJubjubPoint::decode(&key).map_err(|_| Error::<T>::InvalidKey)?;
 
@@ -268,24 +245,26 @@ decl_module! {
 
             let op = Ops::SetKey(U256::from(0), U256::from(0), U256::from(0), nonce, account_index, 0u32, x, y);
 
-            PendingReqMap::insert(&req_id, op);
-            KeyMap::insert(account_index, (x, y));
-            ReqIndex::put(req_id);
+            req_submit::<T>(&req_id, OP_SETKEY, op, account_index);
+            KeyMap::<T>::insert(account_index, (x, y));
+            merkle_leaf_update::<T>(MerkleLeaf::Key(account_index));
             NonceMap::<T>::insert(&who, new_nonce);
 
-            Self::deposit_event(RawEvent::SetKey(req_id, U256::from(0), U256::from(0), U256::from(0), nonce, account_index, 0u32, x, y));
-            return Ok(());
+            merkle_root_commit::<T>(&req_id);
+            Self::deposit_event(Event::<T>::SetKey(req_id, U256::from(0), U256::from(0), U256::from(0), nonce, account_index, 0u32, x, y));
+            return Ok(().into());
         }
 
-        #[weight = 10_000 + T::DbWeight::get().writes(1)]
+        #[pallet::weight(weights::add_pool::<T>())]
         pub fn add_pool(
-            origin,
+            origin: OriginFor<T>,
             sign: [u8; 64],
             token_index_0: TokenIndex,
             token_index_1: TokenIndex,
             nonce: NonceId
-        ) -> dispatch::DispatchResult {
+        ) -> DispatchResultWithPostInfo {
             let who = ensure_signed(origin)?;
+            ensure_not_paused::<T>(OP_ADDPOOL, None)?;
             is_admin::<T>(&who)?;
 
             if token_index_0 >= MAX_TOKEN_COUNT {
@@ -296,6 +275,9 @@ decl_module! {
                 return Err(Error::<T>::InvalidTokenIndex)?;
             }
 
+            token_check::<T>(&token_index_0)?;
+            token_check::<T>(&token_index_1)?;
+
             if token_index_0 == token_index_1 {
                 return Err(Error::<T>::InvalidTokenPair)?;
             }
@@ -303,12 +285,7 @@ decl_module! {
             let who_account_index = get_account_index::<T>(&who)?;
             let new_nonce = nonce_check::<T>(&who, nonce)?;
 
-            let mut command = [0u8; 81];
-            command[0] = OP_ADDPOOL;
-            command[1..9].copy_from_slice(&nonce.to_be_bytes());
-            command[9..13].copy_from_slice(&token_index_0.to_be_bytes());
-            command[13..17].copy_from_slice(&token_index_1.to_be_bytes());
-            // command[17..49] and command[49..81] is reserved in current implementataion.
+            let command = Command::AddPool { nonce, token_index_0, token_index_1 }.encode();
             let sign = check_sign::<T>(who_account_index, &command, &sign)?;
 
             let req_id = req_id_get::<T>()?;
@@ -323,29 +300,30 @@ decl_module! {
             let pool_index = create_pool_index::<T>(&_token_index_0, &_token_index_1)?;
             let op = Ops::AddPool(sign.0, sign.1, sign.2, nonce, token_index_0, token_index_1, U256::from(0), U256::from(0), pool_index, who_account_index);
 
-            PendingReqMap::insert(&req_id, op);
-            ReqIndex::put(req_id);
+            req_submit::<T>(&req_id, OP_ADDPOOL, op, who_account_index);
             NonceMap::<T>::insert(&who, new_nonce);
 
+            merkle_root_commit::<T>(&req_id);
             Self::deposit_event(Event::<T>::AddPool(
                 req_id, sign.0, sign.1, sign.2, nonce,
                 token_index_0, token_index_1, U256::from(0), U256::from(0),
                 pool_index, who_account_index
             ));
-            return Ok(());
+            return Ok(().into());
         }
 
-        #[weight = 10_000 + T::DbWeight::get().writes(1)]
+        #[pallet::weight(weights::deposit::<T>())]
         pub fn deposit(
-            origin,
+            origin: OriginFor<T>,
             sign: [u8; 64],
             account_index: AccountIndex,
             token_index: TokenIndex,
             amount: Amount,
             l1_tx_hash: L1TxHash,
             nonce: NonceId
-        ) -> dispatch::DispatchResult {
+        ) -> DispatchResultWithPostInfo {
             let who = ensure_signed(origin)?;
+            ensure_not_paused::<T>(OP_DEPOSIT, None)?;
             is_admin::<T>(&who)?;
 
             let who_account_index = get_account_index::<T>(&who)?;
@@ -354,11 +332,13 @@ decl_module! {
                 return Err(Error::<T>::InvalidTokenIndex)?;
             }
 
-            if account_index >= AccountIndexCount::get() {
+            token_check::<T>(&token_index)?;
+
+            if account_index >= AccountIndexCount::<T>::get() {
                 return Err(Error::<T>::InvalidAccount)?;
             }
 
-            if L1TxMap::get(l1_tx_hash) != 0u8 {
+            if L1TxMap::<T>::contains_key(l1_tx_hash) {
                 return Err(Error::<T>::L1TXExists)?;
             }
 
@@ -366,42 +346,40 @@ decl_module! {
 
             let new_nonce = nonce_check::<T>(&who, nonce)?;
 
-            let mut command = [0u8; 81];
-            command[0] = OP_DEPOSIT;
-            command[1..9].copy_from_slice(&nonce.to_be_bytes());
-            command[9..13].copy_from_slice(&account_index.to_be_bytes());
-            command[13..17].copy_from_slice(&token_index.to_be_bytes());
-            command[17..49].copy_from_slice(&amount.to_be_bytes());
-            // command[49..81] is reserved. The l1_tx_hash exceeds field limits, so not in signature.
+            let command = Command::Deposit { nonce, account_index, token_index, amount }.encode();
             let sign = check_sign::<T>(who_account_index, &command, &sign)?;
 
+            deposit_limit_check::<T>(&account_index, &token_index, amount)?;
+
             let req_id = req_id_get::<T>()?;
 
             let new_balance_amount = balance_add::<T>(&account_index, &token_index, amount)?;
 
             let op = Ops::Deposit(sign.0, sign.1, sign.2, nonce, account_index, token_index, amount, U256::from(0), who_account_index);
 
-            balance_set(&account_index, &token_index, new_balance_amount);
-            PendingReqMap::insert(&req_id, op);
-            ReqIndex::put(req_id);
+            balance_set::<T>(&account_index, &token_index, new_balance_amount);
+            req_submit::<T>(&req_id, OP_DEPOSIT, op, who_account_index);
             NonceMap::<T>::insert(&who, new_nonce);
-            DepositMap::insert(&req_id, l1_tx_hash);
-            L1TxMap::insert(&l1_tx_hash, PENDING);
+            DepositMap::<T>::insert(&req_id, l1_tx_hash);
+            L1TxMap::<T>::insert(&l1_tx_hash, L1TxStatus::Pending);
+            L1TxReqMap::<T>::insert(&l1_tx_hash, req_id);
 
+            merkle_root_commit::<T>(&req_id);
             Self::deposit_event(Event::<T>::Deposit(req_id, sign.0, sign.1, sign.2, nonce, account_index, token_index, amount, U256::from(0), who_account_index));
-            return Ok(());
+            return Ok(().into());
         }
 
-        #[weight = 10_000 + T::DbWeight::get().writes(1)]
+        #[pallet::weight(weights::withdraw::<T>())]
         pub fn withdraw(
-            origin,
+            origin: OriginFor<T>,
             sign: [u8; 64],
             token_index: TokenIndex,
             amount: Amount,
             l1account: L1Account,
             nonce: NonceId
-        ) -> dispatch::DispatchResult {
+        ) -> DispatchResultWithPostInfo {
             let who = ensure_signed(origin)?;
+            ensure_not_paused::<T>(OP_WITHDRAW, None)?;
             let account = who;
             let account_index = get_account_index::<T>(&account)?;
 
@@ -409,6 +387,8 @@ decl_module! {
                 return Err(Error::<T>::InvalidTokenIndex)?;
             }
 
+            token_check::<T>(&token_index)?;
+
             amount.valid_on_circuit().ok_or(Error::<T>::InvalidAmount)?;
 
             let req_id = req_id_get::<T>()?;
@@ -416,40 +396,40 @@ decl_module! {
             let new_balance = balance_sub::<T>(&account_index, &token_index, amount)?;
             l1account_check::<T>(l1account)?;
 
-            let mut command = [0u8; 81];
-            command[0] = OP_WITHDRAW;
-            command[1..9].copy_from_slice(&nonce.to_be_bytes());
-            command[9..13].copy_from_slice(&account_index.to_be_bytes());
-            command[13..17].copy_from_slice(&token_index.to_be_bytes());
-            command[17..49].copy_from_slice(&amount.to_be_bytes());
-            command[49..81].copy_from_slice(&l1account.to_be_bytes());
+            let command = Command::Withdraw { nonce, account_index, token_index, amount, l1account }.encode();
             let sign = check_sign::<T>(account_index, &command, &sign)?;
 
+            let withdraw_period = withdraw_limit_check::<T>(&account_index, &token_index, amount)?;
+
             let op = Ops::Withdraw(sign.0, sign.1, sign.2, nonce, account_index, token_index, amount, l1account);
-            PendingReqMap::insert(&req_id, op);
-            ReqIndex::put(req_id);
+            req_submit::<T>(&req_id, OP_WITHDRAW, op, account_index);
 
-            balance_set(&account_index, &token_index, new_balance);
+            balance_set::<T>(&account_index, &token_index, new_balance);
             NonceMap::<T>::insert(&account, new_nonce);
+            if let Some(withdraw_period) = withdraw_period {
+                WithdrawPeriodMap::<T>::insert(&token_index, withdraw_period);
+            }
 
+            merkle_root_commit::<T>(&req_id);
             Self::deposit_event(Event::<T>::Withdraw(
                 req_id,
                 sign.0, sign.1, sign.2, nonce, account_index, token_index, amount, l1account
             ));
 
-            return Ok(());
+            return Ok(().into());
         }
 
-        #[weight = 10_000 + T::DbWeight::get().writes(1)]
+        #[pallet::weight(weights::swap::<T>())]
         pub fn swap(
-            origin,
+            origin: OriginFor<T>,
             sign: [u8; 64],
             pool_index: PoolIndex,
             reverse: Reverse,
             amount: Amount,
             nonce: NonceId
-        ) -> dispatch::DispatchResult {
+        ) -> DispatchResultWithPostInfo {
             let who = ensure_signed(origin)?;
+            ensure_not_paused::<T>(OP_SWAP, Some(&pool_index))?;
             let account = who;
             let account_index = get_account_index::<T>(&account)?;
 
@@ -457,13 +437,15 @@ decl_module! {
                 return Err(Error::<T>::InvalidAmount)?;
             }
             valid_pool_amount(amount).ok_or(Error::<T>::InvalidAmount)?;
+            let direction = SwapDirection::try_from(reverse).map_err(|_| Error::<T>::InvalidReverse)?;
+            let token0_in = direction == SwapDirection::Token0ToToken1;
 
             let req_id = req_id_get::<T>()?;
             let new_nonce = nonce_check::<T>(&account, nonce)?;
 
             let ((token_input, amount_input), (token_output, amount_output)) = {
-                let (token0, token1, amount0, amount1, _) = PoolMap::get(&pool_index).ok_or(Error::<T>::PoolNotExists)?;
-                if reverse == 0u8 {
+                let (token0, token1, amount0, amount1, _) = PoolMap::<T>::get(&pool_index).ok_or(Error::<T>::PoolNotExists)?;
+                if token0_in {
                     ((token0, amount0), (token1, amount1))
                 } else {
                     ((token1, amount1), (token0, amount0))
@@ -471,13 +453,7 @@ decl_module! {
             };
             non_zero_pool_amount(amount_output).ok_or(Error::<T>::PoolBalanceNotEnough)?;
 
-            let mut command = [0u8; 81];
-            command[0] = OP_SWAP;
-            command[1..9].copy_from_slice(&nonce.to_be_bytes());
-            command[9..13].copy_from_slice(&account_index.to_be_bytes());
-            command[13..17].copy_from_slice(&pool_index.to_be_bytes());
-            command[17..49].copy_from_slice(&U256::from(reverse).to_be_bytes());
-            command[49..81].copy_from_slice(&amount.to_be_bytes());
+            let command = Command::Swap { nonce, account_index, pool_index, reverse, amount }.encode();
             let sign = check_sign::<T>(account_index, &command, &sign)?;
 
             let result_amount = calculate_swap_result_amount::<T>(amount_input, amount_output, amount)?;
@@ -485,18 +461,18 @@ decl_module! {
             let new_balance_input = balance_sub::<T>(&account_index, &token_input, amount)?;
             let new_balance_output = balance_add::<T>(&account_index, &token_output, result_amount)?;
 
-            pool_change::<T>(&pool_index, reverse == 0, if reverse == 0 {amount} else {result_amount}, reverse != 0, if reverse == 0 {result_amount} else {amount})?;
+            pool_change::<T>(&pool_index, token0_in, if token0_in {amount} else {result_amount}, !token0_in, if token0_in {result_amount} else {amount})?;
 
             let op = Ops::Swap(sign.0, sign.1, sign.2, nonce, account_index, pool_index, reverse, amount);
 
-            PendingReqMap::insert(&req_id, op);
-            ReqIndex::put(req_id);
+            req_submit::<T>(&req_id, OP_SWAP, op, account_index);
 
-            balance_set(&account_index, &token_input, new_balance_input);
-            balance_set(&account_index, &token_output, new_balance_output);
+            balance_set::<T>(&account_index, &token_input, new_balance_input);
+            balance_set::<T>(&account_index, &token_output, new_balance_output);
             NonceMap::<T>::insert(&account, new_nonce);
             //We emit an extra value `result_amount` which contains the output amount of the swap operation. 
             //This is not passed into the Op/circuit, but is useful for history.
+            merkle_root_commit::<T>(&req_id);
             Self::deposit_event(
                 Event::<T>::Swap(
                     req_id,
@@ -504,19 +480,20 @@ decl_module! {
                 )
             );
 
-            return Ok(());
+            return Ok(().into());
         }
 
-        #[weight = 10_000 + T::DbWeight::get().writes(1)]
+        #[pallet::weight(weights::pool_supply::<T>())]
         pub fn pool_supply(
-            origin,
+            origin: OriginFor<T>,
             sign: [u8; 64],
             pool_index: PoolIndex,
             amount0: Amount,
             amount1: Amount,
             nonce: NonceId
-        ) -> dispatch::DispatchResult {
+        ) -> DispatchResultWithPostInfo {
             let who = ensure_signed(origin)?;
+            ensure_not_paused::<T>(OP_SUPPLY, Some(&pool_index))?;
             let account = who;
             let account_index = get_account_index::<T>(&account)?;
 
@@ -526,22 +503,16 @@ decl_module! {
                 return Err(Error::<T>::InvalidAmount)?;
             }
 
-            let (token0, token1, liq0, liq1, _) = PoolMap::get(&pool_index).ok_or(Error::<T>::PoolNotExists)?;
+            let (token0, token1, liq0, liq1, _) = PoolMap::<T>::get(&pool_index).ok_or(Error::<T>::PoolNotExists)?;
             valid_input_y_amount(liq0, liq1, amount0, amount1, true).ok_or(Error::<T>::InvalidAmountRatio)?;
 
             let req_id = req_id_get::<T>()?;
             let new_nonce = nonce_check::<T>(&account, nonce)?;
 
-            let mut command = [0u8; 81];
-            command[0] = OP_SUPPLY;
-            command[1..9].copy_from_slice(&nonce.to_be_bytes());
-            command[9..13].copy_from_slice(&account_index.to_be_bytes());
-            command[13..17].copy_from_slice(&pool_index.to_be_bytes());
-            command[17..49].copy_from_slice(&amount0.to_be_bytes());
-            command[49..81].copy_from_slice(&amount1.to_be_bytes());
+            let command = Command::PoolSupply { nonce, account_index, pool_index, amount0, amount1 }.encode();
             let sign = check_sign::<T>(account_index, &command, &sign)?;
 
-            let amount1_to_pool = if is_pool_empty(&pool_index) {
+            let amount1_to_pool = if is_pool_empty::<T>(&pool_index) {
                 amount1
             } else {
                 calculate_amount1_to_pool::<T>(&pool_index, amount0, true)?
@@ -554,51 +525,46 @@ decl_module! {
             pool_change_with_share::<T>(&pool_index, true, amount0, true, amount1_to_pool, share_change)?;
 
             let op = Ops::PoolSupply(sign.0, sign.1, sign.2, nonce, account_index, pool_index, amount0, amount1);
-            PendingReqMap::insert(&req_id, op);
-            ReqIndex::put(req_id);
+            req_submit::<T>(&req_id, OP_SUPPLY, op, account_index);
 
-            balance_set(&account_index, &token0, new_balance_0);
-            balance_set(&account_index, &token1, new_balance_1);
-            ShareMap::insert((&account_index, &pool_index), new_share);
+            balance_set::<T>(&account_index, &token0, new_balance_0);
+            balance_set::<T>(&account_index, &token1, new_balance_1);
+            share_set::<T>(&account_index, &pool_index, new_share);
             NonceMap::<T>::insert(&account, new_nonce);
 
+            merkle_root_commit::<T>(&req_id);
             Self::deposit_event(
                 Event::<T>::PoolSupply(
                     req_id, sign.0, sign.1, sign.2, nonce, account_index, pool_index, amount0, amount1, share_change)
             );
 
-            return Ok(());
+            return Ok(().into());
         }
 
-        #[weight = 10_000 + T::DbWeight::get().writes(1)]
+        #[pallet::weight(weights::pool_retrieve::<T>())]
         pub fn pool_retrieve(
-            origin,
+            origin: OriginFor<T>,
             sign: [u8; 64],
             pool_index: PoolIndex,
             amount0: Amount,
             amount1: Amount,
             nonce: NonceId
-        ) -> dispatch::DispatchResult {
+        ) -> DispatchResultWithPostInfo {
             let who = ensure_signed(origin)?;
+            ensure_not_paused::<T>(OP_RETRIEVE, Some(&pool_index))?;
             let account = who;
             let account_index = get_account_index::<T>(&account)?;
 
             valid_pool_amount(amount0).ok_or(Error::<T>::InvalidAmount)?;
             valid_pool_amount(amount1).ok_or(Error::<T>::InvalidAmount)?;
 
-            let (token0, token1, liq0, liq1, _) = PoolMap::get(&pool_index).ok_or(Error::<T>::PoolNotExists)?;
+            let (token0, token1, liq0, liq1, _) = PoolMap::<T>::get(&pool_index).ok_or(Error::<T>::PoolNotExists)?;
             valid_input_y_amount(liq0, liq1, amount0, amount1, false).ok_or(Error::<T>::InvalidAmountRatio)?;
 
             let req_id = req_id_get::<T>()?;
             let new_nonce = nonce_check::<T>(&account, nonce)?;
 
-            let mut command = [0u8; 81];
-            command[0] = OP_RETRIEVE;
-            command[1..9].copy_from_slice(&nonce.to_be_bytes());
-            command[9..13].copy_from_slice(&account_index.to_be_bytes());
-            command[13..17].copy_from_slice(&pool_index.to_be_bytes());
-            command[17..49].copy_from_slice(&amount0.to_be_bytes());
-            command[49..81].copy_from_slice(&amount1.to_be_bytes());
+            let command = Command::PoolRetrieve { nonce, account_index, pool_index, amount0, amount1 }.encode();
             let sign = check_sign::<T>(account_index, &command, &sign)?;
 
             // for user account
@@ -613,33 +579,34 @@ decl_module! {
 
             let op = Ops::PoolRetrieve(sign.0, sign.1, sign.2, nonce, account_index, pool_index, amount0, amount1);
 
-            PendingReqMap::insert(&req_id, op);
-            ReqIndex::put(req_id);
+            req_submit::<T>(&req_id, OP_RETRIEVE, op, account_index);
 
-            balance_set(&account_index, &token0, new_balance_0);
-            balance_set(&account_index, &token1, new_balance_1);
-            ShareMap::insert((&account_index, &pool_index), new_share);
+            balance_set::<T>(&account_index, &token0, new_balance_0);
+            balance_set::<T>(&account_index, &token1, new_balance_1);
+            share_set::<T>(&account_index, &pool_index, new_share);
             NonceMap::<T>::insert(&account, new_nonce);
 
+            merkle_root_commit::<T>(&req_id);
             Self::deposit_event(
                 Event::<T>::PoolRetrieve(
                     req_id, sign.0, sign.1, sign.2, nonce, account_index, pool_index, amount0, amount1, share_change
                 )
             );
 
-            return Ok(());
+            return Ok(().into());
         }
 
-        #[weight = 10_000 + T::DbWeight::get().writes(1)]
+        #[pallet::weight(weights::deposit_nft::<T>())]
         pub fn deposit_nft(
-            origin,
+            origin: OriginFor<T>,
             sign: [u8; 64],
             account_index: AccountIndex,
             nft_id: NFTId,
             l1_tx_hash: L1TxHash,
             nonce: NonceId
-        ) -> dispatch::DispatchResult {
+        ) -> DispatchResultWithPostInfo {
             let who = ensure_signed(origin)?;
+            ensure_not_paused::<T>(OP_DEPOSIT_NFT, None)?;
             is_admin::<T>(&who)?;
 
             let caller_account_index = get_account_index::<T>(&who)?;
@@ -648,18 +615,13 @@ decl_module! {
 
             validation_nft_index::<T>(nft_id)?;
 
-            if L1TxMap::get(l1_tx_hash) != 0u8 {
+            if L1TxMap::<T>::contains_key(l1_tx_hash) {
                 return Err(Error::<T>::L1TXExists)?;
             }
 
             let new_nonce = nonce_check::<T>(&who, nonce)?;
 
-            let mut command = [0u8; 81];
-            command[0] = OP_DEPOSIT_NFT;
-            command[1..9].copy_from_slice(&nonce.to_be_bytes());
-            command[9..13].copy_from_slice(&account_index.to_be_bytes());
-            command[13..17].copy_from_slice(&nft_id.to_be_bytes());
-            command[17..49].copy_from_slice(&l1_tx_hash.to_be_bytes());
+            let command = Command::DepositNFT { nonce, account_index, nft_id, l1_tx_hash }.encode();
             let sign = check_sign::<T>(caller_account_index, &command, &sign)?;
 
             let req_id = req_id_get::<T>()?;
@@ -667,26 +629,28 @@ decl_module! {
             nft_add::<T>(&account_index, &nft_id)?;
             let op = Ops::DepositNFT(sign.0, sign.1, sign.2, nonce, account_index, nft_id, caller_account_index);
 
-            PendingReqMap::insert(&req_id, op);
-            ReqIndex::put(req_id);
+            req_submit::<T>(&req_id, OP_DEPOSIT_NFT, op, caller_account_index);
             NonceMap::<T>::insert(&who, new_nonce);
-            DepositMap::insert(&req_id, l1_tx_hash);
-            L1TxMap::insert(&l1_tx_hash, PENDING);
+            DepositMap::<T>::insert(&req_id, l1_tx_hash);
+            L1TxMap::<T>::insert(&l1_tx_hash, L1TxStatus::Pending);
+            L1TxReqMap::<T>::insert(&l1_tx_hash, req_id);
 
+            merkle_root_commit::<T>(&req_id);
             Self::deposit_event(Event::<T>::DepositNFT(req_id, sign.0, sign.1, sign.2, nonce, account_index, nft_id, caller_account_index));
 
-            return Ok(());
+            return Ok(().into());
         }
 
-        #[weight = 10_000 + T::DbWeight::get().writes(1)]
+        #[pallet::weight(weights::withdraw_nft::<T>())]
         pub fn withdraw_nft(
-            origin,
+            origin: OriginFor<T>,
             sign: [u8; 64],
             nft_id: NFTId,
             l1account: L1Account,
             nonce: NonceId
-        ) -> dispatch::DispatchResult {
+        ) -> DispatchResultWithPostInfo {
             let who = ensure_signed(origin)?;
+            ensure_not_paused::<T>(OP_WITHDRAW_NFT, None)?;
             let account_index = get_account_index::<T>(&who)?;
 
             validation_nft_index::<T>(nft_id)?;
@@ -695,12 +659,7 @@ decl_module! {
 
             let new_nonce = nonce_check::<T>(&who, nonce)?;
 
-            let mut command = [0u8; 81];
-            command[0] = OP_WITHDRAW_NFT;
-            command[1..9].copy_from_slice(&nonce.to_be_bytes());
-            command[9..13].copy_from_slice(&account_index.to_be_bytes());
-            command[13..17].copy_from_slice(&nft_id.to_be_bytes());
-            command[17..49].copy_from_slice(&l1account.to_be_bytes());
+            let command = Command::WithdrawNFT { nonce, account_index, nft_id, l1account }.encode();
             let sign = check_sign::<T>(account_index, &command, &sign)?;
 
             let req_id = req_id_get::<T>()?;
@@ -708,24 +667,25 @@ decl_module! {
             nft_withdraw::<T>(&account_index, &nft_id)?;
             let op = Ops::WithdrawNFT(sign.0, sign.1, sign.2, nonce, account_index, nft_id, l1account);
 
-            PendingReqMap::insert(&req_id, op);
-            ReqIndex::put(req_id);
+            req_submit::<T>(&req_id, OP_WITHDRAW_NFT, op, account_index);
             NonceMap::<T>::insert(&who, new_nonce);
 
+            merkle_root_commit::<T>(&req_id);
             Self::deposit_event(Event::<T>::WithdrawNFT(req_id, sign.0, sign.1, sign.2, nonce, account_index, nft_id, l1account));
 
-            return Ok(());
+            return Ok(().into());
         }
 
-        #[weight = 10_000 + T::DbWeight::get().writes(1)]
+        #[pallet::weight(weights::transfer_nft::<T>())]
         pub fn transfer_nft(
-            origin,
+            origin: OriginFor<T>,
             sign: [u8; 64],
             nft_id: NFTId,
             recipient: AccountIndex,
             nonce: NonceId
-        ) -> dispatch::DispatchResult {
+        ) -> DispatchResultWithPostInfo {
             let who = ensure_signed(origin)?;
+            ensure_not_paused::<T>(OP_TRANSFER_NFT, None)?;
             let account_index = get_account_index::<T>(&who)?;
 
             validation_nft_index::<T>(nft_id)?;
@@ -734,14 +694,7 @@ decl_module! {
 
             let new_nonce = nonce_check::<T>(&who, nonce)?;
 
-            let mut pad_recipient = [0u8; 32];
-            pad_recipient[28..].copy_from_slice(&recipient.to_be_bytes());
-            let mut command = [0u8; 81];
-            command[0] = OP_TRANSFER_NFT;
-            command[1..9].copy_from_slice(&nonce.to_be_bytes());
-            command[9..13].copy_from_slice(&account_index.to_be_bytes());
-            command[13..17].copy_from_slice(&nft_id.to_be_bytes());
-            command[17..49].copy_from_slice(&pad_recipient);
+            let command = Command::TransferNFT { nonce, account_index, nft_id, recipient }.encode();
             let sign = check_sign::<T>(account_index, &command, &sign)?;
 
             let req_id = req_id_get::<T>()?;
@@ -749,76 +702,69 @@ decl_module! {
             nft_transfer::<T>(&account_index, &recipient, &nft_id)?;
             let op = Ops::TransferNFT(sign.0, sign.1, sign.2, nonce, account_index, nft_id, recipient);
 
-            PendingReqMap::insert(&req_id, op);
-            ReqIndex::put(req_id);
+            req_submit::<T>(&req_id, OP_TRANSFER_NFT, op, account_index);
             NonceMap::<T>::insert(&who, new_nonce);
 
+            merkle_root_commit::<T>(&req_id);
             Self::deposit_event(Event::<T>::TransferNFT(req_id, sign.0, sign.1, sign.2, nonce, account_index, recipient, nft_id));
 
-            return Ok(());
+            return Ok(().into());
         }
 
-        #[weight = 10_000 + T::DbWeight::get().writes(1)]
+        #[pallet::weight(weights::bid_nft::<T>())]
         pub fn bid_nft(
-            origin,
+            origin: OriginFor<T>,
             sign: [u8; 64],
             nft_id: NFTId,
             amount: Amount,
             nonce: NonceId
-        ) -> dispatch::DispatchResult {
+        ) -> DispatchResultWithPostInfo {
             let who = ensure_signed(origin)?;
+            ensure_not_paused::<T>(OP_BID_NFT, None)?;
             let account_index = get_account_index::<T>(&who)?;
 
             validation_nft_index::<T>(nft_id)?;
 
             amount.valid_on_circuit().ok_or(Error::<T>::InvalidAmount)?;
-            let nft = NFTMap::get(&nft_id);
+            let nft = NFTMap::<T>::get(&nft_id);
             if amount <= nft.1 {
                 return Err(Error::<T>::InvalidAmount)?;
             }
 
             let new_nonce = nonce_check::<T>(&who, nonce)?;
 
-            let mut command = [0u8; 81];
-            command[0] = OP_BID_NFT;
-            command[1..9].copy_from_slice(&nonce.to_be_bytes());
-            command[9..13].copy_from_slice(&account_index.to_be_bytes());
-            command[13..17].copy_from_slice(&nft_id.to_be_bytes());
-            command[17..49].copy_from_slice(&amount.to_be_bytes());
+            let command = Command::BidNFT { nonce, account_index, nft_id, amount }.encode();
             let sign = check_sign::<T>(account_index, &command, &sign)?;
 
             let req_id = req_id_get::<T>()?;
 
             nft_bid::<T>(&account_index, amount, &nft_id)?;
             let op = Ops::BidNFT(sign.0, sign.1, sign.2, nonce, account_index, nft_id, amount);
-            PendingReqMap::insert(&req_id, op);
-            ReqIndex::put(req_id);
+            req_submit::<T>(&req_id, OP_BID_NFT, op, account_index);
             NonceMap::<T>::insert(&who, new_nonce);
 
+            merkle_root_commit::<T>(&req_id);
             Self::deposit_event(Event::<T>::BidNFT(req_id, sign.0, sign.1, sign.2, nonce, account_index, nft_id, amount));
 
-            return Ok(());
+            return Ok(().into());
         }
 
-        #[weight = 10_000 + T::DbWeight::get().writes(1)]
+        #[pallet::weight(weights::finalize_nft::<T>())]
         pub fn finalize_nft(
-            origin,
+            origin: OriginFor<T>,
             sign: [u8; 64],
             nft_id: NFTId,
             nonce: NonceId
-        ) -> dispatch::DispatchResult {
+        ) -> DispatchResultWithPostInfo {
             let who = ensure_signed(origin)?;
+            ensure_not_paused::<T>(OP_FINALIZE_NFT, None)?;
             let account_index = get_account_index::<T>(&who)?;
 
             validation_nft_index::<T>(nft_id)?;
 
             let new_nonce = nonce_check::<T>(&who, nonce)?;
 
-            let mut command = [0u8; 81];
-            command[0] = OP_FINALIZE_NFT;
-            command[1..9].copy_from_slice(&nonce.to_be_bytes());
-            command[9..13].copy_from_slice(&account_index.to_be_bytes());
-            command[13..17].copy_from_slice(&nft_id.to_be_bytes());
+            let command = Command::FinalizeNFT { nonce, account_index, nft_id }.encode();
             let sign = check_sign::<T>(account_index, &command, &sign)?;
 
             let req_id = req_id_get::<T>()?;
@@ -826,59 +772,1011 @@ decl_module! {
             nft_finalize::<T>(&account_index, &nft_id)?;
             let op = Ops::FinalizeNFT(sign.0, sign.1, sign.2, nonce, account_index, nft_id);
 
-            PendingReqMap::insert(&req_id, op);
-            ReqIndex::put(req_id);
+            req_submit::<T>(&req_id, OP_FINALIZE_NFT, op, account_index);
             NonceMap::<T>::insert(&who, new_nonce);
 
+            merkle_root_commit::<T>(&req_id);
             Self::deposit_event(Event::<T>::FinalizeNFT(req_id, sign.0, sign.1, sign.2, nonce, account_index, nft_id));
 
-            return Ok(());
+            return Ok(().into());
         }
 
-        #[weight = 10_000 + T::DbWeight::get().writes(1)]
+        #[pallet::weight(weights::ack::<T>(ACK_BATCH_SIZE))]
         pub fn ack(
-            origin,
+            origin: OriginFor<T>,
             req_id_start: ReqId
-        ) -> dispatch::DispatchResult {
+        ) -> DispatchResultWithPostInfo {
             let _who = ensure_signed(origin)?;
+            ensure_migrated::<T>()?;
 
-            let nack = (1u8 << T::AckAdmins::get().len()) - 1;
+            let admins = Admins::<T>::get();
+            let nack = (1u8 << admins.len()) - 1;
 
-            let ack = T::AckAdmins::get().iter().position(|x| x.clone() == _who).ok_or(Error::<T>::NoAccess)?;
+            let ack = admins.iter().position(|x| x.clone() == _who).ok_or(Error::<T>::NoAccess)?;
             let ack_bits = 1u8 << ack;
 
-            let batch_size = 10 as u32;
-
-            for i in 0..batch_size {
+            let mut batch_complete = true;
+            for i in 0..ACK_BATCH_SIZE {
                 let req_id = req_id_start + U256::from(i + 1);
 
-                PendingReqMap::get(&req_id).ok_or(Error::<T>::InvalidReqId)?;
+                if RejectedReqMap::<T>::contains_key(&req_id) {
+                    continue;
+                }
+
+                PendingReqMap::<T>::get(&req_id).ok_or(Error::<T>::InvalidReqId)?;
 
-                let acks = AckMap::get(&req_id) | ack_bits;
+                let acks = AckMap::<T>::get(&req_id).unwrap_or(0u8) | ack_bits;
 
-                AckMap::insert(&req_id, &acks);
+                AckMap::<T>::insert(&req_id, &acks);
+                ReqRecordMap::<T>::mutate(&req_id, |record| {
+                    if let Some(record) = record {
+                        record.acks = acks;
+                    }
+                });
 
                 if acks == nack {
-                    let l1txhash = DepositMap::get(&req_id);
-                    match l1txhash {
-                        None => {},
-                        Some(v) => {
-                            L1TxMap::insert(v, DONE);
+                    req_complete::<T>(&req_id);
+                } else {
+                    batch_complete = false;
+                }
+            }
+
+            /* The batch moves on once every admin acked it */
+            if batch_complete {
+                let req_id_end = req_id_start + U256::from(ACK_BATCH_SIZE);
+                if req_id_end > CompleteReqIndex::<T>::get() {
+                    CompleteReqIndex::<T>::set(req_id_end);
+                }
+                LastAckBlock::<T>::put(<frame_system::Module<T>>::block_number());
+            }
+            Self::deposit_event(Event::<T>::Ack(req_id_start, ack_bits));
+            return Ok(().into());
+        }
+
+        /// Pauses or resumes every L2 operation. `ack` keeps working so that queued requests can complete.
+        #[pallet::weight(10_000 + T::DbWeight::get().writes(1))]
+        pub fn set_pallet_paused(
+            origin: OriginFor<T>,
+            paused: bool
+        ) -> DispatchResultWithPostInfo {
+            let who = ensure_signed(origin)?;
+            is_admin::<T>(&who)?;
+
+            if PalletPaused::<T>::get() != paused {
+                PalletPaused::<T>::put(paused);
+                Self::deposit_event(Event::<T>::PalletPauseChanged(paused));
+            }
+            return Ok(().into());
+        }
+
+        /// Pauses or resumes the operations on a single pool.
+        #[pallet::weight(10_000 + T::DbWeight::get().writes(1))]
+        pub fn set_pool_paused(
+            origin: OriginFor<T>,
+            pool_index: PoolIndex,
+            paused: bool
+        ) -> DispatchResultWithPostInfo {
+            let who = ensure_signed(origin)?;
+            is_admin::<T>(&who)?;
+
+            PoolMap::<T>::get(&pool_index).ok_or(Error::<T>::PoolNotExists)?;
+
+            if PausedPoolMap::<T>::get(&pool_index) != paused {
+                PausedPoolMap::<T>::insert(&pool_index, paused);
+                Self::deposit_event(Event::<T>::PoolPauseChanged(pool_index, paused));
+            }
+            return Ok(().into());
+        }
+
+        /// Pauses or resumes a single op type, e.g. OP_SWAP.
+        #[pallet::weight(10_000 + T::DbWeight::get().writes(1))]
+        pub fn set_op_paused(
+            origin: OriginFor<T>,
+            op: u8,
+            paused: bool
+        ) -> DispatchResultWithPostInfo {
+            let who = ensure_signed(origin)?;
+            is_admin::<T>(&who)?;
+
+            if op > OP_FINALIZE_NFT {
+                return Err(Error::<T>::InvalidOp)?;
+            }
+
+            if PausedOpMap::<T>::get(op) != paused {
+                PausedOpMap::<T>::insert(op, paused);
+                Self::deposit_event(Event::<T>::OpPauseChanged(op, paused));
+            }
+            return Ok(().into());
+        }
+
+        #[pallet::weight(10_000 + T::DbWeight::get().writes(1))]
+        pub fn register_token(
+            origin: OriginFor<T>,
+            token_index: TokenIndex,
+            l1_address: L1Account,
+            decimals: u8,
+            symbol: Vec<u8>
+        ) -> DispatchResultWithPostInfo {
+            let who = ensure_signed(origin)?;
+            is_admin::<T>(&who)?;
+
+            if token_index >= MAX_TOKEN_COUNT {
+                return Err(Error::<T>::TokenIndexOverflow)?;
+            }
+
+            /* Tokens found by the storage migration are registered with an empty TokenInfo */
+            if TokenMap::<T>::get(&token_index).map_or(false, |info| info != TokenInfo::default()) {
+                return Err(Error::<T>::TokenExists)?;
+            }
+
+            if symbol.len() > MAX_TOKEN_SYMBOL_LENGTH {
+                return Err(Error::<T>::InvalidTokenSymbol)?;
+            }
+
+            l1account_check::<T>(l1_address)?;
+
+            TokenMap::<T>::insert(&token_index, TokenInfo {
+                l1_address,
+                decimals,
+                symbol: symbol.clone(),
+            });
+
+            Self::deposit_event(Event::<T>::TokenRegistered(token_index, l1_address, decimals, symbol));
+            return Ok(().into());
+        }
+
+        /// Sets the bridge limits of a token. `None` disables a limit; the rolling
+        /// withdrawal cap is accounted over `period` blocks, which must not be zero when it is set.
+        #[pallet::weight(10_000 + T::DbWeight::get().writes(1))]
+        pub fn set_token_limit(
+            origin: OriginFor<T>,
+            token_index: TokenIndex,
+            max_deposit: Option<Amount>,
+            max_withdraw: Option<Amount>,
+            period: T::BlockNumber,
+            max_period_withdraw: Option<Amount>
+        ) -> DispatchResultWithPostInfo {
+            let who = ensure_signed(origin)?;
+            is_admin::<T>(&who)?;
+
+            token_check::<T>(&token_index)?;
+
+            if max_period_withdraw.is_some() && period.is_zero() {
+                return Err(Error::<T>::InvalidTokenLimit)?;
+            }
+
+            TokenLimitMap::<T>::insert(&token_index, TokenLimit {
+                max_deposit,
+                max_withdraw,
+                period,
+                max_period_withdraw,
+            });
+
+            Self::deposit_event(Event::<T>::TokenLimitSet(token_index, max_deposit, max_withdraw, period, max_period_withdraw));
+            return Ok(().into());
+        }
+
+        /// Rejects a pending withdrawal refused by L1 and refunds the balance or NFT through a
+        /// new `Ops::Reject` request with its own Merkle root. Rejected requests are skipped by `ack`.
+        #[pallet::weight(weights::reject_req::<T>())]
+        pub fn reject_req(
+            origin: OriginFor<T>,
+            req_id: ReqId,
+            reason: Vec<u8>
+        ) -> DispatchResultWithPostInfo {
+            let who = ensure_signed(origin)?;
+            is_admin::<T>(&who)?;
+            ensure_migrated::<T>()?;
+
+            let op = PendingReqMap::<T>::get(&req_id).ok_or(Error::<T>::InvalidReqId)?;
+            let submitted_at = ReqRecordMap::<T>::get(&req_id).map(|record| record.submitted_at);
+            let refund_req_id = req_id_get::<T>()?;
+
+            let account_index = match &op {
+                Ops::Withdraw(rx, _, _, _, account_index, token_index, amount, _) => {
+                    let new_balance = balance_add::<T>(account_index, token_index, *amount)?;
+                    balance_set::<T>(account_index, token_index, new_balance);
+
+                    /* Emergency withdrawals are unsigned and were not counted in the period */
+                    if !rx.is_zero() {
+                        if let Some(submitted_at) = submitted_at {
+                            withdraw_period_refund::<T>(token_index, *amount, submitted_at);
                         }
-                    };
-                    match PendingReqMap::get(&req_id) {
-                        Some (req) => {
-                            CompleteReqMap::insert(req_id, req);
-                            PendingReqMap::remove(&req_id);
-                        },
-                        _ => {}
-                    };
+                    }
+                    *account_index
+                },
+                Ops::WithdrawNFT(_, _, _, _, account_index, nft_id, _) => {
+                    nft_add::<T>(account_index, nft_id)?;
+                    *account_index
+                },
+                _ => return Err(Error::<T>::NotRejectable)?,
+            };
+
+            req_submit::<T>(&refund_req_id, OP_REJECT, Ops::Reject(req_id), account_index);
+            merkle_root_commit::<T>(&refund_req_id);
+
+            PendingReqMap::<T>::remove(&req_id);
+            AckMap::<T>::remove(&req_id);
+            RejectedReqMap::<T>::insert(&req_id, (op, reason));
+            let now = <frame_system::Module<T>>::block_number();
+            ReqRecordMap::<T>::mutate(&req_id, |record| {
+                if let Some(record) = record {
+                    record.completed_at = Some(now);
+                    record.status = ReqStatus::Rejected;
                 }
+            });
+
+            LastAckBlock::<T>::put(now);
+
+            Self::deposit_event(Event::<T>::Abort(req_id));
+            Self::deposit_event(Event::<T>::Reject(refund_req_id, req_id));
+            return Ok(().into());
+        }
+
+        /// Prunes completed and rejected requests up to `up_to` regardless of the retention window,
+        /// at most `MaxPrunePerBlock` of them per call.
+        #[pallet::weight(10_000 + T::DbWeight::get().reads_writes(
+            3 * T::MaxPrunePerBlock::get() as Weight + 3,
+            5 * T::MaxPrunePerBlock::get() as Weight + 1
+        ))]
+        pub fn prune_complete_reqs(
+            origin: OriginFor<T>,
+            up_to: ReqId
+        ) -> DispatchResultWithPostInfo {
+            let who = ensure_signed(origin)?;
+            is_admin::<T>(&who)?;
+            ensure_migrated::<T>()?;
+
+            if up_to > ReqIndex::<T>::get() {
+                return Err(Error::<T>::InvalidReqId)?;
+            }
+
+            let pruned = complete_reqs_prune::<T>(up_to, T::MaxPrunePerBlock::get(), None);
+
+            Self::deposit_event(Event::<T>::ReqsPruned(PrunedReqIndex::<T>::get(), pruned));
+            return Ok(().into());
+        }
+
+        /// Completes the `count` requests after `req_id_start` if `proof` shows that they take the
+        /// L2 state from the Merkle root after `req_id_start` to `new_root`. Rejected requests are
+        /// part of the transition, their refund being a later `Ops::Reject` request, but stay rejected.
+        /// Requests already completed by an ack or an earlier proof are part of the transition too
+        /// and are left as they are.
+        #[pallet::weight(weights::submit_batch_proof::<T>(*count, proof.len() as u32))]
+        pub fn submit_batch_proof(
+            origin: OriginFor<T>,
+            req_id_start: ReqId,
+            count: u32,
+            new_root: H256,
+            proof: Vec<u8>
+        ) -> DispatchResultWithPostInfo {
+            let _who = ensure_signed(origin)?;
+            ensure_migrated::<T>()?;
+
+            if count == 0 || count > MAX_BATCH_PROOF_SIZE {
+                return Err(Error::<T>::InvalidBatchSize)?;
+            }
+            if proof.len() > T::MaxProofLen::get() as usize {
+                return Err(Error::<T>::ProofTooLong)?;
+            }
+
+            let req_id_end = req_id_start + U256::from(count);
+            let old_root = if req_id_start.is_zero() {
+                merkle_zero_hash(MERKLE_DEPTH)
+            } else {
+                MerkleRootMap::<T>::get(&req_id_start).ok_or(Error::<T>::InvalidMerkleRoot)?
+            };
+            if MerkleRootMap::<T>::get(&req_id_end) != Some(new_root) {
+                return Err(Error::<T>::InvalidMerkleRoot)?;
+            }
+
+            let mut req_ids = Vec::new();
+            let mut ops = Vec::new();
+            for i in 0..count {
+                let req_id = req_id_start + U256::from(i + 1);
+
+                if let Some((op, _)) = RejectedReqMap::<T>::get(&req_id) {
+                    ops.push(op);
+                    continue;
+                }
+
+                if let Some(op) = CompleteReqMap::<T>::get(&req_id) {
+                    ops.push(op);
+                    continue;
+                }
+
+                let op = PendingReqMap::<T>::get(&req_id).ok_or(Error::<T>::InvalidReqId)?;
+                req_ids.push(req_id);
+                ops.push(op);
+            }
+
+            if !T::BatchProofVerifier::verify(&old_root, &new_root, &ops, &proof) {
+                return Err(Error::<T>::InvalidProof)?;
+            }
+
+            for req_id in req_ids.iter() {
+                req_complete::<T>(req_id);
+            }
+
+            if req_id_end > CompleteReqIndex::<T>::get() {
+                CompleteReqIndex::<T>::set(req_id_end);
+            }
+            LastAckBlock::<T>::put(<frame_system::Module<T>>::block_number());
+
+            Self::deposit_event(Event::<T>::BatchProofAccepted(req_id_start, count, new_root));
+            return Ok(().into());
+        }
+
+        /// Escape hatch in exit mode: queues unsigned withdrawals of the caller's balances of the
+        /// registered tokens `token_indexes` and of its NFTs `nft_ids` to `l1account`, emitting the
+        /// usual `Withdraw` and `WithdrawNFT` events for L1 to act on. Zero balances are skipped.
+        #[pallet::weight(weights::emergency_withdraw::<T>(token_indexes.len() as u32, nft_ids.len() as u32))]
+        pub fn emergency_withdraw(
+            origin: OriginFor<T>,
+            l1account: L1Account,
+            token_indexes: Vec<TokenIndex>,
+            nft_ids: Vec<NFTId>
+        ) -> DispatchResultWithPostInfo {
+            let who = ensure_signed(origin)?;
+            ensure_migrated::<T>()?;
+
+            if !ExitMode::<T>::get() {
+                return Err(Error::<T>::NotInExitMode)?;
+            }
+
+            let account_index = get_account_index::<T>(&who)?;
+            l1account_check::<T>(l1account)?;
+
+            let mut token_indexes = token_indexes;
+            token_indexes.sort();
+            token_indexes.dedup();
+            let mut balances = Vec::new();
+            for token_index in token_indexes {
+                token_check::<T>(&token_index)?;
+                let amount = BalanceMap::<T>::get((&account_index, &token_index));
+                if !amount.is_zero() {
+                    balances.push((token_index, amount));
+                }
+            }
+            let mut nft_ids = nft_ids;
+            nft_ids.sort();
+            nft_ids.dedup();
+            for nft_id in nft_ids.iter() {
+                validation_nft_index::<T>(*nft_id)?;
+                NFTData::<T>::checked_owner(&NFTMap::<T>::get(nft_id), &account_index)?;
+            }
+
+            let count = (balances.len() + nft_ids.len()) as u32;
+            let mut nonce = NonceMap::<T>::get(&who);
+            let mut req_id = ReqIndex::<T>::get();
+            nonce.checked_add(count as u64).ok_or(Error::<T>::NonceOverflow)?;
+            req_id.checked_add_on_circuit(U256::from(count)).ok_or(Error::<T>::ReqIdOverflow)?;
+
+            for (token_index, amount) in balances {
+                req_id = req_id + U256::from(1u32);
+                let op = Ops::Withdraw(U256::from(0), U256::from(0), U256::from(0), nonce, account_index, token_index, amount, l1account);
+                req_submit::<T>(&req_id, OP_WITHDRAW, op, account_index);
+                balance_set::<T>(&account_index, &token_index, U256::from(0));
+
+                merkle_root_commit::<T>(&req_id);
+                Self::deposit_event(Event::<T>::Withdraw(
+                    req_id,
+                    U256::from(0), U256::from(0), U256::from(0), nonce, account_index, token_index, amount, l1account
+                ));
+                nonce += 1;
+            }
+
+            for nft_id in nft_ids {
+                req_id = req_id + U256::from(1u32);
+                nft_withdraw::<T>(&account_index, &nft_id)?;
+                let op = Ops::WithdrawNFT(U256::from(0), U256::from(0), U256::from(0), nonce, account_index, nft_id, l1account);
+                req_submit::<T>(&req_id, OP_WITHDRAW_NFT, op, account_index);
+
+                merkle_root_commit::<T>(&req_id);
+                Self::deposit_event(Event::<T>::WithdrawNFT(
+                    req_id,
+                    U256::from(0), U256::from(0), U256::from(0), nonce, account_index, nft_id, l1account
+                ));
+                nonce += 1;
+            }
+
+            NonceMap::<T>::insert(&who, nonce);
+
+            Self::deposit_event(Event::<T>::EmergencyWithdraw(account_index, l1account, count));
+            return Ok(().into());
+        }
+
+        /// Replaces the admins. The AckMap bits of pending requests are not moved, so the
+        /// admins should be changed once the pending requests are acked.
+        #[pallet::weight(10_000 + T::DbWeight::get().writes(1))]
+        pub fn set_admins(
+            origin: OriginFor<T>,
+            admins: Vec<T::AccountId>
+        ) -> DispatchResultWithPostInfo {
+            ensure_root(origin)?;
+            admins_check::<T>(&admins)?;
+
+            Admins::<T>::put(&admins);
+
+            Self::deposit_event(Event::<T>::AdminsChanged(admins));
+            return Ok(().into());
+        }
+
+        /// Leaves exit mode, e.g. once admins are acking again. The ack delay restarts from the
+        /// current block.
+        #[pallet::weight(10_000 + T::DbWeight::get().reads_writes(1, 2))]
+        pub fn leave_exit_mode(origin: OriginFor<T>) -> DispatchResultWithPostInfo {
+            ensure_root(origin)?;
+
+            if !ExitMode::<T>::get() {
+                return Err(Error::<T>::NotInExitMode)?;
             }
 
-            CompleteReqIndex::set(req_id_start + U256::from(10 as u32));
-            Self::deposit_event(RawEvent::Ack(req_id_start, ack_bits));
-            return Ok(());
+            let now = <frame_system::Module<T>>::block_number();
+            ExitMode::<T>::put(false);
+            LastAckBlock::<T>::put(now);
+
+            Self::deposit_event(Event::<T>::ExitModeLeft(now));
+            return Ok(().into());
+        }
+    }
+
+    #[pallet::event]
+    #[pallet::metadata(T::AccountId = "AccountId", BalanceOf<T> = "Balance", T::BlockNumber = "BlockNumber")]
+    #[pallet::generate_deposit(pub(super) fn deposit_event)]
+    pub enum Event<T: Config> {
+        SetKey(
+            ReqId,
+            SignatureRX,
+            SignatureRY,
+            SignatureS,
+            NonceId,
+            AccountIndex,
+            ReserveU32,
+            PublicKeyX,
+            PublicKeyY,
+        ),
+        Deposit(
+            ReqId,
+            SignatureRX,
+            SignatureRY,
+            SignatureS,
+            NonceId,
+            AccountIndex,
+            TokenIndex,
+            Amount,
+            ReserveU256,
+            AccountIndex
+        ),
+        Withdraw(
+            ReqId,
+            SignatureRX,
+            SignatureRY,
+            SignatureS,
+            NonceId,
+            AccountIndex,
+            TokenIndex,
+            Amount,
+            L1Account,
+        ),
+        Swap(
+            ReqId,
+            SignatureRX,
+            SignatureRY,
+            SignatureS,
+            NonceId,
+            AccountIndex,
+            PoolIndex,
+            Reverse,
+            Amount,
+            Amount,
+        ),
+        PoolSupply(
+            ReqId,
+            SignatureRX,
+            SignatureRY,
+            SignatureS,
+            NonceId,
+            AccountIndex,
+            PoolIndex,
+            Amount,
+            Amount,
+            Amount
+        ),
+        PoolRetrieve(
+            ReqId,
+            SignatureRX,
+            SignatureRY,
+            SignatureS,
+            NonceId,
+            AccountIndex,
+            PoolIndex,
+            Amount,
+            Amount,
+            Amount
+        ),
+        AddPool(
+            ReqId,
+            SignatureRX,
+            SignatureRY,
+            SignatureS,
+            NonceId,
+            TokenIndex,
+            TokenIndex,
+            ReserveU256,
+            ReserveU256,
+            PoolIndex,
+            AccountIndex
+        ),
+        DepositNFT(
+            ReqId,
+            SignatureRX,
+            SignatureRY,
+            SignatureS,
+            NonceId,
+            AccountIndex,
+            NFTId,
+            AccountIndex
+        ),
+        WithdrawNFT(
+            ReqId,
+            SignatureRX,
+            SignatureRY,
+            SignatureS,
+            NonceId,
+            AccountIndex,
+            NFTId,
+            L1Account,
+        ),
+        TransferNFT(
+            ReqId,
+            SignatureRX,
+            SignatureRY,
+            SignatureS,
+            NonceId,
+            AccountIndex, // From
+            AccountIndex, // To
+            NFTId,
+        ),
+        BidNFT(
+            ReqId,
+            SignatureRX,
+            SignatureRY,
+            SignatureS,
+            NonceId,
+            AccountIndex,
+            NFTId,
+            Amount
+        ),
+        FinalizeNFT(
+            ReqId,
+            SignatureRX,
+            SignatureRY,
+            SignatureS,
+            NonceId,
+            AccountIndex,
+            NFTId
+        ),
+        Ack(ReqId, u8),
+        Abort(ReqId),
+        RewardFunds(T::AccountId, BalanceOf<T>, T::BlockNumber),
+        PalletPauseChanged(bool),
+        PoolPauseChanged(PoolIndex, bool),
+        OpPauseChanged(u8, bool),
+        TokenRegistered(TokenIndex, L1Account, u8, Vec<u8>),
+        TokenLimitSet(TokenIndex, Option<Amount>, Option<Amount>, T::BlockNumber, Option<Amount>),
+        ReqsPruned(ReqId, u32),
+        BatchProofAccepted(ReqId, u32, H256),
+        ExitModeEntered(T::BlockNumber),
+        EmergencyWithdraw(AccountIndex, L1Account, u32),
+        AdminsChanged(Vec<T::AccountId>),
+        ExitModeLeft(T::BlockNumber),
+        /* Refund request and the rejected request it refunds */
+        Reject(ReqId, ReqId),
+        /* Account, token, limit, amount the op would have reached and the limit */
+        TokenLimitExceeded(AccountIndex, TokenIndex, TokenLimitKind, Amount, Amount),
+    }
+
+    #[pallet::error]
+    pub enum Error<T> {
+        NoneValue,
+        BalanceOverflow,
+        BalanceNotEnough,
+        NFTNoAuthority,
+        LockedBalanceOverflow,
+        LockedBalanceNotEnough,
+        PoolBalanceNotEnough,
+        PoolBalanceOverflow,
+        ShareOverflow,
+        ShareNotEnough,
+        ReqIdOverflow,
+        InvalidReqId,
+        NotImplemented,
+        NonceInconsistent,
+        NonceOverflow,
+        InvalidPool,
+        AccountExists,
+        AccountNotExists,
+        AccountIndexOverflow,
+        TokenExists,
+        TokenNotExists,
+        TokenIndexOverflow,
+        NoAccess,
+        PoolExists,
+        PoolNotExists,
+        PoolIndexOverflow,
+        L1TXExists,
+        L1AccountOverflow,
+        InvalidTokenPair,
+        InvalidTokenIndex,
+        InvalidAmount,
+        InvalidKey,
+        InvalidSignature,
+        InvalidAccount,
+        IsNotOwner,
+        InvalidNFTIndex,
+        InvalidAmountRatio,
+        InternalCalcOverflow,
+        Paused,
+        InvalidOp,
+        InvalidTokenSymbol,
+        DepositLimitExceeded,
+        WithdrawLimitExceeded,
+        PeriodWithdrawLimitExceeded,
+        InvalidTokenLimit,
+        NotRejectable,
+        InvalidBatchSize,
+        InvalidMerkleRoot,
+        InvalidProof,
+        ExitMode,
+        NotInExitMode,
+        InvalidReverse,
+        InvalidAdmins,
+        Migrating,
+        ProofTooLong
+    }
+
+    /* Storage layout version, see migrations.rs */
+    #[pallet::storage]
+    #[pallet::getter(fn storage_version)]
+    pub type StorageVersion<T: Config> = StorageValue<_, Releases, ValueQuery>;
+
+    /* Accounts allowed to submit admin ops and ack, the position of an admin is its AckMap bit */
+    #[pallet::storage]
+    #[pallet::getter(fn admins)]
+    pub type Admins<T: Config> = StorageValue<_, Vec<T::AccountId>, ValueQuery>;
+
+    #[pallet::storage]
+    pub type AccountIndexCount<T: Config> = StorageValue<_, AccountIndex, ValueQuery>;
+
+    #[pallet::storage]
+    #[pallet::getter(fn account_index_map)]
+    pub type AccountIndexMap<T: Config> = StorageMap<_, Blake2_128Concat, T::AccountId, AccountIndex, OptionQuery>;
+
+    #[pallet::storage]
+    pub type PoolIndexCount<T: Config> = StorageValue<_, PoolIndex, ValueQuery>;
+
+    #[pallet::storage]
+    #[pallet::getter(fn pool_index_map)]
+    pub type PoolIndexMap<T: Config> = StorageMap<_, Blake2_128Concat, (TokenIndex, TokenIndex), PoolIndex, OptionQuery>;
+
+    #[pallet::storage]
+    pub type NFTIDCount<T: Config> = StorageValue<_, NFTId, ValueQuery>;
+
+    #[pallet::storage]
+    #[pallet::getter(fn balance_map)]
+    pub type BalanceMap<T: Config> = StorageMap<_, Blake2_128Concat, (AccountIndex, TokenIndex), Amount, ValueQuery>;
+
+    #[pallet::storage]
+    #[pallet::getter(fn share_map)]
+    pub type ShareMap<T: Config> = StorageMap<_, Blake2_128Concat, (AccountIndex, PoolIndex), Amount, ValueQuery>;
+
+    #[pallet::storage]
+    #[pallet::getter(fn pool_map)]
+    pub type PoolMap<T: Config> = StorageMap<_, Blake2_128Concat, PoolIndex, (TokenIndex, TokenIndex, Amount, Amount, Amount), OptionQuery>;
+
+    /* Owner * bid * CurrentWinner */
+    #[pallet::storage]
+    #[pallet::getter(fn nft_map)]
+    pub type NFTMap<T: Config> = StorageMap<_, Blake2_128Concat, NFTId, (AccountIndex, Amount, Option<AccountIndex>), ValueQuery>;
+
+    #[pallet::storage]
+    #[pallet::getter(fn pending_req_map)]
+    pub type PendingReqMap<T: Config> = StorageMap<_, Blake2_128Concat, ReqId, Ops, OptionQuery>;
+
+    #[pallet::storage]
+    #[pallet::getter(fn complete_req_map)]
+    pub type CompleteReqMap<T: Config> = StorageMap<_, Blake2_128Concat, ReqId, Ops, OptionQuery>;
+    /* Rejected op * reason */
+    #[pallet::storage]
+    #[pallet::getter(fn rejected_req_map)]
+    pub type RejectedReqMap<T: Config> = StorageMap<_, Blake2_128Concat, ReqId, (Ops, Vec<u8>), OptionQuery>;
+    /* Hash of the op of completed requests moved out of CompleteReqMap */
+    #[pallet::storage]
+    #[pallet::getter(fn pruned_req_map)]
+    pub type PrunedReqMap<T: Config> = StorageMap<_, Blake2_128Concat, ReqId, T::Hash, OptionQuery>;
+
+    #[pallet::storage]
+    #[pallet::getter(fn pruned_req_index)]
+    pub type PrunedReqIndex<T: Config> = StorageValue<_, ReqId, ValueQuery>;
+
+    #[pallet::storage]
+    #[pallet::getter(fn req_record_map)]
+    pub type ReqRecordMap<T: Config> = StorageMap<_, Blake2_128Concat, ReqId, ReqRecord<T::BlockNumber>, OptionQuery>;
+    /* Requests submitted by an account, in submission order */
+    #[pallet::storage]
+    #[pallet::getter(fn account_req_count)]
+    pub type AccountReqCount<T: Config> = StorageMap<_, Blake2_128Concat, AccountIndex, u32, ValueQuery>;
+
+    #[pallet::storage]
+    #[pallet::getter(fn account_req_map)]
+    pub type AccountReqMap<T: Config> = StorageDoubleMap<_, Blake2_128Concat, AccountIndex, Blake2_128Concat, u32, ReqId, OptionQuery>;
+    /* Block of the last ack, batch proof or rejection, used to detect stalled admins */
+    #[pallet::storage]
+    #[pallet::getter(fn last_ack_block)]
+    pub type LastAckBlock<T: Config> = StorageValue<_, T::BlockNumber, ValueQuery>;
+    /* Block the storage was migrated from the legacy layout at, requests without a ReqRecordMap
+     * entry were submitted before it */
+    #[pallet::storage]
+    #[pallet::getter(fn upgraded_at)]
+    pub type UpgradedAt<T: Config> = StorageValue<_, T::BlockNumber, ValueQuery>;
+    /* Block whose on_runtime_upgrade took a migration step, so that its on_initialize does not */
+    #[pallet::storage]
+    pub type MigrationStepAt<T: Config> = StorageValue<_, T::BlockNumber, OptionQuery>;
+    /* Once set, only emergency_withdraw is accepted */
+    #[pallet::storage]
+    #[pallet::getter(fn exit_mode)]
+    pub type ExitMode<T: Config> = StorageValue<_, bool, ValueQuery>;
+
+    #[pallet::storage]
+    #[pallet::getter(fn ack_map)]
+    pub type AckMap<T: Config> = StorageMap<_, Blake2_128Concat, ReqId, u8, OptionQuery>;
+
+    #[pallet::storage]
+    #[pallet::getter(fn req_index)]
+    pub type ReqIndex<T: Config> = StorageValue<_, ReqId, ValueQuery>;
+
+    #[pallet::storage]
+    #[pallet::getter(fn complete_req_index)]
+    pub type CompleteReqIndex<T: Config> = StorageValue<_, ReqId, ValueQuery>;
+
+    #[pallet::storage]
+    #[pallet::getter(fn nonce_map)]
+    pub type NonceMap<T: Config> = StorageMap<_, Blake2_128Concat, T::AccountId, NonceId, ValueQuery>;
+
+    #[pallet::storage]
+    #[pallet::getter(fn key_map)]
+    pub type KeyMap<T: Config> = StorageMap<_, Blake2_128Concat, AccountIndex, PublicKey, OptionQuery>;
+
+    /* Sparse Merkle tree over BalanceMap, ShareMap, PoolMap, NFTMap and KeyMap, see merkle.rs */
+    #[pallet::storage]
+    #[pallet::getter(fn merkle_node_map)]
+    pub type MerkleNodeMap<T: Config> = StorageDoubleMap<_, Blake2_128Concat, u8, Blake2_128Concat, u32, H256, OptionQuery>;
+
+    #[pallet::storage]
+    #[pallet::getter(fn merkle_root_value)]
+    pub type MerkleRoot<T: Config> = StorageValue<_, H256, OptionQuery>;
+    /* Merkle root after the op of each request is applied */
+    #[pallet::storage]
+    #[pallet::getter(fn merkle_root_map)]
+    pub type MerkleRootMap<T: Config> = StorageMap<_, Blake2_128Concat, ReqId, H256, OptionQuery>;
+    /* Leaf map and last key visited by the legacy state scan of the storage migration */
+    #[pallet::storage]
+    pub type LegacyScanCursor<T: Config> = StorageValue<_, (u8, Vec<u8>), OptionQuery>;
+
+    #[pallet::storage]
+    #[pallet::getter(fn deposit_map)]
+    pub type DepositMap<T: Config> = StorageMap<_, Blake2_128Concat, ReqId, L1TxHash, OptionQuery>;
+
+    #[pallet::storage]
+    #[pallet::getter(fn l1txhash_map)]
+    pub type L1TxMap<T: Config> = StorageMap<_, Blake2_128Concat, L1TxHash, L1TxStatus, OptionQuery>;
+
+    #[pallet::storage]
+    #[pallet::getter(fn l1tx_req_map)]
+    pub type L1TxReqMap<T: Config> = StorageMap<_, Blake2_128Concat, L1TxHash, ReqId, OptionQuery>;
+
+    /* Ring buffer of the last MAX_PRICE_OBSERVATIONS price accumulators of each pool */
+    #[pallet::storage]
+    #[pallet::getter(fn price_observation_count)]
+    pub type PriceObservationCount<T: Config> = StorageMap<_, Blake2_128Concat, PoolIndex, u64, ValueQuery>;
+
+    #[pallet::storage]
+    #[pallet::getter(fn price_observation_map)]
+    pub type PriceObservationMap<T: Config> = StorageDoubleMap<_, Blake2_128Concat, PoolIndex, Blake2_128Concat, u32, PriceObservation<T::BlockNumber>, OptionQuery>;
+
+    /* amount0 * amount1 * total_share at the end of a block, for pools changed in that block */
+    #[pallet::storage]
+    #[pallet::getter(fn pool_snapshot_map)]
+    pub type PoolSnapshotMap<T: Config> = StorageDoubleMap<_, Blake2_128Concat, T::BlockNumber, Blake2_128Concat, PoolIndex, (Amount, Amount, Amount), OptionQuery>;
+
+    /* Circuit breakers */
+    #[pallet::storage]
+    #[pallet::getter(fn pallet_paused)]
+    pub type PalletPaused<T: Config> = StorageValue<_, bool, ValueQuery>;
+
+    #[pallet::storage]
+    #[pallet::getter(fn paused_pool_map)]
+    pub type PausedPoolMap<T: Config> = StorageMap<_, Blake2_128Concat, PoolIndex, bool, ValueQuery>;
+
+    #[pallet::storage]
+    #[pallet::getter(fn paused_op_map)]
+    pub type PausedOpMap<T: Config> = StorageMap<_, Blake2_128Concat, u8, bool, ValueQuery>;
+
+    #[pallet::storage]
+    #[pallet::getter(fn token_map)]
+    pub type TokenMap<T: Config> = StorageMap<_, Blake2_128Concat, TokenIndex, TokenInfo, OptionQuery>;
+
+    #[pallet::storage]
+    #[pallet::getter(fn token_limit_map)]
+    pub type TokenLimitMap<T: Config> = StorageMap<_, Blake2_128Concat, TokenIndex, TokenLimit<T::BlockNumber>, OptionQuery>;
+    /* Period start * withdrawn amount in the period */
+    #[pallet::storage]
+    #[pallet::getter(fn withdraw_period_map)]
+    pub type WithdrawPeriodMap<T: Config> = StorageMap<_, Blake2_128Concat, TokenIndex, (T::BlockNumber, Amount), ValueQuery>;
+
+    #[pallet::genesis_config]
+    pub struct GenesisConfig<T: Config> {
+        pub admins: Vec<T::AccountId>,
+        /* TokenIndex * L1 contract address * decimals * symbol */
+        pub tokens: Vec<(TokenIndex, L1Account, u8, Vec<u8>)>,
+        /* Token pairs of the empty pools, the pool index is the position */
+        pub pools: Vec<(TokenIndex, TokenIndex)>,
+        /* Account * BabyJubjub public key * (TokenIndex * amount), the account index is the position */
+        pub balances: Vec<(T::AccountId, [u8; 32], Vec<(TokenIndex, Amount)>)>,
+    }
+
+    #[cfg(feature = "std")]
+    impl<T: Config> Default for GenesisConfig<T> {
+        fn default() -> Self {
+            Self { admins: Vec::new(), tokens: Vec::new(), pools: Vec::new(), balances: Vec::new() }
+        }
+    }
+
+    #[pallet::genesis_build]
+    impl<T: Config> GenesisBuild<T> for GenesisConfig<T> {
+        fn build(&self) {
+            StorageVersion::<T>::put(Releases::LATEST);
+            assert!(admins_check::<T>(&self.admins).is_ok(), "Invalid swap admins");
+            Admins::<T>::put(&self.admins);
+            for (token_index, l1_address, decimals, symbol) in self.tokens.iter() {
+                TokenMap::<T>::insert(token_index, TokenInfo {
+                    l1_address: *l1_address,
+                    decimals: *decimals,
+                    symbol: symbol.clone(),
+                });
+            }
+            for (token_index_0, token_index_1) in self.pools.iter() {
+                assert!(token_check::<T>(token_index_0).is_ok() && token_check::<T>(token_index_1).is_ok(), "Unknown swap pool token");
+                assert!(token_index_0 != token_index_1, "Invalid swap pool token pair");
+                let (token_index_0, token_index_1) = if token_index_0 < token_index_1 {
+                    (token_index_0, token_index_1)
+                } else {
+                    (token_index_1, token_index_0)
+                };
+                assert!(create_pool_index::<T>(token_index_0, token_index_1).is_ok(), "Invalid swap pool");
+            }
+            for (account, key, balances) in self.balances.iter() {
+                let account_index = create_account_index::<T>(account).expect("Invalid swap account");
+                let key = match BabyJubjubPoint::decode(key) {
+                    Ok(key) => key,
+                    Err(_) => panic!("Invalid swap account key"),
+                };
+                KeyMap::<T>::insert(account_index, (u256_from_bigint(&key.x.v), u256_from_bigint(&key.y.v)));
+                merkle_leaf_update::<T>(MerkleLeaf::Key(account_index));
+                for (token_index, amount) in balances.iter() {
+                    assert!(token_check::<T>(token_index).is_ok(), "Unknown swap balance token");
+                    assert!(amount.valid_on_circuit().is_some(), "Invalid swap balance amount");
+                    balance_set::<T>(&account_index, token_index, *amount);
+                }
+            }
+        }
+    }
+}
+
+impl<T: Config> Pallet<T> {
+    /// Lifecycle record and op of a request, `None` once the request is pruned.
+    pub fn request(req_id: ReqId) -> Option<(ReqId, ReqRecord<T::BlockNumber>, Option<Ops>)> {
+        let record = ReqRecordMap::<T>::get(&req_id)?;
+        let op = match record.status {
+            ReqStatus::Pending => PendingReqMap::<T>::get(&req_id),
+            ReqStatus::Complete => CompleteReqMap::<T>::get(&req_id),
+            ReqStatus::Rejected => RejectedReqMap::<T>::get(&req_id).map(|(op, _)| op),
+        };
+        Some((req_id, record, op))
+    }
+
+    /// At most `MAX_REQ_PAGE_SIZE` requests from `start`, in req id order.
+    pub fn requests(start: ReqId, count: u32) -> Vec<(ReqId, ReqRecord<T::BlockNumber>, Option<Ops>)> {
+        (0..count.min(MAX_REQ_PAGE_SIZE))
+            .filter_map(|i| Self::request(start.saturating_add(U256::from(i))))
+            .collect()
+    }
+
+    /// At most `MAX_REQ_PAGE_SIZE` requests submitted by an account, skipping its first `start` ones.
+    pub fn account_requests(
+        account_index: AccountIndex,
+        start: u32,
+        count: u32
+    ) -> Vec<(ReqId, ReqRecord<T::BlockNumber>, Option<Ops>)> {
+        let end = start.saturating_add(count.min(MAX_REQ_PAGE_SIZE)).min(AccountReqCount::<T>::get(account_index));
+        (start..end)
+            .filter_map(|i| AccountReqMap::<T>::get(account_index, i))
+            .filter_map(|req_id| Self::request(req_id))
+            .collect()
+    }
+
+    /// Merkle root after the op of `req_id` is applied, or the current root if `req_id` is `None`.
+    pub fn merkle_root(req_id: Option<ReqId>) -> Option<H256> {
+        match req_id {
+            None => Some(merkle_root::<T>()),
+            Some(req_id) => MerkleRootMap::<T>::get(&req_id),
+        }
+    }
+
+    /// Proof of the current value of `leaf` against the current Merkle root.
+    pub fn merkle_proof(leaf: MerkleLeaf) -> MerkleProof {
+        merkle_proof::<T>(leaf)
+    }
+
+    /// Status of the deposit (or NFT deposit) made by an L1 transaction.
+    pub fn deposit_status(l1_tx_hash: L1TxHash) -> DepositStatus {
+        match L1TxReqMap::<T>::get(&l1_tx_hash) {
+            None => DepositStatus::Unknown,
+            Some(req_id) => {
+                if L1TxMap::<T>::get(&l1_tx_hash) == Some(L1TxStatus::Done) {
+                    DepositStatus::Done(req_id)
+                } else {
+                    DepositStatus::Pending(req_id)
+                }
+            }
+        }
+    }
+
+    /// Time-weighted average price of a pool between two blocks, scaled by 10 ^ 18.
+    /// With `Token0ToToken1` it is the price of token0 in token1, as in `swap`.
+    pub fn twap(
+        pool_index: PoolIndex,
+        direction: SwapDirection,
+        from: T::BlockNumber,
+        to: T::BlockNumber
+    ) -> Option<U256> {
+        if from >= to || to > <frame_system::Module<T>>::block_number() {
+            return None;
+        }
+
+        let (from_cumulative_0, from_cumulative_1) = price_cumulative_at::<T>(&pool_index, from)?;
+        let (to_cumulative_0, to_cumulative_1) = price_cumulative_at::<T>(&pool_index, to)?;
+        let elapsed = U256::from((to - from).saturated_into::<u64>());
+
+        // accumulators wrap around like in uniswap, so the difference is taken modulo 2 ^ 256
+        let delta = if direction == SwapDirection::Token0ToToken1 {
+            to_cumulative_0.overflowing_sub(from_cumulative_0).0
+        } else {
+            to_cumulative_1.overflowing_sub(from_cumulative_1).0
+        };
+        return Some(delta / elapsed);
+    }
+
+    /// Snapshots of `(amount0, amount1, total_share)` of a pool for the blocks in `from..=to`
+    /// which changed it. Only the last `SnapshotWindow` blocks are available.
+    pub fn pool_history(
+        pool_index: PoolIndex,
+        from: T::BlockNumber,
+        to: T::BlockNumber
+    ) -> Vec<(T::BlockNumber, Amount, Amount, Amount)> {
+        let now = <frame_system::Module<T>>::block_number();
+        let oldest = now.saturating_sub(T::SnapshotWindow::get()) + One::one();
+        let mut block_number = if from > oldest { from } else { oldest };
+        let to = if to < now { to } else { now };
+
+        let mut history = Vec::new();
+        while block_number <= to {
+            if let Some((amount0, amount1, total_share)) = PoolSnapshotMap::<T>::get(block_number, pool_index) {
+                history.push((block_number, amount0, amount1, total_share));
+            }
+            block_number += One::one();
         }
+        return history;
     }
 }
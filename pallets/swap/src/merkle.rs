@@ -0,0 +1,184 @@
+use super::*;
+use hex_literal::hex;
+use sha2::{Digest, Sha256};
+
+/*
+ * Sparse Merkle tree of depth MERKLE_DEPTH over the L2 state, hashed with SHA-256 as in the circuit.
+ *
+ * Leaf index layout (32 bits):
+ *   00 | account(20) | token(10)     BalanceMap
+ *   01 | account(20) | pool(10)      ShareMap
+ *   10 | 00 | pool(28)               PoolMap
+ *   10 | 01 | nft(28)                NFTMap
+ *   10 | 10 | account(28)            KeyMap
+ *
+ * A leaf is the hash of the big-endian encoding of its entry, or zero if the entry is
+ * empty. Nodes equal to the root of an empty subtree are not stored.
+ */
+
+fn sha256(data: &[u8]) -> H256 {
+    let mut hasher = Sha256::new();
+    hasher.update(data);
+    H256::from_slice(&hasher.finalize())
+}
+
+fn merkle_hash(left: &H256, right: &H256) -> H256 {
+    let mut data = [0u8; 64];
+    data[..32].copy_from_slice(left.as_bytes());
+    data[32..].copy_from_slice(right.as_bytes());
+    sha256(&data)
+}
+
+/* Roots of empty subtrees of every height from 0 to MERKLE_DEPTH, each the hash of two copies of
+ * the previous one */
+const MERKLE_ZERO_HASHES: [[u8; 32]; MERKLE_DEPTH as usize + 1] = [
+    hex!("0000000000000000000000000000000000000000000000000000000000000000"),
+    hex!("f5a5fd42d16a20302798ef6ed309979b43003d2320d9f0e8ea9831a92759fb4b"),
+    hex!("db56114e00fdd4c1f85c892bf35ac9a89289aaecb1ebd0a96cde606a748b5d71"),
+    hex!("c78009fdf07fc56a11f122370658a353aaa542ed63e44c4bc15ff4cd105ab33c"),
+    hex!("536d98837f2dd165a55d5eeae91485954472d56f246df256bf3cae19352a123c"),
+    hex!("9efde052aa15429fae05bad4d0b1d7c64da64d03d7a1854a588c2cb8430c0d30"),
+    hex!("d88ddfeed400a8755596b21942c1497e114c302e6118290f91e6772976041fa1"),
+    hex!("87eb0ddba57e35f6d286673802a4af5975e22506c7cf4c64bb6be5ee11527f2c"),
+    hex!("26846476fd5fc54a5d43385167c95144f2643f533cc85bb9d16b782f8d7db193"),
+    hex!("506d86582d252405b840018792cad2bf1259f1ef5aa5f887e13cb2f0094f51e1"),
+    hex!("ffff0ad7e659772f9534c195c815efc4014ef1e1daed4404c06385d11192e92b"),
+    hex!("6cf04127db05441cd833107a52be852868890e4317e6a02ab47683aa75964220"),
+    hex!("b7d05f875f140027ef5118a2247bbb84ce8f2f0f1123623085daf7960c329f5f"),
+    hex!("df6af5f5bbdb6be9ef8aa618e4bf8073960867171e29676f8b284dea6a08a85e"),
+    hex!("b58d900f5e182e3c50ef74969ea16c7726c549757cc23523c369587da7293784"),
+    hex!("d49a7502ffcfb0340b1d7885688500ca308161a7f96b62df9d083b71fcc8f2bb"),
+    hex!("8fe6b1689256c0d385f42f5bbe2027a22c1996e110ba97c171d3e5948de92beb"),
+    hex!("8d0d63c39ebade8509e0ae3c9c3876fb5fa112be18f905ecacfecb92057603ab"),
+    hex!("95eec8b2e541cad4e91de38385f2e046619f54496c2382cb6cacd5b98c26f5a4"),
+    hex!("f893e908917775b62bff23294dbbe3a1cd8e6cc1c35b4801887b646a6f81f17f"),
+    hex!("cddba7b592e3133393c16194fac7431abf2f5485ed711db282183c819e08ebaa"),
+    hex!("8a8d7fe3af8caa085a7639a832001457dfb9128a8061142ad0335629ff23ff9c"),
+    hex!("feb3c337d7a51a6fbf00b9e34c52e1c9195c969bd4e7a0bfd51d5c5bed9c1167"),
+    hex!("e71f0aa83cc32edfbefa9f4d3e0174ca85182eec9f3a09f6a6c0df6377a510d7"),
+    hex!("31206fa80a50bb6abe29085058f16212212a60eec8f049fecb92d8c8e0a84bc0"),
+    hex!("21352bfecbeddde993839f614c3dac0a3ee37543f9b412b16199dc158e23b544"),
+    hex!("619e312724bb6d7c3153ed9de791d764a366b389af13c58bf8a8d90481a46765"),
+    hex!("7cdd2986268250628d0c10e385c58c6191e6fbe05191bcc04f133f2cea72c1c4"),
+    hex!("848930bd7ba8cac54661072113fb278869e07bb8587f91392933374d017bcbe1"),
+    hex!("8869ff2c22b28cc10510d9853292803328be4fb0e80495e8bb8d271f5b889636"),
+    hex!("b5fe28e79f1b850f8658246ce9b6a1e7b49fc06db7143e8fe0b4f2b0c5523a5c"),
+    hex!("985e929f70af28d0bdd1a90a808f977f597c7c778c489e98d3bd8910d31ac0f7"),
+    hex!("c6f67e02e6e4e1bdefb994c6098953f34636ba2b6ca20a4721d2b26a886722ff"),
+];
+
+/// Root of an empty subtree of height `level`.
+pub fn merkle_zero_hash(level: u8) -> H256 {
+    H256(MERKLE_ZERO_HASHES[level as usize])
+}
+
+fn merkle_leaf_data<T: Config>(leaf: &MerkleLeaf) -> Vec<u8> {
+    let mut data = Vec::new();
+    match leaf {
+        MerkleLeaf::Balance(account_index, token_index) => {
+            data.extend_from_slice(&BalanceMap::<T>::get((account_index, token_index)).to_be_bytes());
+        },
+        MerkleLeaf::Share(account_index, pool_index) => {
+            data.extend_from_slice(&ShareMap::<T>::get((account_index, pool_index)).to_be_bytes());
+        },
+        MerkleLeaf::Pool(pool_index) => {
+            let (token_index_0, token_index_1, amount_0, amount_1, total_share) =
+                PoolMap::<T>::get(pool_index).unwrap_or_default();
+            data.extend_from_slice(&token_index_0.to_be_bytes());
+            data.extend_from_slice(&token_index_1.to_be_bytes());
+            data.extend_from_slice(&amount_0.to_be_bytes());
+            data.extend_from_slice(&amount_1.to_be_bytes());
+            data.extend_from_slice(&total_share.to_be_bytes());
+        },
+        MerkleLeaf::NFT(nft_id) => {
+            let (owner, amount, bidder) = NFTMap::<T>::get(nft_id);
+            data.extend_from_slice(&owner.to_be_bytes());
+            data.extend_from_slice(&amount.to_be_bytes());
+            data.push(bidder.is_some() as u8);
+            data.extend_from_slice(&bidder.unwrap_or(0u32).to_be_bytes());
+        },
+        MerkleLeaf::Key(account_index) => {
+            let (x, y) = KeyMap::<T>::get(account_index).unwrap_or_default();
+            data.extend_from_slice(&x.to_be_bytes());
+            data.extend_from_slice(&y.to_be_bytes());
+        },
+    };
+    data
+}
+
+/// Hash of the current state entry of `leaf`.
+pub fn merkle_leaf_hash<T: Config>(leaf: &MerkleLeaf) -> H256 {
+    let data = merkle_leaf_data::<T>(leaf);
+    if data.iter().all(|b| *b == 0u8) {
+        H256::zero()
+    } else {
+        sha256(&data)
+    }
+}
+
+/// Recomputes the path from `leaf` to the root after its state entry changed.
+pub fn merkle_leaf_update<T: Config>(leaf: MerkleLeaf) -> () {
+    let mut index = leaf.index();
+    let mut node = merkle_leaf_hash::<T>(&leaf);
+
+    for level in 0..MERKLE_DEPTH {
+        if node == merkle_zero_hash(level) {
+            MerkleNodeMap::<T>::remove(level, index);
+        } else {
+            MerkleNodeMap::<T>::insert(level, index, node);
+        }
+
+        let sibling = MerkleNodeMap::<T>::get(level, index ^ 1).unwrap_or_else(|| merkle_zero_hash(level));
+        node = if index & 1 == 0 {
+            merkle_hash(&node, &sibling)
+        } else {
+            merkle_hash(&sibling, &node)
+        };
+        index >>= 1;
+    }
+
+    MerkleRoot::<T>::put(node);
+}
+
+/// Current root, which is the root of the empty tree before any update.
+pub fn merkle_root<T: Config>() -> H256 {
+    MerkleRoot::<T>::get().unwrap_or_else(|| merkle_zero_hash(MERKLE_DEPTH))
+}
+
+/// Records the root after the op of `req_id` is applied.
+pub fn merkle_root_commit<T: Config>(req_id: &ReqId) -> () {
+    MerkleRootMap::<T>::insert(req_id, merkle_root::<T>());
+}
+
+/// Proof of the current value of `leaf` against the current root.
+pub fn merkle_proof<T: Config>(leaf: MerkleLeaf) -> MerkleProof {
+    let mut index = leaf.index();
+    let mut siblings = Vec::with_capacity(MERKLE_DEPTH as usize);
+
+    for level in 0..MERKLE_DEPTH {
+        siblings.push(MerkleNodeMap::<T>::get(level, index ^ 1).unwrap_or_else(|| merkle_zero_hash(level)));
+        index >>= 1;
+    }
+
+    MerkleProof {
+        index: leaf.index(),
+        leaf: merkle_leaf_hash::<T>(&leaf),
+        siblings,
+        root: merkle_root::<T>(),
+    }
+}
+
+/// Root obtained by folding `proof.leaf` with `proof.siblings`.
+pub fn merkle_proof_root(proof: &MerkleProof) -> H256 {
+    let mut index = proof.index;
+    let mut node = proof.leaf;
+    for sibling in proof.siblings.iter() {
+        node = if index & 1 == 0 {
+            merkle_hash(&node, sibling)
+        } else {
+            merkle_hash(sibling, &node)
+        };
+        index >>= 1;
+    }
+    node
+}
@@ -0,0 +1,267 @@
+use super::*;
+use frame_support::sp_io::{hashing::twox_128, storage::next_key};
+use frame_support::storage::{unhashed, StoragePrefixedMap};
+
+/* Storage prefix of the pallet up to Releases::V1_0_0 */
+pub const LEGACY_PREFIX: &[u8] = b"SimpleMap";
+pub const PREFIX: &[u8] = STORAGE_PREFIX.as_bytes();
+
+/// Brings the storage of the pallet up to the latest `Releases` version, writing about
+/// `max_keys` storage keys at most. Called again every block until `StorageVersion` is the
+/// latest, the pallet refuses ops meanwhile.
+pub fn migrate<T: Config>(max_keys: u32) -> Weight {
+    let mut weight = T::DbWeight::get().reads(1);
+    let mut max_keys = max_keys;
+
+    if StorageVersion::<T>::get() == Releases::V1_0_0 {
+        let (moved, done) = move_prefix::<T>(LEGACY_PREFIX, PREFIX, max_keys);
+        weight = weight.saturating_add(T::DbWeight::get().reads_writes(moved as Weight + 1, 2 * moved as Weight));
+        if !done {
+            return weight;
+        }
+        max_keys -= moved;
+
+        weight = weight
+            .saturating_add(migrate_to_typed_status::<T>())
+            .saturating_add(record_upgrade::<T>())
+            .saturating_add(T::DbWeight::get().writes(1));
+        StorageVersion::<T>::put(Releases::V2_0_0);
+    }
+
+    if StorageVersion::<T>::get() == Releases::V2_0_0 {
+        weight = weight
+            .saturating_add(seed_admins::<T>())
+            .saturating_add(T::DbWeight::get().writes(1));
+        StorageVersion::<T>::put(Releases::V3_0_0);
+    }
+
+    if StorageVersion::<T>::get() == Releases::V3_0_0 {
+        /* Every leaf rewrites a node per level */
+        let max_leaves = (max_keys / (MERKLE_DEPTH as u32 + 1)).max(1);
+        let (visited, done) = legacy_state_scan::<T>(max_leaves);
+        weight = weight
+            .saturating_add(T::DbWeight::get().reads_writes(3 * visited as Weight + 1, 2 * visited as Weight + 1))
+            .saturating_add((visited as Weight).saturating_mul(weights::leaf_update::<T>()));
+        if !done {
+            return weight;
+        }
+
+        merkle_root_commit::<T>(&ReqIndex::<T>::get());
+        weight = weight.saturating_add(T::DbWeight::get().reads_writes(2, 2));
+        StorageVersion::<T>::put(Releases::V4_0_0);
+    }
+
+    weight
+}
+
+/// Visits at most `max_leaves` entries of the leaf maps, resuming after the key recorded in
+/// `LegacyScanCursor`, to bring the state written before the Merkle tree and the token registry
+/// existed into them: updates the leaf of every entry and registers the tokens of balances and
+/// pools. Returns the number of entries visited and whether every leaf map was visited.
+pub fn legacy_state_scan<T: Config>(max_leaves: u32) -> (u32, bool) {
+    let prefixes = [
+        BalanceMap::<T>::final_prefix(),
+        ShareMap::<T>::final_prefix(),
+        PoolMap::<T>::final_prefix(),
+        NFTMap::<T>::final_prefix(),
+        KeyMap::<T>::final_prefix(),
+    ];
+    let (mut map, mut key) = LegacyScanCursor::<T>::get()
+        .unwrap_or_else(|| (0u8, prefixes[0].to_vec()));
+    let mut count = 0u32;
+
+    while let Some(prefix) = prefixes.get(map as usize) {
+        let next = match next_key(&key) {
+            Some(next) if next.starts_with(prefix) => next,
+            _ => {
+                map += 1;
+                if let Some(prefix) = prefixes.get(map as usize) {
+                    key = prefix.to_vec();
+                }
+                continue;
+            },
+        };
+        if count == max_leaves {
+            LegacyScanCursor::<T>::put((map, key));
+            return (count, false);
+        }
+
+        /* Blake2_128Concat keys: prefix, 16 bytes of hash, then the encoded key */
+        if let Some(leaf) = next.get(prefix.len() + 16..).and_then(|encoded| merkle_leaf_of_key(map, encoded)) {
+            match leaf {
+                MerkleLeaf::Balance(_, token_index) => token_register_found::<T>(token_index),
+                MerkleLeaf::Pool(pool_index) => {
+                    if let Some((token_index_0, token_index_1, _, _, _)) = PoolMap::<T>::get(pool_index) {
+                        token_register_found::<T>(token_index_0);
+                        token_register_found::<T>(token_index_1);
+                    }
+                },
+                _ => {},
+            }
+            merkle_leaf_update::<T>(leaf);
+        }
+
+        count += 1;
+        key = next;
+    }
+
+    LegacyScanCursor::<T>::kill();
+    (count, true)
+}
+
+/* Registers a token found in the legacy state with an empty TokenInfo, which admins complete
+ * with register_token */
+fn token_register_found<T: Config>(token_index: TokenIndex) {
+    if token_index < MAX_TOKEN_COUNT && !TokenMap::<T>::contains_key(token_index) {
+        TokenMap::<T>::insert(token_index, TokenInfo::default());
+    }
+}
+
+/* Leaf of the entry of the `map`-th leaf map of legacy_state_scan with the encoded key `key` */
+fn merkle_leaf_of_key(map: u8, mut key: &[u8]) -> Option<MerkleLeaf> {
+    match map {
+        0 => <(AccountIndex, TokenIndex)>::decode(&mut key).ok()
+            .map(|(account_index, token_index)| MerkleLeaf::Balance(account_index, token_index)),
+        1 => <(AccountIndex, PoolIndex)>::decode(&mut key).ok()
+            .map(|(account_index, pool_index)| MerkleLeaf::Share(account_index, pool_index)),
+        2 => PoolIndex::decode(&mut key).ok().map(MerkleLeaf::Pool),
+        3 => NFTId::decode(&mut key).ok().map(MerkleLeaf::NFT),
+        4 => AccountIndex::decode(&mut key).ok().map(MerkleLeaf::Key),
+        _ => None,
+    }
+}
+
+/// Writes `LegacyAdmins` to `Admins` if no admins are set. Invalid legacy admins are skipped,
+/// leaving root to call `set_admins`.
+pub fn seed_admins<T: Config>() -> Weight {
+    if !Admins::<T>::get().is_empty() {
+        return T::DbWeight::get().reads(1);
+    }
+
+    let admins = T::LegacyAdmins::get();
+    if admins_check::<T>(&admins).is_ok() {
+        Admins::<T>::put(&admins);
+    }
+    T::DbWeight::get().reads_writes(1, 1)
+}
+
+/// Records the upgrade block, from which requests submitted before the migration, which have
+/// no ReqRecordMap entry, and the admins wait for their ack before the pallet enters exit mode.
+pub fn record_upgrade<T: Config>() -> Weight {
+    let now = <frame_system::Module<T>>::block_number();
+    UpgradedAt::<T>::put(now);
+    LastAckBlock::<T>::put(now);
+
+    T::DbWeight::get().reads_writes(1, 2)
+}
+
+/// Number of keys stored under the pallet prefix `prefix`.
+#[cfg(any(test, feature = "try-runtime"))]
+pub fn prefix_key_count(prefix: &[u8]) -> u64 {
+    let prefix = twox_128(prefix);
+    let mut count = 0u64;
+    let mut key = prefix.to_vec();
+    while let Some(next) = next_key(&key) {
+        if !next.starts_with(&prefix) {
+            break;
+        }
+        count += 1;
+        key = next;
+    }
+    count
+}
+
+/// Moves at most `max_keys` keys under the pallet prefix `from` to the pallet prefix `to`,
+/// keeping the rest of the key, so that all the storage items of the pallet are renamed.
+/// Returns the number of keys moved and whether no key is left under `from`.
+pub fn move_prefix<T: Config>(from: &[u8], to: &[u8], max_keys: u32) -> (u32, bool) {
+    let from_prefix = twox_128(from);
+    let to_prefix = twox_128(to);
+    let mut count = 0u32;
+    let mut key = from_prefix.to_vec();
+
+    let done = loop {
+        let next = match next_key(&key) {
+            Some(next) if next.starts_with(&from_prefix) => next,
+            _ => break true,
+        };
+        if count == max_keys {
+            break false;
+        }
+
+        if let Some(value) = unhashed::get_raw(&next) {
+            let mut new_key = to_prefix.to_vec();
+            new_key.extend_from_slice(&next[from_prefix.len()..]);
+            unhashed::put_raw(&new_key, &value);
+        }
+        unhashed::kill(&next);
+
+        count += 1;
+        key = next;
+    };
+
+    (count, done)
+}
+
+/// Returns the number of keys to be moved to the new prefix, if the storage is still under the
+/// legacy prefix.
+#[cfg(feature = "try-runtime")]
+pub fn pre_upgrade<T: Config>() -> Result<Option<u64>, &'static str> {
+    if StorageVersion::<T>::get() != Releases::V1_0_0 {
+        return Ok(None);
+    }
+
+    if prefix_key_count(PREFIX) != 0 {
+        return Err("storage under the new prefix before migration");
+    }
+    Ok(Some(prefix_key_count(LEGACY_PREFIX)))
+}
+
+/// Checks the storage after `on_runtime_upgrade`, which may leave keys to move or leaves to build
+/// in later blocks.
+#[cfg(feature = "try-runtime")]
+pub fn post_upgrade<T: Config>(moved: Option<u64>) -> Result<(), &'static str> {
+    if StorageVersion::<T>::get() == Releases::V1_0_0 {
+        let moved = moved.ok_or("storage version went back")?;
+        if prefix_key_count(LEGACY_PREFIX) + prefix_key_count(PREFIX) != moved {
+            return Err("storage lost while moving to the new prefix");
+        }
+        return Ok(());
+    }
+
+    if StorageVersion::<T>::get() != Releases::LATEST {
+        /* The Merkle tree is built in the next blocks */
+        return Ok(());
+    }
+
+    if Admins::<T>::get().is_empty() {
+        return Err("no admins after migration");
+    }
+
+    if moved.is_some() && prefix_key_count(LEGACY_PREFIX) != 0 {
+        return Err("storage left under the legacy prefix");
+    }
+
+    if MerkleRootMap::<T>::get(ReqIndex::<T>::get()) != Some(merkle_root::<T>()) {
+        return Err("Merkle root of the state not committed");
+    }
+    Ok(())
+}
+
+/// Converts the `u8` status codes of `L1TxMap` into `L1TxStatus`, dropping unknown codes.
+/// Queued swaps keep their `reverse` byte as signed: the signature covers it, and the prover
+/// reads any non-zero legacy value as a reverse swap. Safe to run more than once.
+pub fn migrate_to_typed_status<T: Config>() -> Weight {
+    let mut count = 0u64;
+
+    L1TxMap::<T>::translate::<u8, _>(|_, status| {
+        count += 1;
+        match status {
+            1u8 => Some(L1TxStatus::Pending),
+            2u8 => Some(L1TxStatus::Done),
+            _ => None,
+        }
+    });
+
+    T::DbWeight::get().reads_writes(count, count)
+}
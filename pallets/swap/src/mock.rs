@@ -1,10 +1,10 @@
 use crate as swap;
-use frame_support::parameter_types;
+use frame_support::{parameter_types, traits::GenesisBuild};
 use frame_system as system;
-use sp_core::H256;
+use sp_core::{H256, U256};
 use sp_runtime::{
-    testing::Header,
-    traits::{BlakeTwo256, IdentityLookup},
+    testing::{Header, TestSignature, TestXt, UintAuthorityId},
+    traits::{BlakeTwo256, Extrinsic as ExtrinsicT, IdentityLookup},
 };
 use pallet_balances::{Account, AccountData};
 use frame_support::traits::StorageMapShim;
@@ -35,11 +35,13 @@ frame_support::construct_runtime!(
         UncheckedExtrinsic = UncheckedExtrinsic,
     {
         System: frame_system::{Module, Call, Config, Storage, Event<T>},
-        SwapModule: swap::{Module, Call, Storage, Event<T>},
+        Swap: swap::{Module, Call, Config<T>, Storage, Event<T>},
         Balances: pallet_balances::{Module, Call, Storage, Event<T>},
     }
 );
 
+pub type SwapModule = Swap;
+
 parameter_types! {
     pub const BlockHashCount: u64 = 250;
     pub const SS58Prefix: u8 = 42;
@@ -70,24 +72,104 @@ impl system::Config for Test {
     type SS58Prefix = SS58Prefix;
 }
 
-fn admins() -> Vec<u64> {
-    vec![1u64, 2u64]
-}
-
 parameter_types! {
-    pub AckAdmins: Vec<u64> = admins();
+    pub const SnapshotWindow: u64 = 10;
+    pub const CompleteReqRetention: u64 = 10;
+    pub const MaxPrunePerBlock: u32 = 5;
+    pub const ExitModeDelay: u64 = 20;
+    pub const MaxMigrationKeysPerBlock: u32 = 5;
+    pub const MaxProofLen: u32 = 64;
+    pub LegacyAdmins: Vec<u64> = vec![1, 2];
 }
 
 impl swap::Config for Test {
     type Currency = Balances;
     type Event = Event;
-    type AckAdmins = AckAdmins;
+    type SnapshotWindow = SnapshotWindow;
+    type CompleteReqRetention = CompleteReqRetention;
+    type MaxPrunePerBlock = MaxPrunePerBlock;
+    type BatchProofVerifier = MockVerifier;
+    type MaxProofLen = MaxProofLen;
+    type ExitModeDelay = ExitModeDelay;
+    type AuthorityId = MockAckAuthId;
+    type AckValidator = MockAckValidator;
+    type L1EventSource = swap::LocalStorageL1Source;
+    type MaxMigrationKeysPerBlock = MaxMigrationKeysPerBlock;
+    type LegacyAdmins = LegacyAdmins;
+}
+
+pub type Extrinsic = TestXt<Call, ()>;
+
+/// Signs with the `UintAuthorityId` keys set by `UintAuthorityId::set_all_keys`.
+pub struct MockAckAuthId;
+
+impl system::offchain::AppCrypto<UintAuthorityId, TestSignature> for MockAckAuthId {
+    type RuntimeAppPublic = UintAuthorityId;
+    type GenericSignature = TestSignature;
+    type GenericPublic = UintAuthorityId;
+}
+
+impl system::offchain::SigningTypes for Test {
+    type Public = UintAuthorityId;
+    type Signature = TestSignature;
+}
+
+impl<LocalCall> system::offchain::SendTransactionTypes<LocalCall> for Test where Call: From<LocalCall> {
+    type OverarchingCall = Call;
+    type Extrinsic = Extrinsic;
+}
+
+impl<LocalCall> system::offchain::CreateSignedTransaction<LocalCall> for Test where Call: From<LocalCall> {
+    fn create_transaction<C: system::offchain::AppCrypto<Self::Public, Self::Signature>>(
+        call: Call,
+        _public: UintAuthorityId,
+        _account: u64,
+        nonce: u64,
+    ) -> Option<(Call, <Extrinsic as ExtrinsicT>::SignaturePayload)> {
+        Some((call, (nonce, ())))
+    }
+}
+
+parameter_types! {
+    pub static MockAckAllowed: bool = true;
+}
+
+/// Acks a batch only while `MockAckAllowed` is set.
+pub struct MockAckValidator;
+
+impl swap::AckValidator for MockAckValidator {
+    fn validate(_req_id_start: &U256, _ops: &[(U256, swap::Ops)]) -> bool {
+        MockAckAllowed::get()
+    }
+}
+
+pub const MOCK_VALID_PROOF: &[u8] = b"valid proof";
+
+/// Accepts `MOCK_VALID_PROOF` only.
+pub struct MockVerifier;
+
+impl swap::BatchProofVerifier for MockVerifier {
+    fn verify(_old_root: &H256, _new_root: &H256, _ops: &[swap::Ops], proof: &[u8]) -> bool {
+        proof == MOCK_VALID_PROOF
+    }
+
+    fn verify_weight(_ops: u32, _proof_len: u32) -> frame_support::weights::Weight {
+        0
+    }
 }
 
 // Build genesis storage according to the mock runtime.
 pub fn new_test_ext() -> sp_io::TestExternalities {
-    system::GenesisConfig::default()
+    let mut storage = system::GenesisConfig::default()
         .build_storage::<Test>()
-        .unwrap()
-        .into()
+        .unwrap();
+    // Accounts 1 and 2 are the admins, tokens 0 to 3 are registered
+    let config = swap::GenesisConfig::<Test> {
+        admins: vec![1u64, 2u64],
+        tokens: (0u32..4u32).map(|i| (i, U256::from(i + 1), 18u8, b"TKN".to_vec())).collect(),
+        pools: vec![],
+        balances: vec![],
+    };
+    GenesisBuild::<Test>::assimilate_storage(&config, &mut storage).unwrap();
+    storage.into()
 }
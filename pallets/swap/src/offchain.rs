@@ -0,0 +1,278 @@
+use super::*;
+use frame_support::sp_io;
+use frame_support::sp_runtime::offchain::storage::StorageValueRef;
+use frame_support::sp_runtime::traits::IdentifyAccount;
+use frame_support::sp_runtime::RuntimeAppPublic;
+use frame_system::offchain::{AppCrypto, SendSignedTransaction, Signer};
+use sp_core::crypto::KeyTypeId;
+
+/// Key type of the admin keys used by the off-chain worker to sign `ack`.
+pub const KEY_TYPE: KeyTypeId = KeyTypeId(*b"swap");
+
+/// Number of batches the worker looks back and ahead of `CompleteReqIndex` for batches still missing its ack.
+const MAX_ACK_LOOKBACK: u32 = 10u32;
+/// Blocks to wait for a submitted `ack` or deposit to be included before submitting it again.
+const ACK_RESUBMIT_DELAY: u32 = 5u32;
+const ACK_LOCK_PREFIX: &[u8] = b"pallet_swap::ack::";
+const DEPOSIT_LOCK_PREFIX: &[u8] = b"pallet_swap::deposit::";
+
+/// Offchain storage key of the SCALE encoded `bool` that turns on acking with the local admin keys.
+/// Off by default: `T::AckValidator` is all the worker checks before acking a batch.
+pub const AUTO_ACK_STORAGE: &[u8] = b"pallet_swap::auto_ack";
+
+/// Key type of the ed25519 keys the BabyJubjub L2 secret keys of the admins are derived from.
+pub const L2_KEY_TYPE: KeyTypeId = KeyTypeId(*b"zkl2");
+/// Message signed with an `L2_KEY_TYPE` key to derive an L2 secret key. ed25519 signatures are
+/// deterministic, so the same key always derives the same L2 secret key.
+pub const L2_SECRET_KEY_MESSAGE: &[u8] = b"pallet_swap::l2_secret_key";
+
+pub mod crypto {
+    use super::KEY_TYPE;
+    use frame_support::sp_runtime::app_crypto::{app_crypto, sr25519};
+    use frame_support::sp_runtime::{MultiSignature, MultiSigner};
+
+    app_crypto!(sr25519, KEY_TYPE);
+
+    /// Signs the `ack` transactions of the off-chain worker with a `swap` key of the keystore.
+    pub struct AckAuthId;
+
+    impl frame_system::offchain::AppCrypto<MultiSigner, MultiSignature> for AckAuthId {
+        type RuntimeAppPublic = Public;
+        type GenericSignature = sp_core::sr25519::Signature;
+        type GenericPublic = sp_core::sr25519::Public;
+    }
+}
+
+/// Requests of the batch acked by `ack(req_id_start)`, `None` if `ack` would fail on it.
+fn ack_batch<T: Config>(req_id_start: &ReqId) -> Option<Vec<(ReqId, Ops)>> {
+    let mut ops = Vec::new();
+    for i in 0..crate::ACK_BATCH_SIZE {
+        let req_id = *req_id_start + U256::from(i + 1);
+        if RejectedReqMap::<T>::contains_key(&req_id) {
+            continue;
+        }
+        ops.push((req_id, PendingReqMap::<T>::get(&req_id)?));
+    }
+    return Some(ops);
+}
+
+fn is_batch_pending<T: Config>(req_id_start: &ReqId) -> bool {
+    (0..crate::ACK_BATCH_SIZE).any(|i| PendingReqMap::<T>::contains_key(*req_id_start + U256::from(i + 1)))
+}
+
+fn is_batch_acked<T: Config>(req_id_start: &ReqId, ack_bits: u8) -> bool {
+    (0..crate::ACK_BATCH_SIZE).all(|i| {
+        let req_id = *req_id_start + U256::from(i + 1);
+        !PendingReqMap::<T>::contains_key(&req_id) || AckMap::<T>::get(&req_id).unwrap_or(0u8) & ack_bits != 0
+    })
+}
+
+/// Oldest full batch still missing the ack of the admin with `ack_bits`. `CompleteReqIndex` only
+/// moves on once a batch is acked by every admin, so the batches after it may already have this
+/// admin's ack. A batch proof or a later batch can move it past batches still waiting for this admin.
+fn next_ack_start<T: Config>(ack_bits: u8) -> Option<ReqId> {
+    let batch_size = U256::from(crate::ACK_BATCH_SIZE);
+    let complete_req_index = CompleteReqIndex::<T>::get();
+
+    let mut req_id_start = complete_req_index;
+    for _ in 0..MAX_ACK_LOOKBACK {
+        if req_id_start < batch_size || !is_batch_pending::<T>(&(req_id_start - batch_size)) {
+            break;
+        }
+        req_id_start = req_id_start - batch_size;
+    }
+
+    let req_index = ReqIndex::<T>::get();
+    let req_id_last = complete_req_index + batch_size * U256::from(MAX_ACK_LOOKBACK);
+    while req_id_start + batch_size <= req_index && req_id_start <= req_id_last {
+        /* A batch after CompleteReqIndex without pending requests still needs an ack to move it on */
+        let moves_on = req_id_start >= complete_req_index && !is_batch_pending::<T>(&req_id_start);
+        if moves_on || !is_batch_acked::<T>(&req_id_start, ack_bits) {
+            return Some(req_id_start);
+        }
+        req_id_start = req_id_start + batch_size;
+    }
+    return None;
+}
+
+/// Signs `call` with `public` only, `true` if it reached the transaction pool.
+fn submit<T: Config>(public: &T::Public, call: Call<T>) -> bool {
+    let mut keys = Vec::new();
+    keys.push(public.clone());
+    let results = Signer::<T, T::AuthorityId>::all_accounts()
+        .with_filter(keys)
+        .send_signed_transaction(|_| call.clone());
+    results.iter().any(|(_, result)| result.is_ok())
+}
+
+/// Submits `ack` for the next batch missing the ack of this admin, `true` if one was submitted.
+fn offchain_ack<T: Config>(public: &T::Public, ack_bits: u8, block_number: T::BlockNumber) -> bool {
+    let req_id_start = match next_ack_start::<T>(ack_bits) {
+        Some(req_id_start) => req_id_start,
+        None => return false,
+    };
+    let ops = match ack_batch::<T>(&req_id_start) {
+        Some(ops) => ops,
+        None => return false,
+    };
+    if !T::AckValidator::validate(&req_id_start, &ops) {
+        return false;
+    }
+
+    /* Wait for the previous ack of this admin to be included */
+    let mut lock_key = ACK_LOCK_PREFIX.to_vec();
+    lock_key.push(ack_bits);
+    let lock = StorageValueRef::persistent(&lock_key);
+    if let Some(Some((last_start, last_block))) = lock.get::<(ReqId, T::BlockNumber)>() {
+        if last_start == req_id_start && block_number < last_block + ACK_RESUBMIT_DELAY.into() {
+            return false;
+        }
+    }
+
+    if !submit::<T>(public, Call::ack(req_id_start)) {
+        return false;
+    }
+    lock.set(&(req_id_start, block_number));
+    return true;
+}
+
+/// BabyJubjub L2 secret key derived from the signature of `L2_SECRET_KEY_MESSAGE`.
+pub fn l2_secret_key_from_signature(signature: &[u8; 64]) -> [u8; 32] {
+    sp_io::hashing::blake2_256(signature)
+}
+
+/// L2 secret key derived from the `L2_KEY_TYPE` key of the keystore whose L2 public key is the
+/// key of `account_index`, if any.
+fn l2_secret_key<T: Config>(account_index: AccountIndex) -> Option<[u8; 32]> {
+    let key = KeyMap::<T>::get(account_index)?;
+    sp_io::crypto::ed25519_public_keys(L2_KEY_TYPE).iter().find_map(|public| {
+        let signature = sp_io::crypto::ed25519_sign(L2_KEY_TYPE, public, L2_SECRET_KEY_MESSAGE)?;
+        let secret_key = l2_secret_key_from_signature(&signature.0);
+        let public_key = BabyJubjubPoint::decode(&BabyJubjub::pubkey_from_secretkey(&secret_key).encode()).ok()?;
+        match (u256_from_bigint(&public_key.x.v), u256_from_bigint(&public_key.y.v)) == key {
+            true => Some(secret_key),
+            false => None,
+        }
+    })
+}
+
+/// Whether the deposit would pass the checks of `deposit` or `deposit_nft` other than the signature.
+fn is_deposit_valid<T: Config>(deposit: &L1Deposit) -> bool {
+    if L1TxMap::<T>::contains_key(deposit.l1_tx_hash()) {
+        return false;
+    }
+
+    match deposit {
+        L1Deposit::Token { account_index, token_index, amount, .. } => {
+            *account_index < AccountIndexCount::<T>::get()
+                && token_check::<T>(token_index).is_ok()
+                && amount.valid_on_circuit().is_some()
+                && deposit_limit_check::<T>(account_index, token_index, *amount).is_ok()
+        },
+        L1Deposit::NFT { account_index, nft_id, .. } => {
+            validation_account_index::<T>(*account_index).is_ok()
+                && validation_nft_index::<T>(*nft_id).is_ok()
+                && NFTData::<T>::checked_empty(&NFTMap::<T>::get(nft_id)).is_ok()
+        },
+    }
+}
+
+/// Deposits of `T::L1EventSource` that are not on L2 yet. The ones on L2 are forgotten.
+fn l1_deposits<T: Config>() -> Vec<L1Deposit> {
+    let mut deposits = T::L1EventSource::deposits().unwrap_or_default();
+    deposits.retain(|deposit| {
+        if L1TxMap::<T>::contains_key(deposit.l1_tx_hash()) {
+            T::L1EventSource::forget(&deposit.l1_tx_hash());
+            return false;
+        }
+        true
+    });
+    deposits
+}
+
+/// Submits the first valid deposit of `deposits`, read from `T::L1EventSource` by the first admin
+/// that needs them, signed with the L2 key of the admin derived from the keystore. `true` if one
+/// was submitted.
+fn offchain_deposit<T: Config>(
+    public: &T::Public,
+    who: &T::AccountId,
+    ack_bits: u8,
+    block_number: T::BlockNumber,
+    deposits: &mut Option<Vec<L1Deposit>>,
+) -> bool {
+    let secret_key = match AccountIndexMap::<T>::get(who).and_then(l2_secret_key::<T>) {
+        Some(secret_key) => secret_key,
+        None => return false,
+    };
+    let deposits = deposits.get_or_insert_with(l1_deposits::<T>);
+
+    /* Wait for the previous deposit of this admin to be included */
+    let mut lock_key = DEPOSIT_LOCK_PREFIX.to_vec();
+    lock_key.push(ack_bits);
+    let lock = StorageValueRef::persistent(&lock_key);
+    if let Some(Some((l1_tx_hash, last_block))) = lock.get::<(L1TxHash, T::BlockNumber)>() {
+        if !L1TxMap::<T>::contains_key(l1_tx_hash) && block_number < last_block + ACK_RESUBMIT_DELAY.into() {
+            return false;
+        }
+    }
+
+    let deposit = match deposits.iter().find(|deposit| is_deposit_valid::<T>(deposit)) {
+        Some(deposit) => deposit.clone(),
+        None => return false,
+    };
+
+    let nonce = NonceMap::<T>::get(who);
+    let command = match deposit {
+        L1Deposit::Token { account_index, token_index, amount, .. } =>
+            Command::Deposit { nonce, account_index, token_index, amount },
+        L1Deposit::NFT { l1_tx_hash, account_index, nft_id } =>
+            Command::DepositNFT { nonce, account_index, nft_id, l1_tx_hash },
+    };
+
+    let sign = BabyJubjub::sign(&command.encode(), &secret_key);
+    let mut sign_formatted = [0u8; 64];
+    sign_formatted[..32].copy_from_slice(&sign.r.encode());
+    sign_formatted[32..].copy_from_slice(&sign.s.encode());
+
+    let call = match deposit {
+        L1Deposit::Token { l1_tx_hash, account_index, token_index, amount } =>
+            Call::deposit(sign_formatted, account_index, token_index, amount, l1_tx_hash, nonce),
+        L1Deposit::NFT { l1_tx_hash, account_index, nft_id } =>
+            Call::deposit_nft(sign_formatted, account_index, nft_id, l1_tx_hash, nonce),
+    };
+
+    if !submit::<T>(public, call) {
+        return false;
+    }
+    /* The next admin submits another deposit */
+    deposits.retain(|other| other.l1_tx_hash() != deposit.l1_tx_hash());
+    lock.set(&(deposit.l1_tx_hash(), block_number));
+    return true;
+}
+
+/// Acks, if `AUTO_ACK_STORAGE` is set, and submits L1 deposits with every admin key of the local
+/// keystore. Each admin submits at most one transaction per block, as the transactions would share
+/// the account nonce. The L1 deposits are read once per block for all the admins.
+pub fn offchain_run<T: Config>(block_number: T::BlockNumber) {
+    if StorageVersion::<T>::get() != Releases::LATEST {
+        return;
+    }
+
+    let auto_ack = StorageValueRef::persistent(AUTO_ACK_STORAGE).get::<bool>() == Some(Some(true));
+    let admins = Admins::<T>::get();
+    let mut deposits = None;
+
+    for key in <T::AuthorityId as AppCrypto<T::Public, T::Signature>>::RuntimeAppPublic::all() {
+        let generic_public = <T::AuthorityId as AppCrypto<T::Public, T::Signature>>::GenericPublic::from(key);
+        let public: T::Public = generic_public.into();
+        let who = public.clone().into_account();
+        let ack_bits = match admins.iter().position(|x| *x == who) {
+            Some(ack) => 1u8 << ack,
+            None => continue,
+        };
+
+        if !auto_ack || !offchain_ack::<T>(&public, ack_bits, block_number) {
+            offchain_deposit::<T>(&public, &who, ack_bits, block_number, &mut deposits);
+        }
+    }
+}
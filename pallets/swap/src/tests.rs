@@ -35,6 +35,39 @@ mod pool_retrieve_tests;
 #[path = "tests/unit_tests/ops/swap_tests.rs"]
 mod swap_tests;
 
+#[path = "tests/unit_tests/ops/pause_tests.rs"]
+mod pause_tests;
+
+#[path = "tests/unit_tests/ops/register_token_tests.rs"]
+mod register_token_tests;
+
+#[path = "tests/unit_tests/ops/token_limit_tests.rs"]
+mod token_limit_tests;
+
+#[path = "tests/unit_tests/ops/reject_req_tests.rs"]
+mod reject_req_tests;
+
+#[path = "tests/unit_tests/ops/prune_complete_reqs_tests.rs"]
+mod prune_complete_reqs_tests;
+
+#[path = "tests/unit_tests/ops/submit_batch_proof_tests.rs"]
+mod submit_batch_proof_tests;
+
+#[path = "tests/unit_tests/ops/emergency_withdraw_tests.rs"]
+mod emergency_withdraw_tests;
+
+#[path = "tests/unit_tests/ops/offchain_ack_tests.rs"]
+mod offchain_ack_tests;
+
+#[path = "tests/unit_tests/ops/offchain_deposit_tests.rs"]
+mod offchain_deposit_tests;
+
+#[path = "tests/unit_tests/ops/set_admins_tests.rs"]
+mod set_admins_tests;
+
+#[path = "tests/unit_tests/ops/leave_exit_mode_tests.rs"]
+mod leave_exit_mode_tests;
+
 #[path = "tests/unit_tests/helpers/get_share_change_tests.rs"]
 mod get_share_change_tests;
 
@@ -44,6 +77,39 @@ mod calculate_swap_result_amount_tests;
 #[path = "tests/unit_tests/helpers/calculate_amount1_to_pool_tests.rs"]
 mod calculate_amount1_to_pool_tests;
 
+#[path = "tests/unit_tests/helpers/twap_tests.rs"]
+mod twap_tests;
+
+#[path = "tests/unit_tests/helpers/pool_snapshot_tests.rs"]
+mod pool_snapshot_tests;
+
+#[path = "tests/unit_tests/helpers/deposit_status_tests.rs"]
+mod deposit_status_tests;
+
+#[path = "tests/unit_tests/helpers/req_lifecycle_tests.rs"]
+mod req_lifecycle_tests;
+
+#[path = "tests/unit_tests/helpers/merkle_tests.rs"]
+mod merkle_tests;
+
+#[path = "tests/unit_tests/helpers/command_tests.rs"]
+mod command_tests;
+
+#[path = "tests/unit_tests/helpers/l1_source_tests.rs"]
+mod l1_source_tests;
+
+#[path = "tests/unit_tests/helpers/genesis_tests.rs"]
+mod genesis_tests;
+
+#[path = "tests/unit_tests/migrations/typed_status_tests.rs"]
+mod typed_status_tests;
+
+#[path = "tests/unit_tests/migrations/storage_version_tests.rs"]
+mod storage_version_tests;
+
+#[path = "tests/unit_tests/migrations/token_registry_tests.rs"]
+mod token_registry_tests;
+
 #[path = "tests/scenario_tests/1_multi_supplier_multi_swap_cannot_retrieve_all_swap_after_supply_test.rs"]
 mod multi_supplier_multi_swap_cannot_retrieve_all_swap_after_supply_test;
 
@@ -56,11 +56,7 @@ fn multi_supplier_multi_swap_cannot_retrieve_all_swap_after_supply() {
             49, 180, 255,  97, 249,  95,   1, 203
         ];
 
-        let mut command = [0u8; 81];
-        command[0] = OP_ADDPOOL;
-        command[1..9].copy_from_slice(&nonce.to_be_bytes());
-        command[9..13].copy_from_slice(&token_index_0.to_be_bytes());
-        command[13..17].copy_from_slice(&token_index_1.to_be_bytes());
+        let mut command = Command::AddPool { nonce, token_index_0, token_index_1 }.encode();
 
         let mut command_sign = BabyJubjub::sign(&command, &secret_key_1);
         let mut command_sign_formatted :[u8; 64] = [0 as u8;64];
@@ -77,12 +73,7 @@ fn multi_supplier_multi_swap_cannot_retrieve_all_swap_after_supply() {
         let mut l1_tx_hash = U256::from(0);
         nonce = 2u64;
 
-        command = [0u8; 81];
-        command[0] = OP_DEPOSIT;
-        command[1..9].copy_from_slice(&nonce.to_be_bytes());
-        command[9..13].copy_from_slice(&account_index.to_be_bytes());
-        command[13..17].copy_from_slice(&token_index.to_be_bytes());
-        command[17..49].copy_from_slice(&amount.to_be_bytes());
+        command = Command::Deposit { nonce, account_index, token_index, amount }.encode();
 
         command_sign = BabyJubjub::sign(&command, &secret_key_1);
         command_sign_formatted = [0 as u8;64];
@@ -96,12 +87,7 @@ fn multi_supplier_multi_swap_cannot_retrieve_all_swap_after_supply() {
         l1_tx_hash = U256::from(1);
         nonce = 3u64;
 
-        command = [0u8; 81];
-        command[0] = OP_DEPOSIT;
-        command[1..9].copy_from_slice(&nonce.to_be_bytes());
-        command[9..13].copy_from_slice(&account_index.to_be_bytes());
-        command[13..17].copy_from_slice(&token_index.to_be_bytes());
-        command[17..49].copy_from_slice(&amount.to_be_bytes());
+        command = Command::Deposit { nonce, account_index, token_index, amount }.encode();
 
         command_sign = BabyJubjub::sign(&command, &secret_key_1);
         command_sign_formatted = [0 as u8;64];
@@ -117,12 +103,7 @@ fn multi_supplier_multi_swap_cannot_retrieve_all_swap_after_supply() {
         l1_tx_hash = U256::from(2);
         nonce = 4u64;
 
-        command = [0u8; 81];
-        command[0] = OP_DEPOSIT;
-        command[1..9].copy_from_slice(&nonce.to_be_bytes());
-        command[9..13].copy_from_slice(&account_index.to_be_bytes());
-        command[13..17].copy_from_slice(&token_index.to_be_bytes());
-        command[17..49].copy_from_slice(&amount.to_be_bytes());
+        command = Command::Deposit { nonce, account_index, token_index, amount }.encode();
 
         command_sign = BabyJubjub::sign(&command, &secret_key_1);
         command_sign_formatted = [0 as u8;64];
@@ -136,12 +117,7 @@ fn multi_supplier_multi_swap_cannot_retrieve_all_swap_after_supply() {
         l1_tx_hash = U256::from(3);
         nonce = 5u64;
 
-        command = [0u8; 81];
-        command[0] = OP_DEPOSIT;
-        command[1..9].copy_from_slice(&nonce.to_be_bytes());
-        command[9..13].copy_from_slice(&account_index.to_be_bytes());
-        command[13..17].copy_from_slice(&token_index.to_be_bytes());
-        command[17..49].copy_from_slice(&amount.to_be_bytes());
+        command = Command::Deposit { nonce, account_index, token_index, amount }.encode();
 
         command_sign = BabyJubjub::sign(&command, &secret_key_1);
         command_sign_formatted = [0 as u8;64];
@@ -164,13 +140,7 @@ fn multi_supplier_multi_swap_cannot_retrieve_all_swap_after_supply() {
             185, 222, 254,  50, 115,  63,  97, 179
         ];
 
-        command = [0u8; 81];
-        command[0] = OP_SUPPLY;
-        command[1..9].copy_from_slice(&nonce.to_be_bytes());
-        command[9..13].copy_from_slice(&account_index.to_be_bytes());
-        command[13..17].copy_from_slice(&pool_index.to_be_bytes());
-        command[17..49].copy_from_slice(&amount0.to_be_bytes());
-        command[49..81].copy_from_slice(&amount1.to_be_bytes());
+        command = Command::PoolSupply { nonce, account_index, pool_index, amount0, amount1 }.encode();
 
         command_sign = BabyJubjub::sign(&command, &secret_key_2);
         command_sign_formatted = [0 as u8;64];
@@ -192,13 +162,7 @@ fn multi_supplier_multi_swap_cannot_retrieve_all_swap_after_supply() {
             204, 202, 148, 243, 45, 145,   9,   7
         ];
 
-        command = [0u8; 81];
-        command[0] = OP_SUPPLY;
-        command[1..9].copy_from_slice(&nonce.to_be_bytes());
-        command[9..13].copy_from_slice(&account_index.to_be_bytes());
-        command[13..17].copy_from_slice(&pool_index.to_be_bytes());
-        command[17..49].copy_from_slice(&amount0.to_be_bytes());
-        command[49..81].copy_from_slice(&amount1.to_be_bytes());
+        command = Command::PoolSupply { nonce, account_index, pool_index, amount0, amount1 }.encode();
 
         command_sign = BabyJubjub::sign(&command, &secret_key_3);
         command_sign_formatted = [0 as u8;64];
@@ -214,13 +178,7 @@ fn multi_supplier_multi_swap_cannot_retrieve_all_swap_after_supply() {
         amount = U256::from(100);
         nonce = 2u64;
 
-        command = [0u8; 81];
-        command[0] = OP_SWAP;
-        command[1..9].copy_from_slice(&nonce.to_be_bytes());
-        command[9..13].copy_from_slice(&account_index.to_be_bytes());
-        command[13..17].copy_from_slice(&pool_index.to_be_bytes());
-        command[17..49].copy_from_slice(&U256::from(reverse).to_be_bytes());
-        command[49..81].copy_from_slice(&amount.to_be_bytes());
+        command = Command::Swap { nonce, account_index, pool_index, reverse, amount }.encode();
 
         command_sign = BabyJubjub::sign(&command, &secret_key_2);
         command_sign_formatted = [0 as u8;64];
@@ -234,13 +192,7 @@ fn multi_supplier_multi_swap_cannot_retrieve_all_swap_after_supply() {
         amount = U256::from(100);
         nonce = 3u64;
 
-        command = [0u8; 81];
-        command[0] = OP_SWAP;
-        command[1..9].copy_from_slice(&nonce.to_be_bytes());
-        command[9..13].copy_from_slice(&account_index.to_be_bytes());
-        command[13..17].copy_from_slice(&pool_index.to_be_bytes());
-        command[17..49].copy_from_slice(&U256::from(reverse).to_be_bytes());
-        command[49..81].copy_from_slice(&amount.to_be_bytes());
+        command = Command::Swap { nonce, account_index, pool_index, reverse, amount }.encode();
 
         command_sign = BabyJubjub::sign(&command, &secret_key_2);
         command_sign_formatted = [0 as u8;64];
@@ -254,13 +206,7 @@ fn multi_supplier_multi_swap_cannot_retrieve_all_swap_after_supply() {
         amount1 = U256::from(1003);
         nonce = 4u64;
 
-        command = [0u8; 81];
-        command[0] = OP_RETRIEVE;
-        command[1..9].copy_from_slice(&nonce.to_be_bytes());
-        command[9..13].copy_from_slice(&account_index.to_be_bytes());
-        command[13..17].copy_from_slice(&pool_index.to_be_bytes());
-        command[17..49].copy_from_slice(&amount0.to_be_bytes());
-        command[49..81].copy_from_slice(&amount1.to_be_bytes());
+        command = Command::PoolRetrieve { nonce, account_index, pool_index, amount0, amount1 }.encode();
 
         command_sign = BabyJubjub::sign(&command, &secret_key_2);
         command_sign_formatted = [0 as u8;64];
@@ -269,13 +215,13 @@ fn multi_supplier_multi_swap_cannot_retrieve_all_swap_after_supply() {
 
         assert_ok!(SwapModule::pool_retrieve(Origin::signed(origin), command_sign_formatted, pool_index, amount0, amount1, nonce));
 
-        assert_eq!(PoolMap::get(pool_index).unwrap(), (0u32, 1u32, U256::from(499), U256::from(504), U256::from(500_668_896_321_070_234u128)));
+        assert_eq!(PoolMap::<Test>::get(pool_index).unwrap(), (0u32, 1u32, U256::from(499), U256::from(504), U256::from(500_668_896_321_070_234u128)));
 
-        assert_eq!(BalanceMap::get((&account_index, 0u32)), U256::from(2001));
+        assert_eq!(BalanceMap::<Test>::get((&account_index, 0u32)), U256::from(2001));
 
-        assert_eq!(BalanceMap::get((&account_index, 1u32)), U256::from(1996));
+        assert_eq!(BalanceMap::<Test>::get((&account_index, 1u32)), U256::from(1996));
 
-        assert_eq!(ShareMap::get((&account_index, &pool_index)), U256::from(668_896_321_070_234u128));
+        assert_eq!(ShareMap::<Test>::get((&account_index, &pool_index)), U256::from(668_896_321_070_234u128));
 
         assert_eq!(NonceMap::<Test>::get(2u64), 5u64);
 
@@ -286,13 +232,7 @@ fn multi_supplier_multi_swap_cannot_retrieve_all_swap_after_supply() {
         amount1 = U256::from(502);
         nonce = 2u64;
 
-        command = [0u8; 81];
-        command[0] = OP_RETRIEVE;
-        command[1..9].copy_from_slice(&nonce.to_be_bytes());
-        command[9..13].copy_from_slice(&account_index.to_be_bytes());
-        command[13..17].copy_from_slice(&pool_index.to_be_bytes());
-        command[17..49].copy_from_slice(&amount0.to_be_bytes());
-        command[49..81].copy_from_slice(&amount1.to_be_bytes());
+        command = Command::PoolRetrieve { nonce, account_index, pool_index, amount0, amount1 }.encode();
 
         command_sign = BabyJubjub::sign(&command, &secret_key_3);
         command_sign_formatted = [0 as u8;64];
@@ -301,13 +241,13 @@ fn multi_supplier_multi_swap_cannot_retrieve_all_swap_after_supply() {
 
         assert_ok!(SwapModule::pool_retrieve(Origin::signed(origin), command_sign_formatted, pool_index, amount0, amount1, nonce));
 
-        assert_eq!(PoolMap::get(pool_index).unwrap(), (0u32, 1u32, U256::from(1), U256::from(2), U256::from(1_003_344_481_605_351u128)));
+        assert_eq!(PoolMap::<Test>::get(pool_index).unwrap(), (0u32, 1u32, U256::from(1), U256::from(2), U256::from(1_003_344_481_605_351u128)));
 
-        assert_eq!(BalanceMap::get((&account_index, 0u32)), U256::from(1998));
+        assert_eq!(BalanceMap::<Test>::get((&account_index, 0u32)), U256::from(1998));
 
-        assert_eq!(BalanceMap::get((&account_index, 1u32)), U256::from(2002));
+        assert_eq!(BalanceMap::<Test>::get((&account_index, 1u32)), U256::from(2002));
 
-        assert_eq!(ShareMap::get((&account_index, &pool_index)), U256::from(334_448_160_535_117u128));
+        assert_eq!(ShareMap::<Test>::get((&account_index, &pool_index)), U256::from(334_448_160_535_117u128));
 
         assert_eq!(NonceMap::<Test>::get(3u64), 3u64);
 
@@ -319,13 +259,7 @@ fn multi_supplier_multi_swap_cannot_retrieve_all_swap_after_supply() {
         amount1 = U256::from(2);
         nonce = 5u64;
 
-        command = [0u8; 81];
-        command[0] = OP_RETRIEVE;
-        command[1..9].copy_from_slice(&nonce.to_be_bytes());
-        command[9..13].copy_from_slice(&account_index.to_be_bytes());
-        command[13..17].copy_from_slice(&pool_index.to_be_bytes());
-        command[17..49].copy_from_slice(&amount0.to_be_bytes());
-        command[49..81].copy_from_slice(&amount1.to_be_bytes());
+        command = Command::PoolRetrieve { nonce, account_index, pool_index, amount0, amount1 }.encode();
 
         command_sign = BabyJubjub::sign(&command, &secret_key_2);
         command_sign_formatted = [0 as u8;64];
@@ -342,13 +276,7 @@ fn multi_supplier_multi_swap_cannot_retrieve_all_swap_after_supply() {
         amount1 = U256::from(2);
         nonce = 3u64;
 
-        command = [0u8; 81];
-        command[0] = OP_RETRIEVE;
-        command[1..9].copy_from_slice(&nonce.to_be_bytes());
-        command[9..13].copy_from_slice(&account_index.to_be_bytes());
-        command[13..17].copy_from_slice(&pool_index.to_be_bytes());
-        command[17..49].copy_from_slice(&amount0.to_be_bytes());
-        command[49..81].copy_from_slice(&amount1.to_be_bytes());
+        command = Command::PoolRetrieve { nonce, account_index, pool_index, amount0, amount1 }.encode();
 
         command_sign = BabyJubjub::sign(&command, &secret_key_3);
         command_sign_formatted = [0 as u8;64];
@@ -56,11 +56,7 @@ fn multi_supplier_multi_swap_cannot_retrieve_all_swap_not_after_supply() {
             49, 180, 255,  97, 249,  95,   1, 203
         ];
 
-        let mut command = [0u8; 81];
-        command[0] = OP_ADDPOOL;
-        command[1..9].copy_from_slice(&nonce.to_be_bytes());
-        command[9..13].copy_from_slice(&token_index_0.to_be_bytes());
-        command[13..17].copy_from_slice(&token_index_1.to_be_bytes());
+        let mut command = Command::AddPool { nonce, token_index_0, token_index_1 }.encode();
 
         let mut command_sign = BabyJubjub::sign(&command, &secret_key_1);
         let mut command_sign_formatted :[u8; 64] = [0 as u8;64];
@@ -77,12 +73,7 @@ fn multi_supplier_multi_swap_cannot_retrieve_all_swap_not_after_supply() {
         let mut l1_tx_hash = U256::from(0);
         nonce = 2u64;
 
-        command = [0u8; 81];
-        command[0] = OP_DEPOSIT;
-        command[1..9].copy_from_slice(&nonce.to_be_bytes());
-        command[9..13].copy_from_slice(&account_index.to_be_bytes());
-        command[13..17].copy_from_slice(&token_index.to_be_bytes());
-        command[17..49].copy_from_slice(&amount.to_be_bytes());
+        command = Command::Deposit { nonce, account_index, token_index, amount }.encode();
 
         command_sign = BabyJubjub::sign(&command, &secret_key_1);
         command_sign_formatted = [0 as u8;64];
@@ -96,12 +87,7 @@ fn multi_supplier_multi_swap_cannot_retrieve_all_swap_not_after_supply() {
         l1_tx_hash = U256::from(1);
         nonce = 3u64;
 
-        command = [0u8; 81];
-        command[0] = OP_DEPOSIT;
-        command[1..9].copy_from_slice(&nonce.to_be_bytes());
-        command[9..13].copy_from_slice(&account_index.to_be_bytes());
-        command[13..17].copy_from_slice(&token_index.to_be_bytes());
-        command[17..49].copy_from_slice(&amount.to_be_bytes());
+        command = Command::Deposit { nonce, account_index, token_index, amount }.encode();
 
         command_sign = BabyJubjub::sign(&command, &secret_key_1);
         command_sign_formatted = [0 as u8;64];
@@ -117,12 +103,7 @@ fn multi_supplier_multi_swap_cannot_retrieve_all_swap_not_after_supply() {
         l1_tx_hash = U256::from(2);
         nonce = 4u64;
 
-        command = [0u8; 81];
-        command[0] = OP_DEPOSIT;
-        command[1..9].copy_from_slice(&nonce.to_be_bytes());
-        command[9..13].copy_from_slice(&account_index.to_be_bytes());
-        command[13..17].copy_from_slice(&token_index.to_be_bytes());
-        command[17..49].copy_from_slice(&amount.to_be_bytes());
+        command = Command::Deposit { nonce, account_index, token_index, amount }.encode();
 
         command_sign = BabyJubjub::sign(&command, &secret_key_1);
         command_sign_formatted = [0 as u8;64];
@@ -136,12 +117,7 @@ fn multi_supplier_multi_swap_cannot_retrieve_all_swap_not_after_supply() {
         l1_tx_hash = U256::from(3);
         nonce = 5u64;
 
-        command = [0u8; 81];
-        command[0] = OP_DEPOSIT;
-        command[1..9].copy_from_slice(&nonce.to_be_bytes());
-        command[9..13].copy_from_slice(&account_index.to_be_bytes());
-        command[13..17].copy_from_slice(&token_index.to_be_bytes());
-        command[17..49].copy_from_slice(&amount.to_be_bytes());
+        command = Command::Deposit { nonce, account_index, token_index, amount }.encode();
 
         command_sign = BabyJubjub::sign(&command, &secret_key_1);
         command_sign_formatted = [0 as u8;64];
@@ -156,12 +132,7 @@ fn multi_supplier_multi_swap_cannot_retrieve_all_swap_not_after_supply() {
         l1_tx_hash = U256::from(4);
         nonce = 6u64;
 
-        command = [0u8; 81];
-        command[0] = OP_DEPOSIT;
-        command[1..9].copy_from_slice(&nonce.to_be_bytes());
-        command[9..13].copy_from_slice(&account_index.to_be_bytes());
-        command[13..17].copy_from_slice(&token_index.to_be_bytes());
-        command[17..49].copy_from_slice(&amount.to_be_bytes());
+        command = Command::Deposit { nonce, account_index, token_index, amount }.encode();
 
         command_sign = BabyJubjub::sign(&command, &secret_key_1);
         command_sign_formatted = [0 as u8;64];
@@ -175,12 +146,7 @@ fn multi_supplier_multi_swap_cannot_retrieve_all_swap_not_after_supply() {
         l1_tx_hash = U256::from(5);
         nonce = 7u64;
 
-        command = [0u8; 81];
-        command[0] = OP_DEPOSIT;
-        command[1..9].copy_from_slice(&nonce.to_be_bytes());
-        command[9..13].copy_from_slice(&account_index.to_be_bytes());
-        command[13..17].copy_from_slice(&token_index.to_be_bytes());
-        command[17..49].copy_from_slice(&amount.to_be_bytes());
+        command = Command::Deposit { nonce, account_index, token_index, amount }.encode();
 
         command_sign = BabyJubjub::sign(&command, &secret_key_1);
         command_sign_formatted = [0 as u8;64];
@@ -203,13 +169,7 @@ fn multi_supplier_multi_swap_cannot_retrieve_all_swap_not_after_supply() {
             185, 222, 254,  50, 115,  63,  97, 179
         ];
 
-        command = [0u8; 81];
-        command[0] = OP_SUPPLY;
-        command[1..9].copy_from_slice(&nonce.to_be_bytes());
-        command[9..13].copy_from_slice(&account_index.to_be_bytes());
-        command[13..17].copy_from_slice(&pool_index.to_be_bytes());
-        command[17..49].copy_from_slice(&amount0.to_be_bytes());
-        command[49..81].copy_from_slice(&amount1.to_be_bytes());
+        command = Command::PoolSupply { nonce, account_index, pool_index, amount0, amount1 }.encode();
 
         command_sign = BabyJubjub::sign(&command, &secret_key_2);
         command_sign_formatted = [0 as u8;64];
@@ -231,13 +191,7 @@ fn multi_supplier_multi_swap_cannot_retrieve_all_swap_not_after_supply() {
             140, 130,  52, 195,  95, 192,  40,  50
         ];
 
-        command = [0u8; 81];
-        command[0] = OP_SWAP;
-        command[1..9].copy_from_slice(&nonce.to_be_bytes());
-        command[9..13].copy_from_slice(&account_index.to_be_bytes());
-        command[13..17].copy_from_slice(&pool_index.to_be_bytes());
-        command[17..49].copy_from_slice(&U256::from(reverse).to_be_bytes());
-        command[49..81].copy_from_slice(&amount.to_be_bytes());
+        command = Command::Swap { nonce, account_index, pool_index, reverse, amount }.encode();
 
         command_sign = BabyJubjub::sign(&command, &secret_key_0);
         command_sign_formatted = [0 as u8;64];
@@ -251,13 +205,7 @@ fn multi_supplier_multi_swap_cannot_retrieve_all_swap_not_after_supply() {
         amount = U256::from(2000);
         nonce = 2u64;
 
-        command = [0u8; 81];
-        command[0] = OP_SWAP;
-        command[1..9].copy_from_slice(&nonce.to_be_bytes());
-        command[9..13].copy_from_slice(&account_index.to_be_bytes());
-        command[13..17].copy_from_slice(&pool_index.to_be_bytes());
-        command[17..49].copy_from_slice(&U256::from(reverse).to_be_bytes());
-        command[49..81].copy_from_slice(&amount.to_be_bytes());
+        command = Command::Swap { nonce, account_index, pool_index, reverse, amount }.encode();
 
         command_sign = BabyJubjub::sign(&command, &secret_key_0);
         command_sign_formatted = [0 as u8;64];
@@ -280,13 +228,7 @@ fn multi_supplier_multi_swap_cannot_retrieve_all_swap_not_after_supply() {
             204, 202, 148, 243, 45, 145,   9,   7
         ];
 
-        command = [0u8; 81];
-        command[0] = OP_SUPPLY;
-        command[1..9].copy_from_slice(&nonce.to_be_bytes());
-        command[9..13].copy_from_slice(&account_index.to_be_bytes());
-        command[13..17].copy_from_slice(&pool_index.to_be_bytes());
-        command[17..49].copy_from_slice(&amount0.to_be_bytes());
-        command[49..81].copy_from_slice(&amount1.to_be_bytes());
+        command = Command::PoolSupply { nonce, account_index, pool_index, amount0, amount1 }.encode();
 
         command_sign = BabyJubjub::sign(&command, &secret_key_3);
         command_sign_formatted = [0 as u8;64];
@@ -308,13 +250,7 @@ fn multi_supplier_multi_swap_cannot_retrieve_all_swap_not_after_supply() {
             140, 130,  52, 195,  95, 192,  40,  50
         ];
 
-        command = [0u8; 81];
-        command[0] = OP_SWAP;
-        command[1..9].copy_from_slice(&nonce.to_be_bytes());
-        command[9..13].copy_from_slice(&account_index.to_be_bytes());
-        command[13..17].copy_from_slice(&pool_index.to_be_bytes());
-        command[17..49].copy_from_slice(&U256::from(reverse).to_be_bytes());
-        command[49..81].copy_from_slice(&amount.to_be_bytes());
+        command = Command::Swap { nonce, account_index, pool_index, reverse, amount }.encode();
 
         command_sign = BabyJubjub::sign(&command, &secret_key_0);
         command_sign_formatted = [0 as u8;64];
@@ -328,13 +264,7 @@ fn multi_supplier_multi_swap_cannot_retrieve_all_swap_not_after_supply() {
         amount = U256::from(2000);
         nonce = 4u64;
 
-        command = [0u8; 81];
-        command[0] = OP_SWAP;
-        command[1..9].copy_from_slice(&nonce.to_be_bytes());
-        command[9..13].copy_from_slice(&account_index.to_be_bytes());
-        command[13..17].copy_from_slice(&pool_index.to_be_bytes());
-        command[17..49].copy_from_slice(&U256::from(reverse).to_be_bytes());
-        command[49..81].copy_from_slice(&amount.to_be_bytes());
+        command = Command::Swap { nonce, account_index, pool_index, reverse, amount }.encode();
 
         command_sign = BabyJubjub::sign(&command, &secret_key_0);
         command_sign_formatted = [0 as u8;64];
@@ -350,13 +280,7 @@ fn multi_supplier_multi_swap_cannot_retrieve_all_swap_not_after_supply() {
         amount1 = U256::from(376);
         nonce = 2u64;
 
-        command = [0u8; 81];
-        command[0] = OP_RETRIEVE;
-        command[1..9].copy_from_slice(&nonce.to_be_bytes());
-        command[9..13].copy_from_slice(&account_index.to_be_bytes());
-        command[13..17].copy_from_slice(&pool_index.to_be_bytes());
-        command[17..49].copy_from_slice(&amount0.to_be_bytes());
-        command[49..81].copy_from_slice(&amount1.to_be_bytes());
+        command = Command::PoolRetrieve { nonce, account_index, pool_index, amount0, amount1 }.encode();
 
         command_sign = BabyJubjub::sign(&command, &secret_key_3);
         command_sign_formatted = [0 as u8;64];
@@ -365,13 +289,13 @@ fn multi_supplier_multi_swap_cannot_retrieve_all_swap_not_after_supply() {
 
         assert_ok!(SwapModule::pool_retrieve(Origin::signed(origin), command_sign_formatted, pool_index, amount0, amount1, nonce));
 
-        assert_eq!(PoolMap::get(pool_index).unwrap(), (0u32, 1u32, U256::from(2174), U256::from(472), U256::from(1_000_718_790_534_213_713u128)));
+        assert_eq!(PoolMap::<Test>::get(pool_index).unwrap(), (0u32, 1u32, U256::from(2174), U256::from(472), U256::from(1_000_718_790_534_213_713u128)));
 
-        assert_eq!(BalanceMap::get((&account_index, 0u32)), U256::from(1735));
+        assert_eq!(BalanceMap::<Test>::get((&account_index, 0u32)), U256::from(1735));
 
-        assert_eq!(BalanceMap::get((&account_index, 1u32)), U256::from(2051));
+        assert_eq!(BalanceMap::<Test>::get((&account_index, 1u32)), U256::from(2051));
 
-        assert_eq!(ShareMap::get((&account_index, &pool_index)), U256::from(718_790_534_213_713u128));
+        assert_eq!(ShareMap::<Test>::get((&account_index, &pool_index)), U256::from(718_790_534_213_713u128));
 
         assert_eq!(NonceMap::<Test>::get(3u64), 3u64);
 
@@ -382,13 +306,7 @@ fn multi_supplier_multi_swap_cannot_retrieve_all_swap_not_after_supply() {
         amount1 = U256::from(471);
         nonce = 2u64;
 
-        command = [0u8; 81];
-        command[0] = OP_RETRIEVE;
-        command[1..9].copy_from_slice(&nonce.to_be_bytes());
-        command[9..13].copy_from_slice(&account_index.to_be_bytes());
-        command[13..17].copy_from_slice(&pool_index.to_be_bytes());
-        command[17..49].copy_from_slice(&amount0.to_be_bytes());
-        command[49..81].copy_from_slice(&amount1.to_be_bytes());
+        command = Command::PoolRetrieve { nonce, account_index, pool_index, amount0, amount1 }.encode();
 
         command_sign = BabyJubjub::sign(&command, &secret_key_2);
         command_sign_formatted = [0 as u8;64];
@@ -397,13 +315,13 @@ fn multi_supplier_multi_swap_cannot_retrieve_all_swap_not_after_supply() {
 
         assert_ok!(SwapModule::pool_retrieve(Origin::signed(origin), command_sign_formatted, pool_index, amount0, amount1, nonce));
 
-        assert_eq!(PoolMap::get(pool_index).unwrap(), (0u32, 1u32, U256::from(2), U256::from(1), U256::from(920_624_462_312_984u128)));
+        assert_eq!(PoolMap::<Test>::get(pool_index).unwrap(), (0u32, 1u32, U256::from(2), U256::from(1), U256::from(920_624_462_312_984u128)));
 
-        assert_eq!(BalanceMap::get((&account_index, 0u32)), U256::from(3172));
+        assert_eq!(BalanceMap::<Test>::get((&account_index, 0u32)), U256::from(3172));
 
-        assert_eq!(BalanceMap::get((&account_index, 1u32)), U256::from(1471));
+        assert_eq!(BalanceMap::<Test>::get((&account_index, 1u32)), U256::from(1471));
 
-        assert_eq!(ShareMap::get((&account_index, &pool_index)), U256::from(201_833_928_099_271u128));
+        assert_eq!(ShareMap::<Test>::get((&account_index, &pool_index)), U256::from(201_833_928_099_271u128));
 
         assert_eq!(NonceMap::<Test>::get(2u64), 3u64);
 
@@ -413,13 +331,7 @@ fn multi_supplier_multi_swap_cannot_retrieve_all_swap_not_after_supply() {
         amount1 = U256::from(0);
         nonce = 3u64;
 
-        command = [0u8; 81];
-        command[0] = OP_RETRIEVE;
-        command[1..9].copy_from_slice(&nonce.to_be_bytes());
-        command[9..13].copy_from_slice(&account_index.to_be_bytes());
-        command[13..17].copy_from_slice(&pool_index.to_be_bytes());
-        command[17..49].copy_from_slice(&amount0.to_be_bytes());
-        command[49..81].copy_from_slice(&amount1.to_be_bytes());
+        command = Command::PoolRetrieve { nonce, account_index, pool_index, amount0, amount1 }.encode();
 
         command_sign = BabyJubjub::sign(&command, &secret_key_2);
         command_sign_formatted = [0 as u8;64];
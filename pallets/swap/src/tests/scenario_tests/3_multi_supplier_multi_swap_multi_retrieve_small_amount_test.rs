@@ -56,11 +56,7 @@ fn multi_supplier_multi_swap_multi_retrieve_small_amount_works() {
             49, 180, 255,  97, 249,  95,   1, 203
         ];
 
-        let mut command = [0u8; 81];
-        command[0] = OP_ADDPOOL;
-        command[1..9].copy_from_slice(&nonce.to_be_bytes());
-        command[9..13].copy_from_slice(&token_index_0.to_be_bytes());
-        command[13..17].copy_from_slice(&token_index_1.to_be_bytes());
+        let mut command = Command::AddPool { nonce, token_index_0, token_index_1 }.encode();
 
         let mut command_sign = BabyJubjub::sign(&command, &secret_key_1);
         let mut command_sign_formatted :[u8; 64] = [0 as u8;64];
@@ -77,12 +73,7 @@ fn multi_supplier_multi_swap_multi_retrieve_small_amount_works() {
         let mut l1_tx_hash = U256::from(0);
         nonce = 2u64;
 
-        command = [0u8; 81];
-        command[0] = OP_DEPOSIT;
-        command[1..9].copy_from_slice(&nonce.to_be_bytes());
-        command[9..13].copy_from_slice(&account_index.to_be_bytes());
-        command[13..17].copy_from_slice(&token_index.to_be_bytes());
-        command[17..49].copy_from_slice(&amount.to_be_bytes());
+        command = Command::Deposit { nonce, account_index, token_index, amount }.encode();
 
         command_sign = BabyJubjub::sign(&command, &secret_key_1);
         command_sign_formatted = [0 as u8;64];
@@ -96,12 +87,7 @@ fn multi_supplier_multi_swap_multi_retrieve_small_amount_works() {
         l1_tx_hash = U256::from(1);
         nonce = 3u64;
 
-        command = [0u8; 81];
-        command[0] = OP_DEPOSIT;
-        command[1..9].copy_from_slice(&nonce.to_be_bytes());
-        command[9..13].copy_from_slice(&account_index.to_be_bytes());
-        command[13..17].copy_from_slice(&token_index.to_be_bytes());
-        command[17..49].copy_from_slice(&amount.to_be_bytes());
+        command = Command::Deposit { nonce, account_index, token_index, amount }.encode();
 
         command_sign = BabyJubjub::sign(&command, &secret_key_1);
         command_sign_formatted = [0 as u8;64];
@@ -116,12 +102,7 @@ fn multi_supplier_multi_swap_multi_retrieve_small_amount_works() {
         l1_tx_hash = U256::from(2);
         nonce = 4u64;
 
-        command = [0u8; 81];
-        command[0] = OP_DEPOSIT;
-        command[1..9].copy_from_slice(&nonce.to_be_bytes());
-        command[9..13].copy_from_slice(&account_index.to_be_bytes());
-        command[13..17].copy_from_slice(&token_index.to_be_bytes());
-        command[17..49].copy_from_slice(&amount.to_be_bytes());
+        command = Command::Deposit { nonce, account_index, token_index, amount }.encode();
 
         command_sign = BabyJubjub::sign(&command, &secret_key_1);
         command_sign_formatted = [0 as u8;64];
@@ -135,12 +116,7 @@ fn multi_supplier_multi_swap_multi_retrieve_small_amount_works() {
         l1_tx_hash = U256::from(3);
         nonce = 5u64;
 
-        command = [0u8; 81];
-        command[0] = OP_DEPOSIT;
-        command[1..9].copy_from_slice(&nonce.to_be_bytes());
-        command[9..13].copy_from_slice(&account_index.to_be_bytes());
-        command[13..17].copy_from_slice(&token_index.to_be_bytes());
-        command[17..49].copy_from_slice(&amount.to_be_bytes());
+        command = Command::Deposit { nonce, account_index, token_index, amount }.encode();
 
         command_sign = BabyJubjub::sign(&command, &secret_key_1);
         command_sign_formatted = [0 as u8;64];
@@ -163,13 +139,7 @@ fn multi_supplier_multi_swap_multi_retrieve_small_amount_works() {
             185, 222, 254,  50, 115,  63,  97, 179
         ];
 
-        command = [0u8; 81];
-        command[0] = OP_SUPPLY;
-        command[1..9].copy_from_slice(&nonce.to_be_bytes());
-        command[9..13].copy_from_slice(&account_index.to_be_bytes());
-        command[13..17].copy_from_slice(&pool_index.to_be_bytes());
-        command[17..49].copy_from_slice(&amount0.to_be_bytes());
-        command[49..81].copy_from_slice(&amount1.to_be_bytes());
+        command = Command::PoolSupply { nonce, account_index, pool_index, amount0, amount1 }.encode();
 
         command_sign = BabyJubjub::sign(&command, &secret_key_2);
         command_sign_formatted = [0 as u8;64];
@@ -191,13 +161,7 @@ fn multi_supplier_multi_swap_multi_retrieve_small_amount_works() {
             204, 202, 148, 243, 45, 145,   9,   7
         ];
 
-        command = [0u8; 81];
-        command[0] = OP_SUPPLY;
-        command[1..9].copy_from_slice(&nonce.to_be_bytes());
-        command[9..13].copy_from_slice(&account_index.to_be_bytes());
-        command[13..17].copy_from_slice(&pool_index.to_be_bytes());
-        command[17..49].copy_from_slice(&amount0.to_be_bytes());
-        command[49..81].copy_from_slice(&amount1.to_be_bytes());
+        command = Command::PoolSupply { nonce, account_index, pool_index, amount0, amount1 }.encode();
 
         command_sign = BabyJubjub::sign(&command, &secret_key_3);
         command_sign_formatted = [0 as u8;64];
@@ -213,13 +177,7 @@ fn multi_supplier_multi_swap_multi_retrieve_small_amount_works() {
         amount = U256::from(4000);
         nonce = 2u64;
 
-        command = [0u8; 81];
-        command[0] = OP_SWAP;
-        command[1..9].copy_from_slice(&nonce.to_be_bytes());
-        command[9..13].copy_from_slice(&account_index.to_be_bytes());
-        command[13..17].copy_from_slice(&pool_index.to_be_bytes());
-        command[17..49].copy_from_slice(&U256::from(reverse).to_be_bytes());
-        command[49..81].copy_from_slice(&amount.to_be_bytes());
+        command = Command::Swap { nonce, account_index, pool_index, reverse, amount }.encode();
 
         command_sign = BabyJubjub::sign(&command, &secret_key_2);
         command_sign_formatted = [0 as u8;64];
@@ -233,13 +191,7 @@ fn multi_supplier_multi_swap_multi_retrieve_small_amount_works() {
         amount = U256::from(100);
         nonce = 3u64;
 
-        command = [0u8; 81];
-        command[0] = OP_SWAP;
-        command[1..9].copy_from_slice(&nonce.to_be_bytes());
-        command[9..13].copy_from_slice(&account_index.to_be_bytes());
-        command[13..17].copy_from_slice(&pool_index.to_be_bytes());
-        command[17..49].copy_from_slice(&U256::from(reverse).to_be_bytes());
-        command[49..81].copy_from_slice(&amount.to_be_bytes());
+        command = Command::Swap { nonce, account_index, pool_index, reverse, amount }.encode();
 
         command_sign = BabyJubjub::sign(&command, &secret_key_2);
         command_sign_formatted = [0 as u8;64];
@@ -253,13 +205,7 @@ fn multi_supplier_multi_swap_multi_retrieve_small_amount_works() {
         amount1 = U256::from(18236u64);
         nonce = 4u64;
 
-        command = [0u8; 81];
-        command[0] = OP_RETRIEVE;
-        command[1..9].copy_from_slice(&nonce.to_be_bytes());
-        command[9..13].copy_from_slice(&account_index.to_be_bytes());
-        command[13..17].copy_from_slice(&pool_index.to_be_bytes());
-        command[17..49].copy_from_slice(&amount0.to_be_bytes());
-        command[49..81].copy_from_slice(&amount1.to_be_bytes());
+        command = Command::PoolRetrieve { nonce, account_index, pool_index, amount0, amount1 }.encode();
 
         command_sign = BabyJubjub::sign(&command, &secret_key_2);
         command_sign_formatted = [0 as u8;64];
@@ -268,13 +214,13 @@ fn multi_supplier_multi_swap_multi_retrieve_small_amount_works() {
         
         assert_ok!(SwapModule::pool_retrieve(Origin::signed(origin), command_sign_formatted, pool_index, amount0, amount1, nonce));
 
-        assert_eq!(PoolMap::get(pool_index).unwrap(), (0u32, 1u32, U256::from(22160), U256::from(18437), U256::from(20_209_437_386_569_872_958u128)));
+        assert_eq!(PoolMap::<Test>::get(pool_index).unwrap(), (0u32, 1u32, U256::from(22160), U256::from(18437), U256::from(20_209_437_386_569_872_958u128)));
 
-        assert_eq!(BalanceMap::get((&account_index, 0u32)), U256::from(28040));
+        assert_eq!(BalanceMap::<Test>::get((&account_index, 0u32)), U256::from(28040));
 
-        assert_eq!(BalanceMap::get((&account_index, 1u32)), U256::from(31763));
+        assert_eq!(BalanceMap::<Test>::get((&account_index, 1u32)), U256::from(31763));
 
-        assert_eq!(ShareMap::get((&account_index, &pool_index)), U256::from(9_437_386_569_872_958u128));
+        assert_eq!(ShareMap::<Test>::get((&account_index, &pool_index)), U256::from(9_437_386_569_872_958u128));
 
         assert_eq!(NonceMap::<Test>::get(2u64), 5u64);
 
@@ -286,15 +232,8 @@ fn multi_supplier_multi_swap_multi_retrieve_small_amount_works() {
         nonce = 5u64;
 
         let mut index = 0;
-        command = [0u8; 81];
-        
         while index < retrieve_times {
-            command[0] = OP_RETRIEVE;
-            command[1..9].copy_from_slice(&nonce.to_be_bytes());
-            command[9..13].copy_from_slice(&account_index.to_be_bytes());
-            command[13..17].copy_from_slice(&pool_index.to_be_bytes());
-            command[17..49].copy_from_slice(&amount0.to_be_bytes());
-            command[49..81].copy_from_slice(&amount1.to_be_bytes());
+            command = Command::PoolRetrieve { nonce, account_index, pool_index, amount0, amount1 }.encode();
 
             command_sign = BabyJubjub::sign(&command, &secret_key_2);
             command_sign_formatted = [0 as u8;64];
@@ -307,13 +246,13 @@ fn multi_supplier_multi_swap_multi_retrieve_small_amount_works() {
             index += 1;
         }
  
-        assert_eq!(PoolMap::get(pool_index).unwrap(), (0u32, 1u32, U256::from(22150), U256::from(18437), U256::from(20_200_317_604_355_716_878u128)));
+        assert_eq!(PoolMap::<Test>::get(pool_index).unwrap(), (0u32, 1u32, U256::from(22150), U256::from(18437), U256::from(20_200_317_604_355_716_878u128)));
 
-        assert_eq!(BalanceMap::get((&account_index, 0u32)), U256::from(28050));
+        assert_eq!(BalanceMap::<Test>::get((&account_index, 0u32)), U256::from(28050));
 
-        assert_eq!(BalanceMap::get((&account_index, 1u32)), U256::from(31763));
+        assert_eq!(BalanceMap::<Test>::get((&account_index, 1u32)), U256::from(31763));
 
-        assert_eq!(ShareMap::get((&account_index, &pool_index)), U256::from(317_604_355_716_878u128));
+        assert_eq!(ShareMap::<Test>::get((&account_index, &pool_index)), U256::from(317_604_355_716_878u128));
 
         assert_eq!(NonceMap::<Test>::get(2u64), 15u64);
     })
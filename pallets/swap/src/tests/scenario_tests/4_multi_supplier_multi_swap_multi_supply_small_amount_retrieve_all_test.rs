@@ -56,11 +56,7 @@ fn multi_supplier_multi_swap_multi_supply_small_amount_retrieve_all_works() {
             49, 180, 255,  97, 249,  95,   1, 203
         ];
 
-        let mut command = [0u8; 81];
-        command[0] = OP_ADDPOOL;
-        command[1..9].copy_from_slice(&nonce.to_be_bytes());
-        command[9..13].copy_from_slice(&token_index_0.to_be_bytes());
-        command[13..17].copy_from_slice(&token_index_1.to_be_bytes());
+        let mut command = Command::AddPool { nonce, token_index_0, token_index_1 }.encode();
 
         let mut command_sign = BabyJubjub::sign(&command, &secret_key_1);
         let mut command_sign_formatted :[u8; 64] = [0 as u8;64];
@@ -77,12 +73,7 @@ fn multi_supplier_multi_swap_multi_supply_small_amount_retrieve_all_works() {
         let mut l1_tx_hash = U256::from(0);
         nonce = 2u64;
 
-        command = [0u8; 81];
-        command[0] = OP_DEPOSIT;
-        command[1..9].copy_from_slice(&nonce.to_be_bytes());
-        command[9..13].copy_from_slice(&account_index.to_be_bytes());
-        command[13..17].copy_from_slice(&token_index.to_be_bytes());
-        command[17..49].copy_from_slice(&amount.to_be_bytes());
+        command = Command::Deposit { nonce, account_index, token_index, amount }.encode();
 
         command_sign = BabyJubjub::sign(&command, &secret_key_1);
         command_sign_formatted = [0 as u8;64];
@@ -96,12 +87,7 @@ fn multi_supplier_multi_swap_multi_supply_small_amount_retrieve_all_works() {
         l1_tx_hash = U256::from(1);
         nonce = 3u64;
 
-        command = [0u8; 81];
-        command[0] = OP_DEPOSIT;
-        command[1..9].copy_from_slice(&nonce.to_be_bytes());
-        command[9..13].copy_from_slice(&account_index.to_be_bytes());
-        command[13..17].copy_from_slice(&token_index.to_be_bytes());
-        command[17..49].copy_from_slice(&amount.to_be_bytes());
+        command = Command::Deposit { nonce, account_index, token_index, amount }.encode();
 
         command_sign = BabyJubjub::sign(&command, &secret_key_1);
         command_sign_formatted = [0 as u8;64];
@@ -116,12 +102,7 @@ fn multi_supplier_multi_swap_multi_supply_small_amount_retrieve_all_works() {
         l1_tx_hash = U256::from(2);
         nonce = 4u64;
 
-        command = [0u8; 81];
-        command[0] = OP_DEPOSIT;
-        command[1..9].copy_from_slice(&nonce.to_be_bytes());
-        command[9..13].copy_from_slice(&account_index.to_be_bytes());
-        command[13..17].copy_from_slice(&token_index.to_be_bytes());
-        command[17..49].copy_from_slice(&amount.to_be_bytes());
+        command = Command::Deposit { nonce, account_index, token_index, amount }.encode();
 
         command_sign = BabyJubjub::sign(&command, &secret_key_1);
         command_sign_formatted = [0 as u8;64];
@@ -135,12 +116,7 @@ fn multi_supplier_multi_swap_multi_supply_small_amount_retrieve_all_works() {
         l1_tx_hash = U256::from(3);
         nonce = 5u64;
 
-        command = [0u8; 81];
-        command[0] = OP_DEPOSIT;
-        command[1..9].copy_from_slice(&nonce.to_be_bytes());
-        command[9..13].copy_from_slice(&account_index.to_be_bytes());
-        command[13..17].copy_from_slice(&token_index.to_be_bytes());
-        command[17..49].copy_from_slice(&amount.to_be_bytes());
+        command = Command::Deposit { nonce, account_index, token_index, amount }.encode();
 
         command_sign = BabyJubjub::sign(&command, &secret_key_1);
         command_sign_formatted = [0 as u8;64];
@@ -155,12 +131,7 @@ fn multi_supplier_multi_swap_multi_supply_small_amount_retrieve_all_works() {
         l1_tx_hash = U256::from(4);
         nonce = 6u64;
 
-        command = [0u8; 81];
-        command[0] = OP_DEPOSIT;
-        command[1..9].copy_from_slice(&nonce.to_be_bytes());
-        command[9..13].copy_from_slice(&account_index.to_be_bytes());
-        command[13..17].copy_from_slice(&token_index.to_be_bytes());
-        command[17..49].copy_from_slice(&amount.to_be_bytes());
+        command = Command::Deposit { nonce, account_index, token_index, amount }.encode();
 
         command_sign = BabyJubjub::sign(&command, &secret_key_1);
         command_sign_formatted = [0 as u8;64];
@@ -174,12 +145,7 @@ fn multi_supplier_multi_swap_multi_supply_small_amount_retrieve_all_works() {
         l1_tx_hash = U256::from(5);
         nonce = 7u64;
 
-        command = [0u8; 81];
-        command[0] = OP_DEPOSIT;
-        command[1..9].copy_from_slice(&nonce.to_be_bytes());
-        command[9..13].copy_from_slice(&account_index.to_be_bytes());
-        command[13..17].copy_from_slice(&token_index.to_be_bytes());
-        command[17..49].copy_from_slice(&amount.to_be_bytes());
+        command = Command::Deposit { nonce, account_index, token_index, amount }.encode();
 
         command_sign = BabyJubjub::sign(&command, &secret_key_1);
         command_sign_formatted = [0 as u8;64];
@@ -202,13 +168,7 @@ fn multi_supplier_multi_swap_multi_supply_small_amount_retrieve_all_works() {
             185, 222, 254,  50, 115,  63,  97, 179
         ];
 
-        command = [0u8; 81];
-        command[0] = OP_SUPPLY;
-        command[1..9].copy_from_slice(&nonce.to_be_bytes());
-        command[9..13].copy_from_slice(&account_index.to_be_bytes());
-        command[13..17].copy_from_slice(&pool_index.to_be_bytes());
-        command[17..49].copy_from_slice(&amount0.to_be_bytes());
-        command[49..81].copy_from_slice(&amount1.to_be_bytes());
+        command = Command::PoolSupply { nonce, account_index, pool_index, amount0, amount1 }.encode();
 
         command_sign = BabyJubjub::sign(&command, &secret_key_2);
         command_sign_formatted = [0 as u8;64];
@@ -228,13 +188,7 @@ fn multi_supplier_multi_swap_multi_supply_small_amount_retrieve_all_works() {
             204, 202, 148, 243, 45, 145,   9,   7
         ];
 
-        command = [0u8; 81];
-        command[0] = OP_SUPPLY;
-        command[1..9].copy_from_slice(&nonce.to_be_bytes());
-        command[9..13].copy_from_slice(&account_index.to_be_bytes());
-        command[13..17].copy_from_slice(&pool_index.to_be_bytes());
-        command[17..49].copy_from_slice(&amount0.to_be_bytes());
-        command[49..81].copy_from_slice(&amount1.to_be_bytes());
+        command = Command::PoolSupply { nonce, account_index, pool_index, amount0, amount1 }.encode();
 
         command_sign = BabyJubjub::sign(&command, &secret_key_3);
         command_sign_formatted = [0 as u8;64];
@@ -256,13 +210,7 @@ fn multi_supplier_multi_swap_multi_supply_small_amount_retrieve_all_works() {
             140, 130,  52, 195,  95, 192,  40,  50
         ];
 
-        command = [0u8; 81];
-        command[0] = OP_SWAP;
-        command[1..9].copy_from_slice(&nonce.to_be_bytes());
-        command[9..13].copy_from_slice(&account_index.to_be_bytes());
-        command[13..17].copy_from_slice(&pool_index.to_be_bytes());
-        command[17..49].copy_from_slice(&U256::from(reverse).to_be_bytes());
-        command[49..81].copy_from_slice(&amount.to_be_bytes());
+        command = Command::Swap { nonce, account_index, pool_index, reverse, amount }.encode();
 
         command_sign = BabyJubjub::sign(&command, &secret_key_0);
         command_sign_formatted = [0 as u8;64];
@@ -275,13 +223,7 @@ fn multi_supplier_multi_swap_multi_supply_small_amount_retrieve_all_works() {
         amount = U256::from(100);
         nonce = 2u64;
 
-        command = [0u8; 81];
-        command[0] = OP_SWAP;
-        command[1..9].copy_from_slice(&nonce.to_be_bytes());
-        command[9..13].copy_from_slice(&account_index.to_be_bytes());
-        command[13..17].copy_from_slice(&pool_index.to_be_bytes());
-        command[17..49].copy_from_slice(&U256::from(reverse).to_be_bytes());
-        command[49..81].copy_from_slice(&amount.to_be_bytes());
+        command = Command::Swap { nonce, account_index, pool_index, reverse, amount }.encode();
 
         command_sign = BabyJubjub::sign(&command, &secret_key_0);
         command_sign_formatted = [0 as u8;64];
@@ -297,13 +239,7 @@ fn multi_supplier_multi_swap_multi_supply_small_amount_retrieve_all_works() {
         amount1 = U256::from(4903);
         nonce = 2u64;
 
-        command = [0u8; 81];
-        command[0] = OP_RETRIEVE;
-        command[1..9].copy_from_slice(&nonce.to_be_bytes());
-        command[9..13].copy_from_slice(&account_index.to_be_bytes());
-        command[13..17].copy_from_slice(&pool_index.to_be_bytes());
-        command[17..49].copy_from_slice(&amount0.to_be_bytes());
-        command[49..81].copy_from_slice(&amount1.to_be_bytes());
+        command = Command::PoolRetrieve { nonce, account_index, pool_index, amount0, amount1 }.encode();
 
         command_sign = BabyJubjub::sign(&command, &secret_key_2);
         command_sign_formatted = [0 as u8;64];
@@ -312,13 +248,13 @@ fn multi_supplier_multi_swap_multi_supply_small_amount_retrieve_all_works() {
         
         assert_ok!(SwapModule::pool_retrieve(Origin::signed(origin), command_sign_formatted, pool_index, amount0, amount1, nonce));
 
-        assert_eq!(PoolMap::get(pool_index).unwrap(), (0u32, 1u32, U256::from(5100), U256::from(4903), U256::from(5_000_000_000_000_000_000u128)));
+        assert_eq!(PoolMap::<Test>::get(pool_index).unwrap(), (0u32, 1u32, U256::from(5100), U256::from(4903), U256::from(5_000_000_000_000_000_000u128)));
 
-        assert_eq!(BalanceMap::get((&account_index, 0u32)), U256::from(10100));
+        assert_eq!(BalanceMap::<Test>::get((&account_index, 0u32)), U256::from(10100));
 
-        assert_eq!(BalanceMap::get((&account_index, 1u32)), U256::from(9903));
+        assert_eq!(BalanceMap::<Test>::get((&account_index, 1u32)), U256::from(9903));
 
-        assert_eq!(ShareMap::get((&account_index, &pool_index)), U256::from(0));
+        assert_eq!(ShareMap::<Test>::get((&account_index, &pool_index)), U256::from(0));
 
         assert_eq!(NonceMap::<Test>::get(2u64), 3u64);
 
@@ -329,15 +265,8 @@ fn multi_supplier_multi_swap_multi_supply_small_amount_retrieve_all_works() {
         nonce = 3u64;
 
         let mut index = 0;
-        command = [0u8; 81];
-
         while index < supply_times {
-            command[0] = OP_SUPPLY;
-            command[1..9].copy_from_slice(&nonce.to_be_bytes());
-            command[9..13].copy_from_slice(&account_index.to_be_bytes());
-            command[13..17].copy_from_slice(&pool_index.to_be_bytes());
-            command[17..49].copy_from_slice(&amount0.to_be_bytes());
-            command[49..81].copy_from_slice(&amount1.to_be_bytes());
+            command = Command::PoolSupply { nonce, account_index, pool_index, amount0, amount1 }.encode();
 
             command_sign = BabyJubjub::sign(&command, &secret_key_2);
             command_sign_formatted = [0 as u8;64];
@@ -357,13 +286,7 @@ fn multi_supplier_multi_swap_multi_supply_small_amount_retrieve_all_works() {
         amount1 = U256::from(4906);
         nonce = 2u64;
 
-        command = [0u8; 81];
-        command[0] = OP_RETRIEVE;
-        command[1..9].copy_from_slice(&nonce.to_be_bytes());
-        command[9..13].copy_from_slice(&account_index.to_be_bytes());
-        command[13..17].copy_from_slice(&pool_index.to_be_bytes());
-        command[17..49].copy_from_slice(&amount0.to_be_bytes());
-        command[49..81].copy_from_slice(&amount1.to_be_bytes());
+        command = Command::PoolRetrieve { nonce, account_index, pool_index, amount0, amount1 }.encode();
 
         command_sign = BabyJubjub::sign(&command, &secret_key_3);
         command_sign_formatted = [0 as u8;64];
@@ -372,13 +295,13 @@ fn multi_supplier_multi_swap_multi_supply_small_amount_retrieve_all_works() {
         
         assert_ok!(SwapModule::pool_retrieve(Origin::signed(origin), command_sign_formatted, pool_index, amount0, amount1, nonce));
 
-        assert_eq!(PoolMap::get(pool_index).unwrap(), (0u32, 1u32, U256::from(100), U256::from(97), U256::from(98_039_215_686_274_509u128)));
+        assert_eq!(PoolMap::<Test>::get(pool_index).unwrap(), (0u32, 1u32, U256::from(100), U256::from(97), U256::from(98_039_215_686_274_509u128)));
 
-        assert_eq!(BalanceMap::get((&account_index, 0u32)), U256::from(10100));
+        assert_eq!(BalanceMap::<Test>::get((&account_index, 0u32)), U256::from(10100));
 
-        assert_eq!(BalanceMap::get((&account_index, 1u32)), U256::from(9906));
+        assert_eq!(BalanceMap::<Test>::get((&account_index, 1u32)), U256::from(9906));
 
-        assert_eq!(ShareMap::get((&account_index, &pool_index)), U256::from(9));
+        assert_eq!(ShareMap::<Test>::get((&account_index, &pool_index)), U256::from(9));
 
         assert_eq!(NonceMap::<Test>::get(3u64), 3u64);
     })
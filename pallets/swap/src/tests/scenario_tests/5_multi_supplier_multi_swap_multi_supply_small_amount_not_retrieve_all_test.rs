@@ -56,11 +56,7 @@ fn multi_supplier_multi_swap_multi_supply_small_amount_not_retrieve_all_works()
             49, 180, 255,  97, 249,  95,   1, 203
         ];
 
-        let mut command = [0u8; 81];
-        command[0] = OP_ADDPOOL;
-        command[1..9].copy_from_slice(&nonce.to_be_bytes());
-        command[9..13].copy_from_slice(&token_index_0.to_be_bytes());
-        command[13..17].copy_from_slice(&token_index_1.to_be_bytes());
+        let mut command = Command::AddPool { nonce, token_index_0, token_index_1 }.encode();
 
         let mut command_sign = BabyJubjub::sign(&command, &secret_key_1);
         let mut command_sign_formatted :[u8; 64] = [0 as u8;64];
@@ -77,12 +73,7 @@ fn multi_supplier_multi_swap_multi_supply_small_amount_not_retrieve_all_works()
         let mut l1_tx_hash = U256::from(0);
         nonce = 2u64;
 
-        command = [0u8; 81];
-        command[0] = OP_DEPOSIT;
-        command[1..9].copy_from_slice(&nonce.to_be_bytes());
-        command[9..13].copy_from_slice(&account_index.to_be_bytes());
-        command[13..17].copy_from_slice(&token_index.to_be_bytes());
-        command[17..49].copy_from_slice(&amount.to_be_bytes());
+        command = Command::Deposit { nonce, account_index, token_index, amount }.encode();
 
         command_sign = BabyJubjub::sign(&command, &secret_key_1);
         command_sign_formatted = [0 as u8;64];
@@ -96,12 +87,7 @@ fn multi_supplier_multi_swap_multi_supply_small_amount_not_retrieve_all_works()
         l1_tx_hash = U256::from(1);
         nonce = 3u64;
 
-        command = [0u8; 81];
-        command[0] = OP_DEPOSIT;
-        command[1..9].copy_from_slice(&nonce.to_be_bytes());
-        command[9..13].copy_from_slice(&account_index.to_be_bytes());
-        command[13..17].copy_from_slice(&token_index.to_be_bytes());
-        command[17..49].copy_from_slice(&amount.to_be_bytes());
+        command = Command::Deposit { nonce, account_index, token_index, amount }.encode();
 
         command_sign = BabyJubjub::sign(&command, &secret_key_1);
         command_sign_formatted = [0 as u8;64];
@@ -117,12 +103,7 @@ fn multi_supplier_multi_swap_multi_supply_small_amount_not_retrieve_all_works()
         l1_tx_hash = U256::from(2);
         nonce = 4u64;
 
-        command = [0u8; 81];
-        command[0] = OP_DEPOSIT;
-        command[1..9].copy_from_slice(&nonce.to_be_bytes());
-        command[9..13].copy_from_slice(&account_index.to_be_bytes());
-        command[13..17].copy_from_slice(&token_index.to_be_bytes());
-        command[17..49].copy_from_slice(&amount.to_be_bytes());
+        command = Command::Deposit { nonce, account_index, token_index, amount }.encode();
 
         command_sign = BabyJubjub::sign(&command, &secret_key_1);
         command_sign_formatted = [0 as u8;64];
@@ -136,12 +117,7 @@ fn multi_supplier_multi_swap_multi_supply_small_amount_not_retrieve_all_works()
         l1_tx_hash = U256::from(3);
         nonce = 5u64;
 
-        command = [0u8; 81];
-        command[0] = OP_DEPOSIT;
-        command[1..9].copy_from_slice(&nonce.to_be_bytes());
-        command[9..13].copy_from_slice(&account_index.to_be_bytes());
-        command[13..17].copy_from_slice(&token_index.to_be_bytes());
-        command[17..49].copy_from_slice(&amount.to_be_bytes());
+        command = Command::Deposit { nonce, account_index, token_index, amount }.encode();
 
         command_sign = BabyJubjub::sign(&command, &secret_key_1);
         command_sign_formatted = [0 as u8;64];
@@ -164,13 +140,7 @@ fn multi_supplier_multi_swap_multi_supply_small_amount_not_retrieve_all_works()
             185, 222, 254,  50, 115,  63,  97, 179
         ];
 
-        command = [0u8; 81];
-        command[0] = OP_SUPPLY;
-        command[1..9].copy_from_slice(&nonce.to_be_bytes());
-        command[9..13].copy_from_slice(&account_index.to_be_bytes());
-        command[13..17].copy_from_slice(&pool_index.to_be_bytes());
-        command[17..49].copy_from_slice(&amount0.to_be_bytes());
-        command[49..81].copy_from_slice(&amount1.to_be_bytes());
+        command = Command::PoolSupply { nonce, account_index, pool_index, amount0, amount1 }.encode();
 
         command_sign = BabyJubjub::sign(&command, &secret_key_2);
         command_sign_formatted = [0 as u8;64];
@@ -192,13 +162,7 @@ fn multi_supplier_multi_swap_multi_supply_small_amount_not_retrieve_all_works()
             204, 202, 148, 243, 45, 145,   9,   7
         ];
 
-        command = [0u8; 81];
-        command[0] = OP_SUPPLY;
-        command[1..9].copy_from_slice(&nonce.to_be_bytes());
-        command[9..13].copy_from_slice(&account_index.to_be_bytes());
-        command[13..17].copy_from_slice(&pool_index.to_be_bytes());
-        command[17..49].copy_from_slice(&amount0.to_be_bytes());
-        command[49..81].copy_from_slice(&amount1.to_be_bytes());
+        command = Command::PoolSupply { nonce, account_index, pool_index, amount0, amount1 }.encode();
 
         command_sign = BabyJubjub::sign(&command, &secret_key_3);
         command_sign_formatted = [0 as u8;64];
@@ -214,13 +178,7 @@ fn multi_supplier_multi_swap_multi_supply_small_amount_not_retrieve_all_works()
         amount = U256::from(100);
         nonce = 2u64;
 
-        command = [0u8; 81];
-        command[0] = OP_SWAP;
-        command[1..9].copy_from_slice(&nonce.to_be_bytes());
-        command[9..13].copy_from_slice(&account_index.to_be_bytes());
-        command[13..17].copy_from_slice(&pool_index.to_be_bytes());
-        command[17..49].copy_from_slice(&U256::from(reverse).to_be_bytes());
-        command[49..81].copy_from_slice(&amount.to_be_bytes());
+        command = Command::Swap { nonce, account_index, pool_index, reverse, amount }.encode();
 
         command_sign = BabyJubjub::sign(&command, &secret_key_2);
         command_sign_formatted = [0 as u8;64];
@@ -234,13 +192,7 @@ fn multi_supplier_multi_swap_multi_supply_small_amount_not_retrieve_all_works()
         amount = U256::from(100);
         nonce = 3u64;
 
-        command = [0u8; 81];
-        command[0] = OP_SWAP;
-        command[1..9].copy_from_slice(&nonce.to_be_bytes());
-        command[9..13].copy_from_slice(&account_index.to_be_bytes());
-        command[13..17].copy_from_slice(&pool_index.to_be_bytes());
-        command[17..49].copy_from_slice(&U256::from(reverse).to_be_bytes());
-        command[49..81].copy_from_slice(&amount.to_be_bytes());
+        command = Command::Swap { nonce, account_index, pool_index, reverse, amount }.encode();
 
         command_sign = BabyJubjub::sign(&command, &secret_key_2);
         command_sign_formatted = [0 as u8;64];
@@ -249,26 +201,20 @@ fn multi_supplier_multi_swap_multi_supply_small_amount_not_retrieve_all_works()
 
         assert_ok!(SwapModule::swap(Origin::signed(origin), command_sign_formatted, pool_index, reverse, amount, nonce));
 
-        assert_eq!(PoolMap::get(pool_index).unwrap(), (0u32, 1u32, U256::from(1507), U256::from(1495), U256::from(1_500_000_000_000_000_000u128)));
+        assert_eq!(PoolMap::<Test>::get(pool_index).unwrap(), (0u32, 1u32, U256::from(1507), U256::from(1495), U256::from(1_500_000_000_000_000_000u128)));
 
-        assert_eq!(BalanceMap::get((&account_index, 0u32)), U256::from(993));
+        assert_eq!(BalanceMap::<Test>::get((&account_index, 0u32)), U256::from(993));
 
-        assert_eq!(BalanceMap::get((&account_index, 1u32)), U256::from(1005));
+        assert_eq!(BalanceMap::<Test>::get((&account_index, 1u32)), U256::from(1005));
 
-        assert_eq!(ShareMap::get((&account_index, &pool_index)), U256::from(1_000_000_000_000_000_000u128));
+        assert_eq!(ShareMap::<Test>::get((&account_index, &pool_index)), U256::from(1_000_000_000_000_000_000u128));
 
         //PoolRetrieve amount0 1004 and amount1 996 for poolIndex 0, caller is accountIndex 2
         amount0 = U256::from(1004u32);
         amount1 = U256::from(996u32);
         nonce = 4u64;
 
-        command = [0u8; 81];
-        command[0] = OP_RETRIEVE;
-        command[1..9].copy_from_slice(&nonce.to_be_bytes());
-        command[9..13].copy_from_slice(&account_index.to_be_bytes());
-        command[13..17].copy_from_slice(&pool_index.to_be_bytes());
-        command[17..49].copy_from_slice(&amount0.to_be_bytes());
-        command[49..81].copy_from_slice(&amount1.to_be_bytes());
+        command = Command::PoolRetrieve { nonce, account_index, pool_index, amount0, amount1 }.encode();
 
         command_sign = BabyJubjub::sign(&command, &secret_key_2);
         command_sign_formatted = [0 as u8;64];
@@ -277,13 +223,13 @@ fn multi_supplier_multi_swap_multi_supply_small_amount_not_retrieve_all_works()
 
         assert_ok!(SwapModule::pool_retrieve(Origin::signed(origin), command_sign_formatted, pool_index, amount0, amount1, nonce));
 
-        assert_eq!(PoolMap::get(pool_index).unwrap(), (0u32, 1u32, U256::from(503), U256::from(499), U256::from(500_663_570_006_635_700u128)));
+        assert_eq!(PoolMap::<Test>::get(pool_index).unwrap(), (0u32, 1u32, U256::from(503), U256::from(499), U256::from(500_663_570_006_635_700u128)));
 
-        assert_eq!(BalanceMap::get((&account_index, 0u32)), U256::from(1997));
+        assert_eq!(BalanceMap::<Test>::get((&account_index, 0u32)), U256::from(1997));
 
-        assert_eq!(BalanceMap::get((&account_index, 1u32)), U256::from(2001));
+        assert_eq!(BalanceMap::<Test>::get((&account_index, 1u32)), U256::from(2001));
 
-        assert_eq!(ShareMap::get((&account_index, &pool_index)), U256::from(663_570_006_635_700u128));
+        assert_eq!(ShareMap::<Test>::get((&account_index, &pool_index)), U256::from(663_570_006_635_700u128));
 
         assert_eq!(NonceMap::<Test>::get(2u64), 5u64);
 
@@ -293,13 +239,7 @@ fn multi_supplier_multi_swap_multi_supply_small_amount_not_retrieve_all_works()
         amount1 = U256::from(0);
         nonce = 5u64;
 
-        command = [0u8; 81];
-        command[0] = OP_RETRIEVE;
-        command[1..9].copy_from_slice(&nonce.to_be_bytes());
-        command[9..13].copy_from_slice(&account_index.to_be_bytes());
-        command[13..17].copy_from_slice(&pool_index.to_be_bytes());
-        command[17..49].copy_from_slice(&amount0.to_be_bytes());
-        command[49..81].copy_from_slice(&amount1.to_be_bytes());
+        command = Command::PoolRetrieve { nonce, account_index, pool_index, amount0, amount1 }.encode();
 
         command_sign = BabyJubjub::sign(&command, &secret_key_2);
         command_sign_formatted = [0 as u8;64];
@@ -315,15 +255,8 @@ fn multi_supplier_multi_swap_multi_supply_small_amount_not_retrieve_all_works()
         nonce = 5u64;
 
         let mut index = 0;
-        command = [0u8; 81];
-
         while index < supply_times {
-            command[0] = OP_SUPPLY;
-            command[1..9].copy_from_slice(&nonce.to_be_bytes());
-            command[9..13].copy_from_slice(&account_index.to_be_bytes());
-            command[13..17].copy_from_slice(&pool_index.to_be_bytes());
-            command[17..49].copy_from_slice(&amount0.to_be_bytes());
-            command[49..81].copy_from_slice(&amount1.to_be_bytes());
+            command = Command::PoolSupply { nonce, account_index, pool_index, amount0, amount1 }.encode();
 
             command_sign = BabyJubjub::sign(&command, &secret_key_2);
             command_sign_formatted = [0 as u8;64];
@@ -342,13 +275,7 @@ fn multi_supplier_multi_swap_multi_supply_small_amount_not_retrieve_all_works()
         amount0 = U256::from(10);
         amount1 = U256::from(9);
 
-        command = [0u8; 81];
-        command[0] = OP_RETRIEVE;
-        command[1..9].copy_from_slice(&nonce.to_be_bytes());
-        command[9..13].copy_from_slice(&account_index.to_be_bytes());
-        command[13..17].copy_from_slice(&pool_index.to_be_bytes());
-        command[17..49].copy_from_slice(&amount0.to_be_bytes());
-        command[49..81].copy_from_slice(&amount1.to_be_bytes());
+        command = Command::PoolRetrieve { nonce, account_index, pool_index, amount0, amount1 }.encode();
 
         command_sign = BabyJubjub::sign(&command, &secret_key_2);
         command_sign_formatted = [0 as u8;64];
@@ -357,13 +284,13 @@ fn multi_supplier_multi_swap_multi_supply_small_amount_not_retrieve_all_works()
 
         assert_ok!(SwapModule::pool_retrieve(Origin::signed(origin), command_sign_formatted, pool_index, amount0, amount1, nonce));
         
-        assert_eq!(PoolMap::get(pool_index).unwrap(), (0u32, 1u32, U256::from(503), U256::from(500), U256::from(500_663_570_006_635_699u128)));
+        assert_eq!(PoolMap::<Test>::get(pool_index).unwrap(), (0u32, 1u32, U256::from(503), U256::from(500), U256::from(500_663_570_006_635_699u128)));
 
-        assert_eq!(BalanceMap::get((&account_index, 0u32)), U256::from(1997));
+        assert_eq!(BalanceMap::<Test>::get((&account_index, 0u32)), U256::from(1997));
 
-        assert_eq!(BalanceMap::get((&account_index, 1u32)), U256::from(2000));
+        assert_eq!(BalanceMap::<Test>::get((&account_index, 1u32)), U256::from(2000));
 
-        assert_eq!(ShareMap::get((&account_index, &pool_index)), U256::from(663_570_006_635_699u128));
+        assert_eq!(ShareMap::<Test>::get((&account_index, &pool_index)), U256::from(663_570_006_635_699u128));
 
         assert_eq!(NonceMap::<Test>::get(2u64), 16u64);
     })
@@ -46,11 +46,7 @@ fn single_supplier_multi_swap_retrieve_all_works() {
             49, 180, 255,  97, 249,  95,   1, 203
         ];
 
-        let mut command = [0u8; 81];
-        command[0] = OP_ADDPOOL;
-        command[1..9].copy_from_slice(&nonce.to_be_bytes());
-        command[9..13].copy_from_slice(&token_index_0.to_be_bytes());
-        command[13..17].copy_from_slice(&token_index_1.to_be_bytes());
+        let command = Command::AddPool { nonce, token_index_0, token_index_1 }.encode();
 
         let mut command_sign = BabyJubjub::sign(&command, &secret_key_1);
         let mut command_sign_formatted :[u8; 64] = [0 as u8;64];
@@ -67,12 +63,7 @@ fn single_supplier_multi_swap_retrieve_all_works() {
         let mut l1_tx_hash = U256::from(0);
         let mut nonce = 2u64;
 
-        let mut command = [0u8; 81];
-        command[0] = OP_DEPOSIT;
-        command[1..9].copy_from_slice(&nonce.to_be_bytes());
-        command[9..13].copy_from_slice(&account_index.to_be_bytes());
-        command[13..17].copy_from_slice(&token_index.to_be_bytes());
-        command[17..49].copy_from_slice(&amount.to_be_bytes());
+        let mut command = Command::Deposit { nonce, account_index, token_index, amount }.encode();
 
         command_sign = BabyJubjub::sign(&command, &secret_key_1);
         command_sign_formatted = [0 as u8;64];
@@ -86,12 +77,7 @@ fn single_supplier_multi_swap_retrieve_all_works() {
         l1_tx_hash = U256::from(1);
         nonce = 3u64;
 
-        command = [0u8; 81];
-        command[0] = OP_DEPOSIT;
-        command[1..9].copy_from_slice(&nonce.to_be_bytes());
-        command[9..13].copy_from_slice(&account_index.to_be_bytes());
-        command[13..17].copy_from_slice(&token_index.to_be_bytes());
-        command[17..49].copy_from_slice(&amount.to_be_bytes());
+        command = Command::Deposit { nonce, account_index, token_index, amount }.encode();
 
         command_sign = BabyJubjub::sign(&command, &secret_key_1);
         command_sign_formatted = [0 as u8;64];
@@ -114,13 +100,7 @@ fn single_supplier_multi_swap_retrieve_all_works() {
             185, 222, 254,  50, 115,  63,  97, 179
         ];
 
-        command = [0u8; 81];
-        command[0] = OP_SUPPLY;
-        command[1..9].copy_from_slice(&nonce.to_be_bytes());
-        command[9..13].copy_from_slice(&account_index.to_be_bytes());
-        command[13..17].copy_from_slice(&pool_index.to_be_bytes());
-        command[17..49].copy_from_slice(&amount0.to_be_bytes());
-        command[49..81].copy_from_slice(&amount1.to_be_bytes());
+        command = Command::PoolSupply { nonce, account_index, pool_index, amount0, amount1 }.encode();
 
         command_sign = BabyJubjub::sign(&command, &secret_key_2);
         command_sign_formatted = [0 as u8;64];
@@ -135,13 +115,7 @@ fn single_supplier_multi_swap_retrieve_all_works() {
         amount = U256::from(100);
         nonce = 2u64;
 
-        command = [0u8; 81];
-        command[0] = OP_SWAP;
-        command[1..9].copy_from_slice(&nonce.to_be_bytes());
-        command[9..13].copy_from_slice(&account_index.to_be_bytes());
-        command[13..17].copy_from_slice(&pool_index.to_be_bytes());
-        command[17..49].copy_from_slice(&U256::from(reverse).to_be_bytes());
-        command[49..81].copy_from_slice(&amount.to_be_bytes());
+        command = Command::Swap { nonce, account_index, pool_index, reverse, amount }.encode();
 
         command_sign = BabyJubjub::sign(&command, &secret_key_2);
         command_sign_formatted = [0 as u8;64];
@@ -155,13 +129,7 @@ fn single_supplier_multi_swap_retrieve_all_works() {
         amount = U256::from(100);
         nonce = 3u64;
 
-        command = [0u8; 81];
-        command[0] = OP_SWAP;
-        command[1..9].copy_from_slice(&nonce.to_be_bytes());
-        command[9..13].copy_from_slice(&account_index.to_be_bytes());
-        command[13..17].copy_from_slice(&pool_index.to_be_bytes());
-        command[17..49].copy_from_slice(&U256::from(reverse).to_be_bytes());
-        command[49..81].copy_from_slice(&amount.to_be_bytes());
+        command = Command::Swap { nonce, account_index, pool_index, reverse, amount }.encode();
 
         command_sign = BabyJubjub::sign(&command, &secret_key_2);
         command_sign_formatted = [0 as u8;64];
@@ -175,13 +143,7 @@ fn single_supplier_multi_swap_retrieve_all_works() {
         amount1 = U256::from(509);
         nonce = 4u64;
 
-        command = [0u8; 81];
-        command[0] = OP_RETRIEVE;
-        command[1..9].copy_from_slice(&nonce.to_be_bytes());
-        command[9..13].copy_from_slice(&account_index.to_be_bytes());
-        command[13..17].copy_from_slice(&pool_index.to_be_bytes());
-        command[17..49].copy_from_slice(&amount0.to_be_bytes());
-        command[49..81].copy_from_slice(&amount1.to_be_bytes());
+        command = Command::PoolRetrieve { nonce, account_index, pool_index, amount0, amount1 }.encode();
 
         command_sign = BabyJubjub::sign(&command, &secret_key_2);
         command_sign_formatted = [0 as u8;64];
@@ -190,13 +152,13 @@ fn single_supplier_multi_swap_retrieve_all_works() {
         
         assert_ok!(SwapModule::pool_retrieve(Origin::signed(origin), command_sign_formatted, pool_index, amount0, amount1, nonce));
         
-        assert_eq!(PoolMap::get(pool_index).unwrap(), (0u32, 1u32, U256::from(492), U256::from(501), U256::from(495_967_741_935_483_870u128)));
+        assert_eq!(PoolMap::<Test>::get(pool_index).unwrap(), (0u32, 1u32, U256::from(492), U256::from(501), U256::from(495_967_741_935_483_870u128)));
 
-        assert_eq!(BalanceMap::get((&account_index, 0u32)), U256::from(1508));
+        assert_eq!(BalanceMap::<Test>::get((&account_index, 0u32)), U256::from(1508));
 
-        assert_eq!(BalanceMap::get((&account_index, 1u32)), U256::from(1499));
+        assert_eq!(BalanceMap::<Test>::get((&account_index, 1u32)), U256::from(1499));
 
-        assert_eq!(ShareMap::get((&account_index, &pool_index)), U256::from(495_967_741_935_483_870u128));
+        assert_eq!(ShareMap::<Test>::get((&account_index, &pool_index)), U256::from(495_967_741_935_483_870u128));
 
         assert_eq!(NonceMap::<Test>::get(2u64), 5u64);
 
@@ -205,13 +167,7 @@ fn single_supplier_multi_swap_retrieve_all_works() {
         amount1 = U256::from(501);
         nonce = 5u64;
 
-        command = [0u8; 81];
-        command[0] = OP_RETRIEVE;
-        command[1..9].copy_from_slice(&nonce.to_be_bytes());
-        command[9..13].copy_from_slice(&account_index.to_be_bytes());
-        command[13..17].copy_from_slice(&pool_index.to_be_bytes());
-        command[17..49].copy_from_slice(&amount0.to_be_bytes());
-        command[49..81].copy_from_slice(&amount1.to_be_bytes());
+        command = Command::PoolRetrieve { nonce, account_index, pool_index, amount0, amount1 }.encode();
 
         command_sign = BabyJubjub::sign(&command, &secret_key_2);
         command_sign_formatted = [0 as u8;64];
@@ -220,13 +176,13 @@ fn single_supplier_multi_swap_retrieve_all_works() {
 
         assert_ok!(SwapModule::pool_retrieve(Origin::signed(origin), command_sign_formatted, pool_index, amount0, amount1, nonce));
 
-        assert_eq!(PoolMap::get(pool_index).unwrap(), (0u32, 1u32, U256::from(0), U256::from(0), U256::from(0)));
+        assert_eq!(PoolMap::<Test>::get(pool_index).unwrap(), (0u32, 1u32, U256::from(0), U256::from(0), U256::from(0)));
 
-        assert_eq!(BalanceMap::get((&account_index, 0u32)), U256::from(2000));
+        assert_eq!(BalanceMap::<Test>::get((&account_index, 0u32)), U256::from(2000));
 
-        assert_eq!(BalanceMap::get((&account_index, 1u32)), U256::from(2000));
+        assert_eq!(BalanceMap::<Test>::get((&account_index, 1u32)), U256::from(2000));
 
-        assert_eq!(ShareMap::get((&account_index, &pool_index)), U256::from(0));
+        assert_eq!(ShareMap::<Test>::get((&account_index, &pool_index)), U256::from(0));
 
         assert_eq!(NonceMap::<Test>::get(2u64), 6u64);
     })
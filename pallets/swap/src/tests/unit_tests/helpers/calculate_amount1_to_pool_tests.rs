@@ -8,7 +8,7 @@ fn prepare_unit_test() {
     let amount0 = U256::from(2000);
     let amount1 = U256::from(3000);
     let total_share = U256::from(10000);
-    PoolMap::insert(
+    PoolMap::<Test>::insert(
         pool_index,
         (
             &token_index0.clone(),
@@ -47,7 +47,7 @@ fn calculate_amount1_to_pool_works_is_supply_rem_is_not_zero() {
         let mut amount0 = U256::from(2000);
         let amount1 = U256::from(3041);
         let total_share = U256::from(10000);
-        PoolMap::insert(
+        PoolMap::<Test>::insert(
             pool_index,
             (
                 &token_index0.clone(),
@@ -90,7 +90,7 @@ fn calculate_amount1_to_pool_pool_not_exists() {
     new_test_ext().execute_with(|| {
         prepare_unit_test();
 
-        //PoolMap::get(&1u32) is not exist
+        //PoolMap::<Test>::get(&1u32) is not exist
         let pool_index = 1u32;
         let amount0 = U256::from(1000);
         let is_supply = true;
@@ -115,7 +115,7 @@ fn calculate_amount1_to_pool_internal_calc_overflow() {
         let amount0 = U256::from(3000);
         let amount1 = (U256::from(1) << 250) - 1;
         let total_share = U256::from(10000);
-        PoolMap::insert(
+        PoolMap::<Test>::insert(
             pool_index,
             (
                 &token_index0.clone(),
@@ -0,0 +1,63 @@
+use super::*;
+
+#[test]
+fn command_encode_layout() {
+    let command = Command::Swap {
+        nonce: 2u64,
+        account_index: 3u32,
+        pool_index: 4u32,
+        reverse: 1u8,
+        amount: U256::from(100),
+    }.encode();
+
+    assert_eq!(command[0], OP_SWAP);
+    assert_eq!(command[1..9], 2u64.to_be_bytes());
+    assert_eq!(command[9..13], 3u32.to_be_bytes());
+    assert_eq!(command[13..17], 4u32.to_be_bytes());
+    assert_eq!(command[17..49], U256::from(1).to_be_bytes());
+    assert_eq!(command[49..81], U256::from(100).to_be_bytes());
+
+    let command = Command::TransferNFT { nonce: 1u64, account_index: 0u32, nft_id: 5u32, recipient: 3u32 }.encode();
+    assert_eq!(command[17..45], [0u8; 28]);
+    assert_eq!(command[45..49], 3u32.to_be_bytes());
+}
+
+#[test]
+fn command_round_trip() {
+    let commands = vec![
+        Command::Deposit { nonce: 1, account_index: 0, token_index: 1, amount: U256::from(10) },
+        Command::Withdraw { nonce: 1, account_index: 0, token_index: 1, amount: U256::from(10), l1account: U256::from(7) },
+        Command::Swap { nonce: 1, account_index: 0, pool_index: 1, reverse: 0, amount: U256::from(10) },
+        Command::PoolRetrieve { nonce: 1, account_index: 0, pool_index: 1, amount0: U256::from(10), amount1: U256::from(20) },
+        Command::PoolSupply { nonce: 1, account_index: 0, pool_index: 1, amount0: U256::from(10), amount1: U256::from(20) },
+        Command::AddPool { nonce: 1, token_index_0: 0, token_index_1: 1 },
+        Command::DepositNFT { nonce: 1, account_index: 0, nft_id: 1, l1_tx_hash: U256::from(100) },
+        Command::WithdrawNFT { nonce: 1, account_index: 0, nft_id: 1, l1account: U256::from(7) },
+        Command::TransferNFT { nonce: 1, account_index: 0, nft_id: 1, recipient: 2 },
+        Command::BidNFT { nonce: 1, account_index: 0, nft_id: 1, amount: U256::from(10) },
+        Command::FinalizeNFT { nonce: 1, account_index: 0, nft_id: 1 },
+    ];
+
+    for command in commands {
+        assert_eq!(Command::decode(&command.encode()), Ok(command));
+    }
+}
+
+#[test]
+fn command_decode_rejects_invalid_bytes() {
+    assert_eq!(Command::decode(&[0u8; 80]), Err(CommandError::InvalidLength));
+
+    let mut command = [0u8; COMMAND_SIZE];
+    command[0] = OP_SETKEY;
+    assert_eq!(Command::decode(&command), Err(CommandError::InvalidOp));
+
+    //Reserved bytes of Deposit must be zero
+    let mut command = Command::Deposit { nonce: 1, account_index: 0, token_index: 1, amount: U256::from(10) }.encode();
+    command[80] = 1u8;
+    assert_eq!(Command::decode(&command), Err(CommandError::InvalidField));
+
+    //Reverse must fit in a byte
+    let mut command = Command::Swap { nonce: 1, account_index: 0, pool_index: 1, reverse: 0, amount: U256::from(10) }.encode();
+    command[47] = 1u8;
+    assert_eq!(Command::decode(&command), Err(CommandError::InvalidField));
+}
@@ -0,0 +1,55 @@
+use super::*;
+
+fn prepare_unit_test() {
+    //SetKey for accountIndex 0
+    let mut origin = 0u64;
+    let secret_key_0 = [2u8; 32];
+    let pub_key_0 = BabyJubjub::pubkey_from_secretkey(&secret_key_0).encode();
+    assert_ok!(SwapModule::set_key(Origin::signed(origin), pub_key_0));
+
+    //SetKey for accountIndex 1
+    origin = 1u64;
+    let secret_key_1 = [3u8; 32];
+    let pub_key_1 = BabyJubjub::pubkey_from_secretkey(&secret_key_1).encode();
+    assert_ok!(SwapModule::set_key(Origin::signed(origin), pub_key_1));
+
+    //Deposit 10 into accountIndex 0 as reqId 3, caller is accountIndex 1
+    let account_index = 0u32;
+    let token_index = 1u32;
+    let amount = U256::from(10);
+    let l1_tx_hash = U256::from(100);
+    let nonce = 1u64;
+
+    let command = Command::Deposit { nonce, account_index, token_index, amount }.encode();
+
+    let command_sign = BabyJubjub::sign(&command, &secret_key_1);
+    let mut command_sign_formatted :[u8; 64] = [0 as u8;64];
+    command_sign_formatted[..32].copy_from_slice(&command_sign.r.encode());
+    command_sign_formatted[32..].copy_from_slice(&command_sign.s.encode());
+
+    assert_ok!(SwapModule::deposit(Origin::signed(origin), command_sign_formatted, account_index, token_index, amount, l1_tx_hash, nonce));
+}
+
+#[test]
+fn deposit_status_works() {
+    new_test_ext().execute_with(|| {
+        prepare_unit_test();
+
+        let l1_tx_hash = U256::from(100);
+        assert_eq!(L1TxReqMap::<Test>::get(&l1_tx_hash), Some(U256::from(3)));
+        assert_eq!(SwapModule::deposit_status(l1_tx_hash), DepositStatus::Pending(U256::from(3)));
+
+        //Deposit is acked
+        L1TxMap::<Test>::insert(&l1_tx_hash, L1TxStatus::Done);
+        assert_eq!(SwapModule::deposit_status(l1_tx_hash), DepositStatus::Done(U256::from(3)));
+    })
+}
+
+#[test]
+fn deposit_status_unknown() {
+    new_test_ext().execute_with(|| {
+        prepare_unit_test();
+
+        assert_eq!(SwapModule::deposit_status(U256::from(101)), DepositStatus::Unknown);
+    })
+}
@@ -0,0 +1,49 @@
+use super::*;
+use frame_support::traits::GenesisBuild;
+
+fn genesis_ext(pools: Vec<(TokenIndex, TokenIndex)>, balances: Vec<(u64, [u8; 32], Vec<(TokenIndex, Amount)>)>) -> sp_io::TestExternalities {
+    let mut storage = frame_system::GenesisConfig::default().build_storage::<Test>().unwrap();
+    let config = crate::GenesisConfig::<Test> {
+        admins: vec![1u64, 2u64],
+        tokens: (0u32..4u32).map(|i| (i, U256::from(i + 1), 18u8, b"TKN".to_vec())).collect(),
+        pools,
+        balances,
+    };
+    GenesisBuild::<Test>::assimilate_storage(&config, &mut storage).unwrap();
+    storage.into()
+}
+
+#[test]
+fn genesis_sets_pools_and_balances() {
+    let key = BabyJubjub::pubkey_from_secretkey(&[2u8; 32]).encode();
+    genesis_ext(vec![(0, 1), (3, 2)], vec![(5u64, key, vec![(0, U256::from(100)), (2, U256::from(7))])]).execute_with(|| {
+        assert_eq!(PoolMap::<Test>::get(0), Some((0, 1, U256::zero(), U256::zero(), U256::zero())));
+        assert_eq!(PoolMap::<Test>::get(1), Some((2, 3, U256::zero(), U256::zero(), U256::zero())));
+        assert_eq!(get_account_index::<Test>(&5u64).unwrap(), 0u32);
+        assert!(KeyMap::<Test>::get(0).is_some());
+        assert_eq!(BalanceMap::<Test>::get((0, 0)), U256::from(100));
+        assert_eq!(BalanceMap::<Test>::get((0, 2)), U256::from(7));
+
+        // The key set at genesis blocks another set_key for the account
+        assert_noop!(SwapModule::set_key(Origin::signed(5u64), key), Error::<Test>::AccountExists);
+    })
+}
+
+#[test]
+#[should_panic(expected = "Unknown swap pool token")]
+fn genesis_rejects_unknown_pool_token() {
+    genesis_ext(vec![(0, 4)], vec![]);
+}
+
+#[test]
+#[should_panic(expected = "Invalid swap pool token pair")]
+fn genesis_rejects_same_token_pool() {
+    genesis_ext(vec![(1, 1)], vec![]);
+}
+
+#[test]
+#[should_panic(expected = "Unknown swap balance token")]
+fn genesis_rejects_unknown_balance_token() {
+    let key = BabyJubjub::pubkey_from_secretkey(&[2u8; 32]).encode();
+    genesis_ext(vec![], vec![(5u64, key, vec![(4, U256::from(1))])]);
+}
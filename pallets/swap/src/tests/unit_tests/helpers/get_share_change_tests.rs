@@ -8,7 +8,7 @@ fn prepare_unit_test() {
     let amount0 = U256::from(0);
     let amount1 = U256::from(0);
     let total_share = U256::from(0);
-    PoolMap::insert(
+    PoolMap::<Test>::insert(
         pool_index,
         (
             &token_index0.clone(),
@@ -46,7 +46,7 @@ fn get_share_change_works_is_supply_old_amount0_is_not_zero() {
         let amount0 = U256::from(1000);
         let amount1 = U256::from(1000);
         let total_share = U256::from(1000);
-        PoolMap::insert(
+        PoolMap::<Test>::insert(
             pool_index,
             (
                 &token_index0.clone(),
@@ -77,7 +77,7 @@ fn get_share_change_works_is_not_supply_rem_is_zero() {
         let amount0 = U256::from(1000);
         let amount1 = U256::from(1000);
         let total_share = U256::from(1000);
-        PoolMap::insert(
+        PoolMap::<Test>::insert(
             pool_index,
             (
                 &token_index0.clone(),
@@ -108,7 +108,7 @@ fn get_share_change_works_is_not_supply_rem_is_not_zero() {
         let amount0 = U256::from(333);
         let amount1 = U256::from(333);
         let total_share = U256::from(1000);
-        PoolMap::insert(
+        PoolMap::<Test>::insert(
             pool_index,
             (
                 &token_index0.clone(),
@@ -179,7 +179,7 @@ fn get_share_change_overflow_during_calculation() {
         let amount0 = U256::from(500);
         let amount1 = U256::from(500);
         let total_share = (U256::from(1) << 250) - 1;
-        PoolMap::insert(
+        PoolMap::<Test>::insert(
             pool_index,
             (
                 &token_index0.clone(),
@@ -0,0 +1,230 @@
+use super::*;
+use frame_support::sp_runtime::offchain::{storage::StorageValueRef, storage_lock::{StorageLock, Time}, StorageKind};
+use sp_core::hexdisplay::HexDisplay;
+use sp_core::offchain::{testing::{OffchainState, PendingRequest, TestOffchainExt}, OffchainExt};
+
+const URL: &str = "http://localhost:8545";
+const BRIDGE: [u8; 20] = [0x11u8; 20];
+
+fn hex(bytes: &[u8]) -> String {
+    format!("0x{}", HexDisplay::from(&bytes))
+}
+
+fn topic(event: &str) -> String {
+    hex(&sp_io::hashing::keccak_256(event.as_bytes()))
+}
+
+fn word(value: u64) -> String {
+    format!("{:064x}", value)
+}
+
+fn deposit_log(block: u64, l1_tx_hash: u64, account_index: u64, token_index: u64, amount: u64) -> String {
+    format!(
+        r#"{{"address": "{}", "blockNumber": "{:#x}", "transactionHash": "{:#x}", "topics": ["{}"], "data": "0x{}{}{}", "removed": false}}"#,
+        hex(&BRIDGE), block, l1_tx_hash, topic("Deposit(uint32,uint32,uint256)"), word(account_index), word(token_index), word(amount)
+    )
+}
+
+fn deposit_nft_log(block: u64, l1_tx_hash: u64, account_index: u64, nft_id: u64) -> String {
+    format!(
+        r#"{{"address": "{}", "blockNumber": "{:#x}", "transactionHash": "{:#x}", "topics": ["{}"], "data": "0x{}{}", "removed": false}}"#,
+        hex(&BRIDGE), block, l1_tx_hash, topic("DepositNFT(uint32,uint32)"), word(account_index), word(nft_id)
+    )
+}
+
+fn expect_rpc(state: &mut OffchainState, method: &str, params: &str, result: &str) {
+    state.expect_request(PendingRequest {
+        method: "POST".into(),
+        uri: URL.into(),
+        headers: vec![("Content-Type".into(), "application/json".into())],
+        body: format!(r#"{{"jsonrpc":"2.0","id":1,"method":"{}","params":{}}}"#, method, params).into_bytes(),
+        response: Some(format!(r#"{{"jsonrpc": "2.0", "id": 1, "result": {}}}"#, result).into_bytes()),
+        sent: true,
+        ..Default::default()
+    });
+}
+
+fn expect_logs(state: &mut OffchainState, from: u64, to: u64, logs: &[String]) {
+    let params = format!(
+        r#"[{{"fromBlock":"{:#x}","toBlock":"{:#x}","address":"{}","topics":[["{}","{}"]]}}]"#,
+        from, to, hex(&BRIDGE), topic("Deposit(uint32,uint32,uint256)"), topic("DepositNFT(uint32,uint32)")
+    );
+    expect_rpc(state, "eth_getLogs", &params, &format!("[{}]", logs.join(",")));
+}
+
+fn expect_block_hash(state: &mut OffchainState, block: u64, hash: u64) {
+    expect_rpc(state, "eth_getBlockByNumber", &format!(r#"["{:#x}",false]"#, block), &format!(r#"{{"number": "{:#x}", "hash": "{:#x}"}}"#, block, hash));
+}
+
+fn configure() {
+    sp_io::offchain::local_storage_set(StorageKind::PERSISTENT, L1_RPC_URL_STORAGE, URL.as_bytes());
+    sp_io::offchain::local_storage_set(StorageKind::PERSISTENT, L1_BRIDGE_STORAGE, &BRIDGE);
+}
+
+fn cursor() -> Option<(u64, U256)> {
+    StorageValueRef::persistent(L1_CURSOR_STORAGE).get::<(u64, U256)>().flatten()
+}
+
+fn token_deposit(l1_tx_hash: u64) -> L1Deposit {
+    L1Deposit::Token { l1_tx_hash: U256::from(l1_tx_hash), account_index: 2u32, token_index: 1u32, amount: U256::from(10) }
+}
+
+fn nft_deposit(l1_tx_hash: u64) -> L1Deposit {
+    L1Deposit::NFT { l1_tx_hash: U256::from(l1_tx_hash), account_index: 2u32, nft_id: 4u32 }
+}
+
+#[test]
+fn http_l1_source_idle_until_configured() {
+    let (offchain, _state) = TestOffchainExt::new();
+    let mut ext = new_test_ext();
+    ext.register_extension(OffchainExt::new(offchain));
+
+    ext.execute_with(|| {
+        assert_eq!(HttpL1Source::deposits(), Ok(vec![]));
+
+        //The bridge address is required too
+        sp_io::offchain::local_storage_set(StorageKind::PERSISTENT, L1_RPC_URL_STORAGE, URL.as_bytes());
+        assert_eq!(HttpL1Source::deposits(), Ok(vec![]));
+    });
+}
+
+#[test]
+fn http_l1_source_scans_confirmed_blocks() {
+    let (offchain, state) = TestOffchainExt::new();
+    let mut ext = new_test_ext();
+    ext.register_extension(OffchainExt::new(offchain));
+
+    {
+        let mut state = state.write();
+        //Head 100, blocks 80 to 88 are confirmed
+        expect_rpc(&mut state, "eth_blockNumber", "[]", r#""0x64""#);
+        expect_logs(&mut state, 80, 88, &[deposit_log(82, 5, 2, 1, 10), deposit_nft_log(85, 6, 2, 4)]);
+        expect_block_hash(&mut state, 88, 0xaa);
+        //Head 101, block 89 is confirmed
+        expect_rpc(&mut state, "eth_blockNumber", "[]", r#""0x65""#);
+        expect_block_hash(&mut state, 88, 0xaa);
+        expect_logs(&mut state, 89, 89, &[deposit_log(89, 5, 2, 1, 10)]);
+        expect_block_hash(&mut state, 89, 0xbb);
+    }
+
+    ext.execute_with(|| {
+        configure();
+        StorageValueRef::persistent(L1_START_BLOCK_STORAGE).set(&80u64);
+
+        assert_eq!(HttpL1Source::deposits(), Ok(vec![token_deposit(5), nft_deposit(6)]));
+        assert_eq!(cursor(), Some((88u64, U256::from(0xaa))));
+
+        //Known deposits are not read twice
+        assert_eq!(HttpL1Source::deposits(), Ok(vec![token_deposit(5), nft_deposit(6)]));
+        assert_eq!(cursor(), Some((89u64, U256::from(0xbb))));
+
+        HttpL1Source::forget(&U256::from(5));
+        assert_eq!(
+            StorageValueRef::persistent(L1_PENDING_STORAGE).get::<Vec<(u64, L1Deposit)>>(),
+            Some(Some(vec![(85u64, nft_deposit(6))]))
+        );
+    });
+}
+
+#[test]
+fn http_l1_source_waits_for_confirmations() {
+    let (offchain, state) = TestOffchainExt::new();
+    let mut ext = new_test_ext();
+    ext.register_extension(OffchainExt::new(offchain));
+
+    {
+        let mut state = state.write();
+        //Head 10, no block is 12 blocks deep
+        expect_rpc(&mut state, "eth_blockNumber", "[]", r#""0xa""#);
+        //Head 10 with a depth of 2
+        expect_rpc(&mut state, "eth_blockNumber", "[]", r#""0xa""#);
+        expect_logs(&mut state, 8, 8, &[]);
+        expect_block_hash(&mut state, 8, 0xaa);
+    }
+
+    ext.execute_with(|| {
+        configure();
+        assert_eq!(HttpL1Source::deposits(), Ok(vec![]));
+        assert_eq!(cursor(), None);
+
+        StorageValueRef::persistent(L1_CONFIRMATIONS_STORAGE).set(&2u64);
+        assert_eq!(HttpL1Source::deposits(), Ok(vec![]));
+        assert_eq!(cursor(), Some((8u64, U256::from(0xaa))));
+    });
+}
+
+#[test]
+fn http_l1_source_rewinds_on_reorg() {
+    let (offchain, state) = TestOffchainExt::new();
+    let mut ext = new_test_ext();
+    ext.register_extension(OffchainExt::new(offchain));
+
+    {
+        let mut state = state.write();
+        //Block 88 was replaced, the scan moves back to block 76
+        expect_rpc(&mut state, "eth_blockNumber", "[]", r#""0x65""#);
+        expect_block_hash(&mut state, 88, 0xcc);
+        expect_block_hash(&mut state, 76, 0xdd);
+    }
+
+    ext.execute_with(|| {
+        configure();
+        StorageValueRef::persistent(L1_CURSOR_STORAGE).set(&(88u64, U256::from(0xaa)));
+        StorageValueRef::persistent(L1_PENDING_STORAGE).set(&vec![(70u64, nft_deposit(6)), (82u64, token_deposit(5))]);
+
+        assert_eq!(HttpL1Source::deposits(), Ok(vec![nft_deposit(6)]));
+        assert_eq!(cursor(), Some((76u64, U256::from(0xdd))));
+    });
+}
+
+#[test]
+fn http_l1_source_skips_malformed_logs() {
+    let (offchain, state) = TestOffchainExt::new();
+    let mut ext = new_test_ext();
+    ext.register_extension(OffchainExt::new(offchain));
+
+    {
+        let mut state = state.write();
+        //Account index overflows u32, the next log is still read
+        expect_rpc(&mut state, "eth_blockNumber", "[]", r#""0x64""#);
+        expect_logs(&mut state, 80, 88, &[deposit_log(82, 5, 1u64 << 32, 1, 10), deposit_nft_log(85, 6, 2, 4)]);
+        expect_block_hash(&mut state, 88, 0xaa);
+    }
+
+    ext.execute_with(|| {
+        configure();
+        StorageValueRef::persistent(L1_START_BLOCK_STORAGE).set(&80u64);
+
+        assert_eq!(HttpL1Source::deposits(), Ok(vec![nft_deposit(6)]));
+        assert_eq!(cursor(), Some((88u64, U256::from(0xaa))));
+    });
+}
+
+#[test]
+fn http_l1_source_waits_for_lock() {
+    let (offchain, _state) = TestOffchainExt::new();
+    let mut ext = new_test_ext();
+    ext.register_extension(OffchainExt::new(offchain));
+
+    ext.execute_with(|| {
+        configure();
+        StorageValueRef::persistent(L1_PENDING_STORAGE).set(&vec![(82u64, token_deposit(5))]);
+
+        //Another worker is scanning, nothing is requested or changed
+        let mut lock = StorageLock::<Time>::new(L1_LOCK_STORAGE);
+        let guard = lock.try_lock().unwrap();
+        assert_eq!(HttpL1Source::deposits(), Err(()));
+        HttpL1Source::forget(&U256::from(5));
+        assert_eq!(
+            StorageValueRef::persistent(L1_PENDING_STORAGE).get::<Vec<(u64, L1Deposit)>>(),
+            Some(Some(vec![(82u64, token_deposit(5))]))
+        );
+
+        drop(guard);
+        HttpL1Source::forget(&U256::from(5));
+        assert_eq!(
+            StorageValueRef::persistent(L1_PENDING_STORAGE).get::<Vec<(u64, L1Deposit)>>(),
+            Some(Some(vec![]))
+        );
+    });
+}
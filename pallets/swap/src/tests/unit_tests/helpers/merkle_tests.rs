@@ -0,0 +1,108 @@
+use super::*;
+use sha2::{Digest, Sha256};
+
+fn prepare_unit_test() {
+    //SetKey for accountIndex 0
+    let mut origin = 0u64;
+    let secret_key_0 = [2u8; 32];
+    let pub_key_0 = BabyJubjub::pubkey_from_secretkey(&secret_key_0).encode();
+    assert_ok!(SwapModule::set_key(Origin::signed(origin), pub_key_0));
+
+    //SetKey for accountIndex 1
+    origin = 1u64;
+    let secret_key_1 = [3u8; 32];
+    let pub_key_1 = BabyJubjub::pubkey_from_secretkey(&secret_key_1).encode();
+    assert_ok!(SwapModule::set_key(Origin::signed(origin), pub_key_1));
+
+    //Deposit 10 into accountIndex 0 as reqId 3, caller is accountIndex 1
+    let account_index = 0u32;
+    let token_index = 1u32;
+    let amount = U256::from(10);
+    let l1_tx_hash = U256::from(100);
+    let nonce = 1u64;
+
+    let command = Command::Deposit { nonce, account_index, token_index, amount }.encode();
+
+    let command_sign = BabyJubjub::sign(&command, &secret_key_1);
+    let mut command_sign_formatted :[u8; 64] = [0 as u8;64];
+    command_sign_formatted[..32].copy_from_slice(&command_sign.r.encode());
+    command_sign_formatted[32..].copy_from_slice(&command_sign.s.encode());
+
+    assert_ok!(SwapModule::deposit(Origin::signed(origin), command_sign_formatted, account_index, token_index, amount, l1_tx_hash, nonce));
+}
+
+#[test]
+fn merkle_leaf_index_layout() {
+    assert_eq!(MerkleLeaf::Balance(3, 2).index(), (3u32 << 10) | 2);
+    assert_eq!(MerkleLeaf::Share(3, 2).index(), (1u32 << 30) | (3u32 << 10) | 2);
+    assert_eq!(MerkleLeaf::Pool(2).index(), 2u32 << 30 | 2);
+    assert_eq!(MerkleLeaf::NFT(2).index(), (2u32 << 30) | (1u32 << 28) | 2);
+    assert_eq!(MerkleLeaf::Key(2).index(), (2u32 << 30) | (2u32 << 28) | 2);
+}
+
+#[test]
+fn merkle_zero_hashes_chain() {
+    let mut zero = H256::zero();
+    for level in 0..=MERKLE_DEPTH {
+        assert_eq!(merkle_zero_hash(level), zero);
+
+        let mut data = [0u8; 64];
+        data[..32].copy_from_slice(zero.as_bytes());
+        data[32..].copy_from_slice(zero.as_bytes());
+        zero = H256::from_slice(&Sha256::digest(&data));
+    }
+}
+
+#[test]
+fn merkle_root_per_req() {
+    new_test_ext().execute_with(|| {
+        let empty_root = SwapModule::merkle_root(None).unwrap();
+        assert_eq!(empty_root, merkle_zero_hash(MERKLE_DEPTH));
+
+        prepare_unit_test();
+
+        let root_1 = MerkleRootMap::<Test>::get(U256::from(1)).unwrap();
+        let root_2 = MerkleRootMap::<Test>::get(U256::from(2)).unwrap();
+        let root_3 = MerkleRootMap::<Test>::get(U256::from(3)).unwrap();
+        assert_ne!(root_1, empty_root);
+        assert_ne!(root_1, root_2);
+        assert_ne!(root_2, root_3);
+        assert_eq!(SwapModule::merkle_root(None), Some(root_3));
+        assert_eq!(SwapModule::merkle_root(Some(U256::from(4))), None);
+    })
+}
+
+#[test]
+fn merkle_proof_works() {
+    new_test_ext().execute_with(|| {
+        prepare_unit_test();
+
+        let proof = SwapModule::merkle_proof(MerkleLeaf::Balance(0, 1));
+        assert_eq!(proof.siblings.len(), MERKLE_DEPTH as usize);
+        assert_ne!(proof.leaf, H256::zero());
+        assert_eq!(merkle_proof_root(&proof), proof.root);
+        assert_eq!(proof.root, MerkleRootMap::<Test>::get(U256::from(3)).unwrap());
+
+        let proof = SwapModule::merkle_proof(MerkleLeaf::Key(1));
+        assert_ne!(proof.leaf, H256::zero());
+        assert_eq!(merkle_proof_root(&proof), proof.root);
+
+        //Untouched leaf
+        let proof = SwapModule::merkle_proof(MerkleLeaf::Balance(5, 3));
+        assert_eq!(proof.leaf, H256::zero());
+        assert_eq!(merkle_proof_root(&proof), proof.root);
+    })
+}
+
+#[test]
+fn merkle_empty_entry_restores_root() {
+    new_test_ext().execute_with(|| {
+        prepare_unit_test();
+
+        let root = SwapModule::merkle_root(None).unwrap();
+        balance_set::<Test>(&5u32, &3u32, U256::from(7));
+        assert_ne!(SwapModule::merkle_root(None).unwrap(), root);
+        balance_set::<Test>(&5u32, &3u32, U256::from(0));
+        assert_eq!(SwapModule::merkle_root(None).unwrap(), root);
+    })
+}
@@ -0,0 +1,78 @@
+use super::*;
+use frame_support::traits::OnInitialize;
+
+fn prepare_unit_test() {
+    //PoolMap insert new value
+    let pool_index = 0u32;
+    let token_index0 = 0u32;
+    let token_index1 = 1u32;
+    let amount0 = U256::from(0);
+    let amount1 = U256::from(0);
+    let total_share = U256::from(0);
+    PoolMap::<Test>::insert(
+        pool_index,
+        (
+            &token_index0.clone(),
+            &token_index1.clone(),
+            amount0,
+            amount1,
+            total_share
+        ),
+    );
+
+    //Pool changes at block 1 and 5
+    System::set_block_number(1);
+    assert_ok!(pool_change_with_share::<Test>(&pool_index, true, U256::from(1000), true, U256::from(2000), U256::from(100)));
+    System::set_block_number(5);
+    assert_ok!(pool_change::<Test>(&pool_index, true, U256::from(1000), false, U256::from(1000)));
+}
+
+#[test]
+fn pool_snapshot_works() {
+    new_test_ext().execute_with(|| {
+        prepare_unit_test();
+
+        let pool_index = 0u32;
+        assert_eq!(PoolSnapshotMap::<Test>::get(1, pool_index), Some((U256::from(1000), U256::from(2000), U256::from(100))));
+        assert_eq!(PoolSnapshotMap::<Test>::get(5, pool_index), Some((U256::from(2000), U256::from(1000), U256::from(100))));
+        assert_eq!(PoolSnapshotMap::<Test>::get(3, pool_index), None);
+
+        assert_eq!(
+            SwapModule::pool_history(pool_index, 0, 10),
+            vec![
+                (1, U256::from(1000), U256::from(2000), U256::from(100)),
+                (5, U256::from(2000), U256::from(1000), U256::from(100)),
+            ]
+        );
+        assert_eq!(SwapModule::pool_history(pool_index, 2, 10).len(), 1);
+        assert_eq!(SwapModule::pool_history(1u32, 0, 10).len(), 0);
+    })
+}
+
+#[test]
+fn pool_snapshot_works_multi_change_in_one_block() {
+    new_test_ext().execute_with(|| {
+        prepare_unit_test();
+
+        //The last change of block 5 is kept
+        let pool_index = 0u32;
+        assert_ok!(pool_change::<Test>(&pool_index, false, U256::from(500), true, U256::from(500)));
+        assert_eq!(PoolSnapshotMap::<Test>::get(5, pool_index), Some((U256::from(1500), U256::from(1500), U256::from(100))));
+    })
+}
+
+#[test]
+fn pool_snapshot_pruned_after_window() {
+    new_test_ext().execute_with(|| {
+        prepare_unit_test();
+
+        //SnapshotWindow is 10 in mock, block 1 is pruned when block 11 starts
+        let pool_index = 0u32;
+        System::set_block_number(11);
+        SwapModule::on_initialize(11);
+
+        assert_eq!(PoolSnapshotMap::<Test>::get(1, pool_index), None);
+        assert!(PoolSnapshotMap::<Test>::get(5, pool_index).is_some());
+        assert_eq!(SwapModule::pool_history(pool_index, 0, 11).len(), 1);
+    })
+}
@@ -0,0 +1,69 @@
+use super::*;
+
+fn prepare_unit_test() {
+    System::set_block_number(1);
+
+    //SetKey for accountIndex 0 to 9 as reqId 1 to 10
+    for origin in 0u64..10u64 {
+        let secret_key = [origin as u8 + 2; 32];
+        let pub_key = BabyJubjub::pubkey_from_secretkey(&secret_key).encode();
+        assert_ok!(SwapModule::set_key(Origin::signed(origin), pub_key));
+    }
+}
+
+#[test]
+fn req_lifecycle_pending() {
+    new_test_ext().execute_with(|| {
+        prepare_unit_test();
+
+        let record = ReqRecordMap::<Test>::get(U256::from(4)).unwrap();
+        assert_eq!(record.op, OP_SETKEY);
+        assert_eq!(record.account_index, 3u32);
+        assert_eq!(record.submitted_at, 1u64);
+        assert_eq!(record.acks, 0u8);
+        assert_eq!(record.completed_at, None);
+        assert_eq!(record.status, ReqStatus::Pending);
+
+        let requests = SwapModule::requests(U256::from(1), 20);
+        assert_eq!(requests.len(), 10);
+        assert_eq!(requests[0].0, U256::from(1));
+        assert!(requests[0].2.is_some());
+
+        let account_requests = SwapModule::account_requests(3u32, 0, 5);
+        assert_eq!(account_requests.len(), 1);
+        assert_eq!(account_requests[0].0, U256::from(4));
+        assert_eq!(SwapModule::account_requests(3u32, 1, 5).len(), 0);
+        assert_eq!(SwapModule::account_requests(10u32, 0, 5).len(), 0);
+    })
+}
+
+#[test]
+fn req_lifecycle_ack_and_complete() {
+    new_test_ext().execute_with(|| {
+        prepare_unit_test();
+
+        System::set_block_number(2);
+        assert_ok!(SwapModule::ack(Origin::signed(1), U256::from(0)));
+
+        let record = ReqRecordMap::<Test>::get(U256::from(1)).unwrap();
+        assert_eq!(record.acks, 1u8);
+        assert_eq!(record.status, ReqStatus::Pending);
+        //A partial ack leaves the batch and the ack delay where they were
+        assert_eq!(CompleteReqIndex::<Test>::get(), U256::from(0));
+        assert_eq!(LastAckBlock::<Test>::get(), 0u64);
+
+        System::set_block_number(3);
+        assert_ok!(SwapModule::ack(Origin::signed(2), U256::from(0)));
+        assert_eq!(CompleteReqIndex::<Test>::get(), U256::from(10));
+        assert_eq!(LastAckBlock::<Test>::get(), 3u64);
+
+        let requests = SwapModule::requests(U256::from(1), 10);
+        assert_eq!(requests.len(), 10);
+        for (_, record, op) in requests {
+            assert_eq!(record.acks, 3u8);
+            assert_eq!(record.completed_at, Some(3u64));
+            assert_eq!(record.status, ReqStatus::Complete);
+            assert!(op.is_some());
+        }
+    })
+}
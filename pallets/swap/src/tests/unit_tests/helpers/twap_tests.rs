@@ -0,0 +1,90 @@
+use super::*;
+
+fn prepare_unit_test() {
+    //PoolMap insert new value
+    let pool_index = 0u32;
+    let token_index0 = 0u32;
+    let token_index1 = 1u32;
+    let amount0 = U256::from(0);
+    let amount1 = U256::from(0);
+    let total_share = U256::from(0);
+    PoolMap::<Test>::insert(
+        pool_index,
+        (
+            &token_index0.clone(),
+            &token_index1.clone(),
+            amount0,
+            amount1,
+            total_share
+        ),
+    );
+
+    //Pool becomes 1000:2000 at block 1 and 2000:2000 at block 11
+    System::set_block_number(1);
+    assert_ok!(pool_change::<Test>(&pool_index, true, U256::from(1000), true, U256::from(2000)));
+    System::set_block_number(11);
+    assert_ok!(pool_change::<Test>(&pool_index, true, U256::from(1000), true, U256::from(0)));
+    System::set_block_number(21);
+}
+
+#[test]
+fn twap_works() {
+    new_test_ext().execute_with(|| {
+        prepare_unit_test();
+
+        let pool_index = 0u32;
+        let price_unit = U256::exp10(18);
+
+        assert_eq!(SwapModule::twap(pool_index, SwapDirection::Token0ToToken1, 1, 11), Some(price_unit * 2));
+        assert_eq!(SwapModule::twap(pool_index, SwapDirection::Token0ToToken1, 11, 21), Some(price_unit));
+        assert_eq!(SwapModule::twap(pool_index, SwapDirection::Token0ToToken1, 1, 21), Some(price_unit * 3 / 2));
+    })
+}
+
+#[test]
+fn twap_works_reverse() {
+    new_test_ext().execute_with(|| {
+        prepare_unit_test();
+
+        let pool_index = 0u32;
+        let price_unit = U256::exp10(18);
+
+        assert_eq!(SwapModule::twap(pool_index, SwapDirection::Token1ToToken0, 1, 11), Some(price_unit / 2));
+        assert_eq!(SwapModule::twap(pool_index, SwapDirection::Token1ToToken0, 11, 21), Some(price_unit));
+    })
+}
+
+#[test]
+fn twap_works_multi_change_in_one_block() {
+    new_test_ext().execute_with(|| {
+        prepare_unit_test();
+
+        //Only the reserves at the end of block 21 are accounted for
+        let pool_index = 0u32;
+        assert_ok!(pool_change::<Test>(&pool_index, true, U256::from(2000), true, U256::from(0)));
+        assert_ok!(pool_change::<Test>(&pool_index, false, U256::from(3000), true, U256::from(2000)));
+        System::set_block_number(31);
+
+        let price_unit = U256::exp10(18);
+        assert_eq!(SwapModule::twap(pool_index, SwapDirection::Token0ToToken1, 21, 31), Some(price_unit * 4));
+        assert_eq!(PriceObservationCount::<Test>::get(pool_index), 3);
+    })
+}
+
+#[test]
+fn twap_invalid_range() {
+    new_test_ext().execute_with(|| {
+        prepare_unit_test();
+
+        let pool_index = 0u32;
+
+        //No observation before block 1
+        assert_eq!(SwapModule::twap(pool_index, SwapDirection::Token0ToToken1, 0, 11), None);
+        //Empty range
+        assert_eq!(SwapModule::twap(pool_index, SwapDirection::Token0ToToken1, 11, 11), None);
+        //Future block
+        assert_eq!(SwapModule::twap(pool_index, SwapDirection::Token0ToToken1, 11, 22), None);
+        //Unknown pool
+        assert_eq!(SwapModule::twap(1u32, SwapDirection::Token0ToToken1, 1, 11), None);
+    })
+}
@@ -0,0 +1,249 @@
+use super::*;
+use frame_support::storage::unhashed;
+use frame_support::traits::{OnInitialize, OnRuntimeUpgrade};
+
+fn prepare_unit_test() -> H256 {
+    //SetKey for accountIndex 0
+    let mut origin = 0u64;
+    let secret_key_0 = [2u8; 32];
+    let pub_key_0 = BabyJubjub::pubkey_from_secretkey(&secret_key_0).encode();
+    assert_ok!(SwapModule::set_key(Origin::signed(origin), pub_key_0));
+
+    //SetKey for accountIndex 1
+    origin = 1u64;
+    let secret_key_1 = [3u8; 32];
+    let pub_key_1 = BabyJubjub::pubkey_from_secretkey(&secret_key_1).encode();
+    assert_ok!(SwapModule::set_key(Origin::signed(origin), pub_key_1));
+
+    //Deposit 10 into accountIndex 0 as reqId 3, caller is accountIndex 1
+    let account_index = 0u32;
+    let token_index = 1u32;
+    let amount = U256::from(10);
+    let l1_tx_hash = U256::from(100);
+    let nonce = 1u64;
+
+    let command = Command::Deposit { nonce, account_index, token_index, amount }.encode();
+
+    let command_sign = BabyJubjub::sign(&command, &secret_key_1);
+    let mut command_sign_formatted :[u8; 64] = [0 as u8;64];
+    command_sign_formatted[..32].copy_from_slice(&command_sign.r.encode());
+    command_sign_formatted[32..].copy_from_slice(&command_sign.s.encode());
+
+    assert_ok!(SwapModule::deposit(Origin::signed(origin), command_sign_formatted, account_index, token_index, amount, l1_tx_hash, nonce));
+
+    //Turn the state into the legacy layout: legacy prefix, no storage version, u8 status codes, no request records, no admins
+    ReqRecordMap::<Test>::remove_all();
+    Admins::<Test>::kill();
+    let root = clear_merkle_tree();
+    unhashed::put(&L1TxMap::<Test>::hashed_key_for(U256::from(101)), &0u8);
+    StorageVersion::<Test>::kill();
+    migrations::move_prefix::<Test>(migrations::PREFIX, migrations::LEGACY_PREFIX, u32::MAX);
+    root
+}
+
+//Removes the Merkle tree, as on chains older than it, returning its root
+fn clear_merkle_tree() -> H256 {
+    let root = SwapModule::merkle_root(None).unwrap();
+    MerkleNodeMap::<Test>::remove_all();
+    MerkleRoot::<Test>::kill();
+    MerkleRootMap::<Test>::remove_all();
+    root
+}
+
+#[test]
+fn storage_version_set_at_genesis() {
+    new_test_ext().execute_with(|| {
+        assert_eq!(StorageVersion::<Test>::get(), Releases::LATEST);
+    })
+}
+
+#[test]
+fn migrate_legacy_state() {
+    new_test_ext().execute_with(|| {
+        let root = prepare_unit_test();
+
+        assert_eq!(StorageVersion::<Test>::get(), Releases::V1_0_0);
+        assert_eq!(migrations::prefix_key_count(migrations::PREFIX), 0);
+        assert!(KeyMap::<Test>::get(0u32).is_none());
+
+        migrations::migrate::<Test>(u32::MAX);
+
+        assert_eq!(StorageVersion::<Test>::get(), Releases::LATEST);
+        assert_eq!(migrations::prefix_key_count(migrations::LEGACY_PREFIX), 0);
+        assert!(KeyMap::<Test>::get(0u32).is_some());
+        assert!(KeyMap::<Test>::get(1u32).is_some());
+        assert_eq!(BalanceMap::<Test>::get((0u32, 1u32)), U256::from(10));
+        assert_eq!(ReqIndex::<Test>::get(), U256::from(3));
+        assert!(PendingReqMap::<Test>::contains_key(U256::from(3)));
+        assert_eq!(TokenMap::<Test>::get(1u32).unwrap().decimals, 18u8);
+        assert_eq!(L1TxMap::<Test>::get(U256::from(100)), Some(L1TxStatus::Pending));
+        assert_eq!(L1TxMap::<Test>::get(U256::from(101)), None);
+        assert_eq!(SwapModule::deposit_status(U256::from(100)), DepositStatus::Pending(U256::from(3)));
+        assert_eq!(Admins::<Test>::get(), vec![1u64, 2u64]);
+        assert_eq!(MerkleRootMap::<Test>::get(U256::from(3)), Some(root));
+    })
+}
+
+#[test]
+fn migrate_builds_merkle_tree_over_several_blocks() {
+    new_test_ext().execute_with(|| {
+        let root = prepare_unit_test();
+        migrations::migrate::<Test>(u32::MAX);
+
+        StorageVersion::<Test>::put(Releases::V3_0_0);
+        clear_merkle_tree();
+
+        //One leaf per call
+        let mut calls = 0;
+        while StorageVersion::<Test>::get() != Releases::LATEST {
+            migrations::migrate::<Test>(MERKLE_DEPTH as u32 + 1);
+            calls += 1;
+        }
+
+        //Balance of accountIndex 0 and keys of accountIndexes 0 and 1
+        assert_eq!(calls, 3);
+        assert!(LegacyScanCursor::<Test>::get().is_none());
+        assert_eq!(SwapModule::merkle_root(None), Some(root));
+        assert_eq!(MerkleRootMap::<Test>::get(U256::from(3)), Some(root));
+    })
+}
+
+#[test]
+fn migrate_seeds_admins_on_v2() {
+    new_test_ext().execute_with(|| {
+        StorageVersion::<Test>::put(Releases::V2_0_0);
+        Admins::<Test>::kill();
+
+        migrations::migrate::<Test>(u32::MAX);
+
+        assert_eq!(StorageVersion::<Test>::get(), Releases::LATEST);
+        assert_eq!(Admins::<Test>::get(), vec![1u64, 2u64]);
+    })
+}
+
+#[test]
+fn migrate_keeps_stored_admins() {
+    new_test_ext().execute_with(|| {
+        StorageVersion::<Test>::put(Releases::V2_0_0);
+        Admins::<Test>::put(vec![3u64]);
+
+        migrations::migrate::<Test>(u32::MAX);
+
+        assert_eq!(StorageVersion::<Test>::get(), Releases::LATEST);
+        assert_eq!(Admins::<Test>::get(), vec![3u64]);
+    })
+}
+
+#[test]
+fn migrate_is_noop_on_latest_version() {
+    new_test_ext().execute_with(|| {
+        prepare_unit_test();
+        migrations::migrate::<Test>(u32::MAX);
+
+        let key_count = migrations::prefix_key_count(migrations::PREFIX);
+        migrations::migrate::<Test>(u32::MAX);
+        assert_eq!(migrations::prefix_key_count(migrations::PREFIX), key_count);
+        assert_eq!(BalanceMap::<Test>::get((0u32, 1u32)), U256::from(10));
+    })
+}
+
+#[test]
+fn migrate_restarts_ack_delay() {
+    new_test_ext().execute_with(|| {
+        prepare_unit_test();
+
+        System::set_block_number(50);
+        migrations::migrate::<Test>(u32::MAX);
+        assert_eq!(UpgradedAt::<Test>::get(), 50);
+        assert_eq!(LastAckBlock::<Test>::get(), 50);
+
+        //The legacy requests wait ExitModeDelay blocks from the upgrade
+        SwapModule::on_initialize(69);
+        assert!(!ExitMode::<Test>::get());
+
+        SwapModule::on_initialize(70);
+        assert!(ExitMode::<Test>::get());
+    })
+}
+
+#[test]
+fn migrate_over_several_blocks() {
+    new_test_ext().execute_with(|| {
+        prepare_unit_test();
+        let key_count = migrations::prefix_key_count(migrations::LEGACY_PREFIX);
+        assert!(key_count > 2);
+
+        //Move 2 keys, ops are refused until the migration is done
+        migrations::migrate::<Test>(2);
+        assert_eq!(StorageVersion::<Test>::get(), Releases::V1_0_0);
+        assert_eq!(migrations::prefix_key_count(migrations::LEGACY_PREFIX), key_count - 2);
+        assert_noop!(SwapModule::set_key(Origin::signed(3u64), [0u8; 32]), Error::<Test>::Migrating);
+        assert_noop!(SwapModule::ack(Origin::signed(1u64), U256::from(0)), Error::<Test>::Migrating);
+
+        //Blocks move the rest
+        let mut n = 2u64;
+        while StorageVersion::<Test>::get() != Releases::LATEST {
+            SwapModule::on_initialize(n);
+            n += 1;
+        }
+
+        assert_eq!(migrations::prefix_key_count(migrations::LEGACY_PREFIX), 0);
+        assert_eq!(BalanceMap::<Test>::get((0u32, 1u32)), U256::from(10));
+        assert!(PendingReqMap::<Test>::contains_key(U256::from(3)));
+        assert_eq!(L1TxMap::<Test>::get(U256::from(100)), Some(L1TxStatus::Pending));
+    })
+}
+
+#[test]
+fn upgrade_block_takes_one_migration_step() {
+    new_test_ext().execute_with(|| {
+        prepare_unit_test();
+        let key_count = migrations::prefix_key_count(migrations::LEGACY_PREFIX);
+        assert!(key_count > 2 * MaxMigrationKeysPerBlock::get() as u64);
+
+        //on_runtime_upgrade moves MaxMigrationKeysPerBlock keys, on_initialize of the same block none
+        System::set_block_number(5);
+        SwapModule::on_runtime_upgrade();
+        let moved = key_count - migrations::prefix_key_count(migrations::LEGACY_PREFIX);
+        assert_eq!(moved, MaxMigrationKeysPerBlock::get() as u64);
+        SwapModule::on_initialize(5);
+        assert_eq!(migrations::prefix_key_count(migrations::LEGACY_PREFIX), key_count - moved);
+        assert!(MigrationStepAt::<Test>::get().is_none());
+
+        SwapModule::on_initialize(6);
+        assert_eq!(migrations::prefix_key_count(migrations::LEGACY_PREFIX), key_count - 2 * moved);
+    })
+}
+
+#[test]
+fn snapshots_pruned_while_migrating() {
+    new_test_ext().execute_with(|| {
+        StorageVersion::<Test>::put(Releases::V3_0_0);
+        PoolSnapshotMap::<Test>::insert(1u64, 0u32, (U256::from(1), U256::from(1), U256::from(1)));
+
+        SwapModule::on_initialize(1 + SnapshotWindow::get());
+        assert!(!PoolSnapshotMap::<Test>::contains_key(1u64, 0u32));
+    })
+}
+
+#[test]
+fn charge_refused_while_migrating() {
+    new_test_ext().execute_with(|| {
+        StorageVersion::<Test>::put(Releases::V3_0_0);
+
+        assert_noop!(SwapModule::charge(Origin::signed(1u64), 3u64, 10u64, U256::from(1)), Error::<Test>::Migrating);
+    })
+}
+
+#[cfg(feature = "try-runtime")]
+#[test]
+fn migrate_passes_upgrade_checks() {
+    new_test_ext().execute_with(|| {
+        prepare_unit_test();
+
+        let moved = migrations::pre_upgrade::<Test>().unwrap();
+        assert!(moved.unwrap() > 0);
+        migrations::migrate::<Test>(u32::MAX);
+        assert_ok!(migrations::post_upgrade::<Test>(moved));
+    })
+}
@@ -0,0 +1,42 @@
+use super::*;
+
+fn prepare_unit_test() {
+    //State written before the token registry: a balance of tokenIndex 7 and a pool of tokenIndexes 8 and 9
+    BalanceMap::<Test>::insert((0u32, 7u32), U256::from(10));
+    PoolMap::<Test>::insert(0u32, (8u32, 9u32, U256::from(0), U256::from(0), U256::from(0)));
+    PoolIndexCount::<Test>::put(1u32);
+    StorageVersion::<Test>::put(Releases::V3_0_0);
+}
+
+#[test]
+fn migrate_registers_found_tokens() {
+    new_test_ext().execute_with(|| {
+        prepare_unit_test();
+
+        migrations::migrate::<Test>(u32::MAX);
+
+        assert_eq!(StorageVersion::<Test>::get(), Releases::LATEST);
+        assert_eq!(TokenMap::<Test>::get(7u32), Some(TokenInfo::default()));
+        assert_eq!(TokenMap::<Test>::get(8u32), Some(TokenInfo::default()));
+        assert_eq!(TokenMap::<Test>::get(9u32), Some(TokenInfo::default()));
+        assert_eq!(TokenMap::<Test>::get(1u32).unwrap().decimals, 18u8);
+        assert_eq!(TokenMap::<Test>::get(10u32), None);
+    })
+}
+
+#[test]
+fn register_token_completes_found_token() {
+    new_test_ext().execute_with(|| {
+        prepare_unit_test();
+        migrations::migrate::<Test>(u32::MAX);
+
+        //Caller is admin 1
+        assert_ok!(SwapModule::register_token(Origin::signed(1u64), 7u32, U256::from(70), 6u8, b"USDT".to_vec()));
+        assert_eq!(TokenMap::<Test>::get(7u32).unwrap().decimals, 6u8);
+
+        assert_noop!(
+            SwapModule::register_token(Origin::signed(1u64), 7u32, U256::from(71), 6u8, b"USDT".to_vec()),
+            Error::<Test>::TokenExists
+        );
+    })
+}
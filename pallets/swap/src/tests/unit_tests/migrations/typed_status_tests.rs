@@ -0,0 +1,45 @@
+use super::*;
+use frame_support::storage::unhashed;
+
+fn swap_op(reverse: Reverse) -> Ops {
+    Ops::Swap(U256::from(0), U256::from(0), U256::from(0), 0u64, 2u32, 0u32, reverse, U256::from(100))
+}
+
+#[test]
+fn migrate_l1_tx_status() {
+    new_test_ext().execute_with(|| {
+        //Legacy PENDING, DONE and unknown codes
+        unhashed::put(&L1TxMap::<Test>::hashed_key_for(U256::from(1)), &1u8);
+        unhashed::put(&L1TxMap::<Test>::hashed_key_for(U256::from(2)), &2u8);
+        unhashed::put(&L1TxMap::<Test>::hashed_key_for(U256::from(3)), &0u8);
+
+        migrations::migrate_to_typed_status::<Test>();
+
+        assert_eq!(L1TxMap::<Test>::get(U256::from(1)), Some(L1TxStatus::Pending));
+        assert_eq!(L1TxMap::<Test>::get(U256::from(2)), Some(L1TxStatus::Done));
+        assert_eq!(L1TxMap::<Test>::get(U256::from(3)), None);
+
+        //Running it again changes nothing
+        migrations::migrate_to_typed_status::<Test>();
+
+        assert_eq!(L1TxMap::<Test>::get(U256::from(1)), Some(L1TxStatus::Pending));
+        assert_eq!(L1TxMap::<Test>::get(U256::from(2)), Some(L1TxStatus::Done));
+    })
+}
+
+#[test]
+fn migrate_keeps_signed_swap_reverse() {
+    new_test_ext().execute_with(|| {
+        PendingReqMap::<Test>::insert(U256::from(1), swap_op(0u8));
+        PendingReqMap::<Test>::insert(U256::from(2), swap_op(5u8));
+        CompleteReqMap::<Test>::insert(U256::from(3), swap_op(255u8));
+        RejectedReqMap::<Test>::insert(U256::from(4), (swap_op(2u8), b"reason".to_vec()));
+
+        migrations::migrate_to_typed_status::<Test>();
+
+        assert!(PendingReqMap::<Test>::get(U256::from(1)) == Some(swap_op(0u8)));
+        assert!(PendingReqMap::<Test>::get(U256::from(2)) == Some(swap_op(5u8)));
+        assert!(CompleteReqMap::<Test>::get(U256::from(3)) == Some(swap_op(255u8)));
+        assert!(RejectedReqMap::<Test>::get(U256::from(4)) == Some((swap_op(2u8), b"reason".to_vec())));
+    })
+}
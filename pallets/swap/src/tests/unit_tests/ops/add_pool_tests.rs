@@ -39,11 +39,7 @@ fn add_pool_works() {
             49, 180, 255,  97, 249,  95,   1, 203
         ];
 
-        let mut command = [0u8; 81];
-        command[0] = OP_ADDPOOL;
-        command[1..9].copy_from_slice(&nonce.to_be_bytes());
-        command[9..13].copy_from_slice(&token_index_0.to_be_bytes());
-        command[13..17].copy_from_slice(&token_index_1.to_be_bytes());
+        let command = Command::AddPool { nonce, token_index_0, token_index_1 }.encode();
 
         let command_sign = BabyJubjub::sign(&command, &secret_key_1);
         let mut command_sign_formatted :[u8; 64] = [0 as u8;64];
@@ -52,9 +48,9 @@ fn add_pool_works() {
 
         assert_ok!(SwapModule::add_pool(Origin::signed(origin), command_sign_formatted, token_index_0, token_index_1, nonce));
 
-        assert_eq!(PoolIndexMap::get((&token_index_0, &token_index_1)).unwrap(), 0u32);
+        assert_eq!(PoolIndexMap::<Test>::get((&token_index_0, &token_index_1)).unwrap(), 0u32);
 
-        assert_eq!(PoolMap::get(0u32).unwrap(), (0u32, 1u32, U256::from(0), U256::from(0), U256::from(0)));
+        assert_eq!(PoolMap::<Test>::get(0u32).unwrap(), (0u32, 1u32, U256::from(0), U256::from(0), U256::from(0)));
 
         assert_eq!(NonceMap::<Test>::get(1u64), 2);
     })
@@ -78,11 +74,7 @@ fn add_pool_noaccess() {
             140, 130,  52, 195,  95, 192,  40,  50
         ];
 
-        let mut command = [0u8; 81];
-        command[0] = OP_ADDPOOL;
-        command[1..9].copy_from_slice(&nonce.to_be_bytes());
-        command[9..13].copy_from_slice(&token_index_0.to_be_bytes());
-        command[13..17].copy_from_slice(&token_index_1.to_be_bytes());
+        let command = Command::AddPool { nonce, token_index_0, token_index_1 }.encode();
 
         let command_sign = BabyJubjub::sign(&command, &secret_key_0);
         let mut command_sign_formatted :[u8; 64] = [0 as u8;64];
@@ -111,11 +103,7 @@ fn add_pool_invalid_tokenindex_tokenindex_0_exceeds_range() {
             49, 180, 255,  97, 249,  95,   1, 203
         ];
 
-        let mut command = [0u8; 81];
-        command[0] = OP_ADDPOOL;
-        command[1..9].copy_from_slice(&nonce.to_be_bytes());
-        command[9..13].copy_from_slice(&token_index_0.to_be_bytes());
-        command[13..17].copy_from_slice(&token_index_1.to_be_bytes());
+        let command = Command::AddPool { nonce, token_index_0, token_index_1 }.encode();
 
         let command_sign = BabyJubjub::sign(&command, &secret_key_1);
         let mut command_sign_formatted :[u8; 64] = [0 as u8;64];
@@ -144,11 +132,7 @@ fn add_pool_invalid_tokenindex_tokenindex_1_exceeds_range() {
             49, 180, 255,  97, 249,  95,   1, 203
         ];
 
-        let mut command = [0u8; 81];
-        command[0] = OP_ADDPOOL;
-        command[1..9].copy_from_slice(&nonce.to_be_bytes());
-        command[9..13].copy_from_slice(&token_index_0.to_be_bytes());
-        command[13..17].copy_from_slice(&token_index_1.to_be_bytes());
+        let command = Command::AddPool { nonce, token_index_0, token_index_1 }.encode();
 
         let command_sign = BabyJubjub::sign(&command, &secret_key_1);
         let mut command_sign_formatted :[u8; 64] = [0 as u8;64];
@@ -177,11 +161,7 @@ fn add_pool_invalid_tokenpair() {
             49, 180, 255,  97, 249,  95,   1, 203
         ];
 
-        let mut command = [0u8; 81];
-        command[0] = OP_ADDPOOL;
-        command[1..9].copy_from_slice(&nonce.to_be_bytes());
-        command[9..13].copy_from_slice(&token_index_0.to_be_bytes());
-        command[13..17].copy_from_slice(&token_index_1.to_be_bytes());
+        let command = Command::AddPool { nonce, token_index_0, token_index_1 }.encode();
 
         let command_sign = BabyJubjub::sign(&command, &secret_key_1);
         let mut command_sign_formatted :[u8; 64] = [0 as u8;64];
@@ -210,11 +190,7 @@ fn add_pool_account_not_exists() {
             49, 180, 255,  97, 249,  95,   1, 203
         ];
 
-        let mut command = [0u8; 81];
-        command[0] = OP_ADDPOOL;
-        command[1..9].copy_from_slice(&nonce.to_be_bytes());
-        command[9..13].copy_from_slice(&token_index_0.to_be_bytes());
-        command[13..17].copy_from_slice(&token_index_1.to_be_bytes());
+        let command = Command::AddPool { nonce, token_index_0, token_index_1 }.encode();
 
         //There is no secret_key_2, so use secret_key_1 here
         let command_sign = BabyJubjub::sign(&command, &secret_key_1);
@@ -244,11 +220,7 @@ fn add_pool_nonce_inconsistent() {
             49, 180, 255,  97, 249,  95,   1, 203
         ];
 
-        let mut command = [0u8; 81];
-        command[0] = OP_ADDPOOL;
-        command[1..9].copy_from_slice(&nonce.to_be_bytes());
-        command[9..13].copy_from_slice(&token_index_0.to_be_bytes());
-        command[13..17].copy_from_slice(&token_index_1.to_be_bytes());
+        let command = Command::AddPool { nonce, token_index_0, token_index_1 }.encode();
 
         let command_sign = BabyJubjub::sign(&command, &secret_key_1);
         let mut command_sign_formatted :[u8; 64] = [0 as u8;64];
@@ -276,11 +248,7 @@ fn add_pool_invalid_signature() {
             49, 180, 255,  97, 249,  95,   1, 203
         ];
 
-        let mut command = [0u8; 81];
-        command[0] = OP_ADDPOOL;
-        command[1..9].copy_from_slice(&nonce.to_be_bytes());
-        command[9..13].copy_from_slice(&token_index_0.to_be_bytes());
-        command[13..17].copy_from_slice(&token_index_1.to_be_bytes());
+        let command = Command::AddPool { nonce, token_index_0, token_index_1 }.encode();
 
         let command_sign = BabyJubjub::sign(&command, &secret_key_1);
         let mut command_sign_formatted :[u8; 64] = [0 as u8;64];
@@ -311,11 +279,7 @@ fn add_pool_pool_exists() {
             49, 180, 255,  97, 249,  95,   1, 203
         ];
 
-        let mut command = [0u8; 81];
-        command[0] = OP_ADDPOOL;
-        command[1..9].copy_from_slice(&nonce.to_be_bytes());
-        command[9..13].copy_from_slice(&token_index_0.to_be_bytes());
-        command[13..17].copy_from_slice(&token_index_1.to_be_bytes());
+        let command = Command::AddPool { nonce, token_index_0, token_index_1 }.encode();
 
         let command_sign = BabyJubjub::sign(&command, &secret_key_1);
         let mut command_sign_formatted :[u8; 64] = [0 as u8;64];
@@ -328,11 +292,7 @@ fn add_pool_pool_exists() {
         //AddPool twice with same tokenIndex0 and tokenIndex1
         nonce = 2u64;
 
-        let mut command = [0u8; 81];
-        command[0] = OP_ADDPOOL;
-        command[1..9].copy_from_slice(&nonce.to_be_bytes());
-        command[9..13].copy_from_slice(&token_index_0.to_be_bytes());
-        command[13..17].copy_from_slice(&token_index_1.to_be_bytes());
+        let command = Command::AddPool { nonce, token_index_0, token_index_1 }.encode();
 
         let command_sign = BabyJubjub::sign(&command, &secret_key_1);
         let mut command_sign_formatted :[u8; 64] = [0 as u8;64];
@@ -29,12 +29,7 @@ fn bid_nft_works_has_bidder() {
         let mut l1_tx_hash = U256::from(0);
         let mut nonce = 1u64;
 
-        let mut command = [0u8; 81];
-        command[0] = OP_DEPOSIT;
-        command[1..9].copy_from_slice(&nonce.to_be_bytes());
-        command[9..13].copy_from_slice(&account_index.to_be_bytes());
-        command[13..17].copy_from_slice(&token_index.to_be_bytes());
-        command[17..49].copy_from_slice(&amount.to_be_bytes());
+        let mut command = Command::Deposit { nonce, account_index, token_index, amount }.encode();
 
         let mut command_sign = BabyJubjub::sign(&command, &secret_key_1);
         let mut command_sign_formatted :[u8; 64] = [0 as u8;64];
@@ -48,12 +43,7 @@ fn bid_nft_works_has_bidder() {
         l1_tx_hash = U256::from(1);
         nonce = 2u64;
 
-        command = [0u8; 81];
-        command[0] = OP_DEPOSIT_NFT;
-        command[1..9].copy_from_slice(&nonce.to_be_bytes());
-        command[9..13].copy_from_slice(&account_index.to_be_bytes());
-        command[13..17].copy_from_slice(&nft_id.to_be_bytes());
-        command[17..49].copy_from_slice(&l1_tx_hash.to_be_bytes());
+        command = Command::DepositNFT { nonce, account_index, nft_id, l1_tx_hash }.encode();
 
         command_sign = BabyJubjub::sign(&command, &secret_key_1);
         command_sign_formatted = [0 as u8;64];
@@ -74,12 +64,7 @@ fn bid_nft_works_has_bidder() {
         l1_tx_hash = U256::from(12);
         nonce = 3u64;
 
-        let mut command = [0u8; 81];
-        command[0] = OP_DEPOSIT;
-        command[1..9].copy_from_slice(&nonce.to_be_bytes());
-        command[9..13].copy_from_slice(&account_index.to_be_bytes());
-        command[13..17].copy_from_slice(&token_index.to_be_bytes());
-        command[17..49].copy_from_slice(&amount.to_be_bytes());
+        let mut command = Command::Deposit { nonce, account_index, token_index, amount }.encode();
 
         let mut command_sign = BabyJubjub::sign(&command, &secret_key_1);
         let mut command_sign_formatted :[u8; 64] = [0 as u8;64];
@@ -93,12 +78,7 @@ fn bid_nft_works_has_bidder() {
         amount = U256::from(1);
         nonce = 1u64;
 
-        command = [0u8; 81];
-        command[0] = OP_BID_NFT;
-        command[1..9].copy_from_slice(&nonce.to_be_bytes());
-        command[9..13].copy_from_slice(&account_index.to_be_bytes());
-        command[13..17].copy_from_slice(&nft_id.to_be_bytes());
-        command[17..49].copy_from_slice(&amount.to_be_bytes());
+        command = Command::BidNFT { nonce, account_index, nft_id, amount }.encode();
 
         command_sign = BabyJubjub::sign(&command, &secret_key_3);
         command_sign_formatted = [0 as u8;64];
@@ -120,12 +100,7 @@ fn bid_nft_works_has_bidder() {
         l1_tx_hash = U256::from(8);
         nonce = 4u64;
 
-        command = [0u8; 81];
-        command[0] = OP_DEPOSIT;
-        command[1..9].copy_from_slice(&nonce.to_be_bytes());
-        command[9..13].copy_from_slice(&account_index.to_be_bytes());
-        command[13..17].copy_from_slice(&token_index.to_be_bytes());
-        command[17..49].copy_from_slice(&amount.to_be_bytes());
+        command = Command::Deposit { nonce, account_index, token_index, amount }.encode();
 
         command_sign = BabyJubjub::sign(&command, &secret_key_1);
         command_sign_formatted = [0 as u8;64];
@@ -139,12 +114,7 @@ fn bid_nft_works_has_bidder() {
         amount = U256::from(2);
         nonce = 1u64;
 
-        command = [0u8; 81];
-        command[0] = OP_BID_NFT;
-        command[1..9].copy_from_slice(&nonce.to_be_bytes());
-        command[9..13].copy_from_slice(&account_index.to_be_bytes());
-        command[13..17].copy_from_slice(&nft_id.to_be_bytes());
-        command[17..49].copy_from_slice(&amount.to_be_bytes());
+        command = Command::BidNFT { nonce, account_index, nft_id, amount }.encode();
 
         command_sign = BabyJubjub::sign(&command, &secret_key_4);
         command_sign_formatted = [0 as u8;64];
@@ -153,12 +123,12 @@ fn bid_nft_works_has_bidder() {
 
         assert_ok!(SwapModule::bid_nft(Origin::signed(origin), command_sign_formatted, nft_id, amount, nonce));
 
-        let nft = NFTMap::get(&nft_id);
+        let nft = NFTMap::<Test>::get(&nft_id);
         assert_eq!(nft.0, 2u32);
         assert_eq!(nft.1, U256::from(2));
         assert_eq!(nft.2, Some(4u32));
-        assert_eq!(BalanceMap::get((3u32, token_index)), U256::from(10));
-        assert_eq!(BalanceMap::get((4u32, token_index)), U256::from(8));
+        assert_eq!(BalanceMap::<Test>::get((3u32, token_index)), U256::from(10));
+        assert_eq!(BalanceMap::<Test>::get((4u32, token_index)), U256::from(8));
     })
 }
 
@@ -191,12 +161,7 @@ fn bid_nft_works_no_bidder() {
         let mut l1_tx_hash = U256::from(0);
         let mut nonce = 1u64;
 
-        let mut command = [0u8; 81];
-        command[0] = OP_DEPOSIT;
-        command[1..9].copy_from_slice(&nonce.to_be_bytes());
-        command[9..13].copy_from_slice(&account_index.to_be_bytes());
-        command[13..17].copy_from_slice(&token_index.to_be_bytes());
-        command[17..49].copy_from_slice(&amount.to_be_bytes());
+        let mut command = Command::Deposit { nonce, account_index, token_index, amount }.encode();
 
         let mut command_sign = BabyJubjub::sign(&command, &secret_key_1);
         let mut command_sign_formatted :[u8; 64] = [0 as u8;64];
@@ -210,12 +175,7 @@ fn bid_nft_works_no_bidder() {
         l1_tx_hash = U256::from(1);
         nonce = 2u64;
 
-        command = [0u8; 81];
-        command[0] = OP_DEPOSIT_NFT;
-        command[1..9].copy_from_slice(&nonce.to_be_bytes());
-        command[9..13].copy_from_slice(&account_index.to_be_bytes());
-        command[13..17].copy_from_slice(&nft_id.to_be_bytes());
-        command[17..49].copy_from_slice(&l1_tx_hash.to_be_bytes());
+        command = Command::DepositNFT { nonce, account_index, nft_id, l1_tx_hash }.encode();
 
         command_sign = BabyJubjub::sign(&command, &secret_key_1);
         command_sign_formatted = [0 as u8;64];
@@ -236,12 +196,7 @@ fn bid_nft_works_no_bidder() {
         l1_tx_hash = U256::from(12);
         nonce = 3u64;
 
-        let mut command = [0u8; 81];
-        command[0] = OP_DEPOSIT;
-        command[1..9].copy_from_slice(&nonce.to_be_bytes());
-        command[9..13].copy_from_slice(&account_index.to_be_bytes());
-        command[13..17].copy_from_slice(&token_index.to_be_bytes());
-        command[17..49].copy_from_slice(&amount.to_be_bytes());
+        let mut command = Command::Deposit { nonce, account_index, token_index, amount }.encode();
 
         let mut command_sign = BabyJubjub::sign(&command, &secret_key_1);
         let mut command_sign_formatted :[u8; 64] = [0 as u8;64];
@@ -255,12 +210,7 @@ fn bid_nft_works_no_bidder() {
         amount = U256::from(1);
         nonce = 1u64;
 
-        command = [0u8; 81];
-        command[0] = OP_BID_NFT;
-        command[1..9].copy_from_slice(&nonce.to_be_bytes());
-        command[9..13].copy_from_slice(&account_index.to_be_bytes());
-        command[13..17].copy_from_slice(&nft_id.to_be_bytes());
-        command[17..49].copy_from_slice(&amount.to_be_bytes());
+        command = Command::BidNFT { nonce, account_index, nft_id, amount }.encode();
 
         command_sign = BabyJubjub::sign(&command, &secret_key_3);
         command_sign_formatted = [0 as u8;64];
@@ -269,11 +219,11 @@ fn bid_nft_works_no_bidder() {
 
         assert_ok!(SwapModule::bid_nft(Origin::signed(origin), command_sign_formatted, nft_id, amount, nonce));
 
-        let nft = NFTMap::get(&nft_id);
+        let nft = NFTMap::<Test>::get(&nft_id);
         assert_eq!(nft.0, 2u32);
         assert_eq!(nft.1, U256::from(1));
         assert_eq!(nft.2, Some(3u32));
-        assert_eq!(BalanceMap::get((3u32, token_index)), U256::from(9));
+        assert_eq!(BalanceMap::<Test>::get((3u32, token_index)), U256::from(9));
     })
 }
 
@@ -306,12 +256,7 @@ fn bid_nft_account_not_exists() {
         let mut l1_tx_hash = U256::from(0);
         let mut nonce = 1u64;
 
-        let mut command = [0u8; 81];
-        command[0] = OP_DEPOSIT;
-        command[1..9].copy_from_slice(&nonce.to_be_bytes());
-        command[9..13].copy_from_slice(&account_index.to_be_bytes());
-        command[13..17].copy_from_slice(&token_index.to_be_bytes());
-        command[17..49].copy_from_slice(&amount.to_be_bytes());
+        let mut command = Command::Deposit { nonce, account_index, token_index, amount }.encode();
 
         let mut command_sign = BabyJubjub::sign(&command, &secret_key_1);
         let mut command_sign_formatted :[u8; 64] = [0 as u8;64];
@@ -325,12 +270,7 @@ fn bid_nft_account_not_exists() {
         l1_tx_hash = U256::from(1);
         nonce = 2u64;
 
-        command = [0u8; 81];
-        command[0] = OP_DEPOSIT_NFT;
-        command[1..9].copy_from_slice(&nonce.to_be_bytes());
-        command[9..13].copy_from_slice(&account_index.to_be_bytes());
-        command[13..17].copy_from_slice(&nft_id.to_be_bytes());
-        command[17..49].copy_from_slice(&l1_tx_hash.to_be_bytes());
+        command = Command::DepositNFT { nonce, account_index, nft_id, l1_tx_hash }.encode();
 
         command_sign = BabyJubjub::sign(&command, &secret_key_1);
         command_sign_formatted = [0 as u8;64];
@@ -351,12 +291,7 @@ fn bid_nft_account_not_exists() {
         l1_tx_hash = U256::from(12);
         nonce = 3u64;
 
-        let mut command = [0u8; 81];
-        command[0] = OP_DEPOSIT;
-        command[1..9].copy_from_slice(&nonce.to_be_bytes());
-        command[9..13].copy_from_slice(&account_index.to_be_bytes());
-        command[13..17].copy_from_slice(&token_index.to_be_bytes());
-        command[17..49].copy_from_slice(&amount.to_be_bytes());
+        let mut command = Command::Deposit { nonce, account_index, token_index, amount }.encode();
 
         let mut command_sign = BabyJubjub::sign(&command, &secret_key_1);
         let mut command_sign_formatted :[u8; 64] = [0 as u8;64];
@@ -371,12 +306,7 @@ fn bid_nft_account_not_exists() {
         amount = U256::from(1);
         nonce = 1u64;
 
-        command = [0u8; 81];
-        command[0] = OP_BID_NFT;
-        command[1..9].copy_from_slice(&nonce.to_be_bytes());
-        command[9..13].copy_from_slice(&account_index.to_be_bytes());
-        command[13..17].copy_from_slice(&nft_id.to_be_bytes());
-        command[17..49].copy_from_slice(&amount.to_be_bytes());
+        command = Command::BidNFT { nonce, account_index, nft_id, amount }.encode();
 
         //There is no secret_key_4, so use secret_key_3 here
         command_sign = BabyJubjub::sign(&command, &secret_key_3);
@@ -417,12 +347,7 @@ fn bid_nft_invalid_nftindex() {
         let mut l1_tx_hash = U256::from(0);
         let mut nonce = 1u64;
 
-        let mut command = [0u8; 81];
-        command[0] = OP_DEPOSIT;
-        command[1..9].copy_from_slice(&nonce.to_be_bytes());
-        command[9..13].copy_from_slice(&account_index.to_be_bytes());
-        command[13..17].copy_from_slice(&token_index.to_be_bytes());
-        command[17..49].copy_from_slice(&amount.to_be_bytes());
+        let mut command = Command::Deposit { nonce, account_index, token_index, amount }.encode();
 
         let mut command_sign = BabyJubjub::sign(&command, &secret_key_1);
         let mut command_sign_formatted :[u8; 64] = [0 as u8;64];
@@ -436,12 +361,7 @@ fn bid_nft_invalid_nftindex() {
         l1_tx_hash = U256::from(1);
         nonce = 2u64;
 
-        command = [0u8; 81];
-        command[0] = OP_DEPOSIT_NFT;
-        command[1..9].copy_from_slice(&nonce.to_be_bytes());
-        command[9..13].copy_from_slice(&account_index.to_be_bytes());
-        command[13..17].copy_from_slice(&nft_id.to_be_bytes());
-        command[17..49].copy_from_slice(&l1_tx_hash.to_be_bytes());
+        command = Command::DepositNFT { nonce, account_index, nft_id, l1_tx_hash }.encode();
 
         command_sign = BabyJubjub::sign(&command, &secret_key_1);
         command_sign_formatted = [0 as u8;64];
@@ -462,12 +382,7 @@ fn bid_nft_invalid_nftindex() {
         l1_tx_hash = U256::from(12);
         nonce = 3u64;
 
-        let mut command = [0u8; 81];
-        command[0] = OP_DEPOSIT;
-        command[1..9].copy_from_slice(&nonce.to_be_bytes());
-        command[9..13].copy_from_slice(&account_index.to_be_bytes());
-        command[13..17].copy_from_slice(&token_index.to_be_bytes());
-        command[17..49].copy_from_slice(&amount.to_be_bytes());
+        let mut command = Command::Deposit { nonce, account_index, token_index, amount }.encode();
 
         let mut command_sign = BabyJubjub::sign(&command, &secret_key_1);
         let mut command_sign_formatted :[u8; 64] = [0 as u8;64];
@@ -483,12 +398,7 @@ fn bid_nft_invalid_nftindex() {
         amount = U256::from(1);
         nonce = 1u64;
 
-        command = [0u8; 81];
-        command[0] = OP_BID_NFT;
-        command[1..9].copy_from_slice(&nonce.to_be_bytes());
-        command[9..13].copy_from_slice(&account_index.to_be_bytes());
-        command[13..17].copy_from_slice(&nft_id.to_be_bytes());
-        command[17..49].copy_from_slice(&amount.to_be_bytes());
+        command = Command::BidNFT { nonce, account_index, nft_id, amount }.encode();
 
         command_sign = BabyJubjub::sign(&command, &secret_key_3);
         command_sign_formatted = [0 as u8;64];
@@ -501,12 +411,7 @@ fn bid_nft_invalid_nftindex() {
         //nft_id should not be zero
         nft_id = 0u32;
 
-        command = [0u8; 81];
-        command[0] = OP_BID_NFT;
-        command[1..9].copy_from_slice(&nonce.to_be_bytes());
-        command[9..13].copy_from_slice(&account_index.to_be_bytes());
-        command[13..17].copy_from_slice(&nft_id.to_be_bytes());
-        command[17..49].copy_from_slice(&amount.to_be_bytes());
+        command = Command::BidNFT { nonce, account_index, nft_id, amount }.encode();
 
         command_sign = BabyJubjub::sign(&command, &secret_key_3);
         command_sign_formatted = [0 as u8;64];
@@ -546,12 +451,7 @@ fn bid_nft_invalid_amount() {
         let mut l1_tx_hash = U256::from(0);
         let mut nonce = 1u64;
 
-        let mut command = [0u8; 81];
-        command[0] = OP_DEPOSIT;
-        command[1..9].copy_from_slice(&nonce.to_be_bytes());
-        command[9..13].copy_from_slice(&account_index.to_be_bytes());
-        command[13..17].copy_from_slice(&token_index.to_be_bytes());
-        command[17..49].copy_from_slice(&amount.to_be_bytes());
+        let mut command = Command::Deposit { nonce, account_index, token_index, amount }.encode();
 
         let mut command_sign = BabyJubjub::sign(&command, &secret_key_1);
         let mut command_sign_formatted :[u8; 64] = [0 as u8;64];
@@ -565,12 +465,7 @@ fn bid_nft_invalid_amount() {
         l1_tx_hash = U256::from(1);
         nonce = 2u64;
 
-        command = [0u8; 81];
-        command[0] = OP_DEPOSIT_NFT;
-        command[1..9].copy_from_slice(&nonce.to_be_bytes());
-        command[9..13].copy_from_slice(&account_index.to_be_bytes());
-        command[13..17].copy_from_slice(&nft_id.to_be_bytes());
-        command[17..49].copy_from_slice(&l1_tx_hash.to_be_bytes());
+        command = Command::DepositNFT { nonce, account_index, nft_id, l1_tx_hash }.encode();
 
         command_sign = BabyJubjub::sign(&command, &secret_key_1);
         command_sign_formatted = [0 as u8;64];
@@ -591,12 +486,7 @@ fn bid_nft_invalid_amount() {
         l1_tx_hash = U256::from(12);
         nonce = 3u64;
 
-        let mut command = [0u8; 81];
-        command[0] = OP_DEPOSIT;
-        command[1..9].copy_from_slice(&nonce.to_be_bytes());
-        command[9..13].copy_from_slice(&account_index.to_be_bytes());
-        command[13..17].copy_from_slice(&token_index.to_be_bytes());
-        command[17..49].copy_from_slice(&amount.to_be_bytes());
+        let mut command = Command::Deposit { nonce, account_index, token_index, amount }.encode();
 
         let mut command_sign = BabyJubjub::sign(&command, &secret_key_1);
         let mut command_sign_formatted :[u8; 64] = [0 as u8;64];
@@ -611,12 +501,7 @@ fn bid_nft_invalid_amount() {
         amount = U256::from(1) << 250;
         nonce = 1u64;
 
-        command = [0u8; 81];
-        command[0] = OP_BID_NFT;
-        command[1..9].copy_from_slice(&nonce.to_be_bytes());
-        command[9..13].copy_from_slice(&account_index.to_be_bytes());
-        command[13..17].copy_from_slice(&nft_id.to_be_bytes());
-        command[17..49].copy_from_slice(&amount.to_be_bytes());
+        command = Command::BidNFT { nonce, account_index, nft_id, amount }.encode();
 
         command_sign = BabyJubjub::sign(&command, &secret_key_3);
         command_sign_formatted = [0 as u8;64];
@@ -628,12 +513,7 @@ fn bid_nft_invalid_amount() {
         //BidNFT for accountIndex 3, caller is accountIndex 3
         amount = U256::from(1);
 
-        command = [0u8; 81];
-        command[0] = OP_BID_NFT;
-        command[1..9].copy_from_slice(&nonce.to_be_bytes());
-        command[9..13].copy_from_slice(&account_index.to_be_bytes());
-        command[13..17].copy_from_slice(&nft_id.to_be_bytes());
-        command[17..49].copy_from_slice(&amount.to_be_bytes());
+        command = Command::BidNFT { nonce, account_index, nft_id, amount }.encode();
 
         command_sign = BabyJubjub::sign(&command, &secret_key_3);
         command_sign_formatted = [0 as u8;64];
@@ -646,12 +526,7 @@ fn bid_nft_invalid_amount() {
         //Amount must be greater than last biddingAmount
         amount = U256::from(1);
 
-        command = [0u8; 81];
-        command[0] = OP_BID_NFT;
-        command[1..9].copy_from_slice(&nonce.to_be_bytes());
-        command[9..13].copy_from_slice(&account_index.to_be_bytes());
-        command[13..17].copy_from_slice(&nft_id.to_be_bytes());
-        command[17..49].copy_from_slice(&amount.to_be_bytes());
+        command = Command::BidNFT { nonce, account_index, nft_id, amount }.encode();
 
         command_sign = BabyJubjub::sign(&command, &secret_key_3);
         command_sign_formatted = [0 as u8;64];
@@ -691,12 +566,7 @@ fn bid_nft_nonce_inconsistent() {
         let mut l1_tx_hash = U256::from(0);
         let mut nonce = 1u64;
 
-        let mut command = [0u8; 81];
-        command[0] = OP_DEPOSIT;
-        command[1..9].copy_from_slice(&nonce.to_be_bytes());
-        command[9..13].copy_from_slice(&account_index.to_be_bytes());
-        command[13..17].copy_from_slice(&token_index.to_be_bytes());
-        command[17..49].copy_from_slice(&amount.to_be_bytes());
+        let mut command = Command::Deposit { nonce, account_index, token_index, amount }.encode();
 
         let mut command_sign = BabyJubjub::sign(&command, &secret_key_1);
         let mut command_sign_formatted :[u8; 64] = [0 as u8;64];
@@ -710,12 +580,7 @@ fn bid_nft_nonce_inconsistent() {
         l1_tx_hash = U256::from(1);
         nonce = 2u64;
 
-        command = [0u8; 81];
-        command[0] = OP_DEPOSIT_NFT;
-        command[1..9].copy_from_slice(&nonce.to_be_bytes());
-        command[9..13].copy_from_slice(&account_index.to_be_bytes());
-        command[13..17].copy_from_slice(&nft_id.to_be_bytes());
-        command[17..49].copy_from_slice(&l1_tx_hash.to_be_bytes());
+        command = Command::DepositNFT { nonce, account_index, nft_id, l1_tx_hash }.encode();
 
         command_sign = BabyJubjub::sign(&command, &secret_key_1);
         command_sign_formatted = [0 as u8;64];
@@ -736,12 +601,7 @@ fn bid_nft_nonce_inconsistent() {
         l1_tx_hash = U256::from(12);
         nonce = 3u64;
 
-        let mut command = [0u8; 81];
-        command[0] = OP_DEPOSIT;
-        command[1..9].copy_from_slice(&nonce.to_be_bytes());
-        command[9..13].copy_from_slice(&account_index.to_be_bytes());
-        command[13..17].copy_from_slice(&token_index.to_be_bytes());
-        command[17..49].copy_from_slice(&amount.to_be_bytes());
+        let mut command = Command::Deposit { nonce, account_index, token_index, amount }.encode();
 
         let mut command_sign = BabyJubjub::sign(&command, &secret_key_1);
         let mut command_sign_formatted :[u8; 64] = [0 as u8;64];
@@ -756,12 +616,7 @@ fn bid_nft_nonce_inconsistent() {
         //True nonce is 1u64
         nonce = 2u64;
 
-        command = [0u8; 81];
-        command[0] = OP_BID_NFT;
-        command[1..9].copy_from_slice(&nonce.to_be_bytes());
-        command[9..13].copy_from_slice(&account_index.to_be_bytes());
-        command[13..17].copy_from_slice(&nft_id.to_be_bytes());
-        command[17..49].copy_from_slice(&amount.to_be_bytes());
+        command = Command::BidNFT { nonce, account_index, nft_id, amount }.encode();
 
         command_sign = BabyJubjub::sign(&command, &secret_key_3);
         command_sign_formatted = [0 as u8;64];
@@ -801,12 +656,7 @@ fn bid_nft_invalid_signature() {
         let mut l1_tx_hash = U256::from(0);
         let mut nonce = 1u64;
 
-        let mut command = [0u8; 81];
-        command[0] = OP_DEPOSIT;
-        command[1..9].copy_from_slice(&nonce.to_be_bytes());
-        command[9..13].copy_from_slice(&account_index.to_be_bytes());
-        command[13..17].copy_from_slice(&token_index.to_be_bytes());
-        command[17..49].copy_from_slice(&amount.to_be_bytes());
+        let mut command = Command::Deposit { nonce, account_index, token_index, amount }.encode();
 
         let mut command_sign = BabyJubjub::sign(&command, &secret_key_1);
         let mut command_sign_formatted :[u8; 64] = [0 as u8;64];
@@ -820,12 +670,7 @@ fn bid_nft_invalid_signature() {
         l1_tx_hash = U256::from(1);
         nonce = 2u64;
 
-        command = [0u8; 81];
-        command[0] = OP_DEPOSIT_NFT;
-        command[1..9].copy_from_slice(&nonce.to_be_bytes());
-        command[9..13].copy_from_slice(&account_index.to_be_bytes());
-        command[13..17].copy_from_slice(&nft_id.to_be_bytes());
-        command[17..49].copy_from_slice(&l1_tx_hash.to_be_bytes());
+        command = Command::DepositNFT { nonce, account_index, nft_id, l1_tx_hash }.encode();
 
         command_sign = BabyJubjub::sign(&command, &secret_key_1);
         command_sign_formatted = [0 as u8;64];
@@ -846,12 +691,7 @@ fn bid_nft_invalid_signature() {
         l1_tx_hash = U256::from(12);
         nonce = 3u64;
 
-        let mut command = [0u8; 81];
-        command[0] = OP_DEPOSIT;
-        command[1..9].copy_from_slice(&nonce.to_be_bytes());
-        command[9..13].copy_from_slice(&account_index.to_be_bytes());
-        command[13..17].copy_from_slice(&token_index.to_be_bytes());
-        command[17..49].copy_from_slice(&amount.to_be_bytes());
+        let mut command = Command::Deposit { nonce, account_index, token_index, amount }.encode();
 
         let mut command_sign = BabyJubjub::sign(&command, &secret_key_1);
         let mut command_sign_formatted :[u8; 64] = [0 as u8;64];
@@ -865,12 +705,7 @@ fn bid_nft_invalid_signature() {
         amount = U256::from(1);
         nonce = 1u64;
 
-        command = [0u8; 81];
-        command[0] = OP_BID_NFT;
-        command[1..9].copy_from_slice(&nonce.to_be_bytes());
-        command[9..13].copy_from_slice(&account_index.to_be_bytes());
-        command[13..17].copy_from_slice(&nft_id.to_be_bytes());
-        command[17..49].copy_from_slice(&amount.to_be_bytes());
+        command = Command::BidNFT { nonce, account_index, nft_id, amount }.encode();
 
         command_sign = BabyJubjub::sign(&command, &secret_key_3);
         command_sign_formatted = [0 as u8;64];
@@ -913,12 +748,7 @@ fn bid_nft_invalid_nftindex_no_owner() {
         let l1_tx_hash = U256::from(12);
         let nonce = 1u64;
 
-        let mut command = [0u8; 81];
-        command[0] = OP_DEPOSIT;
-        command[1..9].copy_from_slice(&nonce.to_be_bytes());
-        command[9..13].copy_from_slice(&account_index.to_be_bytes());
-        command[13..17].copy_from_slice(&token_index.to_be_bytes());
-        command[17..49].copy_from_slice(&amount.to_be_bytes());
+        let mut command = Command::Deposit { nonce, account_index, token_index, amount }.encode();
 
         let mut command_sign = BabyJubjub::sign(&command, &secret_key_1);
         let mut command_sign_formatted :[u8; 64] = [0 as u8;64];
@@ -933,12 +763,7 @@ fn bid_nft_invalid_nftindex_no_owner() {
         let nft_id = 4u32;
         amount = U256::from(1);
 
-        command = [0u8; 81];
-        command[0] = OP_BID_NFT;
-        command[1..9].copy_from_slice(&nonce.to_be_bytes());
-        command[9..13].copy_from_slice(&account_index.to_be_bytes());
-        command[13..17].copy_from_slice(&nft_id.to_be_bytes());
-        command[17..49].copy_from_slice(&amount.to_be_bytes());
+        command = Command::BidNFT { nonce, account_index, nft_id, amount }.encode();
 
         command_sign = BabyJubjub::sign(&command, &secret_key_2);
         command_sign_formatted = [0 as u8;64];
@@ -978,12 +803,7 @@ fn bid_nft_has_bidder_balance_overflow() {
         let mut l1_tx_hash = U256::from(0);
         let mut nonce = 1u64;
 
-        let mut command = [0u8; 81];
-        command[0] = OP_DEPOSIT;
-        command[1..9].copy_from_slice(&nonce.to_be_bytes());
-        command[9..13].copy_from_slice(&account_index.to_be_bytes());
-        command[13..17].copy_from_slice(&token_index.to_be_bytes());
-        command[17..49].copy_from_slice(&amount.to_be_bytes());
+        let mut command = Command::Deposit { nonce, account_index, token_index, amount }.encode();
 
         let mut command_sign = BabyJubjub::sign(&command, &secret_key_1);
         let mut command_sign_formatted :[u8; 64] = [0 as u8;64];
@@ -997,12 +817,7 @@ fn bid_nft_has_bidder_balance_overflow() {
         l1_tx_hash = U256::from(1);
         nonce = 2u64;
 
-        command = [0u8; 81];
-        command[0] = OP_DEPOSIT_NFT;
-        command[1..9].copy_from_slice(&nonce.to_be_bytes());
-        command[9..13].copy_from_slice(&account_index.to_be_bytes());
-        command[13..17].copy_from_slice(&nft_id.to_be_bytes());
-        command[17..49].copy_from_slice(&l1_tx_hash.to_be_bytes());
+        command = Command::DepositNFT { nonce, account_index, nft_id, l1_tx_hash }.encode();
 
         command_sign = BabyJubjub::sign(&command, &secret_key_1);
         command_sign_formatted = [0 as u8;64];
@@ -1023,12 +838,7 @@ fn bid_nft_has_bidder_balance_overflow() {
         l1_tx_hash = U256::from(12);
         nonce = 3u64;
 
-        command = [0u8; 81];
-        command[0] = OP_DEPOSIT;
-        command[1..9].copy_from_slice(&nonce.to_be_bytes());
-        command[9..13].copy_from_slice(&account_index.to_be_bytes());
-        command[13..17].copy_from_slice(&token_index.to_be_bytes());
-        command[17..49].copy_from_slice(&amount.to_be_bytes());
+        command = Command::Deposit { nonce, account_index, token_index, amount }.encode();
 
         command_sign = BabyJubjub::sign(&command, &secret_key_1);
         command_sign_formatted = [0 as u8;64];
@@ -1042,12 +852,7 @@ fn bid_nft_has_bidder_balance_overflow() {
         amount = U256::from(1);
         nonce = 1u64;
 
-        command = [0u8; 81];
-        command[0] = OP_BID_NFT;
-        command[1..9].copy_from_slice(&nonce.to_be_bytes());
-        command[9..13].copy_from_slice(&account_index.to_be_bytes());
-        command[13..17].copy_from_slice(&nft_id.to_be_bytes());
-        command[17..49].copy_from_slice(&amount.to_be_bytes());
+        command = Command::BidNFT { nonce, account_index, nft_id, amount }.encode();
 
         command_sign = BabyJubjub::sign(&command, &secret_key_3);
         command_sign_formatted = [0 as u8;64];
@@ -1063,12 +868,7 @@ fn bid_nft_has_bidder_balance_overflow() {
         l1_tx_hash = U256::from(7);
         nonce = 4u64;
 
-        command = [0u8; 81];
-        command[0] = OP_DEPOSIT;
-        command[1..9].copy_from_slice(&nonce.to_be_bytes());
-        command[9..13].copy_from_slice(&account_index.to_be_bytes());
-        command[13..17].copy_from_slice(&token_index.to_be_bytes());
-        command[17..49].copy_from_slice(&amount.to_be_bytes());
+        command = Command::Deposit { nonce, account_index, token_index, amount }.encode();
 
         command_sign = BabyJubjub::sign(&command, &secret_key_1);
         command_sign_formatted = [0 as u8;64];
@@ -1090,12 +890,7 @@ fn bid_nft_has_bidder_balance_overflow() {
         l1_tx_hash = U256::from(8);
         nonce = 5u64;
 
-        command = [0u8; 81];
-        command[0] = OP_DEPOSIT;
-        command[1..9].copy_from_slice(&nonce.to_be_bytes());
-        command[9..13].copy_from_slice(&account_index.to_be_bytes());
-        command[13..17].copy_from_slice(&token_index.to_be_bytes());
-        command[17..49].copy_from_slice(&amount.to_be_bytes());
+        command = Command::Deposit { nonce, account_index, token_index, amount }.encode();
 
         command_sign = BabyJubjub::sign(&command, &secret_key_1);
         command_sign_formatted = [0 as u8;64];
@@ -1110,12 +905,7 @@ fn bid_nft_has_bidder_balance_overflow() {
         amount = U256::from(2);
         nonce = 1u64;
 
-        command = [0u8; 81];
-        command[0] = OP_BID_NFT;
-        command[1..9].copy_from_slice(&nonce.to_be_bytes());
-        command[9..13].copy_from_slice(&account_index.to_be_bytes());
-        command[13..17].copy_from_slice(&nft_id.to_be_bytes());
-        command[17..49].copy_from_slice(&amount.to_be_bytes());
+        command = Command::BidNFT { nonce, account_index, nft_id, amount }.encode();
 
         command_sign = BabyJubjub::sign(&command, &secret_key_4);
         command_sign_formatted = [0 as u8;64];
@@ -1155,12 +945,7 @@ fn bid_nft_balance_not_enough() {
         let mut l1_tx_hash = U256::from(0);
         let mut nonce = 1u64;
 
-        let mut command = [0u8; 81];
-        command[0] = OP_DEPOSIT;
-        command[1..9].copy_from_slice(&nonce.to_be_bytes());
-        command[9..13].copy_from_slice(&account_index.to_be_bytes());
-        command[13..17].copy_from_slice(&token_index.to_be_bytes());
-        command[17..49].copy_from_slice(&amount.to_be_bytes());
+        let mut command = Command::Deposit { nonce, account_index, token_index, amount }.encode();
 
         let mut command_sign = BabyJubjub::sign(&command, &secret_key_1);
         let mut command_sign_formatted :[u8; 64] = [0 as u8;64];
@@ -1174,12 +959,7 @@ fn bid_nft_balance_not_enough() {
         l1_tx_hash = U256::from(1);
         nonce = 2u64;
 
-        command = [0u8; 81];
-        command[0] = OP_DEPOSIT_NFT;
-        command[1..9].copy_from_slice(&nonce.to_be_bytes());
-        command[9..13].copy_from_slice(&account_index.to_be_bytes());
-        command[13..17].copy_from_slice(&nft_id.to_be_bytes());
-        command[17..49].copy_from_slice(&l1_tx_hash.to_be_bytes());
+        command = Command::DepositNFT { nonce, account_index, nft_id, l1_tx_hash }.encode();
 
         command_sign = BabyJubjub::sign(&command, &secret_key_1);
         command_sign_formatted = [0 as u8;64];
@@ -1200,12 +980,7 @@ fn bid_nft_balance_not_enough() {
         l1_tx_hash = U256::from(12);
         nonce = 3u64;
 
-        let mut command = [0u8; 81];
-        command[0] = OP_DEPOSIT;
-        command[1..9].copy_from_slice(&nonce.to_be_bytes());
-        command[9..13].copy_from_slice(&account_index.to_be_bytes());
-        command[13..17].copy_from_slice(&token_index.to_be_bytes());
-        command[17..49].copy_from_slice(&amount.to_be_bytes());
+        let mut command = Command::Deposit { nonce, account_index, token_index, amount }.encode();
 
         let mut command_sign = BabyJubjub::sign(&command, &secret_key_1);
         let mut command_sign_formatted :[u8; 64] = [0 as u8;64];
@@ -1220,12 +995,7 @@ fn bid_nft_balance_not_enough() {
         amount = U256::from(11);
         nonce = 1u64;
 
-        command = [0u8; 81];
-        command[0] = OP_BID_NFT;
-        command[1..9].copy_from_slice(&nonce.to_be_bytes());
-        command[9..13].copy_from_slice(&account_index.to_be_bytes());
-        command[13..17].copy_from_slice(&nft_id.to_be_bytes());
-        command[17..49].copy_from_slice(&amount.to_be_bytes());
+        command = Command::BidNFT { nonce, account_index, nft_id, amount }.encode();
 
         command_sign = BabyJubjub::sign(&command, &secret_key_3);
         command_sign_formatted = [0 as u8;64];
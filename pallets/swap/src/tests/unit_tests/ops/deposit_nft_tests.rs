@@ -29,12 +29,7 @@ fn deposit_nft_works() {
         let mut l1_tx_hash = U256::from(0);
         let mut nonce = 1u64;
 
-        let mut command = [0u8; 81];
-        command[0] = OP_DEPOSIT;
-        command[1..9].copy_from_slice(&nonce.to_be_bytes());
-        command[9..13].copy_from_slice(&account_index.to_be_bytes());
-        command[13..17].copy_from_slice(&token_index.to_be_bytes());
-        command[17..49].copy_from_slice(&amount.to_be_bytes());
+        let mut command = Command::Deposit { nonce, account_index, token_index, amount }.encode();
 
         let mut command_sign = BabyJubjub::sign(&command, &secret_key_1);
         let mut command_sign_formatted :[u8; 64] = [0 as u8;64];
@@ -48,12 +43,7 @@ fn deposit_nft_works() {
         l1_tx_hash = U256::from(1);
         nonce = 2u64;
 
-        command = [0u8; 81];
-        command[0] = OP_DEPOSIT_NFT;
-        command[1..9].copy_from_slice(&nonce.to_be_bytes());
-        command[9..13].copy_from_slice(&account_index.to_be_bytes());
-        command[13..17].copy_from_slice(&nft_id.to_be_bytes());
-        command[17..49].copy_from_slice(&l1_tx_hash.to_be_bytes());
+        command = Command::DepositNFT { nonce, account_index, nft_id, l1_tx_hash }.encode();
 
         command_sign = BabyJubjub::sign(&command, &secret_key_1);
         command_sign_formatted = [0 as u8;64];
@@ -62,7 +52,7 @@ fn deposit_nft_works() {
 
         assert_ok!(SwapModule::deposit_nft(Origin::signed(origin), command_sign_formatted, account_index, nft_id, l1_tx_hash, nonce));
 
-        let nft = NFTMap::get(&nft_id);
+        let nft = NFTMap::<Test>::get(&nft_id);
         assert_eq!(nft.0, account_index);
         assert_eq!(nft.1, U256::from(0));
         assert_eq!(nft.2, None);
@@ -98,12 +88,7 @@ fn deposit_nft_noaccess() {
         let l1_tx_hash = U256::from(0);
         let nonce = 1u64;
 
-        let mut command = [0u8; 81];
-        command[0] = OP_DEPOSIT;
-        command[1..9].copy_from_slice(&nonce.to_be_bytes());
-        command[9..13].copy_from_slice(&account_index.to_be_bytes());
-        command[13..17].copy_from_slice(&token_index.to_be_bytes());
-        command[17..49].copy_from_slice(&amount.to_be_bytes());
+        let mut command = Command::Deposit { nonce, account_index, token_index, amount }.encode();
 
         let mut command_sign = BabyJubjub::sign(&command, &secret_key_1);
         let mut command_sign_formatted :[u8; 64] = [0 as u8;64];
@@ -119,12 +104,7 @@ fn deposit_nft_noaccess() {
         let l1_tx_hash = U256::from(1);
         let nonce = 1u64;
 
-        command = [0u8; 81];
-        command[0] = OP_DEPOSIT_NFT;
-        command[1..9].copy_from_slice(&nonce.to_be_bytes());
-        command[9..13].copy_from_slice(&account_index.to_be_bytes());
-        command[13..17].copy_from_slice(&nft_id.to_be_bytes());
-        command[17..49].copy_from_slice(&l1_tx_hash.to_be_bytes());
+        command = Command::DepositNFT { nonce, account_index, nft_id, l1_tx_hash }.encode();
 
         command_sign = BabyJubjub::sign(&command, &secret_key_0);
         command_sign_formatted = [0 as u8;64];
@@ -152,12 +132,7 @@ fn deposit_nft_account_not_exists() {
         let l1_tx_hash = U256::from(1);
         let nonce = 0u64;
 
-        let mut command = [0u8; 81];
-        command[0] = OP_DEPOSIT_NFT;
-        command[1..9].copy_from_slice(&nonce.to_be_bytes());
-        command[9..13].copy_from_slice(&account_index.to_be_bytes());
-        command[13..17].copy_from_slice(&nft_id.to_be_bytes());
-        command[17..49].copy_from_slice(&l1_tx_hash.to_be_bytes());
+        let command = Command::DepositNFT { nonce, account_index, nft_id, l1_tx_hash }.encode();
 
         //there is no secret_key_1, so use secret_key_0
         let command_sign = BabyJubjub::sign(&command, &secret_key_0);
@@ -198,12 +173,7 @@ fn deposit_nft_invalid_account() {
         let l1_tx_hash = U256::from(0);
         let nonce = 1u64;
 
-        let mut command = [0u8; 81];
-        command[0] = OP_DEPOSIT;
-        command[1..9].copy_from_slice(&nonce.to_be_bytes());
-        command[9..13].copy_from_slice(&account_index.to_be_bytes());
-        command[13..17].copy_from_slice(&token_index.to_be_bytes());
-        command[17..49].copy_from_slice(&amount.to_be_bytes());
+        let mut command = Command::Deposit { nonce, account_index, token_index, amount }.encode();
 
         let mut command_sign = BabyJubjub::sign(&command, &secret_key_1);
         let mut command_sign_formatted :[u8; 64] = [0 as u8;64];
@@ -220,12 +190,7 @@ fn deposit_nft_invalid_account() {
         let l1_tx_hash = U256::from(1);
         let nonce = 2u64;
 
-        command = [0u8; 81];
-        command[0] = OP_DEPOSIT_NFT;
-        command[1..9].copy_from_slice(&nonce.to_be_bytes());
-        command[9..13].copy_from_slice(&account_index.to_be_bytes());
-        command[13..17].copy_from_slice(&nft_id.to_be_bytes());
-        command[17..49].copy_from_slice(&l1_tx_hash.to_be_bytes());
+        command = Command::DepositNFT { nonce, account_index, nft_id, l1_tx_hash }.encode();
 
         command_sign = BabyJubjub::sign(&command, &secret_key_1);
         command_sign_formatted = [0 as u8;64];
@@ -265,12 +230,7 @@ fn deposit_nft_invalid_nftindex() {
         let mut l1_tx_hash = U256::from(0);
         let mut nonce = 1u64;
 
-        let mut command = [0u8; 81];
-        command[0] = OP_DEPOSIT;
-        command[1..9].copy_from_slice(&nonce.to_be_bytes());
-        command[9..13].copy_from_slice(&account_index.to_be_bytes());
-        command[13..17].copy_from_slice(&token_index.to_be_bytes());
-        command[17..49].copy_from_slice(&amount.to_be_bytes());
+        let mut command = Command::Deposit { nonce, account_index, token_index, amount }.encode();
 
         let mut command_sign = BabyJubjub::sign(&command, &secret_key_1);
         let mut command_sign_formatted :[u8; 64] = [0 as u8;64];
@@ -285,12 +245,7 @@ fn deposit_nft_invalid_nftindex() {
         l1_tx_hash = U256::from(1);
         nonce = 2u64;
 
-        command = [0u8; 81];
-        command[0] = OP_DEPOSIT_NFT;
-        command[1..9].copy_from_slice(&nonce.to_be_bytes());
-        command[9..13].copy_from_slice(&account_index.to_be_bytes());
-        command[13..17].copy_from_slice(&nft_id.to_be_bytes());
-        command[17..49].copy_from_slice(&l1_tx_hash.to_be_bytes());
+        command = Command::DepositNFT { nonce, account_index, nft_id, l1_tx_hash }.encode();
 
         command_sign = BabyJubjub::sign(&command, &secret_key_1);
         command_sign_formatted = [0 as u8;64];
@@ -304,12 +259,7 @@ fn deposit_nft_invalid_nftindex() {
         nft_id = 0;
         l1_tx_hash = U256::from(2);
 
-        command = [0u8; 81];
-        command[0] = OP_DEPOSIT_NFT;
-        command[1..9].copy_from_slice(&nonce.to_be_bytes());
-        command[9..13].copy_from_slice(&account_index.to_be_bytes());
-        command[13..17].copy_from_slice(&nft_id.to_be_bytes());
-        command[17..49].copy_from_slice(&l1_tx_hash.to_be_bytes());
+        command = Command::DepositNFT { nonce, account_index, nft_id, l1_tx_hash }.encode();
 
         command_sign = BabyJubjub::sign(&command, &secret_key_1);
         command_sign_formatted = [0 as u8;64];
@@ -323,12 +273,7 @@ fn deposit_nft_invalid_nftindex() {
         l1_tx_hash = U256::from(3);
         nonce = 2u64;
 
-        command = [0u8; 81];
-        command[0] = OP_DEPOSIT_NFT;
-        command[1..9].copy_from_slice(&nonce.to_be_bytes());
-        command[9..13].copy_from_slice(&account_index.to_be_bytes());
-        command[13..17].copy_from_slice(&nft_id.to_be_bytes());
-        command[17..49].copy_from_slice(&l1_tx_hash.to_be_bytes());
+        command = Command::DepositNFT { nonce, account_index, nft_id, l1_tx_hash }.encode();
 
         command_sign = BabyJubjub::sign(&command, &secret_key_1);
         command_sign_formatted = [0 as u8;64];
@@ -342,12 +287,7 @@ fn deposit_nft_invalid_nftindex() {
         l1_tx_hash = U256::from(4);
         nonce = 3u64;
 
-        command = [0u8; 81];
-        command[0] = OP_DEPOSIT_NFT;
-        command[1..9].copy_from_slice(&nonce.to_be_bytes());
-        command[9..13].copy_from_slice(&account_index.to_be_bytes());
-        command[13..17].copy_from_slice(&nft_id.to_be_bytes());
-        command[17..49].copy_from_slice(&l1_tx_hash.to_be_bytes());
+        command = Command::DepositNFT { nonce, account_index, nft_id, l1_tx_hash }.encode();
 
         command_sign = BabyJubjub::sign(&command, &secret_key_1);
         command_sign_formatted = [0 as u8;64];
@@ -387,12 +327,7 @@ fn deposit_nft_l1tx_exists() {
         let mut l1_tx_hash = U256::from(0);
         let mut nonce = 1u64;
 
-        let mut command = [0u8; 81];
-        command[0] = OP_DEPOSIT;
-        command[1..9].copy_from_slice(&nonce.to_be_bytes());
-        command[9..13].copy_from_slice(&account_index.to_be_bytes());
-        command[13..17].copy_from_slice(&token_index.to_be_bytes());
-        command[17..49].copy_from_slice(&amount.to_be_bytes());
+        let mut command = Command::Deposit { nonce, account_index, token_index, amount }.encode();
 
         let mut command_sign = BabyJubjub::sign(&command, &secret_key_1);
         let mut command_sign_formatted :[u8; 64] = [0 as u8;64];
@@ -406,12 +341,7 @@ fn deposit_nft_l1tx_exists() {
         l1_tx_hash = U256::from(1);
         nonce = 2u64;
 
-        command = [0u8; 81];
-        command[0] = OP_DEPOSIT_NFT;
-        command[1..9].copy_from_slice(&nonce.to_be_bytes());
-        command[9..13].copy_from_slice(&account_index.to_be_bytes());
-        command[13..17].copy_from_slice(&nft_id.to_be_bytes());
-        command[17..49].copy_from_slice(&l1_tx_hash.to_be_bytes());
+        command = Command::DepositNFT { nonce, account_index, nft_id, l1_tx_hash }.encode();
 
         command_sign = BabyJubjub::sign(&command, &secret_key_1);
         command_sign_formatted = [0 as u8;64];
@@ -455,12 +385,7 @@ fn deposit_nft_nonce_inconsistent() {
         let mut l1_tx_hash = U256::from(0);
         let mut nonce = 1u64;
 
-        let mut command = [0u8; 81];
-        command[0] = OP_DEPOSIT;
-        command[1..9].copy_from_slice(&nonce.to_be_bytes());
-        command[9..13].copy_from_slice(&account_index.to_be_bytes());
-        command[13..17].copy_from_slice(&token_index.to_be_bytes());
-        command[17..49].copy_from_slice(&amount.to_be_bytes());
+        let mut command = Command::Deposit { nonce, account_index, token_index, amount }.encode();
 
         let mut command_sign = BabyJubjub::sign(&command, &secret_key_1);
         let mut command_sign_formatted :[u8; 64] = [0 as u8;64];
@@ -475,12 +400,7 @@ fn deposit_nft_nonce_inconsistent() {
         //True nonce is 2u64
         nonce = 3u64;
 
-        command = [0u8; 81];
-        command[0] = OP_DEPOSIT_NFT;
-        command[1..9].copy_from_slice(&nonce.to_be_bytes());
-        command[9..13].copy_from_slice(&account_index.to_be_bytes());
-        command[13..17].copy_from_slice(&nft_id.to_be_bytes());
-        command[17..49].copy_from_slice(&l1_tx_hash.to_be_bytes());
+        command = Command::DepositNFT { nonce, account_index, nft_id, l1_tx_hash }.encode();
 
         command_sign = BabyJubjub::sign(&command, &secret_key_1);
         command_sign_formatted = [0 as u8;64];
@@ -520,12 +440,7 @@ fn deposit_nft_invalid_signature() {
         let mut l1_tx_hash = U256::from(0);
         let mut nonce = 1u64;
 
-        let mut command = [0u8; 81];
-        command[0] = OP_DEPOSIT;
-        command[1..9].copy_from_slice(&nonce.to_be_bytes());
-        command[9..13].copy_from_slice(&account_index.to_be_bytes());
-        command[13..17].copy_from_slice(&token_index.to_be_bytes());
-        command[17..49].copy_from_slice(&amount.to_be_bytes());
+        let mut command = Command::Deposit { nonce, account_index, token_index, amount }.encode();
 
         let mut command_sign = BabyJubjub::sign(&command, &secret_key_1);
         let mut command_sign_formatted :[u8; 64] = [0 as u8;64];
@@ -539,12 +454,7 @@ fn deposit_nft_invalid_signature() {
         l1_tx_hash = U256::from(1);
         nonce = 2u64;
 
-        command = [0u8; 81];
-        command[0] = OP_DEPOSIT_NFT;
-        command[1..9].copy_from_slice(&nonce.to_be_bytes());
-        command[9..13].copy_from_slice(&account_index.to_be_bytes());
-        command[13..17].copy_from_slice(&nft_id.to_be_bytes());
-        command[17..49].copy_from_slice(&l1_tx_hash.to_be_bytes());
+        command = Command::DepositNFT { nonce, account_index, nft_id, l1_tx_hash }.encode();
 
         command_sign = BabyJubjub::sign(&command, &secret_key_1);
         command_sign_formatted = [0 as u8;64];
@@ -30,12 +30,7 @@ fn deposit_works() {
         let l1_tx_hash = U256::from(0);
         let nonce = 1u64;
 
-        let mut command = [0u8; 81];
-        command[0] = OP_DEPOSIT;
-        command[1..9].copy_from_slice(&nonce.to_be_bytes());
-        command[9..13].copy_from_slice(&account_index.to_be_bytes());
-        command[13..17].copy_from_slice(&token_index.to_be_bytes());
-        command[17..49].copy_from_slice(&amount.to_be_bytes());
+        let command = Command::Deposit { nonce, account_index, token_index, amount }.encode();
 
         let command_sign = BabyJubjub::sign(&command, &secret_key_1);
         let mut command_sign_formatted :[u8; 64] = [0 as u8;64];
@@ -44,7 +39,7 @@ fn deposit_works() {
 
         assert_ok!(SwapModule::deposit(Origin::signed(origin), command_sign_formatted, account_index, token_index, amount, l1_tx_hash, nonce));
 
-        assert_eq!(BalanceMap::get((&account_index, token_index)), amount);
+        assert_eq!(BalanceMap::<Test>::get((&account_index, token_index)), amount);
     })
 }
 
@@ -78,12 +73,7 @@ fn deposit_noaccess() {
         let l1_tx_hash = U256::from(0);
         let nonce = 1u64;
 
-        let mut command = [0u8; 81];
-        command[0] = OP_DEPOSIT;
-        command[1..9].copy_from_slice(&nonce.to_be_bytes());
-        command[9..13].copy_from_slice(&account_index.to_be_bytes());
-        command[13..17].copy_from_slice(&token_index.to_be_bytes());
-        command[17..49].copy_from_slice(&amount.to_be_bytes());
+        let command = Command::Deposit { nonce, account_index, token_index, amount }.encode();
 
         let command_sign = BabyJubjub::sign(&command, &secret_key_0);
         let mut command_sign_formatted :[u8; 64] = [0 as u8;64];
@@ -112,12 +102,7 @@ fn deposit_account_not_exists() {
         let l1_tx_hash = U256::from(0);
         let nonce = 1u64;
 
-        let mut command = [0u8; 81];
-        command[0] = OP_DEPOSIT;
-        command[1..9].copy_from_slice(&nonce.to_be_bytes());
-        command[9..13].copy_from_slice(&account_index.to_be_bytes());
-        command[13..17].copy_from_slice(&token_index.to_be_bytes());
-        command[17..49].copy_from_slice(&amount.to_be_bytes());
+        let command = Command::Deposit { nonce, account_index, token_index, amount }.encode();
 
         //Only setKey for accountIndex 0, so use secret_key_0 here
         let command_sign = BabyJubjub::sign(&command, &secret_key_0);
@@ -159,12 +144,7 @@ fn deposit_invalid_tokenindex() {
         let l1_tx_hash = U256::from(0);
         let nonce = 1u64;
 
-        let mut command = [0u8; 81];
-        command[0] = OP_DEPOSIT;
-        command[1..9].copy_from_slice(&nonce.to_be_bytes());
-        command[9..13].copy_from_slice(&account_index.to_be_bytes());
-        command[13..17].copy_from_slice(&token_index.to_be_bytes());
-        command[17..49].copy_from_slice(&amount.to_be_bytes());
+        let command = Command::Deposit { nonce, account_index, token_index, amount }.encode();
 
         let command_sign = BabyJubjub::sign(&command, &secret_key_1);
         let mut command_sign_formatted :[u8; 64] = [0 as u8;64];
@@ -205,12 +185,7 @@ fn deposit_invalid_account() {
         let l1_tx_hash = U256::from(0);
         let nonce = 1u64;
 
-        let mut command = [0u8; 81];
-        command[0] = OP_DEPOSIT;
-        command[1..9].copy_from_slice(&nonce.to_be_bytes());
-        command[9..13].copy_from_slice(&account_index.to_be_bytes());
-        command[13..17].copy_from_slice(&token_index.to_be_bytes());
-        command[17..49].copy_from_slice(&amount.to_be_bytes());
+        let command = Command::Deposit { nonce, account_index, token_index, amount }.encode();
 
         let command_sign = BabyJubjub::sign(&command, &secret_key_1);
         let mut command_sign_formatted :[u8; 64] = [0 as u8;64];
@@ -250,12 +225,7 @@ fn deposit_l1tx_exists() {
         let l1_tx_hash = U256::from(0);
         let nonce = 1u64;
 
-        let mut command = [0u8; 81];
-        command[0] = OP_DEPOSIT;
-        command[1..9].copy_from_slice(&nonce.to_be_bytes());
-        command[9..13].copy_from_slice(&account_index.to_be_bytes());
-        command[13..17].copy_from_slice(&token_index.to_be_bytes());
-        command[17..49].copy_from_slice(&amount.to_be_bytes());
+        let command = Command::Deposit { nonce, account_index, token_index, amount }.encode();
 
         let command_sign = BabyJubjub::sign(&command, &secret_key_1);
         let mut command_sign_formatted :[u8; 64] = [0 as u8;64];
@@ -299,12 +269,7 @@ fn deposit_invalid_amount() {
         let l1_tx_hash = U256::from(0);
         let nonce = 1u64;
 
-        let mut command = [0u8; 81];
-        command[0] = OP_DEPOSIT;
-        command[1..9].copy_from_slice(&nonce.to_be_bytes());
-        command[9..13].copy_from_slice(&account_index.to_be_bytes());
-        command[13..17].copy_from_slice(&token_index.to_be_bytes());
-        command[17..49].copy_from_slice(&amount.to_be_bytes());
+        let command = Command::Deposit { nonce, account_index, token_index, amount }.encode();
 
         let command_sign = BabyJubjub::sign(&command, &secret_key_1);
         let mut command_sign_formatted :[u8; 64] = [0 as u8;64];
@@ -345,12 +310,7 @@ fn deposit_nonce_inconsistent() {
         //true nonce is 1u64
         let nonce = 2u64;
 
-        let mut command = [0u8; 81];
-        command[0] = OP_DEPOSIT;
-        command[1..9].copy_from_slice(&nonce.to_be_bytes());
-        command[9..13].copy_from_slice(&account_index.to_be_bytes());
-        command[13..17].copy_from_slice(&token_index.to_be_bytes());
-        command[17..49].copy_from_slice(&amount.to_be_bytes());
+        let command = Command::Deposit { nonce, account_index, token_index, amount }.encode();
 
         let command_sign = BabyJubjub::sign(&command, &secret_key_1);
         let mut command_sign_formatted :[u8; 64] = [0 as u8;64];
@@ -390,12 +350,7 @@ fn deposit_invalid_signature() {
         let l1_tx_hash = U256::from(0);
         let nonce = 1u64;
 
-        let mut command = [0u8; 81];
-        command[0] = OP_DEPOSIT;
-        command[1..9].copy_from_slice(&nonce.to_be_bytes());
-        command[9..13].copy_from_slice(&account_index.to_be_bytes());
-        command[13..17].copy_from_slice(&token_index.to_be_bytes());
-        command[17..49].copy_from_slice(&amount.to_be_bytes());
+        let command = Command::Deposit { nonce, account_index, token_index, amount }.encode();
 
         let command_sign = BabyJubjub::sign(&command, &secret_key_1);
         let mut command_sign_formatted :[u8; 64] = [0 as u8;64];
@@ -438,12 +393,7 @@ fn deposit_balance_overflow() {
         let mut l1_tx_hash = U256::from(0);
         let mut nonce = 1u64;
 
-        let mut command = [0u8; 81];
-        command[0] = OP_DEPOSIT;
-        command[1..9].copy_from_slice(&nonce.to_be_bytes());
-        command[9..13].copy_from_slice(&account_index.to_be_bytes());
-        command[13..17].copy_from_slice(&token_index.to_be_bytes());
-        command[17..49].copy_from_slice(&amount.to_be_bytes());
+        let mut command = Command::Deposit { nonce, account_index, token_index, amount }.encode();
 
         let command_sign = BabyJubjub::sign(&command, &secret_key_1);
         let mut command_sign_formatted :[u8; 64] = [0 as u8;64];
@@ -458,12 +408,7 @@ fn deposit_balance_overflow() {
         l1_tx_hash = U256::from(1);
         nonce = 2u64;
 
-        command = [0u8; 81];
-        command[0] = OP_DEPOSIT;
-        command[1..9].copy_from_slice(&nonce.to_be_bytes());
-        command[9..13].copy_from_slice(&account_index.to_be_bytes());
-        command[13..17].copy_from_slice(&token_index.to_be_bytes());
-        command[17..49].copy_from_slice(&amount.to_be_bytes());
+        command = Command::Deposit { nonce, account_index, token_index, amount }.encode();
 
         let command_sign = BabyJubjub::sign(&command, &secret_key_1);
         let mut command_sign_formatted :[u8; 64] = [0 as u8;64];
@@ -0,0 +1,128 @@
+use super::*;
+use frame_support::traits::OnInitialize;
+
+fn prepare_unit_test() {
+    System::set_block_number(1);
+
+    //SetKey for accountIndex 0
+    let mut origin = 0u64;
+    let secret_key_0 = [2u8; 32];
+    let pub_key_0 = BabyJubjub::pubkey_from_secretkey(&secret_key_0).encode();
+    assert_ok!(SwapModule::set_key(Origin::signed(origin), pub_key_0));
+
+    //SetKey for accountIndex 1
+    origin = 1u64;
+    let secret_key_1 = [3u8; 32];
+    let pub_key_1 = BabyJubjub::pubkey_from_secretkey(&secret_key_1).encode();
+    assert_ok!(SwapModule::set_key(Origin::signed(origin), pub_key_1));
+
+    //SetKey for accountIndex 2
+    origin = 2u64;
+    let secret_key_2 = [4u8;32];
+    let pub_key_2 = BabyJubjub::pubkey_from_secretkey(&secret_key_2).encode();
+    assert_ok!(SwapModule::set_key(Origin::signed(origin), pub_key_2));
+
+    //Deposit 10 into accountIndex 2, caller is accountIndex 1
+    origin = 1u64;
+    let account_index = 2u32;
+    let token_index = 1u32;
+    let amount = U256::from(10);
+    let l1_tx_hash = U256::from(0);
+    let nonce = 1u64;
+
+    let command = Command::Deposit { nonce, account_index, token_index, amount }.encode();
+
+    let command_sign = BabyJubjub::sign(&command, &secret_key_1);
+    let mut command_sign_formatted :[u8; 64] = [0 as u8;64];
+    command_sign_formatted[..32].copy_from_slice(&command_sign.r.encode());
+    command_sign_formatted[32..].copy_from_slice(&command_sign.s.encode());
+
+    assert_ok!(SwapModule::deposit(Origin::signed(origin), command_sign_formatted, account_index, token_index, amount, l1_tx_hash, nonce));
+
+    //NFT 5 is owned by accountIndex 2
+    assert_ok!(nft_add::<Test>(&2u32, &5u32));
+}
+
+#[test]
+fn exit_mode_entered_without_ack() {
+    new_test_ext().execute_with(|| {
+        prepare_unit_test();
+
+        SwapModule::on_initialize(20);
+        assert!(!ExitMode::<Test>::get());
+
+        SwapModule::on_initialize(21);
+        assert!(ExitMode::<Test>::get());
+    })
+}
+
+#[test]
+fn exit_mode_not_entered_without_pending_req() {
+    new_test_ext().execute_with(|| {
+        SwapModule::on_initialize(100);
+        assert!(!ExitMode::<Test>::get());
+    })
+}
+
+#[test]
+fn exit_mode_freezes_ops() {
+    new_test_ext().execute_with(|| {
+        prepare_unit_test();
+        SwapModule::on_initialize(21);
+
+        let secret_key = [5u8; 32];
+        let pub_key = BabyJubjub::pubkey_from_secretkey(&secret_key).encode();
+        assert_noop!(
+            SwapModule::set_key(Origin::signed(3), pub_key),
+            Error::<Test>::ExitMode
+        );
+    })
+}
+
+#[test]
+fn emergency_withdraw_works() {
+    new_test_ext().execute_with(|| {
+        prepare_unit_test();
+        SwapModule::on_initialize(21);
+
+        let l1account = U256::from(3);
+        let nonce = NonceMap::<Test>::get(2u64);
+        assert_ok!(SwapModule::emergency_withdraw(Origin::signed(2), l1account, vec![1u32, 2u32], vec![5u32]));
+
+        assert_eq!(BalanceMap::<Test>::get((2u32, 1u32)), U256::from(0));
+        assert_eq!(NFTMap::<Test>::get(5u32).0, 0u32);
+        assert_eq!(ReqIndex::<Test>::get(), U256::from(6));
+        assert_eq!(NonceMap::<Test>::get(2u64), nonce + 2);
+        assert!(PendingReqMap::<Test>::get(U256::from(5)) == Some(
+            Ops::Withdraw(U256::from(0), U256::from(0), U256::from(0), nonce, 2u32, 1u32, U256::from(10), l1account)
+        ));
+        assert!(PendingReqMap::<Test>::get(U256::from(6)) == Some(
+            Ops::WithdrawNFT(U256::from(0), U256::from(0), U256::from(0), nonce + 1, 2u32, 5u32, l1account)
+        ));
+    })
+}
+
+#[test]
+fn emergency_withdraw_not_owner() {
+    new_test_ext().execute_with(|| {
+        prepare_unit_test();
+        SwapModule::on_initialize(21);
+
+        assert_noop!(
+            SwapModule::emergency_withdraw(Origin::signed(1), U256::from(3), vec![1u32], vec![5u32]),
+            Error::<Test>::IsNotOwner
+        );
+    })
+}
+
+#[test]
+fn emergency_withdraw_not_in_exit_mode() {
+    new_test_ext().execute_with(|| {
+        prepare_unit_test();
+
+        assert_noop!(
+            SwapModule::emergency_withdraw(Origin::signed(2), U256::from(3), vec![1u32], vec![5u32]),
+            Error::<Test>::NotInExitMode
+        );
+    })
+}
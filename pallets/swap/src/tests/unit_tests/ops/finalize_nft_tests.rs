@@ -29,12 +29,7 @@ fn finalize_nft_works() {
         let mut l1_tx_hash = U256::from(0);
         let mut nonce = 1u64;
 
-        let mut command = [0u8; 81];
-        command[0] = OP_DEPOSIT;
-        command[1..9].copy_from_slice(&nonce.to_be_bytes());
-        command[9..13].copy_from_slice(&account_index.to_be_bytes());
-        command[13..17].copy_from_slice(&token_index.to_be_bytes());
-        command[17..49].copy_from_slice(&amount.to_be_bytes());
+        let mut command = Command::Deposit { nonce, account_index, token_index, amount }.encode();
 
         let mut command_sign = BabyJubjub::sign(&command, &secret_key_1);
         let mut command_sign_formatted :[u8; 64] = [0 as u8;64];
@@ -48,12 +43,7 @@ fn finalize_nft_works() {
         l1_tx_hash = U256::from(1);
         nonce = 2u64;
 
-        command = [0u8; 81];
-        command[0] = OP_DEPOSIT_NFT;
-        command[1..9].copy_from_slice(&nonce.to_be_bytes());
-        command[9..13].copy_from_slice(&account_index.to_be_bytes());
-        command[13..17].copy_from_slice(&nft_id.to_be_bytes());
-        command[17..49].copy_from_slice(&l1_tx_hash.to_be_bytes());
+        command = Command::DepositNFT { nonce, account_index, nft_id, l1_tx_hash }.encode();
 
         command_sign = BabyJubjub::sign(&command, &secret_key_1);
         command_sign_formatted = [0 as u8;64];
@@ -74,12 +64,7 @@ fn finalize_nft_works() {
         l1_tx_hash = U256::from(12);
         nonce = 3u64;
 
-        let mut command = [0u8; 81];
-        command[0] = OP_DEPOSIT;
-        command[1..9].copy_from_slice(&nonce.to_be_bytes());
-        command[9..13].copy_from_slice(&account_index.to_be_bytes());
-        command[13..17].copy_from_slice(&token_index.to_be_bytes());
-        command[17..49].copy_from_slice(&amount.to_be_bytes());
+        let mut command = Command::Deposit { nonce, account_index, token_index, amount }.encode();
 
         let mut command_sign = BabyJubjub::sign(&command, &secret_key_1);
         let mut command_sign_formatted :[u8; 64] = [0 as u8;64];
@@ -93,12 +78,7 @@ fn finalize_nft_works() {
         amount = U256::from(1);
         nonce = 1u64;
 
-        command = [0u8; 81];
-        command[0] = OP_BID_NFT;
-        command[1..9].copy_from_slice(&nonce.to_be_bytes());
-        command[9..13].copy_from_slice(&account_index.to_be_bytes());
-        command[13..17].copy_from_slice(&nft_id.to_be_bytes());
-        command[17..49].copy_from_slice(&amount.to_be_bytes());
+        command = Command::BidNFT { nonce, account_index, nft_id, amount }.encode();
 
         command_sign = BabyJubjub::sign(&command, &secret_key_3);
         command_sign_formatted = [0 as u8;64];
@@ -112,11 +92,7 @@ fn finalize_nft_works() {
         account_index = 2u32;
         nonce = 1u64;
 
-        command = [0u8; 81];
-        command[0] = OP_FINALIZE_NFT;
-        command[1..9].copy_from_slice(&nonce.to_be_bytes());
-        command[9..13].copy_from_slice(&account_index.to_be_bytes());
-        command[13..17].copy_from_slice(&nft_id.to_be_bytes());
+        command = Command::FinalizeNFT { nonce, account_index, nft_id }.encode();
 
         command_sign = BabyJubjub::sign(&command, &secret_key_2);
         command_sign_formatted = [0 as u8;64];
@@ -125,11 +101,11 @@ fn finalize_nft_works() {
 
         assert_ok!(SwapModule::finalize_nft(Origin::signed(origin), command_sign_formatted, nft_id, nonce));
 
-        let nft = NFTMap::get(&nft_id);
+        let nft = NFTMap::<Test>::get(&nft_id);
         assert_eq!(nft.0, 3u32);
         assert_eq!(nft.1, U256::from(0));
         assert_eq!(nft.2, None);
-        assert_eq!(BalanceMap::get((2u32, token_index)), U256::from(11));
+        assert_eq!(BalanceMap::<Test>::get((2u32, token_index)), U256::from(11));
     })
 }
 
@@ -162,12 +138,7 @@ fn finalize_nft_account_not_exists() {
         let mut l1_tx_hash = U256::from(0);
         let mut nonce = 1u64;
 
-        let mut command = [0u8; 81];
-        command[0] = OP_DEPOSIT;
-        command[1..9].copy_from_slice(&nonce.to_be_bytes());
-        command[9..13].copy_from_slice(&account_index.to_be_bytes());
-        command[13..17].copy_from_slice(&token_index.to_be_bytes());
-        command[17..49].copy_from_slice(&amount.to_be_bytes());
+        let mut command = Command::Deposit { nonce, account_index, token_index, amount }.encode();
 
         let mut command_sign = BabyJubjub::sign(&command, &secret_key_1);
         let mut command_sign_formatted :[u8; 64] = [0 as u8;64];
@@ -181,12 +152,7 @@ fn finalize_nft_account_not_exists() {
         l1_tx_hash = U256::from(1);
         nonce = 2u64;
 
-        command = [0u8; 81];
-        command[0] = OP_DEPOSIT_NFT;
-        command[1..9].copy_from_slice(&nonce.to_be_bytes());
-        command[9..13].copy_from_slice(&account_index.to_be_bytes());
-        command[13..17].copy_from_slice(&nft_id.to_be_bytes());
-        command[17..49].copy_from_slice(&l1_tx_hash.to_be_bytes());
+        command = Command::DepositNFT { nonce, account_index, nft_id, l1_tx_hash }.encode();
 
         command_sign = BabyJubjub::sign(&command, &secret_key_1);
         command_sign_formatted = [0 as u8;64];
@@ -207,12 +173,7 @@ fn finalize_nft_account_not_exists() {
         l1_tx_hash = U256::from(12);
         nonce = 3u64;
 
-        let mut command = [0u8; 81];
-        command[0] = OP_DEPOSIT;
-        command[1..9].copy_from_slice(&nonce.to_be_bytes());
-        command[9..13].copy_from_slice(&account_index.to_be_bytes());
-        command[13..17].copy_from_slice(&token_index.to_be_bytes());
-        command[17..49].copy_from_slice(&amount.to_be_bytes());
+        let mut command = Command::Deposit { nonce, account_index, token_index, amount }.encode();
 
         let mut command_sign = BabyJubjub::sign(&command, &secret_key_1);
         let mut command_sign_formatted :[u8; 64] = [0 as u8;64];
@@ -226,12 +187,7 @@ fn finalize_nft_account_not_exists() {
         amount = U256::from(1);
         nonce = 1u64;
 
-        command = [0u8; 81];
-        command[0] = OP_BID_NFT;
-        command[1..9].copy_from_slice(&nonce.to_be_bytes());
-        command[9..13].copy_from_slice(&account_index.to_be_bytes());
-        command[13..17].copy_from_slice(&nft_id.to_be_bytes());
-        command[17..49].copy_from_slice(&amount.to_be_bytes());
+        command = Command::BidNFT { nonce, account_index, nft_id, amount }.encode();
 
         command_sign = BabyJubjub::sign(&command, &secret_key_3);
         command_sign_formatted = [0 as u8;64];
@@ -245,11 +201,7 @@ fn finalize_nft_account_not_exists() {
         origin = 4u64;
         nonce = 0u64;
 
-        command = [0u8; 81];
-        command[0] = OP_FINALIZE_NFT;
-        command[1..9].copy_from_slice(&nonce.to_be_bytes());
-        command[9..13].copy_from_slice(&account_index.to_be_bytes());
-        command[13..17].copy_from_slice(&nft_id.to_be_bytes());
+        command = Command::FinalizeNFT { nonce, account_index, nft_id }.encode();
 
         // There is no secret_key_4, use secret_key_3 here
         command_sign = BabyJubjub::sign(&command, &secret_key_3);
@@ -290,12 +242,7 @@ fn finalize_nft_invalid_nftindex() {
         let mut l1_tx_hash = U256::from(0);
         let mut nonce = 1u64;
 
-        let mut command = [0u8; 81];
-        command[0] = OP_DEPOSIT;
-        command[1..9].copy_from_slice(&nonce.to_be_bytes());
-        command[9..13].copy_from_slice(&account_index.to_be_bytes());
-        command[13..17].copy_from_slice(&token_index.to_be_bytes());
-        command[17..49].copy_from_slice(&amount.to_be_bytes());
+        let mut command = Command::Deposit { nonce, account_index, token_index, amount }.encode();
 
         let mut command_sign = BabyJubjub::sign(&command, &secret_key_1);
         let mut command_sign_formatted :[u8; 64] = [0 as u8;64];
@@ -309,12 +256,7 @@ fn finalize_nft_invalid_nftindex() {
         l1_tx_hash = U256::from(1);
         nonce = 2u64;
 
-        command = [0u8; 81];
-        command[0] = OP_DEPOSIT_NFT;
-        command[1..9].copy_from_slice(&nonce.to_be_bytes());
-        command[9..13].copy_from_slice(&account_index.to_be_bytes());
-        command[13..17].copy_from_slice(&nft_id.to_be_bytes());
-        command[17..49].copy_from_slice(&l1_tx_hash.to_be_bytes());
+        command = Command::DepositNFT { nonce, account_index, nft_id, l1_tx_hash }.encode();
 
         command_sign = BabyJubjub::sign(&command, &secret_key_1);
         command_sign_formatted = [0 as u8;64];
@@ -328,11 +270,7 @@ fn finalize_nft_invalid_nftindex() {
         origin = 2u64;
         nonce = 1u64;
 
-        command = [0u8; 81];
-        command[0] = OP_FINALIZE_NFT;
-        command[1..9].copy_from_slice(&nonce.to_be_bytes());
-        command[9..13].copy_from_slice(&account_index.to_be_bytes());
-        command[13..17].copy_from_slice(&nft_id.to_be_bytes());
+        command = Command::FinalizeNFT { nonce, account_index, nft_id }.encode();
 
         command_sign = BabyJubjub::sign(&command, &secret_key_2);
         command_sign_formatted = [0 as u8;64];
@@ -353,12 +291,7 @@ fn finalize_nft_invalid_nftindex() {
         l1_tx_hash = U256::from(12);
         nonce = 3u64;
 
-        let mut command = [0u8; 81];
-        command[0] = OP_DEPOSIT;
-        command[1..9].copy_from_slice(&nonce.to_be_bytes());
-        command[9..13].copy_from_slice(&account_index.to_be_bytes());
-        command[13..17].copy_from_slice(&token_index.to_be_bytes());
-        command[17..49].copy_from_slice(&amount.to_be_bytes());
+        let mut command = Command::Deposit { nonce, account_index, token_index, amount }.encode();
 
         let mut command_sign = BabyJubjub::sign(&command, &secret_key_1);
         let mut command_sign_formatted :[u8; 64] = [0 as u8;64];
@@ -372,12 +305,7 @@ fn finalize_nft_invalid_nftindex() {
         amount = U256::from(1);
         nonce = 1u64;
 
-        command = [0u8; 81];
-        command[0] = OP_BID_NFT;
-        command[1..9].copy_from_slice(&nonce.to_be_bytes());
-        command[9..13].copy_from_slice(&account_index.to_be_bytes());
-        command[13..17].copy_from_slice(&nft_id.to_be_bytes());
-        command[17..49].copy_from_slice(&amount.to_be_bytes());
+        command = Command::BidNFT { nonce, account_index, nft_id, amount }.encode();
 
         command_sign = BabyJubjub::sign(&command, &secret_key_3);
         command_sign_formatted = [0 as u8;64];
@@ -392,11 +320,7 @@ fn finalize_nft_invalid_nftindex() {
         nft_id = 1u32 << 20;
         nonce = 1u64;
 
-        command = [0u8; 81];
-        command[0] = OP_FINALIZE_NFT;
-        command[1..9].copy_from_slice(&nonce.to_be_bytes());
-        command[9..13].copy_from_slice(&account_index.to_be_bytes());
-        command[13..17].copy_from_slice(&nft_id.to_be_bytes());
+        command = Command::FinalizeNFT { nonce, account_index, nft_id }.encode();
 
         command_sign = BabyJubjub::sign(&command, &secret_key_2);
         command_sign_formatted = [0 as u8;64];
@@ -409,11 +333,7 @@ fn finalize_nft_invalid_nftindex() {
         //nft_id should not be zero
         nft_id = 0;
 
-        command = [0u8; 81];
-        command[0] = OP_FINALIZE_NFT;
-        command[1..9].copy_from_slice(&nonce.to_be_bytes());
-        command[9..13].copy_from_slice(&account_index.to_be_bytes());
-        command[13..17].copy_from_slice(&nft_id.to_be_bytes());
+        command = Command::FinalizeNFT { nonce, account_index, nft_id }.encode();
 
         command_sign = BabyJubjub::sign(&command, &secret_key_2);
         command_sign_formatted = [0 as u8;64];
@@ -453,12 +373,7 @@ fn finalize_nft_nonce_inconsistent() {
         let mut l1_tx_hash = U256::from(0);
         let mut nonce = 1u64;
 
-        let mut command = [0u8; 81];
-        command[0] = OP_DEPOSIT;
-        command[1..9].copy_from_slice(&nonce.to_be_bytes());
-        command[9..13].copy_from_slice(&account_index.to_be_bytes());
-        command[13..17].copy_from_slice(&token_index.to_be_bytes());
-        command[17..49].copy_from_slice(&amount.to_be_bytes());
+        let mut command = Command::Deposit { nonce, account_index, token_index, amount }.encode();
 
         let mut command_sign = BabyJubjub::sign(&command, &secret_key_1);
         let mut command_sign_formatted :[u8; 64] = [0 as u8;64];
@@ -472,12 +387,7 @@ fn finalize_nft_nonce_inconsistent() {
         l1_tx_hash = U256::from(1);
         nonce = 2u64;
 
-        command = [0u8; 81];
-        command[0] = OP_DEPOSIT_NFT;
-        command[1..9].copy_from_slice(&nonce.to_be_bytes());
-        command[9..13].copy_from_slice(&account_index.to_be_bytes());
-        command[13..17].copy_from_slice(&nft_id.to_be_bytes());
-        command[17..49].copy_from_slice(&l1_tx_hash.to_be_bytes());
+        command = Command::DepositNFT { nonce, account_index, nft_id, l1_tx_hash }.encode();
 
         command_sign = BabyJubjub::sign(&command, &secret_key_1);
         command_sign_formatted = [0 as u8;64];
@@ -498,12 +408,7 @@ fn finalize_nft_nonce_inconsistent() {
         l1_tx_hash = U256::from(12);
         nonce = 3u64;
 
-        let mut command = [0u8; 81];
-        command[0] = OP_DEPOSIT;
-        command[1..9].copy_from_slice(&nonce.to_be_bytes());
-        command[9..13].copy_from_slice(&account_index.to_be_bytes());
-        command[13..17].copy_from_slice(&token_index.to_be_bytes());
-        command[17..49].copy_from_slice(&amount.to_be_bytes());
+        let mut command = Command::Deposit { nonce, account_index, token_index, amount }.encode();
 
         let mut command_sign = BabyJubjub::sign(&command, &secret_key_1);
         let mut command_sign_formatted :[u8; 64] = [0 as u8;64];
@@ -517,12 +422,7 @@ fn finalize_nft_nonce_inconsistent() {
         amount = U256::from(1);
         nonce = 1u64;
 
-        command = [0u8; 81];
-        command[0] = OP_BID_NFT;
-        command[1..9].copy_from_slice(&nonce.to_be_bytes());
-        command[9..13].copy_from_slice(&account_index.to_be_bytes());
-        command[13..17].copy_from_slice(&nft_id.to_be_bytes());
-        command[17..49].copy_from_slice(&amount.to_be_bytes());
+        command = Command::BidNFT { nonce, account_index, nft_id, amount }.encode();
 
         command_sign = BabyJubjub::sign(&command, &secret_key_3);
         command_sign_formatted = [0 as u8;64];
@@ -536,11 +436,7 @@ fn finalize_nft_nonce_inconsistent() {
         //True nonce is 1u64
         nonce = 2u64;
 
-        command = [0u8; 81];
-        command[0] = OP_FINALIZE_NFT;
-        command[1..9].copy_from_slice(&nonce.to_be_bytes());
-        command[9..13].copy_from_slice(&account_index.to_be_bytes());
-        command[13..17].copy_from_slice(&nft_id.to_be_bytes());
+        command = Command::FinalizeNFT { nonce, account_index, nft_id }.encode();
 
         command_sign = BabyJubjub::sign(&command, &secret_key_2);
         command_sign_formatted = [0 as u8;64];
@@ -580,12 +476,7 @@ fn finalize_nft_invalid_signature() {
         let mut l1_tx_hash = U256::from(0);
         let mut nonce = 1u64;
 
-        let mut command = [0u8; 81];
-        command[0] = OP_DEPOSIT;
-        command[1..9].copy_from_slice(&nonce.to_be_bytes());
-        command[9..13].copy_from_slice(&account_index.to_be_bytes());
-        command[13..17].copy_from_slice(&token_index.to_be_bytes());
-        command[17..49].copy_from_slice(&amount.to_be_bytes());
+        let mut command = Command::Deposit { nonce, account_index, token_index, amount }.encode();
 
         let mut command_sign = BabyJubjub::sign(&command, &secret_key_1);
         let mut command_sign_formatted :[u8; 64] = [0 as u8;64];
@@ -599,12 +490,7 @@ fn finalize_nft_invalid_signature() {
         l1_tx_hash = U256::from(1);
         nonce = 2u64;
 
-        command = [0u8; 81];
-        command[0] = OP_DEPOSIT_NFT;
-        command[1..9].copy_from_slice(&nonce.to_be_bytes());
-        command[9..13].copy_from_slice(&account_index.to_be_bytes());
-        command[13..17].copy_from_slice(&nft_id.to_be_bytes());
-        command[17..49].copy_from_slice(&l1_tx_hash.to_be_bytes());
+        command = Command::DepositNFT { nonce, account_index, nft_id, l1_tx_hash }.encode();
 
         command_sign = BabyJubjub::sign(&command, &secret_key_1);
         command_sign_formatted = [0 as u8;64];
@@ -625,12 +511,7 @@ fn finalize_nft_invalid_signature() {
         l1_tx_hash = U256::from(12);
         nonce = 3u64;
 
-        let mut command = [0u8; 81];
-        command[0] = OP_DEPOSIT;
-        command[1..9].copy_from_slice(&nonce.to_be_bytes());
-        command[9..13].copy_from_slice(&account_index.to_be_bytes());
-        command[13..17].copy_from_slice(&token_index.to_be_bytes());
-        command[17..49].copy_from_slice(&amount.to_be_bytes());
+        let mut command = Command::Deposit { nonce, account_index, token_index, amount }.encode();
 
         let mut command_sign = BabyJubjub::sign(&command, &secret_key_1);
         let mut command_sign_formatted :[u8; 64] = [0 as u8;64];
@@ -644,12 +525,7 @@ fn finalize_nft_invalid_signature() {
         amount = U256::from(1);
         nonce = 1u64;
 
-        command = [0u8; 81];
-        command[0] = OP_BID_NFT;
-        command[1..9].copy_from_slice(&nonce.to_be_bytes());
-        command[9..13].copy_from_slice(&account_index.to_be_bytes());
-        command[13..17].copy_from_slice(&nft_id.to_be_bytes());
-        command[17..49].copy_from_slice(&amount.to_be_bytes());
+        command = Command::BidNFT { nonce, account_index, nft_id, amount }.encode();
 
         command_sign = BabyJubjub::sign(&command, &secret_key_3);
         command_sign_formatted = [0 as u8;64];
@@ -662,11 +538,7 @@ fn finalize_nft_invalid_signature() {
         origin = 2u64;
         nonce = 1u64;
 
-        command = [0u8; 81];
-        command[0] = OP_FINALIZE_NFT;
-        command[1..9].copy_from_slice(&nonce.to_be_bytes());
-        command[9..13].copy_from_slice(&account_index.to_be_bytes());
-        command[13..17].copy_from_slice(&nft_id.to_be_bytes());
+        command = Command::FinalizeNFT { nonce, account_index, nft_id }.encode();
 
         command_sign = BabyJubjub::sign(&command, &secret_key_2);
         command_sign_formatted = [0 as u8;64];
@@ -709,12 +581,7 @@ fn finalize_nft_is_not_owner() {
         let mut l1_tx_hash = U256::from(0);
         let mut nonce = 1u64;
 
-        let mut command = [0u8; 81];
-        command[0] = OP_DEPOSIT;
-        command[1..9].copy_from_slice(&nonce.to_be_bytes());
-        command[9..13].copy_from_slice(&account_index.to_be_bytes());
-        command[13..17].copy_from_slice(&token_index.to_be_bytes());
-        command[17..49].copy_from_slice(&amount.to_be_bytes());
+        let mut command = Command::Deposit { nonce, account_index, token_index, amount }.encode();
 
         let mut command_sign = BabyJubjub::sign(&command, &secret_key_1);
         let mut command_sign_formatted :[u8; 64] = [0 as u8;64];
@@ -728,12 +595,7 @@ fn finalize_nft_is_not_owner() {
         l1_tx_hash = U256::from(1);
         nonce = 2u64;
 
-        command = [0u8; 81];
-        command[0] = OP_DEPOSIT_NFT;
-        command[1..9].copy_from_slice(&nonce.to_be_bytes());
-        command[9..13].copy_from_slice(&account_index.to_be_bytes());
-        command[13..17].copy_from_slice(&nft_id.to_be_bytes());
-        command[17..49].copy_from_slice(&l1_tx_hash.to_be_bytes());
+        command = Command::DepositNFT { nonce, account_index, nft_id, l1_tx_hash }.encode();
 
         command_sign = BabyJubjub::sign(&command, &secret_key_1);
         command_sign_formatted = [0 as u8;64];
@@ -754,12 +616,7 @@ fn finalize_nft_is_not_owner() {
         l1_tx_hash = U256::from(12);
         nonce = 3u64;
 
-        let mut command = [0u8; 81];
-        command[0] = OP_DEPOSIT;
-        command[1..9].copy_from_slice(&nonce.to_be_bytes());
-        command[9..13].copy_from_slice(&account_index.to_be_bytes());
-        command[13..17].copy_from_slice(&token_index.to_be_bytes());
-        command[17..49].copy_from_slice(&amount.to_be_bytes());
+        let mut command = Command::Deposit { nonce, account_index, token_index, amount }.encode();
 
         let mut command_sign = BabyJubjub::sign(&command, &secret_key_1);
         let mut command_sign_formatted :[u8; 64] = [0 as u8;64];
@@ -773,12 +630,7 @@ fn finalize_nft_is_not_owner() {
         amount = U256::from(1);
         nonce = 1u64;
 
-        command = [0u8; 81];
-        command[0] = OP_BID_NFT;
-        command[1..9].copy_from_slice(&nonce.to_be_bytes());
-        command[9..13].copy_from_slice(&account_index.to_be_bytes());
-        command[13..17].copy_from_slice(&nft_id.to_be_bytes());
-        command[17..49].copy_from_slice(&amount.to_be_bytes());
+        command = Command::BidNFT { nonce, account_index, nft_id, amount }.encode();
 
         command_sign = BabyJubjub::sign(&command, &secret_key_3);
         command_sign_formatted = [0 as u8;64];
@@ -793,11 +645,7 @@ fn finalize_nft_is_not_owner() {
         account_index = 1u32;
         nonce = 4u64;
 
-        command = [0u8; 81];
-        command[0] = OP_FINALIZE_NFT;
-        command[1..9].copy_from_slice(&nonce.to_be_bytes());
-        command[9..13].copy_from_slice(&account_index.to_be_bytes());
-        command[13..17].copy_from_slice(&nft_id.to_be_bytes());
+        command = Command::FinalizeNFT { nonce, account_index, nft_id }.encode();
 
         command_sign = BabyJubjub::sign(&command, &secret_key_1);
         command_sign_formatted = [0 as u8;64];
@@ -837,12 +685,7 @@ fn finalize_nft_balance_overflow() {
         let mut l1_tx_hash = U256::from(0);
         let mut nonce = 1u64;
 
-        let mut command = [0u8; 81];
-        command[0] = OP_DEPOSIT;
-        command[1..9].copy_from_slice(&nonce.to_be_bytes());
-        command[9..13].copy_from_slice(&account_index.to_be_bytes());
-        command[13..17].copy_from_slice(&token_index.to_be_bytes());
-        command[17..49].copy_from_slice(&amount.to_be_bytes());
+        let mut command = Command::Deposit { nonce, account_index, token_index, amount }.encode();
 
         let mut command_sign = BabyJubjub::sign(&command, &secret_key_1);
         let mut command_sign_formatted :[u8; 64] = [0 as u8;64];
@@ -856,12 +699,7 @@ fn finalize_nft_balance_overflow() {
         l1_tx_hash = U256::from(1);
         nonce = 2u64;
 
-        command = [0u8; 81];
-        command[0] = OP_DEPOSIT_NFT;
-        command[1..9].copy_from_slice(&nonce.to_be_bytes());
-        command[9..13].copy_from_slice(&account_index.to_be_bytes());
-        command[13..17].copy_from_slice(&nft_id.to_be_bytes());
-        command[17..49].copy_from_slice(&l1_tx_hash.to_be_bytes());
+        command = Command::DepositNFT { nonce, account_index, nft_id, l1_tx_hash }.encode();
 
         command_sign = BabyJubjub::sign(&command, &secret_key_1);
         command_sign_formatted = [0 as u8;64];
@@ -882,12 +720,7 @@ fn finalize_nft_balance_overflow() {
         l1_tx_hash = U256::from(12);
         nonce = 3u64;
 
-        let mut command = [0u8; 81];
-        command[0] = OP_DEPOSIT;
-        command[1..9].copy_from_slice(&nonce.to_be_bytes());
-        command[9..13].copy_from_slice(&account_index.to_be_bytes());
-        command[13..17].copy_from_slice(&token_index.to_be_bytes());
-        command[17..49].copy_from_slice(&amount.to_be_bytes());
+        let mut command = Command::Deposit { nonce, account_index, token_index, amount }.encode();
 
         let mut command_sign = BabyJubjub::sign(&command, &secret_key_1);
         let mut command_sign_formatted :[u8; 64] = [0 as u8;64];
@@ -901,12 +734,7 @@ fn finalize_nft_balance_overflow() {
         amount = U256::from(1);
         nonce = 1u64;
 
-        command = [0u8; 81];
-        command[0] = OP_BID_NFT;
-        command[1..9].copy_from_slice(&nonce.to_be_bytes());
-        command[9..13].copy_from_slice(&account_index.to_be_bytes());
-        command[13..17].copy_from_slice(&nft_id.to_be_bytes());
-        command[17..49].copy_from_slice(&amount.to_be_bytes());
+        command = Command::BidNFT { nonce, account_index, nft_id, amount }.encode();
 
         command_sign = BabyJubjub::sign(&command, &secret_key_3);
         command_sign_formatted = [0 as u8;64];
@@ -922,12 +750,7 @@ fn finalize_nft_balance_overflow() {
         l1_tx_hash = U256::from(2);
         nonce = 4u64;
 
-        command = [0u8; 81];
-        command[0] = OP_DEPOSIT;
-        command[1..9].copy_from_slice(&nonce.to_be_bytes());
-        command[9..13].copy_from_slice(&account_index.to_be_bytes());
-        command[13..17].copy_from_slice(&token_index.to_be_bytes());
-        command[17..49].copy_from_slice(&amount.to_be_bytes());
+        command = Command::Deposit { nonce, account_index, token_index, amount }.encode();
 
         command_sign = BabyJubjub::sign(&command, &secret_key_1);
         command_sign_formatted = [0 as u8;64];
@@ -941,11 +764,7 @@ fn finalize_nft_balance_overflow() {
         origin = 2u64;
         nonce = 1u64;
 
-        command = [0u8; 81];
-        command[0] = OP_FINALIZE_NFT;
-        command[1..9].copy_from_slice(&nonce.to_be_bytes());
-        command[9..13].copy_from_slice(&account_index.to_be_bytes());
-        command[13..17].copy_from_slice(&nft_id.to_be_bytes());
+        command = Command::FinalizeNFT { nonce, account_index, nft_id }.encode();
 
         command_sign = BabyJubjub::sign(&command, &secret_key_2);
         command_sign_formatted = [0 as u8;64];
@@ -0,0 +1,57 @@
+use super::*;
+use frame_support::sp_runtime::DispatchError;
+use frame_support::traits::OnInitialize;
+
+fn prepare_unit_test() {
+    System::set_block_number(1);
+
+    //SetKey for accountIndex 0 as reqId 1, never acked
+    let secret_key_0 = [2u8; 32];
+    let pub_key_0 = BabyJubjub::pubkey_from_secretkey(&secret_key_0).encode();
+    assert_ok!(SwapModule::set_key(Origin::signed(0u64), pub_key_0));
+
+    //Enter exit mode
+    SwapModule::on_initialize(21);
+    assert!(ExitMode::<Test>::get());
+}
+
+#[test]
+fn leave_exit_mode_works() {
+    new_test_ext().execute_with(|| {
+        prepare_unit_test();
+
+        System::set_block_number(30);
+        assert_ok!(SwapModule::leave_exit_mode(Origin::root()));
+        assert!(!ExitMode::<Test>::get());
+        assert_eq!(LastAckBlock::<Test>::get(), 30);
+
+        //Ops are accepted again
+        let secret_key_1 = [3u8; 32];
+        let pub_key_1 = BabyJubjub::pubkey_from_secretkey(&secret_key_1).encode();
+        assert_ok!(SwapModule::set_key(Origin::signed(1u64), pub_key_1));
+
+        //The ack delay restarts from the block exit mode was left at
+        SwapModule::on_initialize(49);
+        assert!(!ExitMode::<Test>::get());
+
+        SwapModule::on_initialize(50);
+        assert!(ExitMode::<Test>::get());
+    })
+}
+
+#[test]
+fn leave_exit_mode_requires_root() {
+    new_test_ext().execute_with(|| {
+        prepare_unit_test();
+
+        assert_noop!(SwapModule::leave_exit_mode(Origin::signed(1u64)), DispatchError::BadOrigin);
+        assert!(ExitMode::<Test>::get());
+    })
+}
+
+#[test]
+fn leave_exit_mode_not_in_exit_mode() {
+    new_test_ext().execute_with(|| {
+        assert_noop!(SwapModule::leave_exit_mode(Origin::root()), Error::<Test>::NotInExitMode);
+    })
+}
@@ -0,0 +1,196 @@
+use super::*;
+use codec::Decode;
+use frame_support::sp_runtime::offchain::storage::StorageValueRef;
+use frame_support::traits::Hooks;
+use sp_core::offchain::{testing::{TestOffchainExt, TestTransactionPoolExt}, OffchainExt, TransactionPoolExt};
+use sp_runtime::testing::UintAuthorityId;
+
+fn submit_reqs(count: u64) {
+    //SetKey from accounts 10, 11, ... as reqId 1, 2, ...
+    for origin in 10u64..10u64 + count {
+        let secret_key = [origin as u8; 32];
+        let pub_key = BabyJubjub::pubkey_from_secretkey(&secret_key).encode();
+        assert_ok!(SwapModule::set_key(Origin::signed(origin), pub_key));
+    }
+}
+
+fn enable_auto_ack() {
+    StorageValueRef::persistent(AUTO_ACK_STORAGE).set(&true);
+}
+
+fn ack_call(req_id_start: u32) -> mock::Call {
+    mock::Call::Swap(crate::Call::ack(U256::from(req_id_start)))
+}
+
+#[test]
+fn offchain_worker_acks_full_batch() {
+    let (offchain, _state) = TestOffchainExt::new();
+    let (pool, pool_state) = TestTransactionPoolExt::new();
+    let mut ext = new_test_ext();
+    ext.register_extension(OffchainExt::new(offchain));
+    ext.register_extension(TransactionPoolExt::new(pool));
+
+    ext.execute_with(|| {
+        UintAuthorityId::set_all_keys(vec![1u64]);
+        enable_auto_ack();
+        submit_reqs(10);
+
+        <SwapModule as Hooks<u64>>::offchain_worker(1u64);
+
+        let tx = pool_state.write().transactions.pop().unwrap();
+        assert!(pool_state.read().transactions.is_empty());
+        let tx = Extrinsic::decode(&mut &*tx).unwrap();
+        assert!(tx.signature.is_some());
+        assert_eq!(tx.call, ack_call(0));
+
+        //The ack is not included yet, it is not submitted again
+        <SwapModule as Hooks<u64>>::offchain_worker(2u64);
+        assert!(pool_state.read().transactions.is_empty());
+    });
+}
+
+#[test]
+fn offchain_worker_auto_ack_off_by_default() {
+    let (offchain, _state) = TestOffchainExt::new();
+    let (pool, pool_state) = TestTransactionPoolExt::new();
+    let mut ext = new_test_ext();
+    ext.register_extension(OffchainExt::new(offchain));
+    ext.register_extension(TransactionPoolExt::new(pool));
+
+    ext.execute_with(|| {
+        UintAuthorityId::set_all_keys(vec![1u64]);
+        submit_reqs(10);
+
+        <SwapModule as Hooks<u64>>::offchain_worker(1u64);
+        assert!(pool_state.read().transactions.is_empty());
+
+        //Turned off again
+        StorageValueRef::persistent(AUTO_ACK_STORAGE).set(&false);
+        <SwapModule as Hooks<u64>>::offchain_worker(2u64);
+        assert!(pool_state.read().transactions.is_empty());
+    });
+}
+
+#[test]
+fn offchain_worker_waits_for_full_batch() {
+    let (offchain, _state) = TestOffchainExt::new();
+    let (pool, pool_state) = TestTransactionPoolExt::new();
+    let mut ext = new_test_ext();
+    ext.register_extension(OffchainExt::new(offchain));
+    ext.register_extension(TransactionPoolExt::new(pool));
+
+    ext.execute_with(|| {
+        UintAuthorityId::set_all_keys(vec![1u64]);
+        enable_auto_ack();
+        submit_reqs(9);
+
+        <SwapModule as Hooks<u64>>::offchain_worker(1u64);
+
+        assert!(pool_state.read().transactions.is_empty());
+    });
+}
+
+#[test]
+fn offchain_worker_acks_batch_acked_by_other_admin() {
+    let (offchain, _state) = TestOffchainExt::new();
+    let (pool, pool_state) = TestTransactionPoolExt::new();
+    let mut ext = new_test_ext();
+    ext.register_extension(OffchainExt::new(offchain));
+    ext.register_extension(TransactionPoolExt::new(pool));
+
+    ext.execute_with(|| {
+        UintAuthorityId::set_all_keys(vec![1u64]);
+        enable_auto_ack();
+        submit_reqs(10);
+        assert_ok!(SwapModule::ack(Origin::signed(2u64), U256::from(0)));
+        assert_eq!(CompleteReqIndex::<Test>::get(), U256::from(0));
+
+        <SwapModule as Hooks<u64>>::offchain_worker(1u64);
+
+        let tx = pool_state.write().transactions.pop().unwrap();
+        let tx = Extrinsic::decode(&mut &*tx).unwrap();
+        assert_eq!(tx.call, ack_call(0));
+    });
+}
+
+#[test]
+fn offchain_worker_skips_batch_acked_by_itself() {
+    let (offchain, _state) = TestOffchainExt::new();
+    let (pool, pool_state) = TestTransactionPoolExt::new();
+    let mut ext = new_test_ext();
+    ext.register_extension(OffchainExt::new(offchain));
+    ext.register_extension(TransactionPoolExt::new(pool));
+
+    ext.execute_with(|| {
+        UintAuthorityId::set_all_keys(vec![1u64]);
+        enable_auto_ack();
+        submit_reqs(10);
+        assert_ok!(SwapModule::ack(Origin::signed(1u64), U256::from(0)));
+
+        <SwapModule as Hooks<u64>>::offchain_worker(1u64);
+
+        assert!(pool_state.read().transactions.is_empty());
+    });
+}
+
+#[test]
+fn offchain_worker_acks_next_batch_after_own_partial_ack() {
+    let (offchain, _state) = TestOffchainExt::new();
+    let (pool, pool_state) = TestTransactionPoolExt::new();
+    let mut ext = new_test_ext();
+    ext.register_extension(OffchainExt::new(offchain));
+    ext.register_extension(TransactionPoolExt::new(pool));
+
+    ext.execute_with(|| {
+        UintAuthorityId::set_all_keys(vec![1u64]);
+        enable_auto_ack();
+        submit_reqs(20);
+        assert_ok!(SwapModule::ack(Origin::signed(1u64), U256::from(0)));
+
+        //The first batch waits for admin 2, admin 1 goes on with the second one
+        <SwapModule as Hooks<u64>>::offchain_worker(1u64);
+
+        let tx = pool_state.write().transactions.pop().unwrap();
+        let tx = Extrinsic::decode(&mut &*tx).unwrap();
+        assert_eq!(tx.call, ack_call(10));
+    });
+}
+
+#[test]
+fn offchain_worker_ignores_non_admin_keys() {
+    let (offchain, _state) = TestOffchainExt::new();
+    let (pool, pool_state) = TestTransactionPoolExt::new();
+    let mut ext = new_test_ext();
+    ext.register_extension(OffchainExt::new(offchain));
+    ext.register_extension(TransactionPoolExt::new(pool));
+
+    ext.execute_with(|| {
+        UintAuthorityId::set_all_keys(vec![3u64]);
+        enable_auto_ack();
+        submit_reqs(10);
+
+        <SwapModule as Hooks<u64>>::offchain_worker(1u64);
+
+        assert!(pool_state.read().transactions.is_empty());
+    });
+}
+
+#[test]
+fn offchain_worker_respects_ack_validator() {
+    let (offchain, _state) = TestOffchainExt::new();
+    let (pool, pool_state) = TestTransactionPoolExt::new();
+    let mut ext = new_test_ext();
+    ext.register_extension(OffchainExt::new(offchain));
+    ext.register_extension(TransactionPoolExt::new(pool));
+
+    ext.execute_with(|| {
+        UintAuthorityId::set_all_keys(vec![1u64]);
+        enable_auto_ack();
+        submit_reqs(10);
+        MockAckAllowed::set(&false);
+
+        <SwapModule as Hooks<u64>>::offchain_worker(1u64);
+
+        assert!(pool_state.read().transactions.is_empty());
+    });
+}
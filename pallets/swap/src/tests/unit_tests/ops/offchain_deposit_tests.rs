@@ -0,0 +1,196 @@
+use super::*;
+use codec::Decode;
+use frame_support::sp_runtime::offchain::storage::StorageValueRef;
+use frame_support::traits::Hooks;
+use sp_core::offchain::{testing::{TestOffchainExt, TestTransactionPoolExt}, OffchainExt, TransactionPoolExt};
+use sp_core::{ed25519, Pair};
+use sp_keystore::{testing::KeyStore, KeystoreExt, SyncCryptoStore};
+use sp_runtime::testing::UintAuthorityId;
+use std::sync::Arc;
+
+const ADMIN_L2_SURI: &str = "//Admin";
+
+fn admin_secret_key() -> [u8; 32] {
+    let signature = ed25519::Pair::from_string(ADMIN_L2_SURI, None).unwrap().sign(L2_SECRET_KEY_MESSAGE);
+    l2_secret_key_from_signature(&signature.0)
+}
+
+fn register_l2_keys(ext: &mut sp_io::TestExternalities, suris: &[&str]) {
+    let keystore = KeyStore::new();
+    for suri in suris {
+        SyncCryptoStore::ed25519_generate_new(&keystore, L2_KEY_TYPE, Some(suri)).unwrap();
+    }
+    ext.register_extension(KeystoreExt(Arc::new(keystore)));
+}
+
+fn prepare_unit_test() {
+    UintAuthorityId::set_all_keys(vec![1u64]);
+
+    //SetKey for accountIndex 0, 1 (admin) and 2
+    let origins = [0u64, 1u64, 2u64];
+    let secret_keys = [[2u8; 32], admin_secret_key(), [4u8; 32]];
+    for (origin, secret_key) in origins.iter().zip(secret_keys.iter()) {
+        let pub_key = BabyJubjub::pubkey_from_secretkey(secret_key).encode();
+        assert_ok!(SwapModule::set_key(Origin::signed(*origin), pub_key));
+    }
+}
+
+fn set_l1_deposits(deposits: Vec<L1Deposit>) {
+    StorageValueRef::persistent(L1_DEPOSITS_STORAGE).set(&deposits);
+}
+
+#[test]
+fn offchain_worker_submits_l1_deposit() {
+    let (offchain, _state) = TestOffchainExt::new();
+    let (pool, pool_state) = TestTransactionPoolExt::new();
+    let mut ext = new_test_ext();
+    ext.register_extension(OffchainExt::new(offchain));
+    ext.register_extension(TransactionPoolExt::new(pool));
+    register_l2_keys(&mut ext, &[ADMIN_L2_SURI]);
+
+    ext.execute_with(|| {
+        prepare_unit_test();
+        let l1_tx_hash = U256::from(5);
+        set_l1_deposits(vec![L1Deposit::Token { l1_tx_hash, account_index: 2u32, token_index: 1u32, amount: U256::from(10) }]);
+
+        <SwapModule as Hooks<u64>>::offchain_worker(1u64);
+
+        let tx = pool_state.write().transactions.pop().unwrap();
+        let tx = Extrinsic::decode(&mut &*tx).unwrap();
+        if let mock::Call::Swap(crate::Call::deposit(sign, account_index, token_index, amount, hash, nonce)) = tx.call {
+            assert_eq!((account_index, token_index, amount, hash), (2u32, 1u32, U256::from(10), l1_tx_hash));
+            assert_ok!(SwapModule::deposit(Origin::signed(1u64), sign, account_index, token_index, amount, hash, nonce));
+        } else {
+            panic!("unexpected call {:?}", tx.call);
+        }
+        assert_eq!(BalanceMap::<Test>::get((2u32, 1u32)), U256::from(10));
+
+        //The deposit is on L2, it is not submitted again
+        <SwapModule as Hooks<u64>>::offchain_worker(2u64);
+        assert!(pool_state.read().transactions.is_empty());
+    });
+}
+
+#[test]
+fn offchain_worker_submits_l1_nft_deposit() {
+    let (offchain, _state) = TestOffchainExt::new();
+    let (pool, pool_state) = TestTransactionPoolExt::new();
+    let mut ext = new_test_ext();
+    ext.register_extension(OffchainExt::new(offchain));
+    ext.register_extension(TransactionPoolExt::new(pool));
+    register_l2_keys(&mut ext, &[ADMIN_L2_SURI]);
+
+    ext.execute_with(|| {
+        prepare_unit_test();
+        let l1_tx_hash = U256::from(6);
+        set_l1_deposits(vec![L1Deposit::NFT { l1_tx_hash, account_index: 2u32, nft_id: 4u32 }]);
+
+        <SwapModule as Hooks<u64>>::offchain_worker(1u64);
+
+        let tx = pool_state.write().transactions.pop().unwrap();
+        let tx = Extrinsic::decode(&mut &*tx).unwrap();
+        if let mock::Call::Swap(crate::Call::deposit_nft(sign, account_index, nft_id, hash, nonce)) = tx.call {
+            assert_ok!(SwapModule::deposit_nft(Origin::signed(1u64), sign, account_index, nft_id, hash, nonce));
+        } else {
+            panic!("unexpected call {:?}", tx.call);
+        }
+        assert_eq!(NFTMap::<Test>::get(4u32).0, 2u32);
+    });
+}
+
+#[test]
+fn offchain_worker_skips_deposits_on_l2_and_invalid_deposits() {
+    let (offchain, _state) = TestOffchainExt::new();
+    let (pool, pool_state) = TestTransactionPoolExt::new();
+    let mut ext = new_test_ext();
+    ext.register_extension(OffchainExt::new(offchain));
+    ext.register_extension(TransactionPoolExt::new(pool));
+    register_l2_keys(&mut ext, &[ADMIN_L2_SURI]);
+
+    ext.execute_with(|| {
+        prepare_unit_test();
+        L1TxMap::<Test>::insert(U256::from(5), L1TxStatus::Done);
+        set_l1_deposits(vec![
+            L1Deposit::Token { l1_tx_hash: U256::from(5), account_index: 2u32, token_index: 1u32, amount: U256::from(10) },
+            //Unknown account
+            L1Deposit::Token { l1_tx_hash: U256::from(6), account_index: 9u32, token_index: 1u32, amount: U256::from(10) },
+            //Unregistered token
+            L1Deposit::Token { l1_tx_hash: U256::from(7), account_index: 2u32, token_index: 9u32, amount: U256::from(10) },
+            //Invalid NFT
+            L1Deposit::NFT { l1_tx_hash: U256::from(8), account_index: 2u32, nft_id: 0u32 },
+        ]);
+
+        <SwapModule as Hooks<u64>>::offchain_worker(1u64);
+
+        assert!(pool_state.read().transactions.is_empty());
+    });
+}
+
+#[test]
+fn offchain_worker_waits_for_submitted_deposit() {
+    let (offchain, _state) = TestOffchainExt::new();
+    let (pool, pool_state) = TestTransactionPoolExt::new();
+    let mut ext = new_test_ext();
+    ext.register_extension(OffchainExt::new(offchain));
+    ext.register_extension(TransactionPoolExt::new(pool));
+    register_l2_keys(&mut ext, &[ADMIN_L2_SURI]);
+
+    ext.execute_with(|| {
+        prepare_unit_test();
+        set_l1_deposits(vec![
+            L1Deposit::Token { l1_tx_hash: U256::from(5), account_index: 2u32, token_index: 1u32, amount: U256::from(10) },
+            L1Deposit::Token { l1_tx_hash: U256::from(6), account_index: 2u32, token_index: 1u32, amount: U256::from(20) },
+        ]);
+
+        <SwapModule as Hooks<u64>>::offchain_worker(1u64);
+        assert_eq!(pool_state.read().transactions.len(), 1);
+
+        <SwapModule as Hooks<u64>>::offchain_worker(2u64);
+        assert_eq!(pool_state.read().transactions.len(), 1);
+    });
+}
+
+#[test]
+fn offchain_worker_needs_admin_l2_key() {
+    let (offchain, _state) = TestOffchainExt::new();
+    let (pool, pool_state) = TestTransactionPoolExt::new();
+    let mut ext = new_test_ext();
+    ext.register_extension(OffchainExt::new(offchain));
+    ext.register_extension(TransactionPoolExt::new(pool));
+    register_l2_keys(&mut ext, &["//Other"]);
+
+    ext.execute_with(|| {
+        //The keystore holds no key deriving the L2 key of the admin
+        prepare_unit_test();
+        set_l1_deposits(vec![L1Deposit::Token { l1_tx_hash: U256::from(5), account_index: 2u32, token_index: 1u32, amount: U256::from(10) }]);
+
+        <SwapModule as Hooks<u64>>::offchain_worker(1u64);
+
+        assert!(pool_state.read().transactions.is_empty());
+    });
+}
+
+#[test]
+fn offchain_worker_picks_admin_l2_key() {
+    let (offchain, _state) = TestOffchainExt::new();
+    let (pool, pool_state) = TestTransactionPoolExt::new();
+    let mut ext = new_test_ext();
+    ext.register_extension(OffchainExt::new(offchain));
+    ext.register_extension(TransactionPoolExt::new(pool));
+    register_l2_keys(&mut ext, &["//Other", ADMIN_L2_SURI]);
+
+    ext.execute_with(|| {
+        prepare_unit_test();
+        set_l1_deposits(vec![L1Deposit::Token { l1_tx_hash: U256::from(5), account_index: 2u32, token_index: 1u32, amount: U256::from(10) }]);
+
+        <SwapModule as Hooks<u64>>::offchain_worker(1u64);
+
+        let tx = pool_state.write().transactions.pop().unwrap();
+        let tx = Extrinsic::decode(&mut &*tx).unwrap();
+        if let mock::Call::Swap(crate::Call::deposit(sign, account_index, token_index, amount, hash, nonce)) = tx.call {
+            assert_ok!(SwapModule::deposit(Origin::signed(1u64), sign, account_index, token_index, amount, hash, nonce));
+        } else {
+            panic!("unexpected call {:?}", tx.call);
+        }
+    });
+}
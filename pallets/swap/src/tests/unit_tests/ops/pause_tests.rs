@@ -0,0 +1,115 @@
+use super::*;
+
+fn prepare_unit_test() {
+    //PoolMap insert new value
+    let pool_index = 0u32;
+    let token_index0 = 0u32;
+    let token_index1 = 1u32;
+    let amount0 = U256::from(1000);
+    let amount1 = U256::from(1000);
+    let total_share = U256::from(1000);
+    PoolMap::<Test>::insert(
+        pool_index,
+        (
+            &token_index0.clone(),
+            &token_index1.clone(),
+            amount0,
+            amount1,
+            total_share
+        ),
+    );
+}
+
+#[test]
+fn set_pool_paused_works() {
+    new_test_ext().execute_with(|| {
+        prepare_unit_test();
+
+        //Pause poolIndex 0, caller is admin 1
+        let origin = 1u64;
+        let pool_index = 0u32;
+        assert_ok!(SwapModule::set_pool_paused(Origin::signed(origin), pool_index, true));
+        assert_eq!(PausedPoolMap::<Test>::get(pool_index), true);
+
+        //Swap, supply and retrieve on poolIndex 0 are rejected before any other check
+        let sign = [0u8; 64];
+        let nonce = 1u64;
+        assert_noop!(SwapModule::swap(Origin::signed(3u64), sign, pool_index, 0u8, U256::from(10), nonce), Error::<Test>::Paused);
+        assert_noop!(SwapModule::pool_supply(Origin::signed(3u64), sign, pool_index, U256::from(10), U256::from(10), nonce), Error::<Test>::Paused);
+        assert_noop!(SwapModule::pool_retrieve(Origin::signed(3u64), sign, pool_index, U256::from(10), U256::from(10), nonce), Error::<Test>::Paused);
+
+        //Other pools are not affected
+        assert_noop!(SwapModule::swap(Origin::signed(3u64), sign, 1u32, 0u8, U256::from(10), nonce), Error::<Test>::AccountNotExists);
+
+        //Resume poolIndex 0
+        assert_ok!(SwapModule::set_pool_paused(Origin::signed(origin), pool_index, false));
+        assert_noop!(SwapModule::swap(Origin::signed(3u64), sign, pool_index, 0u8, U256::from(10), nonce), Error::<Test>::AccountNotExists);
+    })
+}
+
+#[test]
+fn set_pool_paused_pool_not_exists() {
+    new_test_ext().execute_with(|| {
+        let origin = 1u64;
+        assert_noop!(SwapModule::set_pool_paused(Origin::signed(origin), 1u32, true), Error::<Test>::PoolNotExists);
+    })
+}
+
+#[test]
+fn set_op_paused_works() {
+    new_test_ext().execute_with(|| {
+        //Pause OP_WITHDRAW, caller is admin 2
+        let origin = 2u64;
+        assert_ok!(SwapModule::set_op_paused(Origin::signed(origin), OP_WITHDRAW, true));
+        assert_eq!(PausedOpMap::<Test>::get(OP_WITHDRAW), true);
+
+        let sign = [0u8; 64];
+        let nonce = 1u64;
+        assert_noop!(SwapModule::withdraw(Origin::signed(3u64), sign, 1u32, U256::from(10), U256::from(1), nonce), Error::<Test>::Paused);
+
+        //Other ops are not affected
+        assert_noop!(SwapModule::withdraw_nft(Origin::signed(3u64), sign, 1u32, U256::from(1), nonce), Error::<Test>::AccountNotExists);
+    })
+}
+
+#[test]
+fn set_op_paused_invalid_op() {
+    new_test_ext().execute_with(|| {
+        let origin = 1u64;
+        assert_noop!(SwapModule::set_op_paused(Origin::signed(origin), OP_FINALIZE_NFT + 1, true), Error::<Test>::InvalidOp);
+    })
+}
+
+#[test]
+fn set_pallet_paused_works() {
+    new_test_ext().execute_with(|| {
+        //Pause the whole pallet, caller is admin 1
+        let origin = 1u64;
+        assert_ok!(SwapModule::set_pallet_paused(Origin::signed(origin), true));
+
+        let pub_key: [u8; 32] = [
+            31, 191,  89, 175,  20, 249,  30,  36,
+            241, 189, 202, 124,  86, 229, 209, 121,
+            66, 200, 153,  22, 214,  74, 245, 240,
+            154,  86, 172,  63, 104, 123, 204,   6
+        ];
+        assert_noop!(SwapModule::set_key(Origin::signed(0u64), pub_key), Error::<Test>::Paused);
+
+        //Resume the pallet
+        assert_ok!(SwapModule::set_pallet_paused(Origin::signed(origin), false));
+        assert_ok!(SwapModule::set_key(Origin::signed(0u64), pub_key));
+    })
+}
+
+#[test]
+fn set_paused_noaccess() {
+    new_test_ext().execute_with(|| {
+        prepare_unit_test();
+
+        //Caller 0 is not admin
+        let origin = 0u64;
+        assert_noop!(SwapModule::set_pallet_paused(Origin::signed(origin), true), Error::<Test>::NoAccess);
+        assert_noop!(SwapModule::set_pool_paused(Origin::signed(origin), 0u32, true), Error::<Test>::NoAccess);
+        assert_noop!(SwapModule::set_op_paused(Origin::signed(origin), OP_SWAP, true), Error::<Test>::NoAccess);
+    })
+}
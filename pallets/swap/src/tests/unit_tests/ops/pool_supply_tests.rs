@@ -43,11 +43,7 @@ fn prepare_unit_test() {
         49, 180, 255,  97, 249,  95,   1, 203
     ];
 
-    let mut command = [0u8; 81];
-    command[0] = OP_ADDPOOL;
-    command[1..9].copy_from_slice(&nonce.to_be_bytes());
-    command[9..13].copy_from_slice(&token_index_0.to_be_bytes());
-    command[13..17].copy_from_slice(&token_index_1.to_be_bytes());
+    let command = Command::AddPool { nonce, token_index_0, token_index_1 }.encode();
 
     let command_sign = BabyJubjub::sign(&command, &secret_key_1);
     let mut command_sign_formatted :[u8; 64] = [0 as u8;64];
@@ -64,12 +60,7 @@ fn prepare_unit_test() {
     let mut l1_tx_hash = U256::from(0);
     let mut nonce = 2u64;
 
-    let mut command = [0u8; 81];
-    command[0] = OP_DEPOSIT;
-    command[1..9].copy_from_slice(&nonce.to_be_bytes());
-    command[9..13].copy_from_slice(&account_index.to_be_bytes());
-    command[13..17].copy_from_slice(&token_index.to_be_bytes());
-    command[17..49].copy_from_slice(&amount.to_be_bytes());
+    let mut command = Command::Deposit { nonce, account_index, token_index, amount }.encode();
 
     let mut command_sign = BabyJubjub::sign(&command, &secret_key_1);
     let mut command_sign_formatted :[u8; 64] = [0 as u8;64];
@@ -83,12 +74,7 @@ fn prepare_unit_test() {
     l1_tx_hash = U256::from(1);
     nonce = 3u64;
 
-    command = [0u8; 81];
-    command[0] = OP_DEPOSIT;
-    command[1..9].copy_from_slice(&nonce.to_be_bytes());
-    command[9..13].copy_from_slice(&account_index.to_be_bytes());
-    command[13..17].copy_from_slice(&token_index.to_be_bytes());
-    command[17..49].copy_from_slice(&amount.to_be_bytes());
+    command = Command::Deposit { nonce, account_index, token_index, amount }.encode();
 
     command_sign = BabyJubjub::sign(&command, &secret_key_1);
     command_sign_formatted = [0 as u8;64];
@@ -118,13 +104,7 @@ fn pool_supply_works_old_amount0_is_zero() {
             185, 222, 254,  50, 115,  63,  97, 179
         ];
 
-        let mut command = [0u8; 81];
-        command[0] = OP_SUPPLY;
-        command[1..9].copy_from_slice(&nonce.to_be_bytes());
-        command[9..13].copy_from_slice(&account_index.to_be_bytes());
-        command[13..17].copy_from_slice(&pool_index.to_be_bytes());
-        command[17..49].copy_from_slice(&amount0.to_be_bytes());
-        command[49..81].copy_from_slice(&amount1.to_be_bytes());
+        let command = Command::PoolSupply { nonce, account_index, pool_index, amount0, amount1 }.encode();
 
         let command_sign = BabyJubjub::sign(&command, &secret_key_2);
         let mut command_sign_formatted :[u8; 64] = [0 as u8;64];
@@ -133,13 +113,13 @@ fn pool_supply_works_old_amount0_is_zero() {
 
         assert_ok!(SwapModule::pool_supply(Origin::signed(origin), command_sign_formatted, pool_index, amount0, amount1, nonce));
 
-        assert_eq!(PoolMap::get(pool_index).unwrap(), (0u32, 1u32, U256::from(1000), U256::from(1000), U256::from(1_000_000_000_000_000_000u128)));
+        assert_eq!(PoolMap::<Test>::get(pool_index).unwrap(), (0u32, 1u32, U256::from(1000), U256::from(1000), U256::from(1_000_000_000_000_000_000u128)));
 
-        assert_eq!(BalanceMap::get((account_index, 0u32)), U256::from(1000));
+        assert_eq!(BalanceMap::<Test>::get((account_index, 0u32)), U256::from(1000));
 
-        assert_eq!(BalanceMap::get((account_index, 1u32)), U256::from(1000));
+        assert_eq!(BalanceMap::<Test>::get((account_index, 1u32)), U256::from(1000));
 
-        assert_eq!(ShareMap::get((&account_index, &pool_index)), U256::from(1_000_000_000_000_000_000u128));
+        assert_eq!(ShareMap::<Test>::get((&account_index, &pool_index)), U256::from(1_000_000_000_000_000_000u128));
 
         assert_eq!(NonceMap::<Test>::get(2u64), 2u64);
     })
@@ -164,13 +144,7 @@ fn pool_supply_works_old_amount0_is_not_zero() {
             185, 222, 254,  50, 115,  63,  97, 179
         ];
 
-        let mut command = [0u8; 81];
-        command[0] = OP_SUPPLY;
-        command[1..9].copy_from_slice(&nonce.to_be_bytes());
-        command[9..13].copy_from_slice(&account_index.to_be_bytes());
-        command[13..17].copy_from_slice(&pool_index.to_be_bytes());
-        command[17..49].copy_from_slice(&amount0.to_be_bytes());
-        command[49..81].copy_from_slice(&amount1.to_be_bytes());
+        let command = Command::PoolSupply { nonce, account_index, pool_index, amount0, amount1 }.encode();
 
         let command_sign = BabyJubjub::sign(&command, &secret_key_2);
         let mut command_sign_formatted: [u8; 64] = [0 as u8;64];
@@ -183,13 +157,7 @@ fn pool_supply_works_old_amount0_is_not_zero() {
         //Old amount0 in pool is not 0
         nonce = 2u64;
 
-        let mut command = [0u8; 81];
-        command[0] = OP_SUPPLY;
-        command[1..9].copy_from_slice(&nonce.to_be_bytes());
-        command[9..13].copy_from_slice(&account_index.to_be_bytes());
-        command[13..17].copy_from_slice(&pool_index.to_be_bytes());
-        command[17..49].copy_from_slice(&amount0.to_be_bytes());
-        command[49..81].copy_from_slice(&amount1.to_be_bytes());
+        let command = Command::PoolSupply { nonce, account_index, pool_index, amount0, amount1 }.encode();
 
         let command_sign = BabyJubjub::sign(&command, &secret_key_2);
         let mut command_sign_formatted: [u8; 64] = [0 as u8;64];
@@ -198,13 +166,13 @@ fn pool_supply_works_old_amount0_is_not_zero() {
 
         assert_ok!(SwapModule::pool_supply(Origin::signed(origin), command_sign_formatted, pool_index, amount0, amount1, nonce));
 
-        assert_eq!(PoolMap::get(pool_index).unwrap(), (0u32, 1u32, U256::from(2000), U256::from(2000), U256::from(2_000_000_000_000_000_000u128)));
+        assert_eq!(PoolMap::<Test>::get(pool_index).unwrap(), (0u32, 1u32, U256::from(2000), U256::from(2000), U256::from(2_000_000_000_000_000_000u128)));
 
-        assert_eq!(BalanceMap::get((&account_index, 0u32)), U256::from(0));
+        assert_eq!(BalanceMap::<Test>::get((&account_index, 0u32)), U256::from(0));
 
-        assert_eq!(BalanceMap::get((&account_index, 1u32)), U256::from(0));
+        assert_eq!(BalanceMap::<Test>::get((&account_index, 1u32)), U256::from(0));
 
-        assert_eq!(ShareMap::get((&account_index, &pool_index)), U256::from(2_000_000_000_000_000_000u128));
+        assert_eq!(ShareMap::<Test>::get((&account_index, &pool_index)), U256::from(2_000_000_000_000_000_000u128));
 
         assert_eq!(NonceMap::<Test>::get(2u64), 3u64);
     })
@@ -230,13 +198,7 @@ fn pool_supply_account_not_exists() {
             185, 222, 254,  50, 115,  63,  97, 179
         ];
 
-        let mut command = [0u8; 81];
-        command[0] = OP_SUPPLY;
-        command[1..9].copy_from_slice(&nonce.to_be_bytes());
-        command[9..13].copy_from_slice(&account_index.to_be_bytes());
-        command[13..17].copy_from_slice(&pool_index.to_be_bytes());
-        command[17..49].copy_from_slice(&amount0.to_be_bytes());
-        command[49..81].copy_from_slice(&amount1.to_be_bytes());
+        let command = Command::PoolSupply { nonce, account_index, pool_index, amount0, amount1 }.encode();
 
         //There is no secret_key_3, so use secret_key_2 here
         let command_sign = BabyJubjub::sign(&command, &secret_key_2);
@@ -268,13 +230,7 @@ fn pool_supply_invalid_amount_amount0_exceeds_range() {
             185, 222, 254,  50, 115,  63,  97, 179
         ];
 
-        let mut command = [0u8; 81];
-        command[0] = OP_SUPPLY;
-        command[1..9].copy_from_slice(&nonce.to_be_bytes());
-        command[9..13].copy_from_slice(&account_index.to_be_bytes());
-        command[13..17].copy_from_slice(&pool_index.to_be_bytes());
-        command[17..49].copy_from_slice(&amount0.to_be_bytes());
-        command[49..81].copy_from_slice(&amount1.to_be_bytes());
+        let command = Command::PoolSupply { nonce, account_index, pool_index, amount0, amount1 }.encode();
 
         let command_sign = BabyJubjub::sign(&command, &secret_key_2);
         let mut command_sign_formatted :[u8; 64] = [0 as u8;64];
@@ -305,13 +261,7 @@ fn pool_supply_invalid_amount_amount1_exceeds_range() {
             185, 222, 254,  50, 115,  63,  97, 179
         ];
 
-        let mut command = [0u8; 81];
-        command[0] = OP_SUPPLY;
-        command[1..9].copy_from_slice(&nonce.to_be_bytes());
-        command[9..13].copy_from_slice(&account_index.to_be_bytes());
-        command[13..17].copy_from_slice(&pool_index.to_be_bytes());
-        command[17..49].copy_from_slice(&amount0.to_be_bytes());
-        command[49..81].copy_from_slice(&amount1.to_be_bytes());
+        let command = Command::PoolSupply { nonce, account_index, pool_index, amount0, amount1 }.encode();
 
         let command_sign = BabyJubjub::sign(&command, &secret_key_2);
         let mut command_sign_formatted :[u8; 64] = [0 as u8;64];
@@ -342,13 +292,7 @@ fn pool_supply_invalid_amount_amount0_mul_amount1_is_zero() {
             185, 222, 254,  50, 115,  63,  97, 179
         ];
 
-        let mut command = [0u8; 81];
-        command[0] = OP_SUPPLY;
-        command[1..9].copy_from_slice(&nonce.to_be_bytes());
-        command[9..13].copy_from_slice(&account_index.to_be_bytes());
-        command[13..17].copy_from_slice(&pool_index.to_be_bytes());
-        command[17..49].copy_from_slice(&amount0.to_be_bytes());
-        command[49..81].copy_from_slice(&amount1.to_be_bytes());
+        let command = Command::PoolSupply { nonce, account_index, pool_index, amount0, amount1 }.encode();
 
         let command_sign = BabyJubjub::sign(&command, &secret_key_2);
         let mut command_sign_formatted :[u8; 64] = [0 as u8;64];
@@ -380,13 +324,7 @@ fn pool_supply_pool_not_exists() {
             185, 222, 254,  50, 115,  63,  97, 179
         ];
 
-        let mut command = [0u8; 81];
-        command[0] = OP_SUPPLY;
-        command[1..9].copy_from_slice(&nonce.to_be_bytes());
-        command[9..13].copy_from_slice(&account_index.to_be_bytes());
-        command[13..17].copy_from_slice(&pool_index.to_be_bytes());
-        command[17..49].copy_from_slice(&amount0.to_be_bytes());
-        command[49..81].copy_from_slice(&amount1.to_be_bytes());
+        let command = Command::PoolSupply { nonce, account_index, pool_index, amount0, amount1 }.encode();
 
         let command_sign = BabyJubjub::sign(&command, &secret_key_2);
         let mut command_sign_formatted :[u8; 64] = [0 as u8;64];
@@ -417,13 +355,7 @@ fn pool_supply_nonce_inconsistent() {
             185, 222, 254,  50, 115,  63,  97, 179
         ];
 
-        let mut command = [0u8; 81];
-        command[0] = OP_SUPPLY;
-        command[1..9].copy_from_slice(&nonce.to_be_bytes());
-        command[9..13].copy_from_slice(&account_index.to_be_bytes());
-        command[13..17].copy_from_slice(&pool_index.to_be_bytes());
-        command[17..49].copy_from_slice(&amount0.to_be_bytes());
-        command[49..81].copy_from_slice(&amount1.to_be_bytes());
+        let command = Command::PoolSupply { nonce, account_index, pool_index, amount0, amount1 }.encode();
 
         let command_sign = BabyJubjub::sign(&command, &secret_key_2);
         let mut command_sign_formatted :[u8; 64] = [0 as u8;64];
@@ -453,13 +385,7 @@ fn pool_supply_invalid_signature() {
             185, 222, 254,  50, 115,  63,  97, 179
         ];
 
-        let mut command = [0u8; 81];
-        command[0] = OP_SUPPLY;
-        command[1..9].copy_from_slice(&nonce.to_be_bytes());
-        command[9..13].copy_from_slice(&account_index.to_be_bytes());
-        command[13..17].copy_from_slice(&pool_index.to_be_bytes());
-        command[17..49].copy_from_slice(&amount0.to_be_bytes());
-        command[49..81].copy_from_slice(&amount1.to_be_bytes());
+        let command = Command::PoolSupply { nonce, account_index, pool_index, amount0, amount1 }.encode();
 
         let command_sign = BabyJubjub::sign(&command, &secret_key_2);
         let mut command_sign_formatted :[u8; 64] = [0 as u8;64];
@@ -493,13 +419,7 @@ fn pool_supply_balance_not_enough() {
             185, 222, 254,  50, 115,  63,  97, 179
         ];
 
-        let mut command = [0u8; 81];
-        command[0] = OP_SUPPLY;
-        command[1..9].copy_from_slice(&nonce.to_be_bytes());
-        command[9..13].copy_from_slice(&account_index.to_be_bytes());
-        command[13..17].copy_from_slice(&pool_index.to_be_bytes());
-        command[17..49].copy_from_slice(&amount0.to_be_bytes());
-        command[49..81].copy_from_slice(&amount1.to_be_bytes());
+        let command = Command::PoolSupply { nonce, account_index, pool_index, amount0, amount1 }.encode();
 
         let command_sign = BabyJubjub::sign(&command, &secret_key_2);
         let mut command_sign_formatted :[u8; 64] = [0 as u8;64];
@@ -529,13 +449,7 @@ fn pool_supply_y_amount_invalid() {
             185, 222, 254,  50, 115,  63,  97, 179
         ];
 
-        let mut command = [0u8; 81];
-        command[0] = OP_SUPPLY;
-        command[1..9].copy_from_slice(&nonce.to_be_bytes());
-        command[9..13].copy_from_slice(&account_index.to_be_bytes());
-        command[13..17].copy_from_slice(&pool_index.to_be_bytes());
-        command[17..49].copy_from_slice(&amount0.to_be_bytes());
-        command[49..81].copy_from_slice(&amount1.to_be_bytes());
+        let command = Command::PoolSupply { nonce, account_index, pool_index, amount0, amount1 }.encode();
 
         let command_sign = BabyJubjub::sign(&command, &secret_key_2);
         let mut command_sign_formatted: [u8; 64] = [0 as u8;64];
@@ -550,13 +464,7 @@ fn pool_supply_y_amount_invalid() {
         amount1 = U256::from(999);
         nonce = 2u64;
         
-        let mut command = [0u8; 81];
-        command[0] = OP_SUPPLY;
-        command[1..9].copy_from_slice(&nonce.to_be_bytes());
-        command[9..13].copy_from_slice(&account_index.to_be_bytes());
-        command[13..17].copy_from_slice(&pool_index.to_be_bytes());
-        command[17..49].copy_from_slice(&amount0.to_be_bytes());
-        command[49..81].copy_from_slice(&amount1.to_be_bytes());
+        let command = Command::PoolSupply { nonce, account_index, pool_index, amount0, amount1 }.encode();
 
         let command_sign = BabyJubjub::sign(&command, &secret_key_2);
         let mut command_sign_formatted :[u8; 64] = [0 as u8;64];
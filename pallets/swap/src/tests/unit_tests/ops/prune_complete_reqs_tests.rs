@@ -0,0 +1,141 @@
+use super::*;
+use frame_support::sp_runtime::traits::Hash;
+use frame_support::traits::OnInitialize;
+
+fn prepare_unit_test() {
+    System::set_block_number(1);
+
+    //SetKey for accountIndex 0 to 9 as reqId 1 to 10
+    for origin in 0u64..10u64 {
+        let secret_key = [origin as u8 + 2; 32];
+        let pub_key = BabyJubjub::pubkey_from_secretkey(&secret_key).encode();
+        assert_ok!(SwapModule::set_key(Origin::signed(origin), pub_key));
+    }
+}
+
+fn ack_all() {
+    System::set_block_number(2);
+    assert_ok!(SwapModule::ack(Origin::signed(1), U256::from(0)));
+    assert_ok!(SwapModule::ack(Origin::signed(2), U256::from(0)));
+}
+
+#[test]
+fn prune_complete_reqs_after_retention() {
+    new_test_ext().execute_with(|| {
+        prepare_unit_test();
+        ack_all();
+
+        let op = CompleteReqMap::<Test>::get(U256::from(1)).unwrap();
+
+        //Still inside the retention window
+        SwapModule::on_initialize(11);
+        assert_eq!(PrunedReqIndex::<Test>::get(), U256::from(0));
+        assert!(CompleteReqMap::<Test>::contains_key(U256::from(1)));
+
+        //At most MaxPrunePerBlock requests are pruned per block
+        SwapModule::on_initialize(12);
+        assert_eq!(PrunedReqIndex::<Test>::get(), U256::from(5));
+        assert!(!CompleteReqMap::<Test>::contains_key(U256::from(1)));
+        assert!(CompleteReqMap::<Test>::contains_key(U256::from(6)));
+        assert_eq!(
+            PrunedReqMap::<Test>::get(U256::from(1)),
+            Some(<Test as frame_system::Config>::Hashing::hash_of(&op))
+        );
+        assert!(SwapModule::request(U256::from(1)).is_none());
+        assert!(!ReqRecordMap::<Test>::contains_key(U256::from(1)));
+        assert!(!AckMap::<Test>::contains_key(U256::from(1)));
+
+        SwapModule::on_initialize(13);
+        assert_eq!(PrunedReqIndex::<Test>::get(), U256::from(10));
+        assert!(!CompleteReqMap::<Test>::contains_key(U256::from(10)));
+    })
+}
+
+#[test]
+fn prune_complete_reqs_with_rejected() {
+    new_test_ext().execute_with(|| {
+        prepare_unit_test();
+        ack_all();
+
+        //reqId 3 rejected at block 2
+        let op = CompleteReqMap::<Test>::take(U256::from(3)).unwrap();
+        RejectedReqMap::<Test>::insert(U256::from(3), (op.clone(), b"reason".to_vec()));
+
+        //Rejected requests count against MaxPrunePerBlock
+        SwapModule::on_initialize(12);
+        assert_eq!(PrunedReqIndex::<Test>::get(), U256::from(5));
+        assert!(!RejectedReqMap::<Test>::contains_key(U256::from(3)));
+        assert_eq!(
+            PrunedReqMap::<Test>::get(U256::from(3)),
+            Some(<Test as frame_system::Config>::Hashing::hash_of(&op))
+        );
+    })
+}
+
+#[test]
+fn prune_complete_reqs_without_record() {
+    new_test_ext().execute_with(|| {
+        prepare_unit_test();
+        ack_all();
+
+        //Requests completed before the upgrade at block 5 have no record
+        ReqRecordMap::<Test>::remove_all();
+        UpgradedAt::<Test>::put(5);
+
+        SwapModule::on_initialize(14);
+        assert_eq!(PrunedReqIndex::<Test>::get(), U256::from(0));
+
+        SwapModule::on_initialize(15);
+        assert_eq!(PrunedReqIndex::<Test>::get(), U256::from(5));
+    })
+}
+
+#[test]
+fn prune_complete_reqs_stops_at_pending() {
+    new_test_ext().execute_with(|| {
+        prepare_unit_test();
+
+        SwapModule::on_initialize(20);
+        assert_eq!(PrunedReqIndex::<Test>::get(), U256::from(0));
+        assert!(PendingReqMap::<Test>::contains_key(U256::from(1)));
+    })
+}
+
+#[test]
+fn prune_complete_reqs_force() {
+    new_test_ext().execute_with(|| {
+        prepare_unit_test();
+        ack_all();
+
+        assert_ok!(SwapModule::prune_complete_reqs(Origin::signed(1), U256::from(3)));
+        assert_eq!(PrunedReqIndex::<Test>::get(), U256::from(3));
+        assert!(!CompleteReqMap::<Test>::contains_key(U256::from(3)));
+        assert!(CompleteReqMap::<Test>::contains_key(U256::from(4)));
+    })
+}
+
+#[test]
+fn prune_complete_reqs_no_access() {
+    new_test_ext().execute_with(|| {
+        prepare_unit_test();
+        ack_all();
+
+        assert_noop!(
+            SwapModule::prune_complete_reqs(Origin::signed(0), U256::from(3)),
+            Error::<Test>::NoAccess
+        );
+    })
+}
+
+#[test]
+fn prune_complete_reqs_invalid_req_id() {
+    new_test_ext().execute_with(|| {
+        prepare_unit_test();
+        ack_all();
+
+        assert_noop!(
+            SwapModule::prune_complete_reqs(Origin::signed(1), U256::from(11)),
+            Error::<Test>::InvalidReqId
+        );
+    })
+}
@@ -0,0 +1,90 @@
+use super::*;
+
+fn prepare_unit_test() {
+    //SetKey for accountIndex 0
+    let mut origin = 0u64;
+    let pub_key_0: [u8; 32] = [
+        31, 191,  89, 175,  20, 249,  30,  36,
+        241, 189, 202, 124,  86, 229, 209, 121,
+        66, 200, 153,  22, 214,  74, 245, 240,
+        154,  86, 172,  63, 104, 123, 204,   6
+    ];
+    assert_ok!(SwapModule::set_key(Origin::signed(origin), pub_key_0));
+
+    //SetKey for accountIndex 1
+    origin = 1u64;
+    let pub_key_1: [u8; 32] = [
+        87, 18,  13,  76, 122, 234,  36, 117,
+        25, 95, 106, 155, 114, 225, 157, 106,
+        60, 78, 106, 209,  86, 159, 227,  49,
+        150, 88,   7,  37, 132,   7, 145,  28
+    ];
+    assert_ok!(SwapModule::set_key(Origin::signed(origin), pub_key_1));
+}
+
+#[test]
+fn register_token_works() {
+    new_test_ext().execute_with(|| {
+        //Register tokenIndex 4, caller is admin 1
+        let origin = 1u64;
+        let token_index = 4u32;
+        let l1_address = U256::from(0x1234);
+        let decimals = 6u8;
+        let symbol = b"USDT".to_vec();
+
+        assert_ok!(SwapModule::register_token(Origin::signed(origin), token_index, l1_address, decimals, symbol.clone()));
+
+        assert_eq!(TokenMap::<Test>::get(token_index), Some(TokenInfo { l1_address, decimals, symbol }));
+    })
+}
+
+#[test]
+fn register_token_noaccess() {
+    new_test_ext().execute_with(|| {
+        //Caller 0 is not admin
+        let origin = 0u64;
+        assert_noop!(SwapModule::register_token(Origin::signed(origin), 4u32, U256::from(1), 18u8, b"TKN".to_vec()), Error::<Test>::NoAccess);
+    })
+}
+
+#[test]
+fn register_token_token_exists() {
+    new_test_ext().execute_with(|| {
+        //TokenIndex 0 is registered at genesis
+        let origin = 1u64;
+        assert_noop!(SwapModule::register_token(Origin::signed(origin), 0u32, U256::from(1), 18u8, b"TKN".to_vec()), Error::<Test>::TokenExists);
+    })
+}
+
+#[test]
+fn register_token_token_index_overflow() {
+    new_test_ext().execute_with(|| {
+        //1u32 << 10 exceeds the range 10bits
+        let origin = 1u64;
+        assert_noop!(SwapModule::register_token(Origin::signed(origin), 1u32 << 10, U256::from(1), 18u8, b"TKN".to_vec()), Error::<Test>::TokenIndexOverflow);
+    })
+}
+
+#[test]
+fn register_token_invalid_symbol() {
+    new_test_ext().execute_with(|| {
+        let origin = 1u64;
+        assert_noop!(SwapModule::register_token(Origin::signed(origin), 4u32, U256::from(1), 18u8, [b'A'; 17].to_vec()), Error::<Test>::InvalidTokenSymbol);
+    })
+}
+
+#[test]
+fn unregistered_token_rejected() {
+    new_test_ext().execute_with(|| {
+        prepare_unit_test();
+
+        //TokenIndex 5 is not registered
+        let token_index = 5u32;
+        let sign = [0u8; 64];
+        let nonce = 1u64;
+
+        assert_noop!(SwapModule::deposit(Origin::signed(1u64), sign, 0u32, token_index, U256::from(10), U256::from(0), nonce), Error::<Test>::TokenNotExists);
+        assert_noop!(SwapModule::withdraw(Origin::signed(0u64), sign, token_index, U256::from(10), U256::from(1), nonce), Error::<Test>::TokenNotExists);
+        assert_noop!(SwapModule::add_pool(Origin::signed(1u64), sign, 0u32, token_index, nonce), Error::<Test>::TokenNotExists);
+    })
+}
@@ -0,0 +1,207 @@
+use super::*;
+
+fn prepare_unit_test() {
+    //SetKey for accountIndex 0
+    let mut origin = 0u64;
+    let secret_key_0 = [2u8; 32];
+    let pub_key_0 = BabyJubjub::pubkey_from_secretkey(&secret_key_0).encode();
+    assert_ok!(SwapModule::set_key(Origin::signed(origin), pub_key_0));
+
+    //SetKey for accountIndex 1
+    origin = 1u64;
+    let secret_key_1 = [3u8; 32];
+    let pub_key_1 = BabyJubjub::pubkey_from_secretkey(&secret_key_1).encode();
+    assert_ok!(SwapModule::set_key(Origin::signed(origin), pub_key_1));
+
+    //SetKey for accountIndex 2
+    origin = 2u64;
+    let secret_key_2 = [4u8;32];
+    let pub_key_2 = BabyJubjub::pubkey_from_secretkey(&secret_key_2).encode();
+    assert_ok!(SwapModule::set_key(Origin::signed(origin), pub_key_2));
+
+    //Deposit 10 into accountIndex 2, caller is accountIndex 1
+    origin = 1u64;
+    let account_index = 2u32;
+    let token_index = 1u32;
+    let amount = U256::from(10);
+    let mut l1_tx_hash = U256::from(0);
+    let mut nonce = 1u64;
+
+    let mut command = Command::Deposit { nonce, account_index, token_index, amount }.encode();
+
+    let mut command_sign = BabyJubjub::sign(&command, &secret_key_1);
+    let mut command_sign_formatted :[u8; 64] = [0 as u8;64];
+    command_sign_formatted[..32].copy_from_slice(&command_sign.r.encode());
+    command_sign_formatted[32..].copy_from_slice(&command_sign.s.encode());
+
+    assert_ok!(SwapModule::deposit(Origin::signed(origin), command_sign_formatted, account_index, token_index, amount, l1_tx_hash, nonce));
+
+    //DepositNFT for accountIndex 2, caller is accountIndex 1
+    let nft_id = 4u32;
+    l1_tx_hash = U256::from(1);
+    nonce = 2u64;
+
+    command = Command::DepositNFT { nonce, account_index, nft_id, l1_tx_hash }.encode();
+
+    command_sign = BabyJubjub::sign(&command, &secret_key_1);
+    command_sign_formatted = [0 as u8;64];
+    command_sign_formatted[..32].copy_from_slice(&command_sign.r.encode());
+    command_sign_formatted[32..].copy_from_slice(&command_sign.s.encode());
+
+    assert_ok!(SwapModule::deposit_nft(Origin::signed(origin), command_sign_formatted, account_index, nft_id, l1_tx_hash, nonce));
+
+    //Withdraw 4 from accountIndex 2 as reqId 6, caller is accountIndex 2
+    origin = 2u64;
+    let withdraw_amount = U256::from(4);
+    let l1account = U256::from(3);
+    nonce = 1u64;
+
+    command = Command::Withdraw { nonce, account_index, token_index, amount: withdraw_amount, l1account }.encode();
+
+    command_sign = BabyJubjub::sign(&command, &secret_key_2);
+    command_sign_formatted = [0 as u8;64];
+    command_sign_formatted[..32].copy_from_slice(&command_sign.r.encode());
+    command_sign_formatted[32..].copy_from_slice(&command_sign.s.encode());
+
+    assert_ok!(SwapModule::withdraw(Origin::signed(origin), command_sign_formatted, token_index, withdraw_amount, l1account, nonce));
+
+    //WithdrawNFT for accountIndex 2 as reqId 7, caller is accountIndex 2
+    nonce = 2u64;
+
+    command = Command::WithdrawNFT { nonce, account_index, nft_id, l1account }.encode();
+
+    command_sign = BabyJubjub::sign(&command, &secret_key_2);
+    command_sign_formatted = [0 as u8;64];
+    command_sign_formatted[..32].copy_from_slice(&command_sign.r.encode());
+    command_sign_formatted[32..].copy_from_slice(&command_sign.s.encode());
+
+    assert_ok!(SwapModule::withdraw_nft(Origin::signed(origin), command_sign_formatted, nft_id, l1account, nonce));
+}
+
+#[test]
+fn reject_req_works_withdraw() {
+    new_test_ext().execute_with(|| {
+        prepare_unit_test();
+
+        let req_id = U256::from(6);
+        assert_eq!(BalanceMap::<Test>::get((2u32, 1u32)), U256::from(6));
+
+        assert_ok!(SwapModule::reject_req(Origin::signed(1u64), req_id, b"invalid l1 account".to_vec()));
+
+        assert_eq!(BalanceMap::<Test>::get((2u32, 1u32)), U256::from(10));
+        assert!(PendingReqMap::<Test>::get(&req_id).is_none());
+        assert_eq!(RejectedReqMap::<Test>::get(&req_id).unwrap().1, b"invalid l1 account".to_vec());
+
+        //The refund is reqId 8 with its own root
+        let refund_req_id = U256::from(8);
+        assert_eq!(ReqIndex::<Test>::get(), refund_req_id);
+        assert!(PendingReqMap::<Test>::get(&refund_req_id) == Some(Ops::Reject(req_id)));
+        assert_eq!(ReqRecordMap::<Test>::get(&refund_req_id).unwrap().op, OP_REJECT);
+        assert_eq!(MerkleRootMap::<Test>::get(&refund_req_id), Some(SwapModule::merkle_root(None).unwrap()));
+        assert_ne!(MerkleRootMap::<Test>::get(&refund_req_id), MerkleRootMap::<Test>::get(U256::from(7)));
+    })
+}
+
+#[test]
+fn reject_req_refunds_period_withdrawal() {
+    new_test_ext().execute_with(|| {
+        prepare_unit_test();
+
+        //ReqId 6 was counted in the period starting at block 0
+        WithdrawPeriodMap::<Test>::insert(1u32, (0u64, U256::from(4)));
+        assert_ok!(SwapModule::reject_req(Origin::signed(1u64), U256::from(6), vec![]));
+        assert_eq!(WithdrawPeriodMap::<Test>::get(1u32), (0u64, U256::from(0)));
+    })
+}
+
+#[test]
+fn reject_req_keeps_later_period() {
+    new_test_ext().execute_with(|| {
+        prepare_unit_test();
+
+        //ReqId 6 was submitted before the period starting at block 10
+        System::set_block_number(10);
+        WithdrawPeriodMap::<Test>::insert(1u32, (10u64, U256::from(5)));
+        assert_ok!(SwapModule::reject_req(Origin::signed(1u64), U256::from(6), vec![]));
+        assert_eq!(WithdrawPeriodMap::<Test>::get(1u32), (10u64, U256::from(5)));
+    })
+}
+
+#[test]
+fn reject_req_batch_proof() {
+    new_test_ext().execute_with(|| {
+        prepare_unit_test();
+
+        assert_ok!(SwapModule::reject_req(Origin::signed(1u64), U256::from(6), vec![]));
+
+        //A batch spanning the rejection ends at the root of the refund
+        let new_root = MerkleRootMap::<Test>::get(U256::from(8)).unwrap();
+        assert_ok!(SwapModule::submit_batch_proof(Origin::signed(0), U256::from(0), 8, new_root, MOCK_VALID_PROOF.to_vec()));
+
+        assert!(RejectedReqMap::<Test>::contains_key(U256::from(6)));
+        assert!(CompleteReqMap::<Test>::contains_key(U256::from(7)));
+        assert!(CompleteReqMap::<Test>::get(U256::from(8)) == Some(Ops::Reject(U256::from(6))));
+        assert_eq!(CompleteReqIndex::<Test>::get(), U256::from(8));
+    })
+}
+
+#[test]
+fn reject_req_works_withdraw_nft() {
+    new_test_ext().execute_with(|| {
+        prepare_unit_test();
+
+        let req_id = U256::from(7);
+        let nft_id = 4u32;
+        assert_eq!(NFTMap::<Test>::get(&nft_id).0, 0u32);
+
+        assert_ok!(SwapModule::reject_req(Origin::signed(1u64), req_id, vec![]));
+
+        let nft = NFTMap::<Test>::get(&nft_id);
+        assert_eq!(nft.0, 2u32);
+        assert_eq!(nft.1, U256::from(0));
+        assert_eq!(nft.2, None);
+        assert!(PendingReqMap::<Test>::get(&req_id).is_none());
+        assert!(RejectedReqMap::<Test>::get(&req_id).is_some());
+    })
+}
+
+#[test]
+fn reject_req_noaccess() {
+    new_test_ext().execute_with(|| {
+        prepare_unit_test();
+
+        //Caller 0 is not admin
+        assert_noop!(SwapModule::reject_req(Origin::signed(0u64), U256::from(6), vec![]), Error::<Test>::NoAccess);
+    })
+}
+
+#[test]
+fn reject_req_not_rejectable() {
+    new_test_ext().execute_with(|| {
+        prepare_unit_test();
+
+        //ReqId 4 is a deposit
+        assert_noop!(SwapModule::reject_req(Origin::signed(1u64), U256::from(4), vec![]), Error::<Test>::NotRejectable);
+    })
+}
+
+#[test]
+fn reject_req_invalid_req_id() {
+    new_test_ext().execute_with(|| {
+        prepare_unit_test();
+
+        assert_noop!(SwapModule::reject_req(Origin::signed(1u64), U256::from(8), vec![]), Error::<Test>::InvalidReqId);
+
+        //Rejecting twice
+        assert_ok!(SwapModule::reject_req(Origin::signed(1u64), U256::from(6), vec![]));
+        assert_noop!(SwapModule::reject_req(Origin::signed(1u64), U256::from(6), vec![]), Error::<Test>::InvalidReqId);
+    })
+}
+
+#[test]
+fn reject_event_keeps_event_indexes() {
+    //Events keep their SCALE index across upgrades, Reject comes after all of them
+    assert_eq!(Encode::encode(&crate::Event::<Test>::RewardFunds(1u64, 10u64, 1u64))[0], 14u8);
+    assert_eq!(Encode::encode(&crate::Event::<Test>::ExitModeLeft(1u64))[0], 25u8);
+    assert_eq!(Encode::encode(&crate::Event::<Test>::Reject(U256::from(2), U256::from(1)))[0], 26u8);
+}
@@ -0,0 +1,40 @@
+use super::*;
+use frame_support::sp_runtime::DispatchError;
+
+#[test]
+fn set_admins_works() {
+    new_test_ext().execute_with(|| {
+        assert_eq!(Admins::<Test>::get(), vec![1u64, 2u64]);
+
+        //Replace admin 2 by account 3
+        assert_ok!(SwapModule::set_admins(Origin::root(), vec![1u64, 3u64]));
+        assert_eq!(Admins::<Test>::get(), vec![1u64, 3u64]);
+
+        //Account 2 loses admin ops, account 3 gains them
+        let l1_address = U256::from(100);
+        assert_noop!(SwapModule::register_token(Origin::signed(2u64), 10u32, l1_address, 18u8, b"NEW".to_vec()), Error::<Test>::NoAccess);
+        assert_ok!(SwapModule::register_token(Origin::signed(3u64), 10u32, l1_address, 18u8, b"NEW".to_vec()));
+    });
+}
+
+#[test]
+fn set_admins_needs_root() {
+    new_test_ext().execute_with(|| {
+        assert_noop!(SwapModule::set_admins(Origin::signed(1u64), vec![1u64]), DispatchError::BadOrigin);
+    });
+}
+
+#[test]
+fn set_admins_rejects_invalid_admins() {
+    new_test_ext().execute_with(|| {
+        //Duplicated admin
+        assert_noop!(SwapModule::set_admins(Origin::root(), vec![1u64, 3u64, 1u64]), Error::<Test>::InvalidAdmins);
+
+        //More admins than AckMap bits
+        let admins = (1u64..=(MAX_ADMIN_COUNT as u64 + 1)).collect::<Vec<_>>();
+        assert_noop!(SwapModule::set_admins(Origin::root(), admins), Error::<Test>::InvalidAdmins);
+
+        let admins = (1u64..=(MAX_ADMIN_COUNT as u64)).collect::<Vec<_>>();
+        assert_ok!(SwapModule::set_admins(Origin::root(), admins));
+    });
+}
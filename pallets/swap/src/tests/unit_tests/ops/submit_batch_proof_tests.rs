@@ -0,0 +1,149 @@
+use super::*;
+
+fn prepare_unit_test() {
+    //SetKey for accountIndex 0 to 9 as reqId 1 to 10
+    for origin in 0u64..10u64 {
+        let secret_key = [origin as u8 + 2; 32];
+        let pub_key = BabyJubjub::pubkey_from_secretkey(&secret_key).encode();
+        assert_ok!(SwapModule::set_key(Origin::signed(origin), pub_key));
+    }
+}
+
+#[test]
+fn submit_batch_proof_works() {
+    new_test_ext().execute_with(|| {
+        prepare_unit_test();
+
+        let new_root = MerkleRootMap::<Test>::get(U256::from(10)).unwrap();
+        assert_ok!(SwapModule::submit_batch_proof(Origin::signed(0), U256::from(0), 10, new_root, MOCK_VALID_PROOF.to_vec()));
+
+        for i in 1u32..11u32 {
+            let req_id = U256::from(i);
+            assert!(!PendingReqMap::<Test>::contains_key(&req_id));
+            assert!(CompleteReqMap::<Test>::contains_key(&req_id));
+            assert_eq!(ReqRecordMap::<Test>::get(&req_id).unwrap().status, ReqStatus::Complete);
+        }
+        assert_eq!(CompleteReqIndex::<Test>::get(), U256::from(10));
+    })
+}
+
+#[test]
+fn submit_batch_proof_partial_batch() {
+    new_test_ext().execute_with(|| {
+        prepare_unit_test();
+
+        let new_root = MerkleRootMap::<Test>::get(U256::from(3)).unwrap();
+        assert_ok!(SwapModule::submit_batch_proof(Origin::signed(0), U256::from(0), 3, new_root, MOCK_VALID_PROOF.to_vec()));
+        assert!(CompleteReqMap::<Test>::contains_key(U256::from(3)));
+        assert!(PendingReqMap::<Test>::contains_key(U256::from(4)));
+
+        let new_root = MerkleRootMap::<Test>::get(U256::from(10)).unwrap();
+        assert_ok!(SwapModule::submit_batch_proof(Origin::signed(0), U256::from(3), 7, new_root, MOCK_VALID_PROOF.to_vec()));
+        assert!(CompleteReqMap::<Test>::contains_key(U256::from(10)));
+        assert_eq!(CompleteReqIndex::<Test>::get(), U256::from(10));
+    })
+}
+
+#[test]
+fn submit_batch_proof_invalid_proof() {
+    new_test_ext().execute_with(|| {
+        prepare_unit_test();
+
+        let new_root = MerkleRootMap::<Test>::get(U256::from(10)).unwrap();
+        assert_noop!(
+            SwapModule::submit_batch_proof(Origin::signed(0), U256::from(0), 10, new_root, b"invalid proof".to_vec()),
+            Error::<Test>::InvalidProof
+        );
+    })
+}
+
+#[test]
+fn submit_batch_proof_invalid_merkle_root() {
+    new_test_ext().execute_with(|| {
+        prepare_unit_test();
+
+        let new_root = MerkleRootMap::<Test>::get(U256::from(9)).unwrap();
+        assert_noop!(
+            SwapModule::submit_batch_proof(Origin::signed(0), U256::from(0), 10, new_root, MOCK_VALID_PROOF.to_vec()),
+            Error::<Test>::InvalidMerkleRoot
+        );
+
+        //Request 11 does not exist
+        let new_root = MerkleRootMap::<Test>::get(U256::from(10)).unwrap();
+        assert_noop!(
+            SwapModule::submit_batch_proof(Origin::signed(0), U256::from(0), 11, new_root, MOCK_VALID_PROOF.to_vec()),
+            Error::<Test>::InvalidMerkleRoot
+        );
+    })
+}
+
+#[test]
+fn submit_batch_proof_invalid_batch_size() {
+    new_test_ext().execute_with(|| {
+        prepare_unit_test();
+
+        let new_root = MerkleRootMap::<Test>::get(U256::from(10)).unwrap();
+        assert_noop!(
+            SwapModule::submit_batch_proof(Origin::signed(0), U256::from(0), 0, new_root, MOCK_VALID_PROOF.to_vec()),
+            Error::<Test>::InvalidBatchSize
+        );
+        assert_noop!(
+            SwapModule::submit_batch_proof(Origin::signed(0), U256::from(0), 101, new_root, MOCK_VALID_PROOF.to_vec()),
+            Error::<Test>::InvalidBatchSize
+        );
+    })
+}
+
+#[test]
+fn submit_batch_proof_completed_req() {
+    new_test_ext().execute_with(|| {
+        prepare_unit_test();
+
+        let root_3 = MerkleRootMap::<Test>::get(U256::from(3)).unwrap();
+        assert_ok!(SwapModule::submit_batch_proof(Origin::signed(0), U256::from(0), 3, root_3, MOCK_VALID_PROOF.to_vec()));
+        let completed_at = ReqRecordMap::<Test>::get(U256::from(3)).unwrap().completed_at;
+
+        //Requests 1 to 3 are already complete and left as they are
+        System::set_block_number(2);
+        let new_root = MerkleRootMap::<Test>::get(U256::from(10)).unwrap();
+        assert_ok!(SwapModule::submit_batch_proof(Origin::signed(0), U256::from(0), 10, new_root, MOCK_VALID_PROOF.to_vec()));
+        assert_eq!(ReqRecordMap::<Test>::get(U256::from(3)).unwrap().completed_at, completed_at);
+        for i in 1u32..11u32 {
+            assert_eq!(ReqRecordMap::<Test>::get(U256::from(i)).unwrap().status, ReqStatus::Complete);
+        }
+        assert_eq!(CompleteReqIndex::<Test>::get(), U256::from(10));
+
+        //A proof of complete requests only is accepted again
+        assert_ok!(SwapModule::submit_batch_proof(Origin::signed(0), U256::from(0), 10, new_root, MOCK_VALID_PROOF.to_vec()));
+    })
+}
+
+#[test]
+fn submit_batch_proof_pruned_req() {
+    new_test_ext().execute_with(|| {
+        prepare_unit_test();
+
+        let new_root = MerkleRootMap::<Test>::get(U256::from(10)).unwrap();
+        assert_ok!(SwapModule::submit_batch_proof(Origin::signed(0), U256::from(0), 10, new_root, MOCK_VALID_PROOF.to_vec()));
+        assert_ok!(SwapModule::prune_complete_reqs(Origin::signed(1), U256::from(1)));
+
+        //The op of a pruned request is gone, the proof cannot be checked
+        assert_noop!(
+            SwapModule::submit_batch_proof(Origin::signed(0), U256::from(0), 10, new_root, MOCK_VALID_PROOF.to_vec()),
+            Error::<Test>::InvalidReqId
+        );
+    })
+}
+
+#[test]
+fn submit_batch_proof_too_long() {
+    new_test_ext().execute_with(|| {
+        prepare_unit_test();
+
+        let new_root = MerkleRootMap::<Test>::get(U256::from(10)).unwrap();
+        assert_noop!(
+            SwapModule::submit_batch_proof(Origin::signed(0), U256::from(0), 10, new_root, vec![0u8; 65]),
+            Error::<Test>::ProofTooLong
+        );
+    })
+}
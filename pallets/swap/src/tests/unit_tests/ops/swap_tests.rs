@@ -43,11 +43,7 @@ fn prepare_unit_test() {
         49, 180, 255,  97, 249,  95,   1, 203
     ];
 
-    let mut command = [0u8; 81];
-    command[0] = OP_ADDPOOL;
-    command[1..9].copy_from_slice(&nonce.to_be_bytes());
-    command[9..13].copy_from_slice(&token_index_0.to_be_bytes());
-    command[13..17].copy_from_slice(&token_index_1.to_be_bytes());
+    let command = Command::AddPool { nonce, token_index_0, token_index_1 }.encode();
 
     let command_sign = BabyJubjub::sign(&command, &secret_key_1);
     let mut command_sign_formatted :[u8; 64] = [0 as u8;64];
@@ -64,12 +60,7 @@ fn prepare_unit_test() {
     let mut l1_tx_hash = U256::from(0);
     let mut nonce = 2u64;
 
-    let mut command = [0u8; 81];
-    command[0] = OP_DEPOSIT;
-    command[1..9].copy_from_slice(&nonce.to_be_bytes());
-    command[9..13].copy_from_slice(&account_index.to_be_bytes());
-    command[13..17].copy_from_slice(&token_index.to_be_bytes());
-    command[17..49].copy_from_slice(&amount.to_be_bytes());
+    let mut command = Command::Deposit { nonce, account_index, token_index, amount }.encode();
 
     let mut command_sign = BabyJubjub::sign(&command, &secret_key_1);
     let mut command_sign_formatted :[u8; 64] = [0 as u8;64];
@@ -83,12 +74,7 @@ fn prepare_unit_test() {
     l1_tx_hash = U256::from(1);
     nonce = 3u64;
 
-    command = [0u8; 81];
-    command[0] = OP_DEPOSIT;
-    command[1..9].copy_from_slice(&nonce.to_be_bytes());
-    command[9..13].copy_from_slice(&account_index.to_be_bytes());
-    command[13..17].copy_from_slice(&token_index.to_be_bytes());
-    command[17..49].copy_from_slice(&amount.to_be_bytes());
+    command = Command::Deposit { nonce, account_index, token_index, amount }.encode();
 
     command_sign = BabyJubjub::sign(&command, &secret_key_1);
     command_sign_formatted = [0 as u8;64];
@@ -111,13 +97,7 @@ fn prepare_unit_test() {
         185, 222, 254,  50, 115,  63,  97, 179
     ];
 
-    let mut command = [0u8; 81];
-    command[0] = OP_SUPPLY;
-    command[1..9].copy_from_slice(&nonce.to_be_bytes());
-    command[9..13].copy_from_slice(&account_index.to_be_bytes());
-    command[13..17].copy_from_slice(&pool_index.to_be_bytes());
-    command[17..49].copy_from_slice(&amount0.to_be_bytes());
-    command[49..81].copy_from_slice(&amount1.to_be_bytes());
+    let command = Command::PoolSupply { nonce, account_index, pool_index, amount0, amount1 }.encode();
 
     let command_sign = BabyJubjub::sign(&command, &secret_key_2);
     let mut command_sign_formatted :[u8; 64] = [0 as u8;64];
@@ -170,11 +150,7 @@ fn prepare_unit_test_without_supply() {
         49, 180, 255,  97, 249,  95,   1, 203
     ];
 
-    let mut command = [0u8; 81];
-    command[0] = OP_ADDPOOL;
-    command[1..9].copy_from_slice(&nonce.to_be_bytes());
-    command[9..13].copy_from_slice(&token_index_0.to_be_bytes());
-    command[13..17].copy_from_slice(&token_index_1.to_be_bytes());
+    let command = Command::AddPool { nonce, token_index_0, token_index_1 }.encode();
 
     let command_sign = BabyJubjub::sign(&command, &secret_key_1);
     let mut command_sign_formatted :[u8; 64] = [0 as u8;64];
@@ -191,12 +167,7 @@ fn prepare_unit_test_without_supply() {
     let mut l1_tx_hash = U256::from(0);
     let mut nonce = 2u64;
 
-    let mut command = [0u8; 81];
-    command[0] = OP_DEPOSIT;
-    command[1..9].copy_from_slice(&nonce.to_be_bytes());
-    command[9..13].copy_from_slice(&account_index.to_be_bytes());
-    command[13..17].copy_from_slice(&token_index.to_be_bytes());
-    command[17..49].copy_from_slice(&amount.to_be_bytes());
+    let mut command = Command::Deposit { nonce, account_index, token_index, amount }.encode();
 
     let mut command_sign = BabyJubjub::sign(&command, &secret_key_1);
     let mut command_sign_formatted :[u8; 64] = [0 as u8;64];
@@ -210,12 +181,7 @@ fn prepare_unit_test_without_supply() {
     l1_tx_hash = U256::from(1);
     nonce = 3u64;
 
-    command = [0u8; 81];
-    command[0] = OP_DEPOSIT;
-    command[1..9].copy_from_slice(&nonce.to_be_bytes());
-    command[9..13].copy_from_slice(&account_index.to_be_bytes());
-    command[13..17].copy_from_slice(&token_index.to_be_bytes());
-    command[17..49].copy_from_slice(&amount.to_be_bytes());
+    command = Command::Deposit { nonce, account_index, token_index, amount }.encode();
 
     command_sign = BabyJubjub::sign(&command, &secret_key_1);
     command_sign_formatted = [0 as u8;64];
@@ -244,13 +210,7 @@ fn swap_works_reverse_is_zero() {
             185, 222, 254,  50, 115,  63,  97, 179
         ];
         
-        let mut command = [0u8; 81];
-        command[0] = OP_SWAP;
-        command[1..9].copy_from_slice(&nonce.to_be_bytes());
-        command[9..13].copy_from_slice(&account_index.to_be_bytes());
-        command[13..17].copy_from_slice(&pool_index.to_be_bytes());
-        command[17..49].copy_from_slice(&U256::from(reverse).to_be_bytes());
-        command[49..81].copy_from_slice(&amount.to_be_bytes());
+        let command = Command::Swap { nonce, account_index, pool_index, reverse, amount }.encode();
 
         let command_sign = BabyJubjub::sign(&command, &secret_key_2);
         let mut command_sign_formatted :[u8; 64] = [0 as u8;64];
@@ -259,11 +219,11 @@ fn swap_works_reverse_is_zero() {
 
         assert_ok!(SwapModule::swap(Origin::signed(origin), command_sign_formatted, pool_index, reverse, amount, nonce));
 
-        assert_eq!(PoolMap::get(pool_index).unwrap(), (0u32, 1u32, U256::from(1100), U256::from(910), U256::from(1_000_000_000_000_000_000u128)));
+        assert_eq!(PoolMap::<Test>::get(pool_index).unwrap(), (0u32, 1u32, U256::from(1100), U256::from(910), U256::from(1_000_000_000_000_000_000u128)));
 
-        assert_eq!(BalanceMap::get((&account_index, 0u32)), U256::from(1400));
+        assert_eq!(BalanceMap::<Test>::get((&account_index, 0u32)), U256::from(1400));
 
-        assert_eq!(BalanceMap::get((&account_index, 1u32)), U256::from(1590));
+        assert_eq!(BalanceMap::<Test>::get((&account_index, 1u32)), U256::from(1590));
 
         assert_eq!(NonceMap::<Test>::get(2u64), 3u64);
     })
@@ -288,13 +248,7 @@ fn swap_works_reverse_is_one() {
             185, 222, 254,  50, 115,  63,  97, 179
         ];
 
-        let mut command = [0u8; 81];
-        command[0] = OP_SWAP;
-        command[1..9].copy_from_slice(&nonce.to_be_bytes());
-        command[9..13].copy_from_slice(&account_index.to_be_bytes());
-        command[13..17].copy_from_slice(&pool_index.to_be_bytes());
-        command[17..49].copy_from_slice(&U256::from(reverse).to_be_bytes());
-        command[49..81].copy_from_slice(&amount.to_be_bytes());
+        let command = Command::Swap { nonce, account_index, pool_index, reverse, amount }.encode();
 
         let command_sign = BabyJubjub::sign(&command, &secret_key_2);
         let mut command_sign_formatted :[u8; 64] = [0 as u8;64];
@@ -303,16 +257,49 @@ fn swap_works_reverse_is_one() {
 
         assert_ok!(SwapModule::swap(Origin::signed(origin), command_sign_formatted, pool_index, reverse, amount, nonce));
 
-        assert_eq!(PoolMap::get(pool_index).unwrap(), (0u32, 1u32, U256::from(910), U256::from(1100), U256::from(1_000_000_000_000_000_000u128)));
+        assert_eq!(PoolMap::<Test>::get(pool_index).unwrap(), (0u32, 1u32, U256::from(910), U256::from(1100), U256::from(1_000_000_000_000_000_000u128)));
 
-        assert_eq!(BalanceMap::get((&account_index, 0u32)), U256::from(1590));
+        assert_eq!(BalanceMap::<Test>::get((&account_index, 0u32)), U256::from(1590));
 
-        assert_eq!(BalanceMap::get((&account_index, 1u32)), U256::from(1400));
+        assert_eq!(BalanceMap::<Test>::get((&account_index, 1u32)), U256::from(1400));
 
         assert_eq!(NonceMap::<Test>::get(2u64), 3u64);
     })
 }
 
+#[test]
+fn swap_invalid_reverse() {
+    new_test_ext().execute_with(|| {
+        prepare_unit_test();
+
+        //Swap amount 100 for poolIndex 0, caller is accountIndex 2, reverse is 2
+        let origin = 2u64;
+        let account_index = 2u32;
+        let pool_index = 0u32;
+        let reverse = 2u8;
+        let amount = U256::from(100);
+        let nonce = 2u64;
+        let secret_key_2 = [
+            210, 199, 164, 130,  20, 202,  75,  82,
+            215,  24,   9, 195,  86, 213, 230,  20,
+            159, 219, 169, 225,  93, 193, 109, 240,
+            185, 222, 254,  50, 115,  63,  97, 179
+        ];
+
+        let command = Command::Swap { nonce, account_index, pool_index, reverse, amount }.encode();
+
+        let command_sign = BabyJubjub::sign(&command, &secret_key_2);
+        let mut command_sign_formatted :[u8; 64] = [0 as u8;64];
+        command_sign_formatted[..32].copy_from_slice(&command_sign.r.encode());
+        command_sign_formatted[32..].copy_from_slice(&command_sign.s.encode());
+
+        assert_noop!(
+            SwapModule::swap(Origin::signed(origin), command_sign_formatted, pool_index, reverse, amount, nonce),
+            Error::<Test>::InvalidReverse
+        );
+    })
+}
+
 #[test]
 fn swap_multiple_times_works() {
     new_test_ext().execute_with(|| {
@@ -333,8 +320,8 @@ fn swap_multiple_times_works() {
             185, 222, 254,  50, 115,  63,  97, 179
         ];
 
-        let (_, _, _, _, total_share) = PoolMap::get(pool_index).unwrap();
-        let mut command = [0u8; 81];
+        let (_, _, _, _, total_share) = PoolMap::<Test>::get(pool_index).unwrap();
+        let mut command;
         let mut index = 0;
         let mut command_sign;
         let mut command_sign_formatted :[u8; 64];
@@ -347,12 +334,7 @@ fn swap_multiple_times_works() {
 		let mut result_amount;
 
         while index < swap_times {
-            command[0] = OP_SWAP;
-            command[1..9].copy_from_slice(&nonce.to_be_bytes());
-            command[9..13].copy_from_slice(&account_index.to_be_bytes());
-            command[13..17].copy_from_slice(&pool_index.to_be_bytes());
-            command[17..49].copy_from_slice(&U256::from(reverse).to_be_bytes());
-            command[49..81].copy_from_slice(&amount.to_be_bytes());
+            command = Command::Swap { nonce, account_index, pool_index, reverse, amount }.encode();
 
             command_sign = BabyJubjub::sign(&command, &secret_key_2);
             command_sign_formatted = [0 as u8;64];
@@ -373,11 +355,11 @@ fn swap_multiple_times_works() {
             balance1 = balance1 + result_amount;
         }
 
-        assert_eq!(PoolMap::get(pool_index).unwrap(), (0u32, 1u32, liq0, liq1, total_share));
+        assert_eq!(PoolMap::<Test>::get(pool_index).unwrap(), (0u32, 1u32, liq0, liq1, total_share));
 
-        assert_eq!(BalanceMap::get((&account_index, 0u32)), balance0);
+        assert_eq!(BalanceMap::<Test>::get((&account_index, 0u32)), balance0);
 
-        assert_eq!(BalanceMap::get((&account_index, 1u32)), balance1);
+        assert_eq!(BalanceMap::<Test>::get((&account_index, 1u32)), balance1);
 
         assert_eq!(NonceMap::<Test>::get(2u64), nonce);
     })
@@ -403,13 +385,7 @@ fn swap_account_not_exists() {
             185, 222, 254,  50, 115,  63,  97, 179
         ];
         
-        let mut command = [0u8; 81];
-        command[0] = OP_SWAP;
-        command[1..9].copy_from_slice(&nonce.to_be_bytes());
-        command[9..13].copy_from_slice(&account_index.to_be_bytes());
-        command[13..17].copy_from_slice(&pool_index.to_be_bytes());
-        command[17..49].copy_from_slice(&U256::from(reverse).to_be_bytes());
-        command[49..81].copy_from_slice(&amount.to_be_bytes());
+        let command = Command::Swap { nonce, account_index, pool_index, reverse, amount }.encode();
 
         //There is no secret_key_3, so use secret_key_2 here
         let command_sign = BabyJubjub::sign(&command, &secret_key_2);
@@ -441,13 +417,7 @@ fn swap_invalid_amount_amount_is_zero() {
             185, 222, 254,  50, 115,  63,  97, 179
         ];
         
-        let mut command = [0u8; 81];
-        command[0] = OP_SWAP;
-        command[1..9].copy_from_slice(&nonce.to_be_bytes());
-        command[9..13].copy_from_slice(&account_index.to_be_bytes());
-        command[13..17].copy_from_slice(&pool_index.to_be_bytes());
-        command[17..49].copy_from_slice(&U256::from(reverse).to_be_bytes());
-        command[49..81].copy_from_slice(&amount.to_be_bytes());
+        let command = Command::Swap { nonce, account_index, pool_index, reverse, amount }.encode();
 
         let command_sign = BabyJubjub::sign(&command, &secret_key_2);
         let mut command_sign_formatted :[u8; 64] = [0 as u8;64];
@@ -477,13 +447,7 @@ fn swapped_out_pool_amount_is_zero() {
             185, 222, 254,  50, 115,  63,  97, 179
         ];
         
-        let mut command = [0u8; 81];
-        command[0] = OP_SWAP;
-        command[1..9].copy_from_slice(&nonce.to_be_bytes());
-        command[9..13].copy_from_slice(&account_index.to_be_bytes());
-        command[13..17].copy_from_slice(&pool_index.to_be_bytes());
-        command[17..49].copy_from_slice(&U256::from(reverse).to_be_bytes());
-        command[49..81].copy_from_slice(&amount.to_be_bytes());
+        let command = Command::Swap { nonce, account_index, pool_index, reverse, amount }.encode();
 
         let command_sign = BabyJubjub::sign(&command, &secret_key_2);
         let mut command_sign_formatted :[u8; 64] = [0 as u8;64];
@@ -514,13 +478,7 @@ fn swap_invalid_amount_amount_exceeds_range() {
             185, 222, 254,  50, 115,  63,  97, 179
         ];
 
-        let mut command = [0u8; 81];
-        command[0] = OP_SWAP;
-        command[1..9].copy_from_slice(&nonce.to_be_bytes());
-        command[9..13].copy_from_slice(&account_index.to_be_bytes());
-        command[13..17].copy_from_slice(&pool_index.to_be_bytes());
-        command[17..49].copy_from_slice(&U256::from(reverse).to_be_bytes());
-        command[49..81].copy_from_slice(&amount.to_be_bytes());
+        let command = Command::Swap { nonce, account_index, pool_index, reverse, amount }.encode();
 
         let command_sign = BabyJubjub::sign(&command, &secret_key_2);
         let mut command_sign_formatted: [u8; 64] = [0 as u8;64];
@@ -551,13 +509,7 @@ fn swap_nonce_inconsistent() {
             185, 222, 254,  50, 115,  63,  97, 179
         ];
         
-        let mut command = [0u8; 81];
-        command[0] = OP_SWAP;
-        command[1..9].copy_from_slice(&nonce.to_be_bytes());
-        command[9..13].copy_from_slice(&account_index.to_be_bytes());
-        command[13..17].copy_from_slice(&pool_index.to_be_bytes());
-        command[17..49].copy_from_slice(&U256::from(reverse).to_be_bytes());
-        command[49..81].copy_from_slice(&amount.to_be_bytes());
+        let command = Command::Swap { nonce, account_index, pool_index, reverse, amount }.encode();
 
         let command_sign = BabyJubjub::sign(&command, &secret_key_2);
         let mut command_sign_formatted :[u8; 64] = [0 as u8;64];
@@ -588,13 +540,7 @@ fn swap_pool_not_exists() {
             185, 222, 254,  50, 115,  63,  97, 179
         ];
         
-        let mut command = [0u8; 81];
-        command[0] = OP_SWAP;
-        command[1..9].copy_from_slice(&nonce.to_be_bytes());
-        command[9..13].copy_from_slice(&account_index.to_be_bytes());
-        command[13..17].copy_from_slice(&pool_index.to_be_bytes());
-        command[17..49].copy_from_slice(&U256::from(reverse).to_be_bytes());
-        command[49..81].copy_from_slice(&amount.to_be_bytes());
+        let command = Command::Swap { nonce, account_index, pool_index, reverse, amount }.encode();
 
         let command_sign = BabyJubjub::sign(&command, &secret_key_2);
         let mut command_sign_formatted :[u8; 64] = [0 as u8;64];
@@ -624,13 +570,7 @@ fn swap_invalid_signature() {
             185, 222, 254,  50, 115,  63,  97, 179
         ];
         
-        let mut command = [0u8; 81];
-        command[0] = OP_SWAP;
-        command[1..9].copy_from_slice(&nonce.to_be_bytes());
-        command[9..13].copy_from_slice(&account_index.to_be_bytes());
-        command[13..17].copy_from_slice(&pool_index.to_be_bytes());
-        command[17..49].copy_from_slice(&U256::from(reverse).to_be_bytes());
-        command[49..81].copy_from_slice(&amount.to_be_bytes());
+        let command = Command::Swap { nonce, account_index, pool_index, reverse, amount }.encode();
 
         let command_sign = BabyJubjub::sign(&command, &secret_key_2);
         let mut command_sign_formatted :[u8; 64] = [0 as u8;64];
@@ -664,13 +604,7 @@ fn swap_balance_not_enough() {
             185, 222, 254,  50, 115,  63,  97, 179
         ];
         
-        let mut command = [0u8; 81];
-        command[0] = OP_SWAP;
-        command[1..9].copy_from_slice(&nonce.to_be_bytes());
-        command[9..13].copy_from_slice(&account_index.to_be_bytes());
-        command[13..17].copy_from_slice(&pool_index.to_be_bytes());
-        command[17..49].copy_from_slice(&U256::from(reverse).to_be_bytes());
-        command[49..81].copy_from_slice(&amount.to_be_bytes());
+        let command = Command::Swap { nonce, account_index, pool_index, reverse, amount }.encode();
 
         let command_sign = BabyJubjub::sign(&command, &secret_key_2);
         let mut command_sign_formatted :[u8; 64] = [0 as u8;64];
@@ -700,12 +634,7 @@ fn swap_balance_overflow() {
             49, 180, 255,  97, 249,  95,   1, 203
         ];
 
-        let mut command = [0u8; 81];
-        command[0] = OP_DEPOSIT;
-        command[1..9].copy_from_slice(&nonce.to_be_bytes());
-        command[9..13].copy_from_slice(&account_index.to_be_bytes());
-        command[13..17].copy_from_slice(&token_index.to_be_bytes());
-        command[17..49].copy_from_slice(&amount.to_be_bytes());
+        let command = Command::Deposit { nonce, account_index, token_index, amount }.encode();
 
         let mut command_sign = BabyJubjub::sign(&command, &secret_key_1);
         let mut command_sign_formatted :[u8; 64] = [0 as u8;64];
@@ -728,13 +657,7 @@ fn swap_balance_overflow() {
             185, 222, 254,  50, 115,  63,  97, 179
         ];
         
-        let mut command = [0u8; 81];
-        command[0] = OP_SWAP;
-        command[1..9].copy_from_slice(&nonce.to_be_bytes());
-        command[9..13].copy_from_slice(&account_index.to_be_bytes());
-        command[13..17].copy_from_slice(&pool_index.to_be_bytes());
-        command[17..49].copy_from_slice(&U256::from(reverse).to_be_bytes());
-        command[49..81].copy_from_slice(&amount.to_be_bytes());
+        let command = Command::Swap { nonce, account_index, pool_index, reverse, amount }.encode();
 
         command_sign = BabyJubjub::sign(&command, &secret_key_2);
         command_sign_formatted = [0 as u8;64];
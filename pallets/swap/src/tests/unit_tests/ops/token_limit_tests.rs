@@ -0,0 +1,247 @@
+use super::*;
+
+fn prepare_unit_test() {
+    //SetKey for accountIndex 0
+    let mut origin = 0u64;
+    let secret_key_0 = [2u8; 32];
+    let pub_key_0 = BabyJubjub::pubkey_from_secretkey(&secret_key_0).encode();
+    assert_ok!(SwapModule::set_key(Origin::signed(origin), pub_key_0));
+
+    //SetKey for accountIndex 1
+    origin = 1u64;
+    let secret_key_1 = [3u8; 32];
+    let pub_key_1 = BabyJubjub::pubkey_from_secretkey(&secret_key_1).encode();
+    assert_ok!(SwapModule::set_key(Origin::signed(origin), pub_key_1));
+
+    //Deposit 100 into accountIndex 0, caller is accountIndex 1
+    let account_index = 0u32;
+    let token_index = 1u32;
+    let amount = U256::from(100);
+    let l1_tx_hash = U256::from(0);
+    let nonce = 1u64;
+
+    let command = Command::Deposit { nonce, account_index, token_index, amount }.encode();
+
+    let command_sign = BabyJubjub::sign(&command, &secret_key_1);
+    let mut command_sign_formatted :[u8; 64] = [0 as u8;64];
+    command_sign_formatted[..32].copy_from_slice(&command_sign.r.encode());
+    command_sign_formatted[32..].copy_from_slice(&command_sign.s.encode());
+
+    assert_ok!(SwapModule::deposit(Origin::signed(origin), command_sign_formatted, account_index, token_index, amount, l1_tx_hash, nonce));
+
+    //Limit tokenIndex 1: 50 per deposit, 50 per withdrawal, 60 per 10 blocks
+    assert_ok!(SwapModule::set_token_limit(Origin::signed(origin), token_index, Some(U256::from(50)), Some(U256::from(50)), 10u64, Some(U256::from(60))));
+}
+
+fn deposit_sign(nonce: NonceId, amount: Amount, secret_key: &[u8; 32]) -> [u8; 64] {
+    let account_index = 0u32;
+    let token_index = 1u32;
+
+    let command = Command::Deposit { nonce, account_index, token_index, amount }.encode();
+
+    let command_sign = BabyJubjub::sign(&command, secret_key);
+    let mut command_sign_formatted :[u8; 64] = [0 as u8;64];
+    command_sign_formatted[..32].copy_from_slice(&command_sign.r.encode());
+    command_sign_formatted[32..].copy_from_slice(&command_sign.s.encode());
+    command_sign_formatted
+}
+
+fn withdraw_sign(nonce: NonceId, amount: Amount, l1account: L1Account) -> [u8; 64] {
+    let account_index = 0u32;
+    let token_index = 1u32;
+    let secret_key_0 = [2u8; 32];
+
+    let command = Command::Withdraw { nonce, account_index, token_index, amount, l1account }.encode();
+
+    let command_sign = BabyJubjub::sign(&command, &secret_key_0);
+    let mut command_sign_formatted :[u8; 64] = [0 as u8;64];
+    command_sign_formatted[..32].copy_from_slice(&command_sign.r.encode());
+    command_sign_formatted[32..].copy_from_slice(&command_sign.s.encode());
+    command_sign_formatted
+}
+
+fn last_event() -> crate::mock::Event {
+    System::events().pop().expect("an event was deposited").event
+}
+
+#[test]
+fn set_token_limit_works() {
+    new_test_ext().execute_with(|| {
+        prepare_unit_test();
+
+        assert_eq!(
+            TokenLimitMap::<Test>::get(1u32),
+            Some(TokenLimit {
+                max_deposit: Some(U256::from(50)),
+                max_withdraw: Some(U256::from(50)),
+                period: 10u64,
+                max_period_withdraw: Some(U256::from(60)),
+            })
+        );
+    })
+}
+
+#[test]
+fn set_token_limit_noaccess() {
+    new_test_ext().execute_with(|| {
+        //Caller 0 is not admin
+        assert_noop!(SwapModule::set_token_limit(Origin::signed(0u64), 1u32, None, None, 0u64, None), Error::<Test>::NoAccess);
+    })
+}
+
+#[test]
+fn set_token_limit_token_not_exists() {
+    new_test_ext().execute_with(|| {
+        assert_noop!(SwapModule::set_token_limit(Origin::signed(1u64), 5u32, None, None, 0u64, None), Error::<Test>::TokenNotExists);
+    })
+}
+
+#[test]
+fn set_token_limit_zero_period() {
+    new_test_ext().execute_with(|| {
+        //A rolling cap needs a period
+        assert_noop!(SwapModule::set_token_limit(Origin::signed(1u64), 1u32, None, None, 0u64, Some(U256::from(60))), Error::<Test>::InvalidTokenLimit);
+
+        //Without a rolling cap the period is unused
+        assert_ok!(SwapModule::set_token_limit(Origin::signed(1u64), 1u32, Some(U256::from(50)), None, 0u64, None));
+    })
+}
+
+#[test]
+fn deposit_limit_exceeded() {
+    new_test_ext().execute_with(|| {
+        prepare_unit_test();
+
+        //Deposit 51 into accountIndex 0 exceeds the deposit limit 50
+        let amount = U256::from(51);
+        let nonce = 2u64;
+        let sign = deposit_sign(nonce, amount, &[3u8; 32]);
+        assert_noop!(SwapModule::deposit(Origin::signed(1u64), sign, 0u32, 1u32, amount, U256::from(1), nonce), Error::<Test>::DepositLimitExceeded);
+    })
+}
+
+#[test]
+fn deposit_limit_checked_after_signature() {
+    new_test_ext().execute_with(|| {
+        prepare_unit_test();
+
+        //Deposit 51 signed by accountIndex 0 instead of the caller
+        let amount = U256::from(51);
+        let nonce = 2u64;
+        let sign = deposit_sign(nonce, amount, &[2u8; 32]);
+        assert_noop!(SwapModule::deposit(Origin::signed(1u64), sign, 0u32, 1u32, amount, U256::from(1), nonce), Error::<Test>::InvalidSignature);
+    })
+}
+
+#[test]
+fn withdraw_limit_exceeded() {
+    new_test_ext().execute_with(|| {
+        prepare_unit_test();
+
+        //Withdraw 51 exceeds the withdrawal limit 50
+        let origin = 0u64;
+        let amount = U256::from(51);
+        let l1account = U256::from(1);
+        let nonce = 1u64;
+        let sign = withdraw_sign(nonce, amount, l1account);
+        assert_noop!(SwapModule::withdraw(Origin::signed(origin), sign, 1u32, amount, l1account, nonce), Error::<Test>::WithdrawLimitExceeded);
+    })
+}
+
+#[test]
+fn withdraw_limit_checked_after_signature() {
+    new_test_ext().execute_with(|| {
+        prepare_unit_test();
+
+        //Withdraw 51 with a signature of another amount
+        let origin = 0u64;
+        let amount = U256::from(51);
+        let l1account = U256::from(1);
+        let nonce = 1u64;
+        let sign = withdraw_sign(nonce, U256::from(1), l1account);
+        assert_noop!(SwapModule::withdraw(Origin::signed(origin), sign, 1u32, amount, l1account, nonce), Error::<Test>::InvalidSignature);
+    })
+}
+
+#[test]
+fn period_withdraw_limit_exceeded() {
+    new_test_ext().execute_with(|| {
+        prepare_unit_test();
+
+        //Withdraw 40 at block 0
+        let origin = 0u64;
+        let l1account = U256::from(1);
+        let mut amount = U256::from(40);
+        let mut nonce = 1u64;
+        let mut sign = withdraw_sign(nonce, amount, l1account);
+        assert_ok!(SwapModule::withdraw(Origin::signed(origin), sign, 1u32, amount, l1account, nonce));
+        assert_eq!(WithdrawPeriodMap::<Test>::get(1u32), (0u64, U256::from(40)));
+
+        //Withdraw 30 more in the same period exceeds 60
+        amount = U256::from(30);
+        nonce = 2u64;
+        sign = withdraw_sign(nonce, amount, l1account);
+        assert_noop!(SwapModule::withdraw(Origin::signed(origin), sign, 1u32, amount, l1account, nonce), Error::<Test>::PeriodWithdrawLimitExceeded);
+
+        //A new period starts at block 10
+        System::set_block_number(10);
+        assert_ok!(SwapModule::withdraw(Origin::signed(origin), sign, 1u32, amount, l1account, nonce));
+        assert_eq!(WithdrawPeriodMap::<Test>::get(1u32), (10u64, U256::from(30)));
+        assert_eq!(BalanceMap::<Test>::get((0u32, 1u32)), U256::from(30));
+    })
+}
+
+#[test]
+fn deposit_limit_exceeded_event() {
+    new_test_ext().execute_with(|| {
+        prepare_unit_test();
+        System::set_block_number(1);
+
+        let amount = U256::from(51);
+        let nonce = 2u64;
+        let sign = deposit_sign(nonce, amount, &[3u8; 32]);
+        frame_support::assert_err!(SwapModule::deposit(Origin::signed(1u64), sign, 0u32, 1u32, amount, U256::from(1), nonce), Error::<Test>::DepositLimitExceeded);
+        assert_eq!(
+            last_event(),
+            crate::mock::Event::swap(crate::Event::<Test>::TokenLimitExceeded(0u32, 1u32, TokenLimitKind::Deposit, U256::from(51), U256::from(50)))
+        );
+    })
+}
+
+#[test]
+fn withdraw_limit_exceeded_event() {
+    new_test_ext().execute_with(|| {
+        prepare_unit_test();
+        System::set_block_number(1);
+
+        let amount = U256::from(51);
+        let l1account = U256::from(1);
+        let nonce = 1u64;
+        let sign = withdraw_sign(nonce, amount, l1account);
+        frame_support::assert_err!(SwapModule::withdraw(Origin::signed(0u64), sign, 1u32, amount, l1account, nonce), Error::<Test>::WithdrawLimitExceeded);
+        assert_eq!(
+            last_event(),
+            crate::mock::Event::swap(crate::Event::<Test>::TokenLimitExceeded(0u32, 1u32, TokenLimitKind::Withdraw, U256::from(51), U256::from(50)))
+        );
+    })
+}
+
+#[test]
+fn period_withdraw_limit_exceeded_event() {
+    new_test_ext().execute_with(|| {
+        prepare_unit_test();
+        System::set_block_number(1);
+
+        //Withdraw 40 then 30 in the same period, 70 exceeds 60
+        let l1account = U256::from(1);
+        let sign = withdraw_sign(1u64, U256::from(40), l1account);
+        assert_ok!(SwapModule::withdraw(Origin::signed(0u64), sign, 1u32, U256::from(40), l1account, 1u64));
+
+        let sign = withdraw_sign(2u64, U256::from(30), l1account);
+        frame_support::assert_err!(SwapModule::withdraw(Origin::signed(0u64), sign, 1u32, U256::from(30), l1account, 2u64), Error::<Test>::PeriodWithdrawLimitExceeded);
+        assert_eq!(
+            last_event(),
+            crate::mock::Event::swap(crate::Event::<Test>::TokenLimitExceeded(0u32, 1u32, TokenLimitKind::PeriodWithdraw, U256::from(70), U256::from(60)))
+        );
+    })
+}
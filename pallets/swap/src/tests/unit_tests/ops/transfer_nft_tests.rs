@@ -29,12 +29,7 @@ fn transfer_nft_works() {
         let mut l1_tx_hash = U256::from(0);
         let mut nonce = 1u64;
 
-        let mut command = [0u8; 81];
-        command[0] = OP_DEPOSIT;
-        command[1..9].copy_from_slice(&nonce.to_be_bytes());
-        command[9..13].copy_from_slice(&account_index.to_be_bytes());
-        command[13..17].copy_from_slice(&token_index.to_be_bytes());
-        command[17..49].copy_from_slice(&amount.to_be_bytes());
+        let mut command = Command::Deposit { nonce, account_index, token_index, amount }.encode();
 
         let mut command_sign = BabyJubjub::sign(&command, &secret_key_1);
         let mut command_sign_formatted :[u8; 64] = [0 as u8;64];
@@ -48,12 +43,7 @@ fn transfer_nft_works() {
         l1_tx_hash = U256::from(1);
         nonce = 2u64;
 
-        command = [0u8; 81];
-        command[0] = OP_DEPOSIT_NFT;
-        command[1..9].copy_from_slice(&nonce.to_be_bytes());
-        command[9..13].copy_from_slice(&account_index.to_be_bytes());
-        command[13..17].copy_from_slice(&nft_id.to_be_bytes());
-        command[17..49].copy_from_slice(&l1_tx_hash.to_be_bytes());
+        command = Command::DepositNFT { nonce, account_index, nft_id, l1_tx_hash }.encode();
 
         command_sign = BabyJubjub::sign(&command, &secret_key_1);
         command_sign_formatted = [0 as u8;64];
@@ -73,14 +63,7 @@ fn transfer_nft_works() {
         let recipient = 3u32;
         nonce = 1;
 
-        let mut pad_recipient = [0u8; 32];
-        pad_recipient[28..].copy_from_slice(&recipient.to_be_bytes());
-        command = [0u8; 81];
-        command[0] = OP_TRANSFER_NFT;
-        command[1..9].copy_from_slice(&nonce.to_be_bytes());
-        command[9..13].copy_from_slice(&account_index.to_be_bytes());
-        command[13..17].copy_from_slice(&nft_id.to_be_bytes());
-        command[17..49].copy_from_slice(&pad_recipient);
+        command = Command::TransferNFT { nonce, account_index, nft_id, recipient }.encode();
 
         command_sign = BabyJubjub::sign(&command, &secret_key_2);
         command_sign_formatted = [0 as u8;64];
@@ -89,7 +72,7 @@ fn transfer_nft_works() {
 
         assert_ok!(SwapModule::transfer_nft(Origin::signed(origin), command_sign_formatted, nft_id, recipient, nonce));
 
-        let nft = NFTMap::get(&nft_id);
+        let nft = NFTMap::<Test>::get(&nft_id);
         assert_eq!(nft.0, 3u32);
         assert_eq!(nft.1, U256::from(0));
         assert_eq!(nft.2, None);
@@ -125,12 +108,7 @@ fn transfer_nft_account_not_exists() {
         let mut l1_tx_hash = U256::from(0);
         let mut nonce = 1u64;
 
-        let mut command = [0u8; 81];
-        command[0] = OP_DEPOSIT;
-        command[1..9].copy_from_slice(&nonce.to_be_bytes());
-        command[9..13].copy_from_slice(&account_index.to_be_bytes());
-        command[13..17].copy_from_slice(&token_index.to_be_bytes());
-        command[17..49].copy_from_slice(&amount.to_be_bytes());
+        let mut command = Command::Deposit { nonce, account_index, token_index, amount }.encode();
 
         let mut command_sign = BabyJubjub::sign(&command, &secret_key_1);
         let mut command_sign_formatted :[u8; 64] = [0 as u8;64];
@@ -144,12 +122,7 @@ fn transfer_nft_account_not_exists() {
         l1_tx_hash = U256::from(1);
         nonce = 2u64;
 
-        command = [0u8; 81];
-        command[0] = OP_DEPOSIT_NFT;
-        command[1..9].copy_from_slice(&nonce.to_be_bytes());
-        command[9..13].copy_from_slice(&account_index.to_be_bytes());
-        command[13..17].copy_from_slice(&nft_id.to_be_bytes());
-        command[17..49].copy_from_slice(&l1_tx_hash.to_be_bytes());
+        command = Command::DepositNFT { nonce, account_index, nft_id, l1_tx_hash }.encode();
 
         command_sign = BabyJubjub::sign(&command, &secret_key_1);
         command_sign_formatted = [0 as u8;64];
@@ -170,14 +143,7 @@ fn transfer_nft_account_not_exists() {
         let recipient = 3u32;
         nonce = 1;
 
-        let mut pad_recipient = [0u8; 32];
-        pad_recipient[28..].copy_from_slice(&recipient.to_be_bytes());
-        command = [0u8; 81];
-        command[0] = OP_TRANSFER_NFT;
-        command[1..9].copy_from_slice(&nonce.to_be_bytes());
-        command[9..13].copy_from_slice(&account_index.to_be_bytes());
-        command[13..17].copy_from_slice(&nft_id.to_be_bytes());
-        command[17..49].copy_from_slice(&pad_recipient);
+        command = Command::TransferNFT { nonce, account_index, nft_id, recipient }.encode();
 
         // There is no secret_key_4, so use secret_key_3 here
         command_sign = BabyJubjub::sign(&command, &secret_key_3);
@@ -218,12 +184,7 @@ fn transfer_nft_invalid_nftindex() {
         let mut l1_tx_hash = U256::from(0);
         let mut nonce = 1u64;
 
-        let mut command = [0u8; 81];
-        command[0] = OP_DEPOSIT;
-        command[1..9].copy_from_slice(&nonce.to_be_bytes());
-        command[9..13].copy_from_slice(&account_index.to_be_bytes());
-        command[13..17].copy_from_slice(&token_index.to_be_bytes());
-        command[17..49].copy_from_slice(&amount.to_be_bytes());
+        let mut command = Command::Deposit { nonce, account_index, token_index, amount }.encode();
 
         let mut command_sign = BabyJubjub::sign(&command, &secret_key_1);
         let mut command_sign_formatted :[u8; 64] = [0 as u8;64];
@@ -237,12 +198,7 @@ fn transfer_nft_invalid_nftindex() {
         l1_tx_hash = U256::from(1);
         nonce = 2u64;
 
-        command = [0u8; 81];
-        command[0] = OP_DEPOSIT_NFT;
-        command[1..9].copy_from_slice(&nonce.to_be_bytes());
-        command[9..13].copy_from_slice(&account_index.to_be_bytes());
-        command[13..17].copy_from_slice(&nft_id.to_be_bytes());
-        command[17..49].copy_from_slice(&l1_tx_hash.to_be_bytes());
+        command = Command::DepositNFT { nonce, account_index, nft_id, l1_tx_hash }.encode();
 
         command_sign = BabyJubjub::sign(&command, &secret_key_1);
         command_sign_formatted = [0 as u8;64];
@@ -264,14 +220,7 @@ fn transfer_nft_invalid_nftindex() {
         let recipient = 3u32;
         nonce = 1;
 
-        let mut pad_recipient = [0u8; 32];
-        pad_recipient[28..].copy_from_slice(&recipient.to_be_bytes());
-        command = [0u8; 81];
-        command[0] = OP_TRANSFER_NFT;
-        command[1..9].copy_from_slice(&nonce.to_be_bytes());
-        command[9..13].copy_from_slice(&account_index.to_be_bytes());
-        command[13..17].copy_from_slice(&nft_id.to_be_bytes());
-        command[17..49].copy_from_slice(&pad_recipient);
+        command = Command::TransferNFT { nonce, account_index, nft_id, recipient }.encode();
 
         command_sign = BabyJubjub::sign(&command, &secret_key_2);
         command_sign_formatted = [0 as u8;64];
@@ -284,14 +233,7 @@ fn transfer_nft_invalid_nftindex() {
         //nft_id should not be 0
         nft_id = 0;
 
-        pad_recipient = [0u8; 32];
-        pad_recipient[28..].copy_from_slice(&recipient.to_be_bytes());
-        command = [0u8; 81];
-        command[0] = OP_TRANSFER_NFT;
-        command[1..9].copy_from_slice(&nonce.to_be_bytes());
-        command[9..13].copy_from_slice(&account_index.to_be_bytes());
-        command[13..17].copy_from_slice(&nft_id.to_be_bytes());
-        command[17..49].copy_from_slice(&pad_recipient);
+        command = Command::TransferNFT { nonce, account_index, nft_id, recipient }.encode();
 
         command_sign = BabyJubjub::sign(&command, &secret_key_2);
         command_sign_formatted = [0 as u8;64];
@@ -331,12 +273,7 @@ fn transfer_nft_invalid_account() {
         let mut l1_tx_hash = U256::from(0);
         let mut nonce = 1u64;
 
-        let mut command = [0u8; 81];
-        command[0] = OP_DEPOSIT;
-        command[1..9].copy_from_slice(&nonce.to_be_bytes());
-        command[9..13].copy_from_slice(&account_index.to_be_bytes());
-        command[13..17].copy_from_slice(&token_index.to_be_bytes());
-        command[17..49].copy_from_slice(&amount.to_be_bytes());
+        let mut command = Command::Deposit { nonce, account_index, token_index, amount }.encode();
 
         let mut command_sign = BabyJubjub::sign(&command, &secret_key_1);
         let mut command_sign_formatted :[u8; 64] = [0 as u8;64];
@@ -350,12 +287,7 @@ fn transfer_nft_invalid_account() {
         l1_tx_hash = U256::from(1);
         nonce = 2u64;
 
-        command = [0u8; 81];
-        command[0] = OP_DEPOSIT_NFT;
-        command[1..9].copy_from_slice(&nonce.to_be_bytes());
-        command[9..13].copy_from_slice(&account_index.to_be_bytes());
-        command[13..17].copy_from_slice(&nft_id.to_be_bytes());
-        command[17..49].copy_from_slice(&l1_tx_hash.to_be_bytes());
+        command = Command::DepositNFT { nonce, account_index, nft_id, l1_tx_hash }.encode();
 
         command_sign = BabyJubjub::sign(&command, &secret_key_1);
         command_sign_formatted = [0 as u8;64];
@@ -376,14 +308,7 @@ fn transfer_nft_invalid_account() {
         let mut recipient = 4u32;
         nonce = 1;
 
-        let mut pad_recipient = [0u8; 32];
-        pad_recipient[28..].copy_from_slice(&recipient.to_be_bytes());
-        command = [0u8; 81];
-        command[0] = OP_TRANSFER_NFT;
-        command[1..9].copy_from_slice(&nonce.to_be_bytes());
-        command[9..13].copy_from_slice(&account_index.to_be_bytes());
-        command[13..17].copy_from_slice(&nft_id.to_be_bytes());
-        command[17..49].copy_from_slice(&pad_recipient);
+        command = Command::TransferNFT { nonce, account_index, nft_id, recipient }.encode();
 
         command_sign = BabyJubjub::sign(&command, &secret_key_2);
         command_sign_formatted = [0 as u8;64];
@@ -396,14 +321,7 @@ fn transfer_nft_invalid_account() {
         //recipient should no be zero
         recipient = 0u32;
 
-        pad_recipient = [0u8; 32];
-        pad_recipient[28..].copy_from_slice(&recipient.to_be_bytes());
-        command = [0u8; 81];
-        command[0] = OP_TRANSFER_NFT;
-        command[1..9].copy_from_slice(&nonce.to_be_bytes());
-        command[9..13].copy_from_slice(&account_index.to_be_bytes());
-        command[13..17].copy_from_slice(&nft_id.to_be_bytes());
-        command[17..49].copy_from_slice(&pad_recipient);
+        command = Command::TransferNFT { nonce, account_index, nft_id, recipient }.encode();
 
         command_sign = BabyJubjub::sign(&command, &secret_key_2);
         command_sign_formatted = [0 as u8;64];
@@ -443,12 +361,7 @@ fn transfer_nft_nonce_inconsistent() {
         let mut l1_tx_hash = U256::from(0);
         let mut nonce = 1u64;
 
-        let mut command = [0u8; 81];
-        command[0] = OP_DEPOSIT;
-        command[1..9].copy_from_slice(&nonce.to_be_bytes());
-        command[9..13].copy_from_slice(&account_index.to_be_bytes());
-        command[13..17].copy_from_slice(&token_index.to_be_bytes());
-        command[17..49].copy_from_slice(&amount.to_be_bytes());
+        let mut command = Command::Deposit { nonce, account_index, token_index, amount }.encode();
 
         let mut command_sign = BabyJubjub::sign(&command, &secret_key_1);
         let mut command_sign_formatted :[u8; 64] = [0 as u8;64];
@@ -462,12 +375,7 @@ fn transfer_nft_nonce_inconsistent() {
         l1_tx_hash = U256::from(1);
         nonce = 2u64;
 
-        command = [0u8; 81];
-        command[0] = OP_DEPOSIT_NFT;
-        command[1..9].copy_from_slice(&nonce.to_be_bytes());
-        command[9..13].copy_from_slice(&account_index.to_be_bytes());
-        command[13..17].copy_from_slice(&nft_id.to_be_bytes());
-        command[17..49].copy_from_slice(&l1_tx_hash.to_be_bytes());
+        command = Command::DepositNFT { nonce, account_index, nft_id, l1_tx_hash }.encode();
 
         command_sign = BabyJubjub::sign(&command, &secret_key_1);
         command_sign_formatted = [0 as u8;64];
@@ -488,14 +396,7 @@ fn transfer_nft_nonce_inconsistent() {
         //True nonce is 1u64
         nonce = 2u64;
 
-        let mut pad_recipient = [0u8; 32];
-        pad_recipient[28..].copy_from_slice(&recipient.to_be_bytes());
-        command = [0u8; 81];
-        command[0] = OP_TRANSFER_NFT;
-        command[1..9].copy_from_slice(&nonce.to_be_bytes());
-        command[9..13].copy_from_slice(&account_index.to_be_bytes());
-        command[13..17].copy_from_slice(&nft_id.to_be_bytes());
-        command[17..49].copy_from_slice(&pad_recipient);
+        command = Command::TransferNFT { nonce, account_index, nft_id, recipient }.encode();
 
         command_sign = BabyJubjub::sign(&command, &secret_key_2);
         command_sign_formatted = [0 as u8;64];
@@ -535,12 +436,7 @@ fn transfer_nft_invalid_signature() {
         let mut l1_tx_hash = U256::from(0);
         let mut nonce = 1u64;
 
-        let mut command = [0u8; 81];
-        command[0] = OP_DEPOSIT;
-        command[1..9].copy_from_slice(&nonce.to_be_bytes());
-        command[9..13].copy_from_slice(&account_index.to_be_bytes());
-        command[13..17].copy_from_slice(&token_index.to_be_bytes());
-        command[17..49].copy_from_slice(&amount.to_be_bytes());
+        let mut command = Command::Deposit { nonce, account_index, token_index, amount }.encode();
 
         let mut command_sign = BabyJubjub::sign(&command, &secret_key_1);
         let mut command_sign_formatted :[u8; 64] = [0 as u8;64];
@@ -554,12 +450,7 @@ fn transfer_nft_invalid_signature() {
         l1_tx_hash = U256::from(1);
         nonce = 2u64;
 
-        command = [0u8; 81];
-        command[0] = OP_DEPOSIT_NFT;
-        command[1..9].copy_from_slice(&nonce.to_be_bytes());
-        command[9..13].copy_from_slice(&account_index.to_be_bytes());
-        command[13..17].copy_from_slice(&nft_id.to_be_bytes());
-        command[17..49].copy_from_slice(&l1_tx_hash.to_be_bytes());
+        command = Command::DepositNFT { nonce, account_index, nft_id, l1_tx_hash }.encode();
 
         command_sign = BabyJubjub::sign(&command, &secret_key_1);
         command_sign_formatted = [0 as u8;64];
@@ -579,14 +470,7 @@ fn transfer_nft_invalid_signature() {
         let recipient = 3u32;
         nonce = 1;
 
-        let mut pad_recipient = [0u8; 32];
-        pad_recipient[28..].copy_from_slice(&recipient.to_be_bytes());
-        command = [0u8; 81];
-        command[0] = OP_TRANSFER_NFT;
-        command[1..9].copy_from_slice(&nonce.to_be_bytes());
-        command[9..13].copy_from_slice(&account_index.to_be_bytes());
-        command[13..17].copy_from_slice(&nft_id.to_be_bytes());
-        command[17..49].copy_from_slice(&pad_recipient);
+        command = Command::TransferNFT { nonce, account_index, nft_id, recipient }.encode();
 
         command_sign = BabyJubjub::sign(&command, &secret_key_2);
         command_sign_formatted = [0 as u8;64];
@@ -630,12 +514,7 @@ fn transfer_nft_is_not_owner() {
         let mut l1_tx_hash = U256::from(0);
         let mut nonce = 1u64;
 
-        let mut command = [0u8; 81];
-        command[0] = OP_DEPOSIT;
-        command[1..9].copy_from_slice(&nonce.to_be_bytes());
-        command[9..13].copy_from_slice(&account_index.to_be_bytes());
-        command[13..17].copy_from_slice(&token_index.to_be_bytes());
-        command[17..49].copy_from_slice(&amount.to_be_bytes());
+        let mut command = Command::Deposit { nonce, account_index, token_index, amount }.encode();
 
         let mut command_sign = BabyJubjub::sign(&command, &secret_key_1);
         let mut command_sign_formatted :[u8; 64] = [0 as u8;64];
@@ -649,12 +528,7 @@ fn transfer_nft_is_not_owner() {
         l1_tx_hash = U256::from(1);
         nonce = 2u64;
 
-        command = [0u8; 81];
-        command[0] = OP_DEPOSIT_NFT;
-        command[1..9].copy_from_slice(&nonce.to_be_bytes());
-        command[9..13].copy_from_slice(&account_index.to_be_bytes());
-        command[13..17].copy_from_slice(&nft_id.to_be_bytes());
-        command[17..49].copy_from_slice(&l1_tx_hash.to_be_bytes());
+        command = Command::DepositNFT { nonce, account_index, nft_id, l1_tx_hash }.encode();
 
         command_sign = BabyJubjub::sign(&command, &secret_key_1);
         command_sign_formatted = [0 as u8;64];
@@ -677,14 +551,7 @@ fn transfer_nft_is_not_owner() {
         let recipient = 3u32;
         nonce = 3;
 
-        let mut pad_recipient = [0u8; 32];
-        pad_recipient[28..].copy_from_slice(&recipient.to_be_bytes());
-        command = [0u8; 81];
-        command[0] = OP_TRANSFER_NFT;
-        command[1..9].copy_from_slice(&nonce.to_be_bytes());
-        command[9..13].copy_from_slice(&account_index.to_be_bytes());
-        command[13..17].copy_from_slice(&nft_id.to_be_bytes());
-        command[17..49].copy_from_slice(&pad_recipient);
+        command = Command::TransferNFT { nonce, account_index, nft_id, recipient }.encode();
 
         command_sign = BabyJubjub::sign(&command, &secret_key_1);
         command_sign_formatted = [0 as u8;64];
@@ -29,12 +29,7 @@ fn withdraw_nft_works_has_bidder() {
         let mut l1_tx_hash = U256::from(0);
         let mut nonce = 1u64;
 
-        let mut command = [0u8; 81];
-        command[0] = OP_DEPOSIT;
-        command[1..9].copy_from_slice(&nonce.to_be_bytes());
-        command[9..13].copy_from_slice(&account_index.to_be_bytes());
-        command[13..17].copy_from_slice(&token_index.to_be_bytes());
-        command[17..49].copy_from_slice(&amount.to_be_bytes());
+        let mut command = Command::Deposit { nonce, account_index, token_index, amount }.encode();
 
         let mut command_sign = BabyJubjub::sign(&command, &secret_key_1);
         let mut command_sign_formatted :[u8; 64] = [0 as u8;64];
@@ -48,12 +43,7 @@ fn withdraw_nft_works_has_bidder() {
         l1_tx_hash = U256::from(1);
         nonce = 2u64;
 
-        command = [0u8; 81];
-        command[0] = OP_DEPOSIT_NFT;
-        command[1..9].copy_from_slice(&nonce.to_be_bytes());
-        command[9..13].copy_from_slice(&account_index.to_be_bytes());
-        command[13..17].copy_from_slice(&nft_id.to_be_bytes());
-        command[17..49].copy_from_slice(&l1_tx_hash.to_be_bytes());
+        command = Command::DepositNFT { nonce, account_index, nft_id, l1_tx_hash }.encode();
 
         command_sign = BabyJubjub::sign(&command, &secret_key_1);
         command_sign_formatted = [0 as u8;64];
@@ -74,12 +64,7 @@ fn withdraw_nft_works_has_bidder() {
         l1_tx_hash = U256::from(12);
         nonce = 3u64;
 
-        command = [0u8; 81];
-        command[0] = OP_DEPOSIT;
-        command[1..9].copy_from_slice(&nonce.to_be_bytes());
-        command[9..13].copy_from_slice(&account_index.to_be_bytes());
-        command[13..17].copy_from_slice(&token_index.to_be_bytes());
-        command[17..49].copy_from_slice(&amount.to_be_bytes());
+        command = Command::Deposit { nonce, account_index, token_index, amount }.encode();
 
         command_sign = BabyJubjub::sign(&command, &secret_key_1);
         command_sign_formatted = [0 as u8;64];
@@ -93,12 +78,7 @@ fn withdraw_nft_works_has_bidder() {
         amount = U256::from(1);
         nonce = 1u64;
 
-        command = [0u8; 81];
-        command[0] = OP_BID_NFT;
-        command[1..9].copy_from_slice(&nonce.to_be_bytes());
-        command[9..13].copy_from_slice(&account_index.to_be_bytes());
-        command[13..17].copy_from_slice(&nft_id.to_be_bytes());
-        command[17..49].copy_from_slice(&amount.to_be_bytes());
+        command = Command::BidNFT { nonce, account_index, nft_id, amount }.encode();
 
         command_sign = BabyJubjub::sign(&command, &secret_key_3);
         command_sign_formatted = [0 as u8;64];
@@ -113,12 +93,7 @@ fn withdraw_nft_works_has_bidder() {
         let l1account = U256::from(3);
         nonce = 1u64;
 
-        command = [0u8; 81];
-        command[0] = OP_WITHDRAW_NFT;
-        command[1..9].copy_from_slice(&nonce.to_be_bytes());
-        command[9..13].copy_from_slice(&account_index.to_be_bytes());
-        command[13..17].copy_from_slice(&nft_id.to_be_bytes());
-        command[17..49].copy_from_slice(&l1account.to_be_bytes());
+        command = Command::WithdrawNFT { nonce, account_index, nft_id, l1account }.encode();
 
         command_sign = BabyJubjub::sign(&command, &secret_key_2);
         command_sign_formatted = [0 as u8;64];
@@ -127,11 +102,11 @@ fn withdraw_nft_works_has_bidder() {
 
         assert_ok!(SwapModule::withdraw_nft(Origin::signed(origin), command_sign_formatted, nft_id, l1account, nonce));
 
-        let nft = NFTMap::get(&nft_id);
+        let nft = NFTMap::<Test>::get(&nft_id);
         assert_eq!(nft.0, 0);
         assert_eq!(nft.1, U256::from(0));
         assert_eq!(nft.2, None);
-        assert_eq!(BalanceMap::get((&account_index, token_index)), U256::from(10));
+        assert_eq!(BalanceMap::<Test>::get((&account_index, token_index)), U256::from(10));
     })
 }
 
@@ -164,12 +139,7 @@ fn withdraw_nft_works_no_bidder() {
         let mut l1_tx_hash = U256::from(0);
         let mut nonce = 1u64;
 
-        let mut command = [0u8; 81];
-        command[0] = OP_DEPOSIT;
-        command[1..9].copy_from_slice(&nonce.to_be_bytes());
-        command[9..13].copy_from_slice(&account_index.to_be_bytes());
-        command[13..17].copy_from_slice(&token_index.to_be_bytes());
-        command[17..49].copy_from_slice(&amount.to_be_bytes());
+        let mut command = Command::Deposit { nonce, account_index, token_index, amount }.encode();
 
         let mut command_sign = BabyJubjub::sign(&command, &secret_key_1);
         let mut command_sign_formatted :[u8; 64] = [0 as u8;64];
@@ -183,12 +153,7 @@ fn withdraw_nft_works_no_bidder() {
         l1_tx_hash = U256::from(1);
         nonce = 2u64;
 
-        command = [0u8; 81];
-        command[0] = OP_DEPOSIT_NFT;
-        command[1..9].copy_from_slice(&nonce.to_be_bytes());
-        command[9..13].copy_from_slice(&account_index.to_be_bytes());
-        command[13..17].copy_from_slice(&nft_id.to_be_bytes());
-        command[17..49].copy_from_slice(&l1_tx_hash.to_be_bytes());
+        command = Command::DepositNFT { nonce, account_index, nft_id, l1_tx_hash }.encode();
 
         command_sign = BabyJubjub::sign(&command, &secret_key_1);
         command_sign_formatted = [0 as u8;64];
@@ -203,12 +168,7 @@ fn withdraw_nft_works_no_bidder() {
         let l1account = U256::from(3);
         nonce = 1u64;
 
-        command = [0u8; 81];
-        command[0] = OP_WITHDRAW_NFT;
-        command[1..9].copy_from_slice(&nonce.to_be_bytes());
-        command[9..13].copy_from_slice(&account_index.to_be_bytes());
-        command[13..17].copy_from_slice(&nft_id.to_be_bytes());
-        command[17..49].copy_from_slice(&l1account.to_be_bytes());
+        command = Command::WithdrawNFT { nonce, account_index, nft_id, l1account }.encode();
 
         command_sign = BabyJubjub::sign(&command, &secret_key_2);
         command_sign_formatted = [0 as u8;64];
@@ -217,7 +177,7 @@ fn withdraw_nft_works_no_bidder() {
 
         assert_ok!(SwapModule::withdraw_nft(Origin::signed(origin), command_sign_formatted, nft_id, l1account, nonce));
 
-        let nft = NFTMap::get(&nft_id);
+        let nft = NFTMap::<Test>::get(&nft_id);
         assert_eq!(nft.0, 0);
         assert_eq!(nft.1, U256::from(0));
         assert_eq!(nft.2, None);
@@ -253,12 +213,7 @@ fn withdraw_nft_account_not_exists() {
         let mut l1_tx_hash = U256::from(0);
         let mut nonce = 1u64;
 
-        let mut command = [0u8; 81];
-        command[0] = OP_DEPOSIT;
-        command[1..9].copy_from_slice(&nonce.to_be_bytes());
-        command[9..13].copy_from_slice(&account_index.to_be_bytes());
-        command[13..17].copy_from_slice(&token_index.to_be_bytes());
-        command[17..49].copy_from_slice(&amount.to_be_bytes());
+        let mut command = Command::Deposit { nonce, account_index, token_index, amount }.encode();
 
         let mut command_sign = BabyJubjub::sign(&command, &secret_key_1);
         let mut command_sign_formatted :[u8; 64] = [0 as u8;64];
@@ -272,12 +227,7 @@ fn withdraw_nft_account_not_exists() {
         l1_tx_hash = U256::from(1);
         nonce = 2u64;
 
-        command = [0u8; 81];
-        command[0] = OP_DEPOSIT_NFT;
-        command[1..9].copy_from_slice(&nonce.to_be_bytes());
-        command[9..13].copy_from_slice(&account_index.to_be_bytes());
-        command[13..17].copy_from_slice(&nft_id.to_be_bytes());
-        command[17..49].copy_from_slice(&l1_tx_hash.to_be_bytes());
+        command = Command::DepositNFT { nonce, account_index, nft_id, l1_tx_hash }.encode();
 
         command_sign = BabyJubjub::sign(&command, &secret_key_1);
         command_sign_formatted = [0 as u8;64];
@@ -292,12 +242,7 @@ fn withdraw_nft_account_not_exists() {
         let l1account = U256::from(3);
         nonce = 2u64;
 
-        command = [0u8; 81];
-        command[0] = OP_WITHDRAW_NFT;
-        command[1..9].copy_from_slice(&nonce.to_be_bytes());
-        command[9..13].copy_from_slice(&account_index.to_be_bytes());
-        command[13..17].copy_from_slice(&nft_id.to_be_bytes());
-        command[17..49].copy_from_slice(&l1account.to_be_bytes());
+        command = Command::WithdrawNFT { nonce, account_index, nft_id, l1account }.encode();
 
         //There is no secret_key_3, use secret_key_2 here
         command_sign = BabyJubjub::sign(&command, &secret_key_2);
@@ -338,12 +283,7 @@ fn withdraw_nft_invalid_nftindex() {
         let mut l1_tx_hash = U256::from(0);
         let mut nonce = 1u64;
 
-        let mut command = [0u8; 81];
-        command[0] = OP_DEPOSIT;
-        command[1..9].copy_from_slice(&nonce.to_be_bytes());
-        command[9..13].copy_from_slice(&account_index.to_be_bytes());
-        command[13..17].copy_from_slice(&token_index.to_be_bytes());
-        command[17..49].copy_from_slice(&amount.to_be_bytes());
+        let mut command = Command::Deposit { nonce, account_index, token_index, amount }.encode();
 
         let mut command_sign = BabyJubjub::sign(&command, &secret_key_1);
         let mut command_sign_formatted :[u8; 64] = [0 as u8;64];
@@ -357,12 +297,7 @@ fn withdraw_nft_invalid_nftindex() {
         l1_tx_hash = U256::from(1);
         nonce = 2u64;
 
-        command = [0u8; 81];
-        command[0] = OP_DEPOSIT_NFT;
-        command[1..9].copy_from_slice(&nonce.to_be_bytes());
-        command[9..13].copy_from_slice(&account_index.to_be_bytes());
-        command[13..17].copy_from_slice(&nft_id.to_be_bytes());
-        command[17..49].copy_from_slice(&l1_tx_hash.to_be_bytes());
+        command = Command::DepositNFT { nonce, account_index, nft_id, l1_tx_hash }.encode();
 
         command_sign = BabyJubjub::sign(&command, &secret_key_1);
         command_sign_formatted = [0 as u8;64];
@@ -378,12 +313,7 @@ fn withdraw_nft_invalid_nftindex() {
         let l1account = U256::from(3);
         nonce = 2u64;
 
-        command = [0u8; 81];
-        command[0] = OP_WITHDRAW_NFT;
-        command[1..9].copy_from_slice(&nonce.to_be_bytes());
-        command[9..13].copy_from_slice(&account_index.to_be_bytes());
-        command[13..17].copy_from_slice(&nft_id.to_be_bytes());
-        command[17..49].copy_from_slice(&amount.to_be_bytes());
+        command = Command::WithdrawNFT { nonce, account_index, nft_id, l1account: amount }.encode();
 
         command_sign = BabyJubjub::sign(&command, &secret_key_2);
         command_sign_formatted = [0 as u8;64];
@@ -423,12 +353,7 @@ fn withdraw_nft_l1account_overflow() {
         let mut l1_tx_hash = U256::from(0);
         let mut nonce = 1u64;
 
-        let mut command = [0u8; 81];
-        command[0] = OP_DEPOSIT;
-        command[1..9].copy_from_slice(&nonce.to_be_bytes());
-        command[9..13].copy_from_slice(&account_index.to_be_bytes());
-        command[13..17].copy_from_slice(&token_index.to_be_bytes());
-        command[17..49].copy_from_slice(&amount.to_be_bytes());
+        let mut command = Command::Deposit { nonce, account_index, token_index, amount }.encode();
 
         let mut command_sign = BabyJubjub::sign(&command, &secret_key_1);
         let mut command_sign_formatted :[u8; 64] = [0 as u8;64];
@@ -442,12 +367,7 @@ fn withdraw_nft_l1account_overflow() {
         l1_tx_hash = U256::from(1);
         nonce = 2u64;
 
-        command = [0u8; 81];
-        command[0] = OP_DEPOSIT_NFT;
-        command[1..9].copy_from_slice(&nonce.to_be_bytes());
-        command[9..13].copy_from_slice(&account_index.to_be_bytes());
-        command[13..17].copy_from_slice(&nft_id.to_be_bytes());
-        command[17..49].copy_from_slice(&l1_tx_hash.to_be_bytes());
+        command = Command::DepositNFT { nonce, account_index, nft_id, l1_tx_hash }.encode();
 
         command_sign = BabyJubjub::sign(&command, &secret_key_1);
         command_sign_formatted = [0 as u8;64];
@@ -462,12 +382,7 @@ fn withdraw_nft_l1account_overflow() {
         let l1account = U256::from(1) << 250;
         nonce = 2u64;
 
-        command = [0u8; 81];
-        command[0] = OP_WITHDRAW_NFT;
-        command[1..9].copy_from_slice(&nonce.to_be_bytes());
-        command[9..13].copy_from_slice(&account_index.to_be_bytes());
-        command[13..17].copy_from_slice(&nft_id.to_be_bytes());
-        command[17..49].copy_from_slice(&l1account.to_be_bytes());
+        command = Command::WithdrawNFT { nonce, account_index, nft_id, l1account }.encode();
 
         command_sign = BabyJubjub::sign(&command, &secret_key_2);
         command_sign_formatted = [0 as u8;64];
@@ -507,12 +422,7 @@ fn withdraw_nft_nonce_inconsistent() {
         let mut l1_tx_hash = U256::from(0);
         let mut nonce = 1u64;
 
-        let mut command = [0u8; 81];
-        command[0] = OP_DEPOSIT;
-        command[1..9].copy_from_slice(&nonce.to_be_bytes());
-        command[9..13].copy_from_slice(&account_index.to_be_bytes());
-        command[13..17].copy_from_slice(&token_index.to_be_bytes());
-        command[17..49].copy_from_slice(&amount.to_be_bytes());
+        let mut command = Command::Deposit { nonce, account_index, token_index, amount }.encode();
 
         let mut command_sign = BabyJubjub::sign(&command, &secret_key_1);
         let mut command_sign_formatted :[u8; 64] = [0 as u8;64];
@@ -526,12 +436,7 @@ fn withdraw_nft_nonce_inconsistent() {
         l1_tx_hash = U256::from(1);
         nonce = 2u64;
 
-        command = [0u8; 81];
-        command[0] = OP_DEPOSIT_NFT;
-        command[1..9].copy_from_slice(&nonce.to_be_bytes());
-        command[9..13].copy_from_slice(&account_index.to_be_bytes());
-        command[13..17].copy_from_slice(&nft_id.to_be_bytes());
-        command[17..49].copy_from_slice(&l1_tx_hash.to_be_bytes());
+        command = Command::DepositNFT { nonce, account_index, nft_id, l1_tx_hash }.encode();
 
         command_sign = BabyJubjub::sign(&command, &secret_key_1);
         command_sign_formatted = [0 as u8;64];
@@ -546,12 +451,7 @@ fn withdraw_nft_nonce_inconsistent() {
         //True nonce is 1u64
         nonce = 3u64;
 
-        command = [0u8; 81];
-        command[0] = OP_WITHDRAW_NFT;
-        command[1..9].copy_from_slice(&nonce.to_be_bytes());
-        command[9..13].copy_from_slice(&account_index.to_be_bytes());
-        command[13..17].copy_from_slice(&nft_id.to_be_bytes());
-        command[17..49].copy_from_slice(&l1account.to_be_bytes());
+        command = Command::WithdrawNFT { nonce, account_index, nft_id, l1account }.encode();
 
         command_sign = BabyJubjub::sign(&command, &secret_key_2);
         command_sign_formatted = [0 as u8;64];
@@ -591,12 +491,7 @@ fn withdraw_nft_invalid_signature() {
         let mut l1_tx_hash = U256::from(0);
         let mut nonce = 1u64;
 
-        let mut command = [0u8; 81];
-        command[0] = OP_DEPOSIT;
-        command[1..9].copy_from_slice(&nonce.to_be_bytes());
-        command[9..13].copy_from_slice(&account_index.to_be_bytes());
-        command[13..17].copy_from_slice(&token_index.to_be_bytes());
-        command[17..49].copy_from_slice(&amount.to_be_bytes());
+        let mut command = Command::Deposit { nonce, account_index, token_index, amount }.encode();
 
         let mut command_sign = BabyJubjub::sign(&command, &secret_key_1);
         let mut command_sign_formatted :[u8; 64] = [0 as u8;64];
@@ -610,12 +505,7 @@ fn withdraw_nft_invalid_signature() {
         l1_tx_hash = U256::from(1);
         nonce = 2u64;
 
-        command = [0u8; 81];
-        command[0] = OP_DEPOSIT_NFT;
-        command[1..9].copy_from_slice(&nonce.to_be_bytes());
-        command[9..13].copy_from_slice(&account_index.to_be_bytes());
-        command[13..17].copy_from_slice(&nft_id.to_be_bytes());
-        command[17..49].copy_from_slice(&l1_tx_hash.to_be_bytes());
+        command = Command::DepositNFT { nonce, account_index, nft_id, l1_tx_hash }.encode();
 
         command_sign = BabyJubjub::sign(&command, &secret_key_1);
         command_sign_formatted = [0 as u8;64];
@@ -629,12 +519,7 @@ fn withdraw_nft_invalid_signature() {
         let mut l1account = U256::from(3);
         nonce = 1u64;
 
-        command = [0u8; 81];
-        command[0] = OP_WITHDRAW_NFT;
-        command[1..9].copy_from_slice(&nonce.to_be_bytes());
-        command[9..13].copy_from_slice(&account_index.to_be_bytes());
-        command[13..17].copy_from_slice(&nft_id.to_be_bytes());
-        command[17..49].copy_from_slice(&l1account.to_be_bytes());
+        command = Command::WithdrawNFT { nonce, account_index, nft_id, l1account }.encode();
 
         command_sign = BabyJubjub::sign(&command, &secret_key_2);
         command_sign_formatted = [0 as u8;64];
@@ -677,12 +562,7 @@ fn withdraw_nft_is_not_owner() {
         let mut l1_tx_hash = U256::from(0);
         let mut nonce = 1u64;
 
-        let mut command = [0u8; 81];
-        command[0] = OP_DEPOSIT;
-        command[1..9].copy_from_slice(&nonce.to_be_bytes());
-        command[9..13].copy_from_slice(&account_index.to_be_bytes());
-        command[13..17].copy_from_slice(&token_index.to_be_bytes());
-        command[17..49].copy_from_slice(&amount.to_be_bytes());
+        let mut command = Command::Deposit { nonce, account_index, token_index, amount }.encode();
 
         let mut command_sign = BabyJubjub::sign(&command, &secret_key_1);
         let mut command_sign_formatted :[u8; 64] = [0 as u8;64];
@@ -696,12 +576,7 @@ fn withdraw_nft_is_not_owner() {
         l1_tx_hash = U256::from(1);
         nonce = 2u64;
 
-        command = [0u8; 81];
-        command[0] = OP_DEPOSIT_NFT;
-        command[1..9].copy_from_slice(&nonce.to_be_bytes());
-        command[9..13].copy_from_slice(&account_index.to_be_bytes());
-        command[13..17].copy_from_slice(&nft_id.to_be_bytes());
-        command[17..49].copy_from_slice(&l1_tx_hash.to_be_bytes());
+        command = Command::DepositNFT { nonce, account_index, nft_id, l1_tx_hash }.encode();
 
         command_sign = BabyJubjub::sign(&command, &secret_key_1);
         command_sign_formatted = [0 as u8;64];
@@ -717,12 +592,7 @@ fn withdraw_nft_is_not_owner() {
         let l1account = U256::from(3);
         nonce = 3u64;
 
-        command = [0u8; 81];
-        command[0] = OP_WITHDRAW_NFT;
-        command[1..9].copy_from_slice(&nonce.to_be_bytes());
-        command[9..13].copy_from_slice(&account_index.to_be_bytes());
-        command[13..17].copy_from_slice(&nft_id.to_be_bytes());
-        command[17..49].copy_from_slice(&l1account.to_be_bytes());
+        command = Command::WithdrawNFT { nonce, account_index, nft_id, l1account }.encode();
 
         command_sign = BabyJubjub::sign(&command, &secret_key_1);
         command_sign_formatted = [0 as u8;64];
@@ -762,12 +632,7 @@ fn withdraw_nft_has_bidder_balance_overflow() {
         let mut l1_tx_hash = U256::from(0);
         let mut nonce = 1u64;
 
-        let mut command = [0u8; 81];
-        command[0] = OP_DEPOSIT;
-        command[1..9].copy_from_slice(&nonce.to_be_bytes());
-        command[9..13].copy_from_slice(&account_index.to_be_bytes());
-        command[13..17].copy_from_slice(&token_index.to_be_bytes());
-        command[17..49].copy_from_slice(&amount.to_be_bytes());
+        let mut command = Command::Deposit { nonce, account_index, token_index, amount }.encode();
 
         let mut command_sign = BabyJubjub::sign(&command, &secret_key_1);
         let mut command_sign_formatted :[u8; 64] = [0 as u8;64];
@@ -781,12 +646,7 @@ fn withdraw_nft_has_bidder_balance_overflow() {
         l1_tx_hash = U256::from(1);
         nonce = 2u64;
 
-        command = [0u8; 81];
-        command[0] = OP_DEPOSIT_NFT;
-        command[1..9].copy_from_slice(&nonce.to_be_bytes());
-        command[9..13].copy_from_slice(&account_index.to_be_bytes());
-        command[13..17].copy_from_slice(&nft_id.to_be_bytes());
-        command[17..49].copy_from_slice(&l1_tx_hash.to_be_bytes());
+        command = Command::DepositNFT { nonce, account_index, nft_id, l1_tx_hash }.encode();
 
         command_sign = BabyJubjub::sign(&command, &secret_key_1);
         command_sign_formatted = [0 as u8;64];
@@ -807,12 +667,7 @@ fn withdraw_nft_has_bidder_balance_overflow() {
         l1_tx_hash = U256::from(12);
         nonce = 3u64;
 
-        command = [0u8; 81];
-        command[0] = OP_DEPOSIT;
-        command[1..9].copy_from_slice(&nonce.to_be_bytes());
-        command[9..13].copy_from_slice(&account_index.to_be_bytes());
-        command[13..17].copy_from_slice(&token_index.to_be_bytes());
-        command[17..49].copy_from_slice(&amount.to_be_bytes());
+        command = Command::Deposit { nonce, account_index, token_index, amount }.encode();
 
         command_sign = BabyJubjub::sign(&command, &secret_key_1);
         command_sign_formatted = [0 as u8;64];
@@ -826,12 +681,7 @@ fn withdraw_nft_has_bidder_balance_overflow() {
         amount = U256::from(1);
         nonce = 1u64;
 
-        command = [0u8; 81];
-        command[0] = OP_BID_NFT;
-        command[1..9].copy_from_slice(&nonce.to_be_bytes());
-        command[9..13].copy_from_slice(&account_index.to_be_bytes());
-        command[13..17].copy_from_slice(&nft_id.to_be_bytes());
-        command[17..49].copy_from_slice(&amount.to_be_bytes());
+        command = Command::BidNFT { nonce, account_index, nft_id, amount }.encode();
 
         command_sign = BabyJubjub::sign(&command, &secret_key_3);
         command_sign_formatted = [0 as u8;64];
@@ -848,12 +698,7 @@ fn withdraw_nft_has_bidder_balance_overflow() {
         l1_tx_hash = U256::from(9);
         let mut nonce = 4u64;
 
-        let mut command = [0u8; 81];
-        command[0] = OP_DEPOSIT;
-        command[1..9].copy_from_slice(&nonce.to_be_bytes());
-        command[9..13].copy_from_slice(&account_index.to_be_bytes());
-        command[13..17].copy_from_slice(&token_index.to_be_bytes());
-        command[17..49].copy_from_slice(&amount.to_be_bytes());
+        let mut command = Command::Deposit { nonce, account_index, token_index, amount }.encode();
 
         let mut command_sign = BabyJubjub::sign(&command, &secret_key_1);
         let mut command_sign_formatted :[u8; 64] = [0 as u8;64];
@@ -868,12 +713,7 @@ fn withdraw_nft_has_bidder_balance_overflow() {
         let l1account = U256::from(3);
         nonce = 1u64;
 
-        command = [0u8; 81];
-        command[0] = OP_WITHDRAW_NFT;
-        command[1..9].copy_from_slice(&nonce.to_be_bytes());
-        command[9..13].copy_from_slice(&account_index.to_be_bytes());
-        command[13..17].copy_from_slice(&nft_id.to_be_bytes());
-        command[17..49].copy_from_slice(&l1account.to_be_bytes());
+        command = Command::WithdrawNFT { nonce, account_index, nft_id, l1account }.encode();
 
         command_sign = BabyJubjub::sign(&command, &secret_key_2);
         command_sign_formatted = [0 as u8;64];
@@ -1,4 +1,7 @@
 use super::*;
+use frame_support::RuntimeDebug;
+#[cfg(feature = "std")]
+use serde::{Deserialize, Serialize};
 
 pub type NonceId = u64;
 pub type ReqId = U256;
@@ -61,5 +64,182 @@ pub enum Ops {
         SignatureRX, SignatureRY, SignatureS, NonceId,
         AccountIndex, NFTId
 
-    )
+    ),
+    /* Refund of the rejected withdrawal with this ReqId */
+    Reject(ReqId)
+}
+
+#[derive(Encode, Decode, Clone, PartialEq, Default, RuntimeDebug)]
+pub struct PriceObservation<BlockNumber> {
+    pub block_number: BlockNumber,
+    pub price0_cumulative: U256,
+    pub price1_cumulative: U256,
+    /* Reserves of the pool at the end of `block_number` */
+    pub reserve0: Amount,
+    pub reserve1: Amount,
+}
+
+#[derive(Encode, Decode, Clone, PartialEq, Default, RuntimeDebug)]
+pub struct TokenInfo {
+    pub l1_address: L1Account,
+    pub decimals: u8,
+    pub symbol: Vec<u8>,
+}
+
+#[derive(Encode, Decode, Clone, PartialEq, Default, RuntimeDebug)]
+pub struct TokenLimit<BlockNumber> {
+    pub max_deposit: Option<Amount>,
+    pub max_withdraw: Option<Amount>,
+    pub period: BlockNumber,
+    pub max_period_withdraw: Option<Amount>,
+}
+
+/* Limit of a TokenLimit an op exceeded */
+#[derive(Encode, Decode, Clone, Copy, PartialEq, Eq, RuntimeDebug)]
+pub enum TokenLimitKind {
+    Deposit,
+    Withdraw,
+    PeriodWithdraw,
+}
+
+#[derive(Encode, Decode, Clone, PartialEq, RuntimeDebug)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+pub enum DepositStatus {
+    Unknown,
+    Pending(ReqId),
+    Done(ReqId),
+}
+
+#[derive(Encode, Decode, Clone, Copy, PartialEq, RuntimeDebug)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+pub enum ReqStatus {
+    Pending,
+    Complete,
+    Rejected,
+}
+
+#[derive(Encode, Decode, Clone, PartialEq, RuntimeDebug)]
+pub struct ReqRecord<BlockNumber> {
+    /* OP_* code of the request */
+    pub op: u8,
+    /* Account index of the submitter */
+    pub account_index: AccountIndex,
+    pub submitted_at: BlockNumber,
+    /* Bitmap of the admins who acked */
+    pub acks: u8,
+    /* Block the request was completed or rejected at */
+    pub completed_at: Option<BlockNumber>,
+    pub status: ReqStatus,
+}
+
+/* Entry of the L2 state committed in the Merkle tree, see merkle.rs */
+#[derive(Encode, Decode, Clone, Copy, PartialEq, RuntimeDebug)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+pub enum MerkleLeaf {
+    Balance(AccountIndex, TokenIndex),
+    Share(AccountIndex, PoolIndex),
+    Pool(PoolIndex),
+    NFT(NFTId),
+    Key(AccountIndex),
+}
+
+impl MerkleLeaf {
+    pub fn index(&self) -> u32 {
+        match self {
+            MerkleLeaf::Balance(account_index, token_index) => (account_index << 10) | token_index,
+            MerkleLeaf::Share(account_index, pool_index) => (1u32 << 30) | (account_index << 10) | pool_index,
+            MerkleLeaf::Pool(pool_index) => (2u32 << 30) | pool_index,
+            MerkleLeaf::NFT(nft_id) => (2u32 << 30) | (1u32 << 28) | nft_id,
+            MerkleLeaf::Key(account_index) => (2u32 << 30) | (2u32 << 28) | account_index,
+        }
+    }
+}
+
+#[derive(Encode, Decode, Clone, PartialEq, RuntimeDebug)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+pub struct MerkleProof {
+    pub index: u32,
+    pub leaf: H256,
+    /* Siblings from the leaf level up */
+    pub siblings: Vec<H256>,
+    pub root: H256,
+}
+
+/* Status of an L1 deposit transaction, encoded as the former PENDING (1) and DONE (2) codes */
+#[derive(Encode, Decode, Clone, Copy, PartialEq, RuntimeDebug)]
+pub enum L1TxStatus {
+    #[codec(index = 1)]
+    Pending,
+    #[codec(index = 2)]
+    Done,
+}
+
+/* Deposit made on L1, as reported to the off-chain worker by an L1EventSource */
+#[derive(Encode, Decode, Clone, PartialEq, RuntimeDebug)]
+pub enum L1Deposit {
+    Token { l1_tx_hash: L1TxHash, account_index: AccountIndex, token_index: TokenIndex, amount: Amount },
+    NFT { l1_tx_hash: L1TxHash, account_index: AccountIndex, nft_id: NFTId },
+}
+
+impl L1Deposit {
+    pub fn l1_tx_hash(&self) -> L1TxHash {
+        match self {
+            L1Deposit::Token { l1_tx_hash, .. } | L1Deposit::NFT { l1_tx_hash, .. } => *l1_tx_hash,
+        }
+    }
+}
+
+/* Direction of a swap, encoded as the `reverse` byte of the swap command */
+#[derive(Encode, Decode, Clone, Copy, PartialEq, RuntimeDebug)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+pub enum SwapDirection {
+    #[codec(index = 0)]
+    Token0ToToken1,
+    #[codec(index = 1)]
+    Token1ToToken0,
+}
+
+impl TryFrom<Reverse> for SwapDirection {
+    type Error = ();
+
+    fn try_from(reverse: Reverse) -> Result<Self, Self::Error> {
+        match reverse {
+            0u8 => Ok(SwapDirection::Token0ToToken1),
+            1u8 => Ok(SwapDirection::Token1ToToken0),
+            _ => Err(()),
+        }
+    }
+}
+
+impl From<SwapDirection> for Reverse {
+    fn from(direction: SwapDirection) -> Reverse {
+        match direction {
+            SwapDirection::Token0ToToken1 => 0u8,
+            SwapDirection::Token1ToToken0 => 1u8,
+        }
+    }
+}
+
+/* Storage layout versions of the pallet */
+#[derive(Encode, Decode, Clone, Copy, PartialEq, RuntimeDebug)]
+pub enum Releases {
+    /* `SimpleMap` prefix, u8 status codes */
+    V1_0_0,
+    /* `Swap` prefix, typed L1TxMap status */
+    V2_0_0,
+    /* Admins in storage */
+    V3_0_0,
+    /* Merkle tree and token registry of the state written before them */
+    V4_0_0,
+}
+
+impl Releases {
+    /* Version written at genesis, the pallet refuses ops until the storage is migrated to it */
+    pub const LATEST: Releases = Releases::V4_0_0;
+}
+
+impl Default for Releases {
+    fn default() -> Self {
+        Releases::V1_0_0
+    }
 }
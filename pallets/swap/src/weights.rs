@@ -0,0 +1,140 @@
+use super::*;
+
+/*
+ * Weights of the dispatchables, counted from the storage entries they read and write plus the
+ * hashing and signature checks they run. Storage accesses are priced with the runtime DbWeight,
+ * the compute constants are conservative estimates for the wasm executor.
+ */
+
+/* Fixed cost of a dispatchable */
+const BASE_WEIGHT: Weight = 10_000_000;
+/* SHA-256 of one Merkle node */
+const MERKLE_HASH_WEIGHT: Weight = 5_000_000;
+/* BabyJubjub EdDSA verification of a command signature */
+const SIGNATURE_CHECK_WEIGHT: Weight = 2_000_000_000;
+/* Decoding and copying one byte of a batch proof */
+const PROOF_BYTE_WEIGHT: Weight = 1_000;
+
+/* Storage accessed by every L2 op: the pause flags, the caller's account index and nonce, the
+ * request index, the request record and account history, and the Merkle root commit */
+const OP_READS: Weight = 11;
+const OP_WRITES: Weight = 8;
+
+/// Update of one Merkle leaf: its state entry, a sibling per level, a node per level and the root.
+pub fn leaf_update<T: Config>() -> Weight {
+    T::DbWeight::get().reads_writes(MERKLE_DEPTH as Weight + 1, MERKLE_DEPTH as Weight + 1)
+        .saturating_add((MERKLE_DEPTH as Weight + 1) * MERKLE_HASH_WEIGHT)
+}
+
+/* Pool reserves, price observation and snapshot of a pool change, without its Merkle leaf */
+fn pool_change<T: Config>() -> Weight {
+    T::DbWeight::get().reads_writes(3, 4)
+}
+
+/* An L2 op reading and writing `reads` and `writes` entries besides the common ones and updating
+ * `leaves` Merkle leaves */
+fn op<T: Config>(reads: Weight, writes: Weight, leaves: Weight, signed: bool) -> Weight {
+    let signature = if signed { SIGNATURE_CHECK_WEIGHT } else { 0 };
+    BASE_WEIGHT
+        .saturating_add(signature)
+        .saturating_add(T::DbWeight::get().reads_writes(OP_READS + reads, OP_WRITES + writes))
+        .saturating_add(leaves.saturating_mul(leaf_update::<T>()))
+}
+
+pub fn charge<T: Config>() -> Weight {
+    BASE_WEIGHT.saturating_add(T::DbWeight::get().reads_writes(3, 3))
+}
+
+/* Account index creation and key */
+pub fn set_key<T: Config>() -> Weight {
+    op::<T>(2, 3, 1, false)
+}
+
+/* Admins, tokens and pool index; pool index count and pool */
+pub fn add_pool<T: Config>() -> Weight {
+    op::<T>(5, 3, 1, true)
+}
+
+/* Admins, token, account count, L1 tx, limit and balance; deposit and L1 tx indexes, balance */
+pub fn deposit<T: Config>() -> Weight {
+    op::<T>(6, 4, 1, true)
+}
+
+/* Token, limits and balance; period withdrawal and balance */
+pub fn withdraw<T: Config>() -> Weight {
+    op::<T>(4, 2, 1, true)
+}
+
+/* Pool and both balances; both balances */
+pub fn swap<T: Config>() -> Weight {
+    op::<T>(3, 2, 3, true).saturating_add(pool_change::<T>())
+}
+
+/* Pool, both balances and share; both balances and share */
+pub fn pool_supply<T: Config>() -> Weight {
+    op::<T>(4, 3, 4, true).saturating_add(pool_change::<T>())
+}
+
+pub fn pool_retrieve<T: Config>() -> Weight {
+    pool_supply::<T>()
+}
+
+/* Admins, account count, L1 tx and NFT; deposit and L1 tx indexes, NFT */
+pub fn deposit_nft<T: Config>() -> Weight {
+    op::<T>(4, 4, 1, true)
+}
+
+/* NFT and the refunded bid; NFT and the bidder balance */
+pub fn withdraw_nft<T: Config>() -> Weight {
+    op::<T>(2, 2, 2, true)
+}
+
+/* Account count and NFT; NFT */
+pub fn transfer_nft<T: Config>() -> Weight {
+    op::<T>(2, 1, 1, true)
+}
+
+/* NFT and both bidders' balances; NFT and both balances */
+pub fn bid_nft<T: Config>() -> Weight {
+    op::<T>(3, 3, 3, true)
+}
+
+/* NFT and the owner's balance; NFT and balance */
+pub fn finalize_nft<T: Config>() -> Weight {
+    op::<T>(2, 2, 2, true)
+}
+
+/// Ack of `batch_size` requests, each of which may complete.
+pub fn ack<T: Config>(batch_size: u32) -> Weight {
+    BASE_WEIGHT.saturating_add(T::DbWeight::get().reads_writes(
+        3 + 5 * batch_size as Weight,
+        3 + 6 * batch_size as Weight
+    ))
+}
+
+/// Batch proof of `count` requests, each of which may complete, with a proof of `proof_len` bytes.
+pub fn submit_batch_proof<T: Config>(count: u32, proof_len: u32) -> Weight {
+    BASE_WEIGHT
+        .saturating_add(T::DbWeight::get().reads_writes(
+            2 + 4 * count as Weight,
+            1 + 3 * count as Weight
+        ))
+        .saturating_add((proof_len as Weight).saturating_mul(PROOF_BYTE_WEIGHT))
+        .saturating_add(T::BatchProofVerifier::verify_weight(count, proof_len))
+}
+
+/// Rejection of a pending withdrawal refunding the balance or the NFT and the period withdrawal
+/// through a new request.
+pub fn reject_req<T: Config>() -> Weight {
+    BASE_WEIGHT
+        .saturating_add(T::DbWeight::get().reads_writes(9, 14))
+        .saturating_add(leaf_update::<T>())
+}
+
+/// Emergency withdrawal of `tokens` balances and `nfts` NFTs, each queued as its own request.
+pub fn emergency_withdraw<T: Config>(tokens: u32, nfts: u32) -> Weight {
+    let reqs = tokens as Weight + nfts as Weight;
+    BASE_WEIGHT
+        .saturating_add(T::DbWeight::get().reads_writes(6 + 3 * reqs + nfts as Weight, 2 + 7 * reqs))
+        .saturating_add((tokens as Weight + 2 * nfts as Weight).saturating_mul(leaf_update::<T>()))
+}
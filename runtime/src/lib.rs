@@ -7,14 +7,16 @@
 include!(concat!(env!("OUT_DIR"), "/wasm_binary.rs"));
 
 use sp_std::prelude::*;
-use sp_core::{crypto::KeyTypeId, OpaqueMetadata};
+use core::any::TypeId;
+use sp_core::{crypto::KeyTypeId, OpaqueMetadata, U256};
 use sp_runtime::{
 	ApplyExtrinsicResult, generic, create_runtime_str, impl_opaque_keys, MultiSignature,
 	transaction_validity::{TransactionValidity, TransactionSource},
 };
 use sp_runtime::traits::{
-	AccountIdLookup, BlakeTwo256, Block as BlockT, Verify, IdentifyAccount, NumberFor,
+	AccountIdLookup, BlakeTwo256, Block as BlockT, Verify, IdentifyAccount, NumberFor, SaturatedConversion,
 };
+use codec::Encode;
 use sp_api::impl_runtime_apis;
 use sp_consensus_aura::sr25519::AuthorityId as AuraId;
 use pallet_grandpa::{AuthorityId as GrandpaId, AuthorityList as GrandpaAuthorityList};
@@ -24,7 +26,6 @@ use hex_literal;
 
 #[path = "../../generated_config/admins_config.rs"]
 mod admins_config;
-use admins_config::*;
 
 #[cfg(feature = "std")]
 use sp_version::NativeVersion;
@@ -102,7 +103,7 @@ pub const VERSION: RuntimeVersion = RuntimeVersion {
 	spec_name: create_runtime_str!("node-swap"),
 	impl_name: create_runtime_str!("node-swap"),
 	authoring_version: 1,
-	spec_version: 100,
+	spec_version: 101,
 	impl_version: 1,
 	apis: RUNTIME_API_VERSIONS,
 	transaction_version: 1,
@@ -183,8 +184,8 @@ impl frame_system::Config for Runtime {
 	type Version = Version;
 	/// Converts a module to the index of the module in `construct_runtime!`.
 	///
-	/// This type is being generated by `construct_runtime!`.
-	type PalletInfo = PalletInfo;
+	/// Wraps the type generated by `construct_runtime!` to keep the swap storage prefix.
+	type PalletInfo = SwapPalletInfo;
 	/// What to do if a new account is created.
 	type OnNewAccount = ();
 	/// What to do if an account is fully reaped from the system.
@@ -266,15 +267,70 @@ impl pallet_sudo::Config for Runtime {
 }
 
 parameter_types! {
-    pub AckAdmins: Vec<AccountId> = admins();
+    pub LegacyAdmins: Vec<AccountId> = admins_config::admins();
+    pub const SnapshotWindow: BlockNumber = DAYS;
+    pub const CompleteReqRetention: BlockNumber = 7 * DAYS;
+    pub const MaxPrunePerBlock: u32 = 50;
+    pub const ExitModeDelay: BlockNumber = 3 * DAYS;
+    pub const MaxMigrationKeysPerBlock: u32 = 2_000;
+    pub const MaxProofLen: u32 = 64 * 1024;
 }
 
 /// Configure the swap pallet in pallets/swap.
 
 impl pallet_swap::Config for Runtime {
 	type Event = Event;
-	type AckAdmins = AckAdmins;
     type Currency = Balances;
+	type SnapshotWindow = SnapshotWindow;
+	type CompleteReqRetention = CompleteReqRetention;
+	type MaxPrunePerBlock = MaxPrunePerBlock;
+	type BatchProofVerifier = ();
+	type MaxProofLen = MaxProofLen;
+	type ExitModeDelay = ExitModeDelay;
+	type AuthorityId = pallet_swap::crypto::AckAuthId;
+	type AckValidator = ();
+	type L1EventSource = pallet_swap::HttpL1Source;
+	type MaxMigrationKeysPerBlock = MaxMigrationKeysPerBlock;
+	type LegacyAdmins = LegacyAdmins;
+}
+
+impl<LocalCall> frame_system::offchain::CreateSignedTransaction<LocalCall> for Runtime where
+	Call: From<LocalCall>,
+{
+	fn create_transaction<C: frame_system::offchain::AppCrypto<Self::Public, Self::Signature>>(
+		call: Call,
+		public: <Signature as Verify>::Signer,
+		account: AccountId,
+		nonce: Index,
+	) -> Option<(Call, <UncheckedExtrinsic as sp_runtime::traits::Extrinsic>::SignaturePayload)> {
+		let period = BlockHashCount::get() as u64;
+		let current_block = System::block_number().saturated_into::<u64>().saturating_sub(1);
+		let extra: SignedExtra = (
+			frame_system::CheckSpecVersion::<Runtime>::new(),
+			frame_system::CheckTxVersion::<Runtime>::new(),
+			frame_system::CheckGenesis::<Runtime>::new(),
+			frame_system::CheckEra::<Runtime>::from(generic::Era::mortal(period, current_block)),
+			frame_system::CheckNonce::<Runtime>::from(nonce),
+			frame_system::CheckWeight::<Runtime>::new(),
+			pallet_transaction_payment::ChargeTransactionPayment::<Runtime>::from(0),
+		);
+		let raw_payload = SignedPayload::new(call, extra).ok()?;
+		let signature = raw_payload.using_encoded(|payload| C::sign(payload, public))?;
+		let (call, extra, _) = raw_payload.deconstruct();
+		Some((call, (sp_runtime::MultiAddress::Id(account), signature, extra)))
+	}
+}
+
+impl frame_system::offchain::SigningTypes for Runtime {
+	type Public = <Signature as Verify>::Signer;
+	type Signature = Signature;
+}
+
+impl<C> frame_system::offchain::SendTransactionTypes<C> for Runtime where
+	Call: From<C>,
+{
+	type OverarchingCall = Call;
+	type Extrinsic = UncheckedExtrinsic;
 }
 
 // Create the runtime by composing the FRAME pallets that were previously configured.
@@ -293,10 +349,27 @@ construct_runtime!(
 		TransactionPayment: pallet_transaction_payment::{Module, Storage},
 		Sudo: pallet_sudo::{Module, Call, Config<T>, Storage, Event<T>},
 		// Include the custom logic from the swap pallet in the runtime.
-		SwapModule: pallet_swap::{Module, Call, Storage, Event<T>},
+		SwapModule: pallet_swap::{Module, Call, Config<T>, Storage, Event<T>},
 	}
 );
 
+/// Reports the swap pallet under `pallet_swap::STORAGE_PREFIX` so that its storage keeps that
+/// prefix whatever the pallet is named in `construct_runtime!`.
+pub struct SwapPalletInfo;
+
+impl frame_support::traits::PalletInfo for SwapPalletInfo {
+	fn index<P: 'static>() -> Option<usize> {
+		PalletInfo::index::<P>()
+	}
+
+	fn name<P: 'static>() -> Option<&'static str> {
+		if TypeId::of::<P>() == TypeId::of::<pallet_swap::Pallet<Runtime>>() {
+			return Some(pallet_swap::STORAGE_PREFIX);
+		}
+		PalletInfo::name::<P>()
+	}
+}
+
 /// The address format for describing accounts.
 pub type Address = sp_runtime::MultiAddress<AccountId, ()>;
 /// Block header type as expected by this runtime.
@@ -319,6 +392,8 @@ pub type SignedExtra = (
 );
 /// Unchecked extrinsic type as expected by this runtime.
 pub type UncheckedExtrinsic = generic::UncheckedExtrinsic<Address, Call, Signature, SignedExtra>;
+/// The payload being signed in transactions.
+pub type SignedPayload = generic::SignedPayload<Call, SignedExtra>;
 /// Extrinsic type that has already been checked.
 pub type CheckedExtrinsic = generic::CheckedExtrinsic<AccountId, Call, SignedExtra>;
 /// Executive: handles dispatch to the various modules.
@@ -462,6 +537,52 @@ impl_runtime_apis! {
 		}
 	}
 
+	impl pallet_swap_runtime_api::SwapApi<Block, BlockNumber> for Runtime {
+		fn twap(
+			pool_index: pallet_swap::PoolIndex,
+			direction: pallet_swap::SwapDirection,
+			from: BlockNumber,
+			to: BlockNumber,
+		) -> Option<U256> {
+			SwapModule::twap(pool_index, direction, from, to)
+		}
+
+		fn pool_history(
+			pool_index: pallet_swap::PoolIndex,
+			from: BlockNumber,
+			to: BlockNumber,
+		) -> Vec<(BlockNumber, pallet_swap::Amount, pallet_swap::Amount, pallet_swap::Amount)> {
+			SwapModule::pool_history(pool_index, from, to)
+		}
+
+		fn deposit_status(l1_tx_hash: pallet_swap::L1TxHash) -> pallet_swap::DepositStatus {
+			SwapModule::deposit_status(l1_tx_hash)
+		}
+
+		fn requests(
+			start: pallet_swap::ReqId,
+			count: u32,
+		) -> Vec<(pallet_swap::ReqId, pallet_swap::ReqRecord<BlockNumber>, Option<pallet_swap::Ops>)> {
+			SwapModule::requests(start, count)
+		}
+
+		fn account_requests(
+			account_index: pallet_swap::AccountIndex,
+			start: u32,
+			count: u32,
+		) -> Vec<(pallet_swap::ReqId, pallet_swap::ReqRecord<BlockNumber>, Option<pallet_swap::Ops>)> {
+			SwapModule::account_requests(account_index, start, count)
+		}
+
+		fn merkle_root(req_id: Option<pallet_swap::ReqId>) -> Option<Hash> {
+			SwapModule::merkle_root(req_id)
+		}
+
+		fn merkle_proof(leaf: pallet_swap::MerkleLeaf) -> pallet_swap::MerkleProof {
+			SwapModule::merkle_proof(leaf)
+		}
+	}
+
 	#[cfg(feature = "runtime-benchmarks")]
 	impl frame_benchmarking::Benchmark<Block> for Runtime {
 		fn dispatch_benchmark(